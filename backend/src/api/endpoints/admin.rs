@@ -0,0 +1,36 @@
+use crate::api::extractors::AdminUser;
+use crate::dto::responses::admin::RecalculateTotalsResponse;
+use crate::errors::app_error::AppError;
+use crate::repositories::projects::MongoProjectRepository;
+use crate::usecases::projects::ProjectUseCase;
+use actix_web::{post, web, HttpResponse};
+use log::info;
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/recalculate-total-working-time/",
+    responses(
+        (status = 200, description = "総稼働時間の再計算に成功", body = RecalculateTotalsResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "管理者権限がありません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[post("/recalculate-total-working-time/")]
+pub async fn recalculate_total_working_time(
+    usecase: web::Data<Arc<ProjectUseCase<MongoProjectRepository>>>,
+    admin: AdminUser,
+) -> Result<HttpResponse, AppError> {
+    info!(
+        "called POST recalculate_total_working_time!! (admin: {})",
+        admin.0.user_id
+    );
+
+    let corrected_count = usecase.recalculate_all_total_working_time().await?;
+
+    Ok(HttpResponse::Ok().json(RecalculateTotalsResponse { corrected_count }))
+}
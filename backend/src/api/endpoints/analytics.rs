@@ -0,0 +1,46 @@
+use crate::api::extractors::CurrentUser;
+use crate::dto::responses::reports::SkillAnalyticsResponse;
+use crate::errors::app_error::AppError;
+use crate::models::reports::SkillAnalyticsQuery;
+use crate::repositories::reports::MongoReportRepository;
+use crate::usecases::reports::ReportUseCase;
+use actix_web::{get, web, HttpResponse};
+use log::info;
+use std::sync::Arc;
+use validator::Validate;
+
+#[utoipa::path(
+    get,
+    path = "/api/analytics/skills/",
+    params(
+        ("from" = Option<String>, Query, description = "集計期間の開始日（YYYY-MM-DD形式, この日を含む）。未指定の場合は全期間"),
+        ("to" = Option<String>, Query, description = "集計期間の終了日（YYYY-MM-DD形式, この日を含む）。未指定の場合は全期間"),
+        ("limit" = Option<i64>, Query, description = "取得するスキル数の制限（作業時間の多い順）")
+    ),
+    responses(
+        (status = 200, description = "スキル分析の取得に成功", body = SkillAnalyticsResponse),
+        (status = 400, description = "無効なクエリパラメータ", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/skills/")]
+pub async fn get_skill_analytics(
+    usecase: web::Data<Arc<ReportUseCase<MongoReportRepository>>>,
+    current_user: CurrentUser,
+    query: web::Query<SkillAnalyticsQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET get_skill_analytics!!");
+
+    // バリデーションを実行
+    query.validate().map_err(AppError::ValidationError)?;
+
+    let skills = usecase
+        .analyze_skills(&current_user.user_id, &query, current_user.time_zone)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SkillAnalyticsResponse { skills }))
+}
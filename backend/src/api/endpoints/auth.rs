@@ -1,9 +1,15 @@
+use crate::api::extractors::CurrentUser;
 use crate::dto::responses::auth::{AuthResponse, AuthTokenCreatedResponse};
 use crate::errors::app_error::AppError;
-use crate::models::auth::AuthTokenLogin;
+use crate::i18n::messages;
+use crate::models::auth::{
+    AuthTokenLogin, EmailVerificationConfirm, PasswordResetConfirm, PasswordResetRequest,
+    SessionClient,
+};
 use crate::models::users::UserCreate;
 use crate::repositories::auth::MongoAuthRepository;
 use crate::usecases::auth::AuthUseCase;
+use crate::usecases::password_reset::PasswordResetUseCase;
 use crate::utils::cookie_util::{
     set_access_token_cookie, set_first_login_cookie, set_refresh_token_cookie,
 };
@@ -18,6 +24,7 @@ use validator::Validate;
     responses(
         (status = 200, description = "ログインに成功", body = AuthResponse),
         (status = 400, description = "無効なリクエストデータor認証に失敗", body = ErrorResponse),
+        (status = 429, description = "ログインの失敗が続いたため一時的にロック中", body = ErrorResponse),
         (status = 500, description = "サーバーエラー", body = ErrorResponse)
     )
 )]
@@ -25,37 +32,22 @@ use validator::Validate;
 async fn login(
     auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
     login_dto: web::Json<AuthTokenLogin>,
+    client: SessionClient,
 ) -> Result<HttpResponse, AppError> {
     // バリデーションの実行
     login_dto.validate().map_err(AppError::ValidationError)?;
 
-    match auth_usecase
-        .login(&login_dto.email, &login_dto.password)
-        .await
-    {
-        Ok(auth_token) => {
-            // 成功時の処理
-            let auth_response: AuthResponse = AuthResponse::from(auth_token.clone());
-            let mut response = HttpResponse::Ok().json(auth_response);
-            set_access_token_cookie(&mut response, &auth_token.access_token);
-            set_refresh_token_cookie(&mut response, &auth_token.refresh_token);
-            Ok(response)
-        }
-        Err(e) => {
-            log::error!("認証に失敗しました: {}", e);
-            match e {
-                AppError::InternalServerError(_) => {
-                    Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                        "error": "サーバーエラーが発生しました"
-                    })))
-                }
-                // 他者の個人情報を推測できないようにするため、他のエラーは400で統一
-                _ => Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                    "error": "認証に失敗しました"
-                }))),
-            }
-        }
-    }
+    // 他者の個人情報を推測できないようにするため、ユーザーが存在しない場合とパスワードが誤っている場合は同じエラーを返す
+    // クライアントのIPアドレスごとの失敗回数も数える
+    let auth_token = auth_usecase
+        .login(&login_dto.email, &login_dto.password, &client)
+        .await?;
+
+    let auth_response: AuthResponse = AuthResponse::from(auth_token.clone());
+    let mut response = HttpResponse::Ok().json(auth_response);
+    set_access_token_cookie(&mut response, &auth_token.access_token);
+    set_refresh_token_cookie(&mut response, &auth_token.refresh_token);
+    Ok(response)
 }
 
 #[utoipa::path(
@@ -72,6 +64,7 @@ async fn login(
 async fn register(
     auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
     register_dto: web::Json<UserCreate>,
+    client: SessionClient,
 ) -> Result<HttpResponse, AppError> {
     // バリデーションの実行
     register_dto
@@ -79,7 +72,7 @@ async fn register(
         .validate()
         .map_err(AppError::ValidationError)?;
 
-    let auth_token = auth_usecase.register(&register_dto).await?;
+    let auth_token = auth_usecase.register(&register_dto, &client).await?;
 
     let mut response = HttpResponse::Created().json(AuthTokenCreatedResponse {
         message: "ユーザー登録に成功しました".to_string(),
@@ -105,15 +98,9 @@ async fn register(
 #[post("/logout/")]
 async fn logout(
     auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
-    req: HttpRequest,
+    current_user: CurrentUser,
 ) -> Result<HttpResponse, AppError> {
-    let auth_header = req
-        .headers()
-        .get("Authorization")
-        .and_then(|header| header.to_str().ok())
-        .unwrap_or_default(); // ミドルウェアが既に認証済なので、ヘッダーは存在する前提で進める
-
-    auth_usecase.logout(auth_header).await?;
+    auth_usecase.logout(&current_user.token_id).await?;
     Ok(HttpResponse::Ok().finish())
 }
 
@@ -121,8 +108,8 @@ async fn logout(
     post,
     path = "/api/auth/refresh/",
     responses(
-        (status = 200, description = "トークンのリフレッシュに成功", body = AuthResponse),
-        (status = 400, description = "無効なリクエスト", body = ErrorResponse),
+        (status = 200, description = "トークンのリフレッシュに成功（リフレッシュトークンもローテーションする）", body = AuthResponse),
+        (status = 400, description = "無効なリクエスト（ローテーション済みのリフレッシュトークンの場合は同じファミリーのトークンをすべて失効させる）", body = ErrorResponse),
         (status = 401, description = "認証失敗", body = ErrorResponse),
         (status = 500, description = "サーバーエラー", body = ErrorResponse)
     )
@@ -131,21 +118,96 @@ async fn logout(
 async fn refresh(
     auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
     req: HttpRequest,
+    client: SessionClient,
 ) -> Result<HttpResponse, AppError> {
     // クッキーからリフレッシュトークンを取得
     let refresh_token = req
         .cookie("refresh_token")
-        .ok_or_else(|| AppError::BadRequest("無効なリクエストです".to_string()))? // あえて曖昧なエラーメッセージを返す
+        .ok_or_else(|| AppError::BadRequest(messages::INVALID_REQUEST.into()))? // あえて曖昧なエラーメッセージを返す
         .value()
         .to_string();
 
-    let auth_token = auth_usecase.refresh_token(&refresh_token).await?;
-    let access_token = auth_token.access_token.clone();
+    let auth_token = auth_usecase.refresh_token(&refresh_token, &client).await?;
 
-    let auth_response: AuthResponse = auth_token.into();
+    let auth_response: AuthResponse = AuthResponse::from(auth_token.clone());
     let mut response = HttpResponse::Ok().json(auth_response);
 
-    // 新しいアクセストークンをクッキーにセット
-    set_access_token_cookie(&mut response, &access_token);
+    // 新しいアクセストークンとローテーションしたリフレッシュトークンをクッキーにセット
+    set_access_token_cookie(&mut response, &auth_token.access_token);
+    set_refresh_token_cookie(&mut response, &auth_token.refresh_token);
     Ok(response)
 }
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/password-reset/request/",
+    request_body = PasswordResetRequest,
+    responses(
+        (status = 202, description = "パスワードリセットの申請を受け付け（登録済みのメールアドレスの場合のみ再設定用のメールを送信する）"),
+        (status = 400, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    )
+)]
+#[post("/password-reset/request/")]
+async fn request_password_reset(
+    password_reset_usecase: web::Data<Arc<PasswordResetUseCase<MongoAuthRepository>>>,
+    request_dto: web::Json<PasswordResetRequest>,
+) -> Result<HttpResponse, AppError> {
+    // バリデーションの実行
+    request_dto.validate().map_err(AppError::ValidationError)?;
+
+    // 他者の個人情報を推測できないようにするため、メールアドレスが登録されているかに関わらず同じレスポンスを返す
+    password_reset_usecase
+        .request_reset(&request_dto.email)
+        .await?;
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/password-reset/confirm/",
+    request_body = PasswordResetConfirm,
+    responses(
+        (status = 204, description = "パスワードの再設定に成功（既存のセッションはすべて失効する）"),
+        (status = 400, description = "無効なリクエストデータor無効・期限切れ・使用済みのリセットトークン", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    )
+)]
+#[post("/password-reset/confirm/")]
+async fn confirm_password_reset(
+    password_reset_usecase: web::Data<Arc<PasswordResetUseCase<MongoAuthRepository>>>,
+    confirm_dto: web::Json<PasswordResetConfirm>,
+) -> Result<HttpResponse, AppError> {
+    // バリデーションの実行
+    confirm_dto.validate().map_err(AppError::ValidationError)?;
+
+    password_reset_usecase
+        .confirm_reset(&confirm_dto.token, &confirm_dto.new_password)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/email-verification/confirm/",
+    request_body = EmailVerificationConfirm,
+    responses(
+        (status = 204, description = "メールアドレスの確認に成功（変更を申請中のメールアドレスの場合はメールアドレスを変更する）"),
+        (status = 400, description = "無効なリクエストデータor無効・期限切れ・使用済みの確認用リンクorメールアドレスが既に使用されている", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    )
+)]
+#[post("/email-verification/confirm/")]
+async fn confirm_email_verification(
+    auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
+    confirm_dto: web::Json<EmailVerificationConfirm>,
+) -> Result<HttpResponse, AppError> {
+    // バリデーションの実行
+    confirm_dto.validate().map_err(AppError::ValidationError)?;
+
+    auth_usecase.verify_email(&confirm_dto.token).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
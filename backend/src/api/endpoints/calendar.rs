@@ -0,0 +1,37 @@
+use crate::errors::app_error::AppError;
+use crate::usecases::calendar::CalendarUseCase;
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{get, web, HttpResponse};
+use log::info;
+use std::sync::Arc;
+
+#[utoipa::path(
+    get,
+    path = "/api/calendar/{token}.ics",
+    params(
+        ("token" = String, Path, description = "カレンダーフィードのトークン（/api/users/me/calendar-feed/で発行）")
+    ),
+    responses(
+        (status = 200, description = "カレンダーフィード（iCalendar形式）の取得に成功", content_type = "text/calendar"),
+        (status = 404, description = "カレンダーフィードが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    )
+)]
+#[get("/{token}.ics")]
+pub async fn get_calendar_feed(
+    usecase: web::Data<Arc<CalendarUseCase>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET get_calendar_feed!!");
+
+    // カレンダーアプリは認証ヘッダーを送信できないため、URLに含めたトークンで認証する
+    let calendar = usecase.render_feed(&path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .insert_header(CacheControl(vec![
+            CacheDirective::Private,
+            CacheDirective::NoCache,
+        ]))
+        .body(calendar))
+}
@@ -1,20 +1,30 @@
+use crate::api::extractors::CurrentUser;
 use crate::dto::responses::companies::{
     CompaniesWithProjects, CompaniesWithProjectsResponse, CompanyCreatedResponse, CompanyResponse,
 };
+use crate::dto::responses::pagination::PaginatedResponse;
 use crate::errors::app_error::AppError;
-use crate::models::companies::{CompanyCreate, CompanyUpdate};
+use crate::i18n::messages;
+use crate::models::companies::{CompanyCreate, CompanyQuery, CompanyUpdate};
 use crate::repositories::companies::MongoCompanyRepository;
 use crate::usecases::companies::CompanyUseCase;
-use actix_web::{get, post, put, web, HttpResponse};
+use crate::utils::pagination::PageCursor;
+use actix_web::{delete, get, post, put, web, HttpResponse};
 use bson::oid::ObjectId;
 use log::info;
 use std::sync::Arc;
+use validator::Validate;
 
 #[utoipa::path(
     get,
     path = "/api/companies/",
+    params(
+        ("limit" = Option<i64>, Query, description = "取得するドキュメント数の制限"),
+        ("cursor" = Option<String>, Query, description = "次のページを取得するためのカーソル（前回のレスポンスのnext_cursor）")
+    ),
     responses(
-        (status = 200, description = "企業の取得に成功", body = Vec<CompanyResponse>),
+        (status = 200, description = "企業の取得に成功", body = PaginatedCompanyResponse),
+        (status = 400, description = "無効なクエリパラメータ", body = ErrorResponse),
         (status = 401, description = "認証失敗", body = ErrorResponse),
         (status = 500, description = "サーバーエラー", body = ErrorResponse)
     ),
@@ -25,14 +35,22 @@ use std::sync::Arc;
 #[get("/")]
 pub async fn get_all_companies(
     usecase: web::Data<Arc<CompanyUseCase<MongoCompanyRepository>>>,
+    current_user: CurrentUser,
+    query: web::Query<CompanyQuery>,
 ) -> Result<HttpResponse, AppError> {
     info!("called GET get_all_companies!!");
-    let companies = usecase.get_all_companies().await?;
-    let response: Vec<CompanyResponse> = companies
-        .into_iter()
-        .map(CompanyResponse::try_from)
-        .collect::<Result<_, _>>()
-        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?;
+
+    // バリデーションを実行
+    query.validate().map_err(AppError::ValidationError)?;
+
+    let cursor = PageCursor::parse(query.cursor.as_deref(), &[])?;
+    let companies = usecase
+        .get_all_companies(&current_user.user_id, query.limit, cursor)
+        .await?;
+    let response: PaginatedResponse<CompanyResponse> = companies
+        .try_map(CompanyResponse::try_from)
+        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?
+        .into();
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -52,9 +70,12 @@ pub async fn get_all_companies(
 #[get("/with-projects/")]
 pub async fn get_all_companies_with_projects(
     usecase: web::Data<Arc<CompanyUseCase<MongoCompanyRepository>>>,
+    current_user: CurrentUser,
 ) -> Result<HttpResponse, AppError> {
     info!("called GET get_all_companies_with_projects!!");
-    let companies = usecase.get_all_companies_with_projects().await?;
+    let companies = usecase
+        .get_all_companies_with_projects(&current_user.user_id)
+        .await?;
     let total = companies.len() as u64;
     let response: Vec<CompaniesWithProjects> = companies
         .into_iter()
@@ -90,16 +111,20 @@ pub async fn get_all_companies_with_projects(
 #[get("/{id}/")]
 pub async fn get_company_by_id(
     usecase: web::Data<Arc<CompanyUseCase<MongoCompanyRepository>>>,
+    current_user: CurrentUser,
     id: web::Path<String>,
 ) -> Result<HttpResponse, AppError> {
     info!("called GET get_company_by_id!!");
 
     let obj_id = ObjectId::parse_str(id.into_inner())
-        .map_err(|_| AppError::BadRequest("無効なIDです".to_string()))?;
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
 
-    let company = match usecase.get_company_by_id(&obj_id).await {
+    let company = match usecase
+        .get_company_by_id(&current_user.user_id, &obj_id)
+        .await
+    {
         Ok(Some(company)) => company,
-        Ok(None) => return Err(AppError::NotFound("企業が見つかりません".to_string())),
+        Ok(None) => return Err(AppError::NotFound(messages::COMPANY_NOT_FOUND.into())),
         Err(e) => return Err(e), // AppErrorをそのまま返す
     };
 
@@ -126,16 +151,19 @@ pub async fn get_company_by_id(
 #[post("/")]
 pub async fn create_company(
     usecase: web::Data<Arc<CompanyUseCase<MongoCompanyRepository>>>,
+    current_user: CurrentUser,
     company_dto: web::Json<CompanyCreate>,
 ) -> Result<HttpResponse, AppError> {
     info!("called POST create_company!!");
 
     // バリデーションを実行
     company_dto
-        .validate_all()
+        .validate_all(current_user.time_zone)
         .map_err(AppError::ValidationError)?;
 
-    let company_id = usecase.create_company(company_dto.into_inner()).await?;
+    let company_id = usecase
+        .create_company(&current_user.user_id, company_dto.into_inner())
+        .await?;
 
     Ok(HttpResponse::Created().json(CompanyCreatedResponse::from(company_id)))
 }
@@ -161,21 +189,93 @@ pub async fn create_company(
 #[put("/{id}/")]
 pub async fn update_company_by_id(
     usecase: web::Data<Arc<CompanyUseCase<MongoCompanyRepository>>>,
+    current_user: CurrentUser,
     path: web::Path<String>,
     update_dto: web::Json<CompanyUpdate>,
 ) -> Result<HttpResponse, AppError> {
     info!("called PUT update_company_by_id!!");
 
     let obj_id = ObjectId::parse_str(path.into_inner())
-        .map_err(|_| AppError::BadRequest("無効なIDです".to_string()))?;
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
 
     // バリデーションを実行
     update_dto
-        .validate_all()
+        .validate_all(current_user.time_zone)
         .map_err(AppError::ValidationError)?;
 
     usecase
-        .update_company_by_id(&obj_id, &update_dto.into_inner())
+        .update_company_by_id(&current_user.user_id, &obj_id, &update_dto.into_inner())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/companies/{id}/",
+    responses(
+        (status = 204, description = "企業の削除に成功"),
+        (status = 400, description = "無効なIDです", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "企業が見つかりません", body = ErrorResponse),
+        (status = 409, description = "請求書に含まれる勤怠があるため削除できません（conflicting_idsに勤怠のIDを含む）", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "企業ID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[delete("/{id}/")]
+pub async fn delete_company_by_id(
+    usecase: web::Data<Arc<CompanyUseCase<MongoCompanyRepository>>>,
+    current_user: CurrentUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    info!("called DELETE delete_company_by_id!!");
+
+    let obj_id = ObjectId::parse_str(path.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    usecase
+        .delete_company(&current_user.user_id, &obj_id)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/companies/{id}/restore/",
+    responses(
+        (status = 204, description = "企業の復元に成功"),
+        (status = 400, description = "無効なIDです", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "ゴミ箱に企業が見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "企業ID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[post("/{id}/restore/")]
+pub async fn restore_company_by_id(
+    usecase: web::Data<Arc<CompanyUseCase<MongoCompanyRepository>>>,
+    current_user: CurrentUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    info!("called POST restore_company_by_id!!");
+
+    let obj_id = ObjectId::parse_str(path.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    usecase
+        .restore_company(&current_user.user_id, &obj_id)
         .await?;
 
     Ok(HttpResponse::NoContent().finish())
@@ -0,0 +1,77 @@
+use crate::api::extractors::CurrentUser;
+use crate::errors::app_error::AppError;
+use crate::models::exports::{ExportFormat, WorkLogExportQuery, WORK_LOG_EXPORT_HEADERS};
+use crate::usecases::exports::ExportUseCase;
+use crate::utils::export::{csv_cells, csv_line, XlsxBuilder, UTF8_BOM};
+use actix_web::http::header::ContentDisposition;
+use actix_web::web::Bytes;
+use actix_web::{get, web, HttpResponse};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use log::info;
+use std::sync::Arc;
+use validator::Validate;
+
+#[utoipa::path(
+    get,
+    path = "/api/exports/work-logs/",
+    params(
+        ("from" = String, Query, description = "出力期間の開始日（YYYY-MM-DD形式, この日を含む）"),
+        ("to" = String, Query, description = "出力期間の終了日（YYYY-MM-DD形式, この日を含む）"),
+        ("project_id" = Option<String>, Query, description = "プロジェクトID"),
+        ("company_id" = Option<String>, Query, description = "企業ID"),
+        ("format" = Option<ExportFormat>, Query, description = "出力形式（csv, xlsx）。デフォルトはcsv")
+    ),
+    responses(
+        (status = 200, description = "勤怠のエクスポートに成功（CSVはUTF-8のBOM付き）", content_type = "text/csv"),
+        (status = 400, description = "無効なクエリパラメータ", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/work-logs/")]
+pub async fn export_work_logs(
+    usecase: web::Data<Arc<ExportUseCase>>,
+    current_user: CurrentUser,
+    query: web::Query<WorkLogExportQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET export_work_logs!!");
+
+    // バリデーションを実行
+    query.validate().map_err(AppError::ValidationError)?;
+
+    let rows = usecase
+        .export_work_logs(&current_user.user_id, &query, current_user.time_zone)
+        .await?;
+
+    let mut response = HttpResponse::Ok();
+    response
+        .content_type(query.format.content_type())
+        .insert_header(ContentDisposition::attachment(format!(
+            "work_logs_{}_{}.{}",
+            query.from,
+            query.to,
+            query.format.extension()
+        )));
+
+    match query.format {
+        ExportFormat::Csv => {
+            // 全件をメモリに載せないよう、カーソルから取得した順に1行ずつ送信する
+            let mut header = UTF8_BOM.to_vec();
+            header.extend(csv_line(WORK_LOG_EXPORT_HEADERS)?);
+            let body = stream::once(async { Ok(Bytes::from(header)) })
+                .chain(rows.and_then(|row| async { csv_cells(row.cells()).map(Bytes::from) }));
+            Ok(response.streaming(body))
+        }
+        ExportFormat::Xlsx => {
+            let mut builder = XlsxBuilder::new("勤怠", &WORK_LOG_EXPORT_HEADERS)?;
+            let mut rows = rows;
+            while let Some(row) = rows.try_next().await? {
+                builder.write_row(row.cells())?;
+            }
+            Ok(response.body(builder.finish()?))
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use crate::api::extractors::CurrentUser;
+use crate::dto::responses::invoices::{InvoiceCreatedResponse, InvoiceResponse};
+use crate::dto::responses::pagination::PaginatedResponse;
+use crate::errors::app_error::AppError;
+use crate::i18n::messages;
+use crate::models::invoices::{
+    InvoiceCreate, InvoiceDocumentFormat, InvoiceDocumentQuery, InvoiceQuery, InvoiceStatusUpdate,
+};
+use crate::repositories::invoices::MongoInvoiceRepository;
+use crate::usecases::invoices::InvoiceUseCase;
+use crate::utils::invoice_document::{render_invoice_html, render_invoice_pdf};
+use crate::utils::pagination::PageCursor;
+use actix_web::http::header::ContentDisposition;
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use bson::oid::ObjectId;
+use log::info;
+use std::sync::Arc;
+use validator::Validate;
+
+#[utoipa::path(
+    get,
+    path = "/api/invoices/",
+    params(
+        ("company_id" = Option<String>, Query, description = "企業ID"),
+        ("status" = Option<InvoiceStatus>, Query, description = "ステータス（Draft, Sent, Paid）"),
+        ("limit" = Option<i64>, Query, description = "取得するドキュメント数の制限"),
+        ("cursor" = Option<String>, Query, description = "次のページを取得するためのカーソル（前回のレスポンスのnext_cursor）")
+    ),
+    responses(
+        (status = 200, description = "請求書の取得に成功", body = PaginatedInvoiceResponse),
+        (status = 400, description = "無効なクエリパラメータ", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+)]
+#[get("/")]
+pub async fn get_invoices(
+    usecase: web::Data<Arc<InvoiceUseCase<MongoInvoiceRepository>>>,
+    current_user: CurrentUser,
+    query: web::Query<InvoiceQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET get_invoices!!");
+
+    // バリデーションを実行
+    query.validate().map_err(AppError::ValidationError)?;
+
+    let cursor = PageCursor::parse(query.cursor.as_deref(), &[])?;
+    let invoices = usecase
+        .search_invoices(
+            &current_user.user_id,
+            &query.to_filter(),
+            query.limit,
+            cursor,
+        )
+        .await?;
+    let response: PaginatedResponse<InvoiceResponse> = invoices
+        .try_map(InvoiceResponse::try_from)
+        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?
+        .into();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/invoices/{id}/",
+    responses(
+        (status = 200, description = "請求書の取得に成功", body = InvoiceResponse),
+        (status = 400, description = "無効なIDです", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "請求書が見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "請求書ID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/{id}/")]
+pub async fn get_invoice_by_id(
+    usecase: web::Data<Arc<InvoiceUseCase<MongoInvoiceRepository>>>,
+    current_user: CurrentUser,
+    id: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET get_invoice_by_id!!");
+
+    let obj_id = ObjectId::parse_str(id.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    let invoice = usecase
+        .get_invoice_by_id(&current_user.user_id, &obj_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(messages::INVOICE_NOT_FOUND.into()))?;
+
+    let response = InvoiceResponse::try_from(invoice)
+        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/invoices/{id}/document/",
+    params(
+        ("id" = String, Path, description = "請求書ID"),
+        ("format" = Option<InvoiceDocumentFormat>, Query, description = "出力形式（pdf, html）。デフォルトはpdf")
+    ),
+    responses(
+        (status = 200, description = "請求書の出力に成功", content_type = "application/pdf"),
+        (status = 400, description = "無効なIDまたはクエリパラメータ", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "請求書が見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/{id}/document/")]
+pub async fn get_invoice_document(
+    usecase: web::Data<Arc<InvoiceUseCase<MongoInvoiceRepository>>>,
+    current_user: CurrentUser,
+    id: web::Path<String>,
+    query: web::Query<InvoiceDocumentQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET get_invoice_document!!");
+
+    let obj_id = ObjectId::parse_str(id.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    let invoice = usecase
+        .get_invoice_by_id(&current_user.user_id, &obj_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(messages::INVOICE_NOT_FOUND.into()))?;
+
+    let body = match query.format {
+        InvoiceDocumentFormat::Pdf => render_invoice_pdf(&invoice),
+        InvoiceDocumentFormat::Html => render_invoice_html(&invoice).into_bytes(),
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type(query.format.content_type())
+        .insert_header(ContentDisposition::attachment(format!(
+            "{}.{}",
+            invoice.invoice_number,
+            query.format.extension()
+        )))
+        .body(body))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/invoices/",
+    request_body = InvoiceCreate,
+    responses(
+        (status = 201, description = "請求書の作成に成功", body = InvoiceCreatedResponse),
+        (status = 400, description = "無効なリクエストデータ、または請求対象の勤怠がありません", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "請求先の企業が見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[post("/")]
+pub async fn create_invoice(
+    usecase: web::Data<Arc<InvoiceUseCase<MongoInvoiceRepository>>>,
+    current_user: CurrentUser,
+    invoice_dto: web::Json<InvoiceCreate>,
+) -> Result<HttpResponse, AppError> {
+    info!("called POST create_invoice!!");
+
+    // バリデーションを実行
+    invoice_dto.validate().map_err(AppError::ValidationError)?;
+
+    let invoice_id = usecase
+        .create_invoice(&current_user.user_id, &invoice_dto, current_user.time_zone)
+        .await?;
+
+    Ok(HttpResponse::Created().json(InvoiceCreatedResponse::from(invoice_id)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/invoices/{id}/status/",
+    request_body = InvoiceStatusUpdate,
+    responses(
+        (status = 200, description = "ステータスの変更に成功", body = InvoiceResponse),
+        (status = 400, description = "無効なIDまたは変更できないステータス", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "請求書が見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "請求書ID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[put("/{id}/status/")]
+pub async fn update_invoice_status(
+    usecase: web::Data<Arc<InvoiceUseCase<MongoInvoiceRepository>>>,
+    current_user: CurrentUser,
+    path: web::Path<String>,
+    status_dto: web::Json<InvoiceStatusUpdate>,
+) -> Result<HttpResponse, AppError> {
+    info!("called PUT update_invoice_status!!");
+
+    let obj_id = ObjectId::parse_str(path.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    let invoice = usecase
+        .update_invoice_status(&current_user.user_id, &obj_id, status_dto.status)
+        .await?;
+
+    let response = InvoiceResponse::try_from(invoice)
+        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/invoices/{id}/",
+    responses(
+        (status = 204, description = "請求書の削除に成功（含まれていた勤怠は再び編集できるようになる）"),
+        (status = 400, description = "無効なID、または下書き以外の請求書です", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "請求書が見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "請求書ID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[delete("/{id}/")]
+pub async fn delete_invoice_by_id(
+    usecase: web::Data<Arc<InvoiceUseCase<MongoInvoiceRepository>>>,
+    current_user: CurrentUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    info!("called DELETE delete_invoice_by_id!!");
+
+    let obj_id = ObjectId::parse_str(path.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    usecase
+        .delete_invoice(&current_user.user_id, &obj_id)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
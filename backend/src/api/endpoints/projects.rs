@@ -1,9 +1,13 @@
+use crate::api::extractors::CurrentUser;
+use crate::dto::responses::pagination::PaginatedResponse;
 use crate::dto::responses::projects::{ProjectCreatedResponse, ProjectResponse};
 use crate::errors::app_error::AppError;
+use crate::i18n::messages;
 use crate::models::projects::{ProjectCreate, ProjectQuery, ProjectUpdate};
 use crate::repositories::projects::MongoProjectRepository;
 use crate::usecases::projects::ProjectUseCase;
-use actix_web::{get, post, put, web, HttpResponse};
+use crate::utils::pagination::PageCursor;
+use actix_web::{delete, get, post, put, web, HttpResponse};
 use bson::oid::ObjectId;
 use log::info;
 use std::sync::Arc;
@@ -18,11 +22,12 @@ use validator::Validate;
         ("skill_labels" = Option<Vec<String>>, Query, description = "スキルラベルの一覧"),
         ("company_id" = Option<String>, Query, description = "企業ID"),
         ("limit" = Option<i64>, Query, description = "取得するドキュメント数の制限"),
-        ("offset" = Option<u64>, Query, description = "取得を開始する位置(0スタート)"),
+        ("cursor" = Option<String>, Query, description = "次のページを取得するためのカーソル（前回のレスポンスのnext_cursor）"),
         ("sort" = Option<Vec<String>>, Query, description = "ソート条件（例: 'name:asc', 'created_at:desc'）")
     ),
     responses(
-        (status = 200, description = "プロジェクトの取得に成功", body = Vec<ProjectResponse>),
+        (status = 200, description = "プロジェクトの取得に成功", body = PaginatedProjectResponse),
+        (status = 400, description = "無効なクエリパラメータ", body = ErrorResponse),
         (status = 401, description = "認証失敗", body = ErrorResponse),
         (status = 500, description = "サーバーエラー", body = ErrorResponse)
     ),
@@ -33,6 +38,7 @@ use validator::Validate;
 #[get("/")]
 pub async fn get_projects(
     usecase: web::Data<Arc<ProjectUseCase<MongoProjectRepository>>>,
+    current_user: CurrentUser,
     query: web::Query<ProjectQuery>,
 ) -> Result<HttpResponse, AppError> {
     info!("called GET search_projects!!");
@@ -41,20 +47,22 @@ pub async fn get_projects(
     query.validate().map_err(AppError::ValidationError)?;
 
     let query_inner = query.into_inner();
+    let sort = query_inner.parse_sort_params();
+    let cursor = PageCursor::parse(query_inner.cursor.as_deref(), &sort)?;
     let projects = usecase
         .search_projects(
+            &current_user.user_id,
             query_inner.clone().into_filter(),
             query_inner.limit,
-            query_inner.offset,
-            query_inner.parse_sort_params(),
+            cursor,
+            sort,
         )
         .await?;
 
-    let response = projects
-        .into_iter()
-        .map(ProjectResponse::try_from)
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?;
+    let response: PaginatedResponse<ProjectResponse> = projects
+        .try_map(ProjectResponse::try_from)
+        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?
+        .into();
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -79,20 +87,20 @@ pub async fn get_projects(
 #[get("/{id}/")]
 pub async fn get_project_by_id(
     usecase: web::Data<Arc<ProjectUseCase<MongoProjectRepository>>>,
+    current_user: CurrentUser,
     id: web::Path<String>,
 ) -> Result<HttpResponse, AppError> {
     info!("called GET get_project_by_id!!");
 
     let obj_id = ObjectId::parse_str(id.into_inner())
-        .map_err(|_| AppError::BadRequest("無効なIDです".to_string()))?;
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
 
-    let project = match usecase.get_project_by_id(&obj_id).await {
+    let project = match usecase
+        .get_project_by_id(&current_user.user_id, &obj_id)
+        .await
+    {
         Ok(Some(project)) => project,
-        Ok(None) => {
-            return Err(AppError::NotFound(
-                "プロジェクトが見つかりません".to_string(),
-            ))
-        }
+        Ok(None) => return Err(AppError::NotFound(messages::PROJECT_NOT_FOUND.into())),
         Err(e) => return Err(e), // AppErrorをそのまま返す
     };
 
@@ -119,6 +127,7 @@ pub async fn get_project_by_id(
 #[post("/")]
 pub async fn create_project(
     usecase: web::Data<Arc<ProjectUseCase<MongoProjectRepository>>>,
+    current_user: CurrentUser,
     create_dto: web::Json<ProjectCreate>,
 ) -> Result<HttpResponse, AppError> {
     info!("called POST create_project!!");
@@ -126,7 +135,9 @@ pub async fn create_project(
     // バリデーションを実行
     create_dto.validate().map_err(AppError::ValidationError)?;
 
-    let project_id = usecase.create_project(create_dto.into_inner()).await?;
+    let project_id = usecase
+        .create_project(&current_user.user_id, create_dto.into_inner())
+        .await?;
 
     Ok(HttpResponse::Created().json(ProjectCreatedResponse::from(project_id)))
 }
@@ -152,19 +163,91 @@ pub async fn create_project(
 #[put("/{id}/")]
 pub async fn update_project_by_id(
     usecase: web::Data<Arc<ProjectUseCase<MongoProjectRepository>>>,
+    current_user: CurrentUser,
     path: web::Path<String>,
     update_dto: web::Json<ProjectUpdate>,
 ) -> Result<HttpResponse, AppError> {
     info!("called PUT update_project_by_id!!");
 
     let obj_id = ObjectId::parse_str(path.into_inner())
-        .map_err(|_| AppError::BadRequest("無効なIDです".to_string()))?;
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
 
     // バリデーションチェック
     update_dto.validate().map_err(AppError::ValidationError)?;
 
     usecase
-        .update_project(&obj_id, &update_dto.into_inner())
+        .update_project(&current_user.user_id, &obj_id, &update_dto.into_inner())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}/",
+    responses(
+        (status = 204, description = "プロジェクトの削除に成功"),
+        (status = 400, description = "無効なIDです", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "プロジェクトが見つかりません", body = ErrorResponse),
+        (status = 409, description = "請求書に含まれる勤怠があるため削除できません（conflicting_idsに勤怠のIDを含む）", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "プロジェクトID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[delete("/{id}/")]
+pub async fn delete_project_by_id(
+    usecase: web::Data<Arc<ProjectUseCase<MongoProjectRepository>>>,
+    current_user: CurrentUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    info!("called DELETE delete_project_by_id!!");
+
+    let obj_id = ObjectId::parse_str(path.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    usecase
+        .delete_project(&current_user.user_id, &obj_id)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/restore/",
+    responses(
+        (status = 204, description = "プロジェクトの復元に成功"),
+        (status = 400, description = "無効なIDです", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "ゴミ箱にプロジェクトが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "プロジェクトID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[post("/{id}/restore/")]
+pub async fn restore_project_by_id(
+    usecase: web::Data<Arc<ProjectUseCase<MongoProjectRepository>>>,
+    current_user: CurrentUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    info!("called POST restore_project_by_id!!");
+
+    let obj_id = ObjectId::parse_str(path.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    usecase
+        .restore_project(&current_user.user_id, &obj_id)
         .await?;
 
     Ok(HttpResponse::NoContent().finish())
@@ -0,0 +1,95 @@
+use crate::api::extractors::CurrentUser;
+use crate::dto::responses::reports::{EarningsResponse, TimeReportResponse};
+use crate::errors::app_error::AppError;
+use crate::models::reports::{EarningsQuery, TimeReportQuery};
+use crate::repositories::reports::MongoReportRepository;
+use crate::usecases::reports::ReportUseCase;
+use actix_web::{get, web, HttpResponse};
+use log::info;
+use std::sync::Arc;
+use validator::Validate;
+
+#[utoipa::path(
+    get,
+    path = "/api/reports/earnings/",
+    params(
+        ("from" = String, Query, description = "集計期間の開始日（ユーザーのタイムゾーン, YYYY-MM-DD形式, この日を含む）"),
+        ("to" = String, Query, description = "集計期間の終了日（ユーザーのタイムゾーン, YYYY-MM-DD形式, この日を含む）"),
+        ("group_by" = Option<EarningsGroupBy>, Query, description = "集計単位（work_log, project, company, month）。デフォルトはmonth")
+    ),
+    responses(
+        (status = 200, description = "収入の集計に成功", body = EarningsResponse),
+        (status = 400, description = "無効なクエリパラメータ", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/earnings/")]
+pub async fn get_earnings(
+    usecase: web::Data<Arc<ReportUseCase<MongoReportRepository>>>,
+    current_user: CurrentUser,
+    query: web::Query<EarningsQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET get_earnings!!");
+
+    // バリデーションを実行
+    query.validate().map_err(AppError::ValidationError)?;
+
+    let (total, groups) = usecase
+        .calculate_earnings(&current_user.user_id, &query, current_user.time_zone)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(EarningsResponse {
+        from: query.from,
+        to: query.to,
+        group_by: query.group_by,
+        total,
+        groups,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/reports/time/",
+    params(
+        ("from" = String, Query, description = "集計期間の開始日（YYYY-MM-DD形式, この日を含む）"),
+        ("to" = String, Query, description = "集計期間の終了日（YYYY-MM-DD形式, この日を含む）"),
+        ("group_by" = Option<TimeReportGroupBy>, Query, description = "集計単位（day, week, month, project, company, skill_label）。デフォルトはday")
+    ),
+    responses(
+        (status = 200, description = "作業時間の集計に成功", body = TimeReportResponse),
+        (status = 400, description = "無効なクエリパラメータ", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/time/")]
+pub async fn get_time_report(
+    usecase: web::Data<Arc<ReportUseCase<MongoReportRepository>>>,
+    current_user: CurrentUser,
+    query: web::Query<TimeReportQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET get_time_report!!");
+
+    // バリデーションを実行
+    query.validate().map_err(AppError::ValidationError)?;
+
+    let ((work_minutes, work_log_count), groups) = usecase
+        .aggregate_work_time(&current_user.user_id, &query, current_user.time_zone)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(TimeReportResponse {
+        from: query.from,
+        to: query.to,
+        group_by: query.group_by,
+        work_minutes,
+        work_log_count,
+        groups,
+    }))
+}
@@ -0,0 +1,67 @@
+use crate::api::extractors::CurrentUser;
+use crate::dto::responses::companies::CompanyResponse;
+use crate::dto::responses::projects::ProjectResponse;
+use crate::dto::responses::trash::TrashResponse;
+use crate::dto::responses::work_logs::WorkLogResponse;
+use crate::errors::app_error::AppError;
+use crate::repositories::companies::MongoCompanyRepository;
+use crate::repositories::projects::MongoProjectRepository;
+use crate::repositories::work_logs::MongoWorkLogRepository;
+use crate::usecases::companies::CompanyUseCase;
+use crate::usecases::projects::ProjectUseCase;
+use crate::usecases::work_logs::WorkLogUseCase;
+use actix_web::{get, web, HttpResponse};
+use log::info;
+use std::sync::Arc;
+use tokio::try_join;
+
+#[utoipa::path(
+    get,
+    path = "/api/trash/",
+    responses(
+        (status = 200, description = "ゴミ箱の取得に成功", body = TrashResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/")]
+pub async fn get_trash(
+    company_usecase: web::Data<Arc<CompanyUseCase<MongoCompanyRepository>>>,
+    project_usecase: web::Data<Arc<ProjectUseCase<MongoProjectRepository>>>,
+    work_logs_usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET get_trash!!");
+
+    let user_id = &current_user.user_id;
+    let (companies, projects, work_logs) = try_join!(
+        company_usecase.get_deleted_companies(user_id),
+        project_usecase.get_deleted_projects(user_id),
+        work_logs_usecase.get_deleted_work_logs(user_id),
+    )?;
+
+    let to_response_error =
+        |e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e));
+    let response = TrashResponse {
+        companies: companies
+            .into_iter()
+            .map(CompanyResponse::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(to_response_error)?,
+        projects: projects
+            .into_iter()
+            .map(ProjectResponse::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(to_response_error)?,
+        work_logs: work_logs
+            .into_iter()
+            .map(WorkLogResponse::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(to_response_error)?,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -1,9 +1,12 @@
-use crate::dto::responses::users::UserResponse;
+use crate::api::extractors::CurrentUser;
+use crate::dto::responses::users::{CalendarFeedResponse, SessionResponse, UserResponse};
 use crate::errors::app_error::AppError;
+use crate::i18n::messages;
 use crate::models::users::UserUpdate;
 use crate::repositories::auth::MongoAuthRepository;
 use crate::usecases::auth::AuthUseCase;
-use actix_web::{get, put, web, HttpRequest, HttpResponse};
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+use bson::oid::ObjectId;
 use std::sync::Arc;
 use validator::Validate;
 
@@ -22,16 +25,9 @@ use validator::Validate;
 #[get("/me/")]
 pub async fn get_current_user(
     auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
-    req: HttpRequest,
+    current_user: CurrentUser,
 ) -> Result<HttpResponse, AppError> {
-    let auth_header = req
-        .headers()
-        .get("Authorization")
-        .and_then(|header| header.to_str().ok())
-        .ok_or_else(|| AppError::Unauthorized("認証ヘッダーが見つかりません".to_string()))?;
-
-    let token = auth_header.trim_start_matches("Bearer ");
-    let user = auth_usecase.get_current_user(token).await?;
+    let user = auth_usecase.get_current_user(&current_user.user_id).await?;
     let user_response = UserResponse::from(user);
     Ok(HttpResponse::Ok().json(user_response))
 }
@@ -41,8 +37,8 @@ pub async fn get_current_user(
     path = "/api/users/me/",
     request_body = UserUpdate,
     responses(
-        (status = 204, description = "ユーザー情報の更新に成功"),
-        (status = 400, description = "無効なリクエストデータ"),
+        (status = 204, description = "ユーザー情報の更新に成功（メールアドレスは確認用リンクで確認されるまで変更を申請中とする）"),
+        (status = 400, description = "無効なリクエストデータorメールアドレスが既に使用されている"),
         (status = 401, description = "認証失敗", body = ErrorResponse),
         (status = 404, description = "ユーザーが見つかりません"),
         (status = 500, description = "内部サーバーエラー")
@@ -54,22 +50,185 @@ pub async fn get_current_user(
 #[put("/me/")]
 pub async fn update_me(
     auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
-    req: HttpRequest,
+    current_user: CurrentUser,
     update_dto: web::Json<UserUpdate>,
 ) -> Result<HttpResponse, AppError> {
     let update_dto = update_dto.into_inner();
 
-    let auth_header = req
-        .headers()
-        .get("Authorization")
-        .and_then(|header| header.to_str().ok())
-        .ok_or_else(|| AppError::Unauthorized("認証ヘッダーが見つかりません".to_string()))?;
-
-    let token = auth_header.trim_start_matches("Bearer ");
-
     // バリデーションの実行
     update_dto.validate().map_err(AppError::ValidationError)?;
 
-    auth_usecase.update_me(token, &update_dto).await?;
+    auth_usecase
+        .update_me(&current_user.user_id, &update_dto)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/me/email-verification/",
+    responses(
+        (status = 202, description = "確認用リンクの再送信を受け付け（変更を申請中の場合は新しいメールアドレスに送信する）"),
+        (status = 400, description = "メールアドレスは確認済み", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "内部サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[post("/me/email-verification/")]
+pub async fn resend_email_verification(
+    auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
+    current_user: CurrentUser,
+) -> Result<HttpResponse, AppError> {
+    auth_usecase
+        .resend_verification_email(&current_user.user_id)
+        .await?;
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/me/calendar-feed/",
+    responses(
+        (status = 200, description = "カレンダーフィードのURLの発行に成功（発行済みの場合は以前のURLが無効になる）", body = CalendarFeedResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "内部サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[post("/me/calendar-feed/")]
+pub async fn rotate_calendar_feed(
+    auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
+    current_user: CurrentUser,
+    req: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let token = auth_usecase
+        .rotate_calendar_feed_token(&current_user.user_id)
+        .await?;
+
+    let connection_info = req.connection_info();
+    let url = format!(
+        "{}://{}/api/calendar/{}.ics",
+        connection_info.scheme(),
+        connection_info.host(),
+        token
+    );
+
+    Ok(HttpResponse::Ok().json(CalendarFeedResponse { url }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/me/calendar-feed/",
+    responses(
+        (status = 204, description = "カレンダーフィードの無効化に成功"),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "内部サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[delete("/me/calendar-feed/")]
+pub async fn delete_calendar_feed(
+    auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
+    current_user: CurrentUser,
+) -> Result<HttpResponse, AppError> {
+    auth_usecase
+        .disable_calendar_feed(&current_user.user_id)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/me/sessions/",
+    responses(
+        (status = 200, description = "ログイン中のセッション一覧の取得に成功（最後に使用された順）", body = Vec<SessionResponse>),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 500, description = "内部サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/me/sessions/")]
+pub async fn get_sessions(
+    auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
+    current_user: CurrentUser,
+) -> Result<HttpResponse, AppError> {
+    let sessions: Vec<SessionResponse> = auth_usecase
+        .get_sessions(&current_user.user_id)
+        .await?
+        .into_iter()
+        .map(|auth_token| SessionResponse::new(auth_token, &current_user.token_id))
+        .collect();
+
+    Ok(HttpResponse::Ok().json(sessions))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/me/sessions/",
+    responses(
+        (status = 204, description = "現在のセッション以外のすべてのセッションの失効に成功"),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 500, description = "内部サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[delete("/me/sessions/")]
+pub async fn delete_other_sessions(
+    auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
+    current_user: CurrentUser,
+) -> Result<HttpResponse, AppError> {
+    auth_usecase
+        .revoke_other_sessions(&current_user.user_id, &current_user.token_id)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/me/sessions/{id}/",
+    responses(
+        (status = 204, description = "セッションの失効に成功（現在のセッションを指定した場合はログアウトになる）"),
+        (status = 400, description = "無効なIDです", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "セッションが見つかりません", body = ErrorResponse),
+        (status = 500, description = "内部サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "セッションID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[delete("/me/sessions/{id}/")]
+pub async fn delete_session(
+    auth_usecase: web::Data<Arc<AuthUseCase<MongoAuthRepository>>>,
+    current_user: CurrentUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let session_id = ObjectId::parse_str(path.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    auth_usecase
+        .revoke_session(&current_user.user_id, &session_id)
+        .await?;
+
     Ok(HttpResponse::NoContent().finish())
 }
@@ -1,20 +1,49 @@
 use crate::{
-    dto::responses::work_logs::{WorkLogCreatedResponse, WorkLogResponse},
+    api::extractors::{CurrentUser, RequestLocale},
+    dto::responses::pagination::PaginatedResponse,
+    dto::responses::work_logs::{
+        WorkLogCreatedResponse, WorkLogImportErrorResponse, WorkLogImportResponse,
+        WorkLogOverlapResponse, WorkLogResponse,
+    },
     errors::app_error::AppError,
-    models::work_logs::{WorkLogCreate, WorkLogUpdate},
+    i18n::messages,
+    models::imports::WorkLogImportQuery,
+    models::work_logs::{
+        WorkLogCreate, WorkLogOverlapQuery, WorkLogQuery, WorkLogStart, WorkLogStop, WorkLogUpdate,
+    },
     repositories::work_logs::MongoWorkLogRepository,
     usecases::work_logs::WorkLogUseCase,
+    utils::import::parse_work_log_import,
+    utils::pagination::PageCursor,
 };
-use actix_web::{get, post, put, web, HttpResponse};
+use actix_web::{delete, get, post, put, web, HttpResponse};
 use bson::oid::ObjectId;
 use log::info;
 use std::sync::Arc;
+use validator::Validate;
+
+/// ページング前の総件数を返すレスポンスヘッダー
+pub const TOTAL_COUNT_HEADER: &str = "X-Total-Count";
+
+/// 時間が重複している勤怠のID（カンマ区切り）を返すレスポンスヘッダー
+pub const OVERLAPPING_WORK_LOGS_HEADER: &str = "X-Overlapping-Work-Log-Ids";
 
 #[utoipa::path(
     get,
     path = "/api/work-logs/",
+    params(
+        ("project_id" = Option<String>, Query, description = "プロジェクトID"),
+        ("company_id" = Option<String>, Query, description = "企業ID"),
+        ("from" = Option<String>, Query, description = "開始時間の範囲（この日時以降, RFC 3339形式）"),
+        ("to" = Option<String>, Query, description = "開始時間の範囲（この日時より前, RFC 3339形式）"),
+        ("memo" = Option<String>, Query, description = "メモ（部分一致）"),
+        ("limit" = Option<i64>, Query, description = "取得するドキュメント数の制限"),
+        ("cursor" = Option<String>, Query, description = "次のページを取得するためのカーソル（前回のレスポンスのnext_cursor）"),
+        ("sort" = Option<Vec<String>>, Query, description = "ソート条件（例: 'start_time:desc'）")
+    ),
     responses(
-        (status = 200, description = "勤怠の取得に成功", body = Vec<WorkLogResponse>),
+        (status = 200, description = "勤怠の取得に成功（総件数はX-Total-Countヘッダーでも返す）", body = PaginatedWorkLogResponse),
+        (status = 400, description = "無効なクエリパラメータ", body = ErrorResponse),
         (status = 401, description = "認証失敗", body = ErrorResponse),
     ),
     security(
@@ -24,21 +53,34 @@ use std::sync::Arc;
 #[get("/")]
 pub async fn get_all_work_logs(
     usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
+    query: web::Query<WorkLogQuery>,
 ) -> Result<HttpResponse, AppError> {
     info!("called GET get_all_work_logs!!");
 
-    let work_logs = match usecase.get_all_work_logs().await {
-        Ok(work_logs) => work_logs,
-        Err(e) => return Err(e),
-    };
+    // バリデーションを実行
+    query.validate().map_err(AppError::ValidationError)?;
 
-    let response = work_logs
-        .into_iter()
-        .map(WorkLogResponse::try_from)
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?;
+    let sort = query.parse_sort_params();
+    let cursor = PageCursor::parse(query.cursor.as_deref(), &sort)?;
+    let work_logs = usecase
+        .search_work_logs(
+            &current_user.user_id,
+            &query.to_filter(),
+            query.limit,
+            cursor,
+            sort,
+        )
+        .await?;
 
-    Ok(HttpResponse::Ok().json(response))
+    let response: PaginatedResponse<WorkLogResponse> = work_logs
+        .try_map(WorkLogResponse::try_from)
+        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?
+        .into();
+
+    Ok(HttpResponse::Ok()
+        .insert_header((TOTAL_COUNT_HEADER, response.total.to_string()))
+        .json(response))
 }
 
 #[utoipa::path(
@@ -61,16 +103,20 @@ pub async fn get_all_work_logs(
 #[get("/{id}/")]
 pub async fn get_work_logs_by_id(
     usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
     id: web::Path<String>,
 ) -> Result<HttpResponse, AppError> {
     info!("called GET get_work_logs_by_id!!");
 
     let obj_id = ObjectId::parse_str(id.into_inner())
-        .map_err(|_| AppError::BadRequest("無効なIDです".to_string()))?;
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
 
-    let work_logs = match usecase.get_work_logs_by_id(&obj_id).await {
+    let work_logs = match usecase
+        .get_work_logs_by_id(&current_user.user_id, &obj_id)
+        .await
+    {
         Ok(Some(work_logs)) => work_logs,
-        Ok(None) => return Err(AppError::NotFound("勤怠が見つかりません".to_string())),
+        Ok(None) => return Err(AppError::NotFound(messages::WORK_LOG_NOT_FOUND.into())),
         Err(e) => return Err(e),
     };
 
@@ -84,10 +130,14 @@ pub async fn get_work_logs_by_id(
     post,
     path = "/api/work-logs/",
     request_body = WorkLogCreate,
+    params(
+        ("allow_overlap" = Option<bool>, Query, description = "trueの場合は既存の勤怠と時間が重複していても作成し、重複した勤怠のIDを返す")
+    ),
     responses(
-        (status = 201, description = "勤怠の作成に成功", body = WorkLogCreatedResponse),
-        (status = 400, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 201, description = "勤怠の作成に成功（日付（ユーザーのタイムゾーンの0時）をまたぐ勤怠は日ごとに分割し、最初の勤怠のIDを返す）", body = WorkLogCreatedResponse),
+        (status = 400, description = "無効なリクエストデータ、または開始から終了までの時間が上限を超えています", body = ErrorResponse),
         (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 409, description = "既存の勤怠と時間が重複しています（重複した勤怠のIDはconflicting_idsで返す）", body = ErrorResponse),
         (status = 500, description = "サーバーエラー", body = ErrorResponse)
     ),
     security(
@@ -97,6 +147,8 @@ pub async fn get_work_logs_by_id(
 #[post("/")]
 pub async fn create_work_logs(
     usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
+    query: web::Query<WorkLogOverlapQuery>,
     create_dto: web::Json<WorkLogCreate>,
 ) -> Result<HttpResponse, AppError> {
     info!("called POST create_work_logs!!");
@@ -106,9 +158,19 @@ pub async fn create_work_logs(
         .validate_all()
         .map_err(AppError::ValidationError)?;
 
-    let work_logs_id = usecase.create_work_logs(&create_dto.into_inner()).await?;
+    let (work_logs_id, overlapping_work_log_ids) = usecase
+        .create_work_logs(
+            &current_user.user_id,
+            &create_dto.into_inner(),
+            query.allow_overlap,
+            current_user.time_zone,
+        )
+        .await?;
 
-    Ok(HttpResponse::Created().json(WorkLogCreatedResponse::from(work_logs_id)))
+    Ok(HttpResponse::Created().json(WorkLogCreatedResponse {
+        id: work_logs_id,
+        overlapping_work_log_ids,
+    }))
 }
 
 #[utoipa::path(
@@ -116,14 +178,16 @@ pub async fn create_work_logs(
     path = "/api/work-logs/{id}/",
     request_body = WorkLogUpdate,
     responses(
-        (status = 204, description = "勤怠の更新に成功"),
-        (status = 400, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 204, description = "勤怠の更新に成功（時間が重複している勤怠のIDはX-Overlapping-Work-Log-Idsヘッダーで返す。日付をまたぐ場合は最初の日の分に更新し、残りの日の勤怠を登録する）"),
+        (status = 400, description = "無効なリクエストデータ、または開始から終了までの時間が上限を超えています", body = ErrorResponse),
         (status = 401, description = "認証失敗", body = ErrorResponse),
         (status = 404, description = "勤怠が見つかりません", body = ErrorResponse),
+        (status = 409, description = "既存の勤怠と時間が重複しています（重複した勤怠のIDはconflicting_idsで返す）", body = ErrorResponse),
         (status = 500, description = "サーバーエラー", body = ErrorResponse)
     ),
     params(
-        ("id" = String, Path, description = "勤怠ID")
+        ("id" = String, Path, description = "勤怠ID"),
+        ("allow_overlap" = Option<bool>, Query, description = "trueの場合は既存の勤怠と時間が重複していても更新する")
     ),
     security(
         ("bearer_auth" = [])
@@ -132,22 +196,308 @@ pub async fn create_work_logs(
 #[put("/{id}/")]
 pub async fn update_work_logs_by_id(
     usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
     path: web::Path<String>,
+    query: web::Query<WorkLogOverlapQuery>,
     update_dto: web::Json<WorkLogUpdate>,
 ) -> Result<HttpResponse, AppError> {
     info!("called update_work_logs_by_id!!");
 
     let obj_id = ObjectId::parse_str(path.into_inner())
-        .map_err(|_| AppError::BadRequest("無効なIDです".to_string()))?;
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
 
     // バリデーションチェック
     update_dto
         .validate_all()
         .map_err(AppError::ValidationError)?;
 
+    let overlapping_work_log_ids = usecase
+        .update_work_logs(
+            &current_user.user_id,
+            &obj_id,
+            &update_dto.into_inner(),
+            query.allow_overlap,
+            current_user.time_zone,
+        )
+        .await?;
+
+    let mut response = HttpResponse::NoContent();
+    if !overlapping_work_log_ids.is_empty() {
+        let ids: Vec<String> = overlapping_work_log_ids
+            .iter()
+            .map(|id| id.to_hex())
+            .collect();
+        response.insert_header((OVERLAPPING_WORK_LOGS_HEADER, ids.join(",")));
+    }
+    Ok(response.finish())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/work-logs/{id}/",
+    responses(
+        (status = 204, description = "勤怠の削除に成功"),
+        (status = 400, description = "無効なIDです", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "勤怠が見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "勤怠ID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[delete("/{id}/")]
+pub async fn delete_work_logs_by_id(
+    usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    info!("called DELETE delete_work_logs_by_id!!");
+
+    let obj_id = ObjectId::parse_str(path.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
     usecase
-        .update_work_logs(&obj_id, &update_dto.into_inner())
+        .delete_work_logs(&current_user.user_id, &obj_id)
         .await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
+
+#[utoipa::path(
+    post,
+    path = "/api/work-logs/{id}/restore/",
+    responses(
+        (status = 204, description = "勤怠の復元に成功"),
+        (status = 400, description = "無効なIDです", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "ゴミ箱に勤怠が見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "勤怠ID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[post("/{id}/restore/")]
+pub async fn restore_work_logs_by_id(
+    usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    info!("called POST restore_work_logs_by_id!!");
+
+    let obj_id = ObjectId::parse_str(path.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    usecase
+        .restore_work_logs(&current_user.user_id, &obj_id)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/work-logs/active/",
+    responses(
+        (status = 200, description = "作業中の勤怠の取得に成功", body = WorkLogResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "作業中の勤怠はありません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/active/")]
+pub async fn get_active_work_log(
+    usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET get_active_work_log!!");
+
+    let work_log = usecase
+        .get_active_work_log(&current_user.user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(messages::ACTIVE_WORK_LOG_NOT_FOUND.into()))?;
+
+    let response = WorkLogResponse::try_from(work_log)
+        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/work-logs/overlaps/",
+    responses(
+        (status = 200, description = "時間が重複している勤怠の組み合わせの取得に成功（重複の開始時間順）", body = Vec<WorkLogOverlapResponse>),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/overlaps/")]
+pub async fn get_overlapping_work_logs(
+    usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
+) -> Result<HttpResponse, AppError> {
+    info!("called GET get_overlapping_work_logs!!");
+
+    let overlaps = usecase
+        .find_overlapping_work_logs(&current_user.user_id)
+        .await?;
+    let response: Vec<WorkLogOverlapResponse> = overlaps
+        .into_iter()
+        .map(WorkLogOverlapResponse::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/work-logs/start/",
+    request_body = WorkLogStart,
+    responses(
+        (status = 201, description = "作業の開始に成功", body = WorkLogCreatedResponse),
+        (status = 400, description = "無効なリクエストデータ、または既に作業中の勤怠があります", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "プロジェクトが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[post("/start/")]
+pub async fn start_work_log(
+    usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
+    start_dto: web::Json<WorkLogStart>,
+) -> Result<HttpResponse, AppError> {
+    info!("called POST start_work_log!!");
+
+    // バリデーションチェック
+    start_dto.validate().map_err(AppError::ValidationError)?;
+
+    let work_logs_id = usecase
+        .start_work_log(&current_user.user_id, &start_dto.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Created().json(WorkLogCreatedResponse::from(work_logs_id)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/work-logs/{id}/stop/",
+    request_body = WorkLogStop,
+    responses(
+        (status = 200, description = "作業の終了に成功（日付をまたいだ場合は最初の日の分の勤怠を返す）", body = WorkLogResponse),
+        (status = 400, description = "無効なリクエストデータ、既に終了している勤怠、または作業時間が上限を超えています", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "勤怠が見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "勤怠ID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[post("/{id}/stop/")]
+pub async fn stop_work_log(
+    usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
+    path: web::Path<String>,
+    stop_dto: web::Json<WorkLogStop>,
+) -> Result<HttpResponse, AppError> {
+    info!("called POST stop_work_log!!");
+
+    let obj_id = ObjectId::parse_str(path.into_inner())
+        .map_err(|_| AppError::BadRequest(messages::INVALID_ID.into()))?;
+
+    // バリデーションチェック
+    stop_dto.validate().map_err(AppError::ValidationError)?;
+
+    let work_log = usecase
+        .stop_work_log(
+            &current_user.user_id,
+            &obj_id,
+            &stop_dto.into_inner(),
+            current_user.time_zone,
+        )
+        .await?;
+
+    let response = WorkLogResponse::try_from(work_log)
+        .map_err(|e| AppError::InternalServerError(format!("データの変換に失敗しました: {}", e)))?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/work-logs/import/",
+    request_body(
+        content = String,
+        description = "CSV（列見出し: project,start_time,end_time,break_time,actual_work_minutes,memo）またはJSON Lines。projectにはプロジェクトIDまたはプロジェクト名を指定する",
+        content_type = "text/csv"
+    ),
+    params(
+        ("format" = Option<ImportFormat>, Query, description = "入力形式（csv, jsonl）。デフォルトはcsv"),
+        ("dry_run" = Option<bool>, Query, description = "trueの場合は検証のみ行い、勤怠を登録しない"),
+        ("allow_overlap" = Option<bool>, Query, description = "trueの場合は既存の勤怠や他の行と時間が重複していても登録する")
+    ),
+    responses(
+        (status = 200, description = "インポートに成功（エラーのあった行は登録せずerrorsで返す）", body = WorkLogImportResponse),
+        (status = 400, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+// リクエストボディの上限をこのエンドポイントのみ引き上げるため、ルーティングは`routes::work_logs_scope`で登録する
+pub async fn import_work_logs(
+    usecase: web::Data<Arc<WorkLogUseCase<MongoWorkLogRepository>>>,
+    current_user: CurrentUser,
+    RequestLocale(locale): RequestLocale,
+    query: web::Query<WorkLogImportQuery>,
+    body: web::Bytes,
+) -> Result<HttpResponse, AppError> {
+    info!("called POST import_work_logs!!");
+
+    let rows = parse_work_log_import(&body, query.format)?;
+    let total_rows = rows.len();
+
+    let (valid_rows, errors) = usecase
+        .import_work_logs(
+            &current_user.user_id,
+            rows,
+            query.dry_run,
+            query.allow_overlap,
+            current_user.time_zone,
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(WorkLogImportResponse {
+        dry_run: query.dry_run,
+        total_rows,
+        valid_rows,
+        imported_rows: if query.dry_run { 0 } else { valid_rows },
+        errors: errors
+            .into_iter()
+            .map(|error| WorkLogImportErrorResponse::new(error, locale))
+            .collect(),
+    }))
+}
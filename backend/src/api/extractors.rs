@@ -0,0 +1,166 @@
+use crate::config::admin::AdminConfig;
+use crate::constants::time_zone::DEFAULT_TIME_ZONE;
+use crate::errors::app_error::AppError;
+use crate::i18n::{messages, negotiate_locale};
+use crate::models::auth::SessionClient;
+use crate::models::users::{EngineerRole, Locale, UserAuthState};
+use crate::utils::jwt::Claims;
+use actix_web::http::header::{HeaderMap, ACCEPT_LANGUAGE, USER_AGENT};
+use actix_web::{dev::Payload, web, FromRequest, HttpMessage, HttpRequest};
+use bson::oid::ObjectId;
+use chrono_tz::Tz;
+use std::future::{ready, Ready};
+
+/// 認証済みユーザーの識別情報
+///
+/// JWT認証ミドルウェア(`middleware::jwt::validator`)が検証済みのトークンから生成し、
+/// リクエストのextensionsに格納する。ハンドラーは引数に指定するだけで取得でき、
+/// ハンドラー側でDBへの問い合わせは発生しない。
+///
+/// ※`role`はトークン発行時点の値のため、ユーザー情報更新後はトークンのリフレッシュまで反映されない。
+/// `time_zone`・`locale`は認証ミドルウェアがトークンの検証時に（同じ問い合わせで）ユーザー情報から
+/// 取得して設定するため、更新後すぐに反映される
+#[derive(Debug, Clone)]
+pub struct CurrentUser {
+    pub user_id: ObjectId,          // ユーザーID
+    pub role: Option<EngineerRole>, // ユーザーのロール
+    pub token_id: ObjectId,         // 認証トークン(auth_tokensドキュメント)のID
+    pub time_zone: Tz,              // 日付の区切りや集計に使うタイムゾーン
+    pub locale: Option<Locale>,     // ユーザーが設定した表示言語（未設定の場合はNone）
+}
+
+impl CurrentUser {
+    /// ユーザー情報のタイムゾーン・表示言語を設定する
+    pub fn with_preferences(self, user: &UserAuthState) -> Self {
+        Self {
+            time_zone: user.time_zone(),
+            locale: user.locale,
+            ..self
+        }
+    }
+}
+
+impl TryFrom<Claims> for CurrentUser {
+    type Error = AppError;
+
+    fn try_from(claims: Claims) -> Result<Self, Self::Error> {
+        let invalid_token = || AppError::Unauthorized(messages::INVALID_ACCESS_TOKEN.into());
+
+        Ok(Self {
+            user_id: ObjectId::parse_str(&claims.sub).map_err(|_| invalid_token())?,
+            role: claims.role,
+            token_id: ObjectId::parse_str(&claims.jti).map_err(|_| invalid_token())?,
+            time_zone: DEFAULT_TIME_ZONE,
+            locale: None,
+        })
+    }
+}
+
+impl FromRequest for CurrentUser {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        // 認証ミドルウェアが適用されていないスコープで使用された場合は認証エラーとする
+        ready(
+            req.extensions()
+                .get::<CurrentUser>()
+                .cloned()
+                .ok_or_else(|| AppError::Unauthorized(messages::UNAUTHENTICATED.into())),
+        )
+    }
+}
+
+/// 管理者として認証済みのユーザー
+///
+/// `CurrentUser`のうち、`ADMIN_USER_IDS`に含まれるユーザーのみを受け付ける。
+#[derive(Debug, Clone)]
+pub struct AdminUser(pub CurrentUser);
+
+impl FromRequest for AdminUser {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let current_user = match CurrentUser::from_request(req, payload).into_inner() {
+            Ok(current_user) => current_user,
+            Err(e) => return ready(Err(e)),
+        };
+
+        let is_admin = req
+            .app_data::<web::Data<AdminConfig>>()
+            .is_some_and(|config| config.is_admin(&current_user.user_id));
+
+        ready(if is_admin {
+            Ok(Self(current_user))
+        } else {
+            Err(AppError::Forbidden(messages::ADMIN_REQUIRED.into()))
+        })
+    }
+}
+
+/// リクエストの表示言語
+///
+/// ユーザーが表示言語を設定している場合はその言語、未設定または未認証の場合は
+/// `Accept-Language`ヘッダーから選んだ言語、いずれもなければデフォルト（日本語）とする。
+#[derive(Debug, Clone, Copy)]
+pub struct RequestLocale(pub Locale);
+
+impl RequestLocale {
+    pub fn of(req: &HttpRequest) -> Self {
+        let user_locale = req
+            .extensions()
+            .get::<CurrentUser>()
+            .and_then(|current_user| current_user.locale);
+        Self(
+            user_locale
+                .or_else(|| Self::accept_language(req.headers()))
+                .unwrap_or_default(),
+        )
+    }
+
+    /// `Accept-Language`ヘッダーから選んだ表示言語（ヘッダーがない・対応する言語がない場合はNone）
+    pub fn accept_language(headers: &HeaderMap) -> Option<Locale> {
+        headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(negotiate_locale)
+    }
+}
+
+impl FromRequest for RequestLocale {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(Ok(Self::of(req)))
+    }
+}
+
+/// 保存するUser-Agentの最大文字数（極端に長いヘッダーをそのまま保存しないため）
+const USER_AGENT_MAX_CHARS: usize = 512;
+
+/// リクエスト元のクライアントの情報（User-Agent・IPアドレス）
+///
+/// IPアドレスはレート制限と同様に`realip_remote_addr`から取得する
+impl FromRequest for SessionClient {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let user_agent = req
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.chars().take(USER_AGENT_MAX_CHARS).collect());
+        let ip_address = req
+            .connection_info()
+            .realip_remote_addr()
+            .map(str::to_string);
+
+        ready(Ok(Self {
+            user_agent,
+            ip_address,
+        }))
+    }
+}
@@ -1,3 +1,4 @@
 pub mod common;
 pub mod endpoints;
+pub mod extractors;
 pub mod routes;
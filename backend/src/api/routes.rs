@@ -1,6 +1,11 @@
 use actix_web::{web, Scope};
 
-use crate::api::endpoints::{companies, projects, users, work_logs};
+use crate::models::imports::IMPORT_MAX_BYTES;
+
+use crate::api::endpoints::{
+    admin, analytics, calendar, companies, exports, invoices, projects, reports, trash, users,
+    work_logs,
+};
 
 pub fn projects_scope() -> Scope {
     web::scope("/projects")
@@ -8,14 +13,29 @@ pub fn projects_scope() -> Scope {
         .service(projects::get_project_by_id)
         .service(projects::create_project)
         .service(projects::update_project_by_id)
+        .service(projects::delete_project_by_id)
+        .service(projects::restore_project_by_id)
 }
 
 pub fn work_logs_scope() -> Scope {
     web::scope("/work-logs")
         .service(work_logs::get_all_work_logs)
+        // "/{id}/"より先に登録しないとIDとして扱われるため注意
+        .service(work_logs::get_active_work_log)
+        .service(work_logs::get_overlapping_work_logs)
+        .service(work_logs::start_work_log)
+        .service(work_logs::stop_work_log)
+        .service(
+            web::resource("/import/")
+                // 一括インポートでは数ヶ月分の勤怠を受け付けるため、このエンドポイントのみリクエストボディの上限を引き上げる
+                .app_data(web::PayloadConfig::new(IMPORT_MAX_BYTES))
+                .route(web::post().to(work_logs::import_work_logs)),
+        )
         .service(work_logs::get_work_logs_by_id)
         .service(work_logs::create_work_logs)
         .service(work_logs::update_work_logs_by_id)
+        .service(work_logs::delete_work_logs_by_id)
+        .service(work_logs::restore_work_logs_by_id)
 }
 
 pub fn companies_scope() -> Scope {
@@ -25,10 +45,55 @@ pub fn companies_scope() -> Scope {
         .service(companies::get_company_by_id)
         .service(companies::create_company)
         .service(companies::update_company_by_id)
+        .service(companies::delete_company_by_id)
+        .service(companies::restore_company_by_id)
+}
+
+pub fn reports_scope() -> Scope {
+    web::scope("/reports")
+        .service(reports::get_earnings)
+        .service(reports::get_time_report)
+}
+
+pub fn analytics_scope() -> Scope {
+    web::scope("/analytics").service(analytics::get_skill_analytics)
+}
+
+pub fn exports_scope() -> Scope {
+    web::scope("/exports").service(exports::export_work_logs)
+}
+
+pub fn invoices_scope() -> Scope {
+    web::scope("/invoices")
+        .service(invoices::get_invoices)
+        .service(invoices::get_invoice_by_id)
+        .service(invoices::get_invoice_document)
+        .service(invoices::create_invoice)
+        .service(invoices::update_invoice_status)
+        .service(invoices::delete_invoice_by_id)
+}
+
+pub fn trash_scope() -> Scope {
+    web::scope("/trash").service(trash::get_trash)
 }
 
 pub fn users_scope() -> Scope {
     web::scope("/users")
         .service(users::get_current_user)
         .service(users::update_me)
+        .service(users::resend_email_verification)
+        .service(users::rotate_calendar_feed)
+        .service(users::delete_calendar_feed)
+        .service(users::get_sessions)
+        .service(users::delete_other_sessions)
+        .service(users::delete_session)
+}
+
+/// カレンダーアプリから取得されるため、認証ミドルウェアの外側に登録する
+pub fn calendar_scope() -> Scope {
+    web::scope("/calendar").service(calendar::get_calendar_feed)
+}
+
+pub fn admin_scope() -> Scope {
+    web::scope("/admin").service(admin::recalculate_total_working_time)
 }
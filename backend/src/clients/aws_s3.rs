@@ -5,6 +5,7 @@ use uuid::Uuid;
 
 use crate::config::s3::S3Config;
 use crate::errors::app_error::AppError;
+use crate::i18n::messages;
 
 pub struct S3Client {
     config: Arc<S3Config>,
@@ -20,15 +21,14 @@ impl S3Client {
         let file_name = format!("avatars/{}.png", Uuid::now_v7());
 
         // 画像フォーマットを明示的に判別
-        let format = image::guess_format(image_data).map_err(|e| {
-            AppError::BadRequest(format!("画像フォーマットの判別に失敗しました: {}", e))
-        })?;
+        let format = image::guess_format(image_data)
+            .map_err(|e| AppError::BadRequest(messages::UNKNOWN_IMAGE_FORMAT.with("detail", e)))?;
 
         log::debug!("Detected image format: {:?}", format);
 
         // 画像をデコードし、PNGに変換
         let img = image::load_from_memory_with_format(image_data, format)
-            .map_err(|e| AppError::BadRequest(format!("無効な画像データ: {}", e)))?;
+            .map_err(|e| AppError::BadRequest(messages::INVALID_IMAGE.with("detail", e)))?;
 
         let mut png_data = Vec::new();
         img.write_to(&mut std::io::Cursor::new(&mut png_data), ImageFormat::Png)
@@ -0,0 +1,138 @@
+use crate::errors::app_error::AppError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// 送信するメール（本文はテキスト形式）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// メールの送信手段
+///
+/// 本番環境ではSMTP、ローカル開発やテストではファイルに書き出すアウトボックスを使用する
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, mail: &Mail) -> Result<(), AppError>;
+}
+
+/// SMTPサーバー経由でメールを送信する
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    /// STARTTLSを使用しない場合は、ローカルのメールサーバー（MailHogなど）への接続のみに使用すること
+    pub fn new(
+        host: &str,
+        port: u16,
+        credentials: Option<(String, String)>,
+        starttls: bool,
+        from: &str,
+    ) -> Result<Self, AppError> {
+        let mut builder = if starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host).map_err(|e| {
+                AppError::InternalServerError(format!("SMTPの接続設定に失敗しました: {}", e))
+            })?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+        }
+        .port(port);
+        if let Some((username, password)) = credentials {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        let from = from.parse().map_err(|e| {
+            AppError::InternalServerError(format!("送信元のメールアドレスが無効です: {}", e))
+        })?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), AppError> {
+        let to: Mailbox = mail.to.parse().map_err(|e| {
+            AppError::InternalServerError(format!("送信先のメールアドレスが無効です: {}", e))
+        })?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(&mail.subject)
+            .body(mail.body.clone())
+            .map_err(|e| {
+                AppError::InternalServerError(format!("メールの作成に失敗しました: {}", e))
+            })?;
+
+        self.transport.send(message).await.map_err(|e| {
+            AppError::InternalServerError(format!("メールの送信に失敗しました: {}", e))
+        })?;
+        Ok(())
+    }
+}
+
+/// アウトボックスに書き出すメール
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutboxMail {
+    #[serde(flatten)]
+    pub mail: Mail,
+    pub created_at: DateTime<Utc>,
+}
+
+/// メールを送信せず、アウトボックス（ディレクトリ）にJSONファイルとして書き出す（ローカル開発・テスト用）
+pub struct OutboxMailer {
+    dir: PathBuf,
+}
+
+impl OutboxMailer {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl Mailer for OutboxMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), AppError> {
+        let outbox_error = |e: std::io::Error| {
+            AppError::InternalServerError(format!(
+                "アウトボックスへのメールの書き出しに失敗しました: {}",
+                e
+            ))
+        };
+
+        let outbox_mail = OutboxMail {
+            mail: mail.clone(),
+            created_at: Utc::now(),
+        };
+        let json = serde_json::to_vec_pretty(&outbox_mail)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+        // UUID v7は時系列順に並ぶため、ファイル名の順に送信順となる
+        let path = self.dir.join(format!("{}.json", Uuid::now_v7()));
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(outbox_error)?;
+        tokio::fs::write(&path, json).await.map_err(outbox_error)?;
+
+        log::info!(
+            "メールをアウトボックスに書き出しました: to={}, subject={}, path={}",
+            mail.to,
+            mail.subject,
+            path.display()
+        );
+        Ok(())
+    }
+}
@@ -1,2 +1,3 @@
 pub mod aws_s3;
+pub mod mailer;
 pub mod redis;
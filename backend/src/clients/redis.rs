@@ -1,4 +1,4 @@
-use redis::{Client, RedisError};
+use redis::{AsyncCommands, Client, FromRedisValue, RedisError, ToRedisArgs};
 use std::io::{Error, ErrorKind, Result};
 use std::sync::Arc;
 use std::time::Duration;
@@ -38,7 +38,7 @@ impl RedisClient {
         let con = timeout(self.timeout, client.get_multiplexed_async_connection())
             .await
             .map_err(|_| Error::new(ErrorKind::TimedOut, "Connection timed out"))?
-            .map_err(|e| Error::new(ErrorKind::Other, format!("Redis error: {}", e)))?;
+            .map_err(|e| Error::other(format!("Redis error: {}", e)))?;
 
         // 指定された操作の実行とタイムアウト処理
         timeout(self.timeout, f(con))
@@ -57,6 +57,16 @@ impl RedisClient {
         max_requests: u64,
         expiry: u64,
     ) -> Result<bool> {
+        let count = self.increment(key, expiry).await?;
+        // カウントが最大リクエスト数以下かどうかを返す
+        Ok(count <= max_requests)
+    }
+
+    /// キーをインクリメントし、インクリメント後の値を返す
+    ///
+    /// - `key`: インクリメントするキー
+    /// - `expiry`: キーの有効期限（秒）
+    pub async fn increment(&self, key: &str, expiry: u64) -> Result<u64> {
         let key = key.to_string();
 
         self.with_connection(move |mut con| {
@@ -69,11 +79,57 @@ impl RedisClient {
                     .ignore() // expireの結果を無視
                     .query_async(&mut con)
                     .await
-                    .map_err(|e: RedisError| Error::new(ErrorKind::Other, e))?;
+                    .map_err(|e: RedisError| Error::other(e))?;
+
+                Ok(result.0)
+            })
+        })
+        .await
+    }
+
+    /// キーの値を取得する（キーが存在しない場合はNone）
+    pub async fn get<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: FromRedisValue + Send + 'static,
+    {
+        let key = key.to_string();
+
+        self.with_connection(move |mut con| {
+            Box::pin(async move { con.get(&key).await.map_err(|e: RedisError| Error::other(e)) })
+        })
+        .await
+    }
 
-                let count = result.0;
-                // カウントが最大リクエスト数以下かどうかを返す
-                Ok(count <= max_requests)
+    /// 有効期限付きでキーの値を設定する
+    ///
+    /// - `key`: 設定するキー
+    /// - `value`: 設定する値
+    /// - `expiry`: キーの有効期限（秒）
+    pub async fn set_with_expiry<T>(&self, key: &str, value: T, expiry: u64) -> Result<()>
+    where
+        T: ToRedisArgs + Send + Sync + 'static,
+    {
+        let key = key.to_string();
+
+        self.with_connection(move |mut con| {
+            Box::pin(async move {
+                con.set_ex(&key, value, expiry)
+                    .await
+                    .map_err(|e: RedisError| Error::other(e))
+            })
+        })
+        .await
+    }
+
+    /// キーを削除する
+    pub async fn delete(&self, keys: &[String]) -> Result<()> {
+        let keys = keys.to_vec();
+
+        self.with_connection(move |mut con| {
+            Box::pin(async move {
+                con.del(&keys)
+                    .await
+                    .map_err(|e: RedisError| Error::other(e))
             })
         })
         .await
@@ -88,7 +144,7 @@ impl RedisClient {
                 redis::cmd("PING")
                     .query_async(&mut con)
                     .await
-                    .map_err(|e: RedisError| Error::new(ErrorKind::Other, e))
+                    .map_err(|e: RedisError| Error::other(e))
             })
         })
         .await
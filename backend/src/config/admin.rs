@@ -0,0 +1,32 @@
+use bson::oid::ObjectId;
+use dotenvy::dotenv;
+use std::collections::HashSet;
+use std::env;
+
+#[derive(Clone, Default)]
+pub struct AdminConfig {
+    pub user_ids: HashSet<ObjectId>, // 管理者として扱うユーザーID
+}
+
+impl AdminConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        let user_ids = env::var("ADMIN_USER_IDS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .filter_map(|id| {
+                ObjectId::parse_str(id)
+                    .map_err(|e| log::warn!("ADMIN_USER_IDSの解析に失敗: {} ({})", id, e))
+                    .ok()
+            })
+            .collect();
+
+        Self { user_ids }
+    }
+
+    pub fn is_admin(&self, user_id: &ObjectId) -> bool {
+        self.user_ids.contains(user_id)
+    }
+}
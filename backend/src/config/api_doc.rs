@@ -1,20 +1,50 @@
-use crate::api::endpoints::{auth, companies, projects, users, work_logs};
+use crate::api::endpoints::{
+    admin, analytics, auth, calendar, companies, exports, invoices, projects, reports, trash,
+    users, work_logs,
+};
+use crate::dto::responses::admin::RecalculateTotalsResponse;
 use crate::dto::responses::auth::{AuthResponse, AuthTokenCreatedResponse};
 use crate::dto::responses::companies::{
     CompaniesWithProjects, CompaniesWithProjectsResponse, CompanyCreatedResponse, CompanyResponse,
 };
+use crate::dto::responses::invoices::{
+    InvoiceCreatedResponse, InvoiceLineItemResponse, InvoiceResponse,
+};
+use crate::dto::responses::pagination::{
+    PaginatedCompanyResponse, PaginatedInvoiceResponse, PaginatedProjectResponse,
+    PaginatedWorkLogResponse,
+};
 use crate::dto::responses::projects::{ProjectCreatedResponse, ProjectResponse};
-use crate::dto::responses::users::UserResponse;
-use crate::dto::responses::work_logs::{WorkLogCreatedResponse, WorkLogResponse};
+use crate::dto::responses::reports::{
+    EarningsResponse, SkillAnalyticsResponse, TimeReportResponse,
+};
+use crate::dto::responses::trash::TrashResponse;
+use crate::dto::responses::users::{CalendarFeedResponse, SessionResponse, UserResponse};
+use crate::dto::responses::work_logs::{
+    WorkLogCreatedResponse, WorkLogImportErrorResponse, WorkLogImportResponse,
+    WorkLogOverlapResponse, WorkLogResponse,
+};
 use crate::errors::app_error::FieldError;
 use crate::errors::app_error::{AppError, ErrorResponse};
-use crate::models::auth::{AuthTokenInDB, AuthTokenLogin};
+use crate::models::auth::{
+    AuthTokenInDB, AuthTokenLogin, EmailVerificationConfirm, PasswordResetConfirm,
+    PasswordResetRequest,
+};
 use crate::models::companies::{
     AnnualSales, Bonus, CompanyCommon, CompanyCreate, CompanyStatus, CompanyUpdate, ContractType,
 };
+use crate::models::exports::ExportFormat;
+use crate::models::imports::ImportFormat;
+use crate::models::invoices::{
+    InvoiceCreate, InvoiceDocumentFormat, InvoiceStatus, InvoiceStatusUpdate,
+};
 use crate::models::projects::{ProjectCreate, ProjectStatus, ProjectUpdate};
-use crate::models::users::{EngineerRole, UserCreate, UserUpdate};
-use crate::models::work_logs::{WorkLogCreate, WorkLogUpdate};
+use crate::models::reports::{
+    EarningsAmount, EarningsGroup, EarningsGroupBy, SkillCompany, SkillTrend, SkillUsage,
+    TimeReportGroup, TimeReportGroupBy,
+};
+use crate::models::users::{EngineerRole, Locale, UserCreate, UserUpdate};
+use crate::models::work_logs::{WorkLogCreate, WorkLogStart, WorkLogStop, WorkLogUpdate};
 use utoipa::OpenApi;
 
 #[derive(OpenApi)]
@@ -24,21 +54,54 @@ use utoipa::OpenApi;
         projects::create_project,
         projects::update_project_by_id,
         projects::get_projects,
+        projects::delete_project_by_id,
+        projects::restore_project_by_id,
         work_logs::get_all_work_logs,
         work_logs::get_work_logs_by_id,
         work_logs::create_work_logs,
         work_logs::update_work_logs_by_id,
+        work_logs::delete_work_logs_by_id,
+        work_logs::restore_work_logs_by_id,
+        work_logs::get_active_work_log,
+        work_logs::get_overlapping_work_logs,
+        work_logs::start_work_log,
+        work_logs::stop_work_log,
+        work_logs::import_work_logs,
         companies::get_company_by_id,
         companies::create_company,
         companies::update_company_by_id,
         companies::get_all_companies,
         companies::get_all_companies_with_projects,
+        companies::delete_company_by_id,
+        companies::restore_company_by_id,
+        trash::get_trash,
+        reports::get_earnings,
+        reports::get_time_report,
+        analytics::get_skill_analytics,
+        exports::export_work_logs,
+        invoices::get_invoices,
+        invoices::get_invoice_by_id,
+        invoices::get_invoice_document,
+        invoices::create_invoice,
+        invoices::update_invoice_status,
+        invoices::delete_invoice_by_id,
+        admin::recalculate_total_working_time,
         auth::login,
         auth::logout,
         auth::refresh,
         auth::register,
+        auth::request_password_reset,
+        auth::confirm_password_reset,
+        auth::confirm_email_verification,
         users::get_current_user,
         users::update_me,
+        users::resend_email_verification,
+        users::rotate_calendar_feed,
+        users::delete_calendar_feed,
+        users::get_sessions,
+        users::delete_other_sessions,
+        users::delete_session,
+        calendar::get_calendar_feed,
     ),
     components(
         schemas(
@@ -53,6 +116,12 @@ use utoipa::OpenApi;
             WorkLogCreate,
             WorkLogCreatedResponse,
             WorkLogUpdate,
+            WorkLogStart,
+            WorkLogStop,
+            WorkLogImportResponse,
+            WorkLogOverlapResponse,
+            WorkLogImportErrorResponse,
+            ImportFormat,
             CompanyResponse,
             CompaniesWithProjects,
             CompaniesWithProjectsResponse,
@@ -64,14 +133,45 @@ use utoipa::OpenApi;
             CompanyStatus,
             ContractType,
             CompanyCommon,
+            PaginatedProjectResponse,
+            PaginatedCompanyResponse,
+            PaginatedWorkLogResponse,
+            TrashResponse,
+            EarningsResponse,
+            EarningsAmount,
+            EarningsGroup,
+            EarningsGroupBy,
+            TimeReportResponse,
+            TimeReportGroup,
+            TimeReportGroupBy,
+            SkillAnalyticsResponse,
+            SkillUsage,
+            SkillTrend,
+            SkillCompany,
+            ExportFormat,
+            InvoiceResponse,
+            InvoiceLineItemResponse,
+            InvoiceCreatedResponse,
+            InvoiceCreate,
+            InvoiceStatus,
+            InvoiceStatusUpdate,
+            InvoiceDocumentFormat,
+            PaginatedInvoiceResponse,
+            RecalculateTotalsResponse,
             AuthTokenLogin,
+            PasswordResetRequest,
+            PasswordResetConfirm,
+            EmailVerificationConfirm,
             AuthTokenInDB,
             AuthResponse,
             AuthTokenCreatedResponse,
             UserResponse,
+            CalendarFeedResponse,
+            SessionResponse,
             UserCreate,
             UserUpdate,
             EngineerRole,
+            Locale,
             FieldError,
         )
     ),
@@ -81,6 +181,13 @@ use utoipa::OpenApi;
         (name = "companies", description = "企業関連のエンドポイント"),
         (name = "auth", description = "認証関連のエンドポイント"),
         (name = "users", description = "ユーザー関連のエンドポイント"),
+        (name = "trash", description = "ゴミ箱関連のエンドポイント"),
+        (name = "reports", description = "集計レポート関連のエンドポイント"),
+        (name = "analytics", description = "分析関連のエンドポイント"),
+        (name = "exports", description = "エクスポート関連のエンドポイント"),
+        (name = "invoices", description = "請求書関連のエンドポイント"),
+        (name = "calendar", description = "カレンダーフィード関連のエンドポイント"),
+        (name = "admin", description = "管理者向けのエンドポイント"),
     ),
     modifiers(&SecurityAddon)
 )]
@@ -1,7 +1,9 @@
 use dotenvy::dotenv;
 use mongodb::{bson::doc, error::Result, options::IndexOptions, Client, Database};
 
-use crate::models::auth::AuthTokenInDB;
+use crate::models::auth::{AuthTokenInDB, PasswordResetTokenInDB};
+use crate::models::companies::CompanyInDB;
+use crate::models::invoices::InvoiceInDB;
 use crate::models::projects::ProjectInDB;
 use crate::models::users::UserInDB;
 use crate::models::work_logs::WorkLogInDB;
@@ -18,9 +20,12 @@ pub async fn init_db() -> Result<Database> {
 pub async fn create_indexes(db: &Database) -> Result<()> {
     log::info!("Creating indexes...");
     create_auth_indexes(db).await?;
+    create_password_reset_indexes(db).await?;
     create_users_indexes(db).await?;
+    create_companies_indexes(db).await?;
     create_projects_indexes(db).await?;
     create_work_logs_indexes(db).await?;
+    create_invoices_indexes(db).await?;
     log::info!("Indexes created successfully.");
     Ok(())
 }
@@ -59,6 +64,94 @@ async fn create_auth_indexes(db: &Database) -> Result<()> {
         .create_index(refresh_token_index, None)
         .await?;
 
+    // リフレッシュトークンの再利用を検知した際に、同じファミリーのトークンをまとめて失効させるためのインデックス
+    let family_id_index = mongodb::IndexModel::builder()
+        .keys(doc! { "family_id": 1 })
+        .options(
+            IndexOptions::builder()
+                .name("idx_family_id".to_string())
+                .build(),
+        )
+        .build();
+
+    // リフレッシュトークンの有効期限が過ぎたトークン（ローテーション済みのものを含む）を自動で削除する
+    let refresh_expires_at_index = mongodb::IndexModel::builder()
+        .keys(doc! { "refresh_expires_at": 1 })
+        .options(
+            IndexOptions::builder()
+                .expire_after(std::time::Duration::from_secs(0))
+                .name("idx_refresh_expires_at_ttl".to_string())
+                .build(),
+        )
+        .build();
+
+    // ユーザーのセッション（ファミリー）の一覧・失効のためのインデックス
+    let user_id_family_id_index = mongodb::IndexModel::builder()
+        .keys(doc! { "user_id": 1, "family_id": 1 })
+        .options(
+            IndexOptions::builder()
+                .name("idx_user_id_family_id".to_string())
+                .build(),
+        )
+        .build();
+
+    tokens_collection
+        .create_indexes(
+            vec![
+                family_id_index,
+                refresh_expires_at_index,
+                user_id_family_id_index,
+            ],
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// password_reset_tokensコレクションのインデックス作成
+async fn create_password_reset_indexes(db: &Database) -> Result<()> {
+    let collection = db.collection::<PasswordResetTokenInDB>("password_reset_tokens");
+
+    // token_hashにユニークインデックスを作成（トークンからの検索用）
+    let token_hash_index = mongodb::IndexModel::builder()
+        .keys(doc! { "token_hash": 1 })
+        .options(
+            IndexOptions::builder()
+                .unique(true)
+                .name("idx_token_hash_unique".to_string())
+                .build(),
+        )
+        .build();
+
+    // user_idフィールドにインデックスを作成（ユーザー単位での削除用）
+    let user_id_index = mongodb::IndexModel::builder()
+        .keys(doc! { "user_id": 1 })
+        .options(
+            IndexOptions::builder()
+                .name("idx_user_id".to_string())
+                .build(),
+        )
+        .build();
+
+    // 有効期限が過ぎたトークン（使用済みのものを含む）を自動で削除する
+    let expires_at_index = mongodb::IndexModel::builder()
+        .keys(doc! { "expires_at": 1 })
+        .options(
+            IndexOptions::builder()
+                .expire_after(std::time::Duration::from_secs(0))
+                .name("idx_expires_at_ttl".to_string())
+                .build(),
+        )
+        .build();
+
+    collection
+        .create_indexes(
+            vec![token_hash_index, user_id_index, expires_at_index],
+            None,
+        )
+        .await?;
+
     Ok(())
 }
 
@@ -77,7 +170,39 @@ async fn create_users_indexes(db: &Database) -> Result<()> {
         )
         .build();
 
-    collection.create_index(email_index, None).await?;
+    // カレンダーフィードのトークンにユニークインデックスを作成（未発行のユーザーは対象外）
+    let calendar_feed_token_index = mongodb::IndexModel::builder()
+        .keys(doc! { "calendar_feed_token": 1 })
+        .options(
+            IndexOptions::builder()
+                .unique(true)
+                .sparse(true)
+                .name("idx_calendar_feed_token_unique".to_string())
+                .build(),
+        )
+        .build();
+
+    collection
+        .create_indexes(vec![email_index, calendar_feed_token_index], None)
+        .await?;
+    Ok(())
+}
+
+/// companiesコレクションのインデックス作成
+async fn create_companies_indexes(db: &Database) -> Result<()> {
+    let collection = db.collection::<CompanyInDB>("companies");
+
+    // user_idフィールドにインデックスを作成（ユーザー単位での絞り込み用）
+    let user_id_index = mongodb::IndexModel::builder()
+        .keys(doc! { "user_id": 1 })
+        .options(
+            IndexOptions::builder()
+                .name("idx_user_id".to_string())
+                .build(),
+        )
+        .build();
+
+    collection.create_index(user_id_index, None).await?;
     Ok(())
 }
 
@@ -85,6 +210,16 @@ async fn create_users_indexes(db: &Database) -> Result<()> {
 async fn create_projects_indexes(db: &Database) -> Result<()> {
     let collection = db.collection::<ProjectInDB>("projects");
 
+    // user_idフィールドにインデックスを作成（ユーザー単位での絞り込み用）
+    let user_id_index = mongodb::IndexModel::builder()
+        .keys(doc! { "user_id": 1 })
+        .options(
+            IndexOptions::builder()
+                .name("idx_user_id".to_string())
+                .build(),
+        )
+        .build();
+
     // company_idフィールドにインデックスを作成
     let company_id_index = mongodb::IndexModel::builder()
         .keys(doc! { "company_id": 1 })
@@ -128,6 +263,7 @@ async fn create_projects_indexes(db: &Database) -> Result<()> {
     collection
         .create_indexes(
             vec![
+                user_id_index,
                 company_id_index,
                 status_index,
                 hourly_pay_index,
@@ -143,6 +279,16 @@ async fn create_projects_indexes(db: &Database) -> Result<()> {
 async fn create_work_logs_indexes(db: &Database) -> Result<()> {
     let collection = db.collection::<WorkLogInDB>("work_logs");
 
+    // user_idフィールドにインデックスを作成（ユーザー単位での絞り込み用）
+    let user_id_index = mongodb::IndexModel::builder()
+        .keys(doc! { "user_id": 1 })
+        .options(
+            IndexOptions::builder()
+                .name("idx_user_id".to_string())
+                .build(),
+        )
+        .build();
+
     // project_idフィールドにインデックスを作成
     let project_id_index = mongodb::IndexModel::builder()
         .keys(doc! { "project_id": 1 })
@@ -153,6 +299,80 @@ async fn create_work_logs_indexes(db: &Database) -> Result<()> {
         )
         .build();
 
-    collection.create_index(project_id_index, None).await?;
+    // 勤怠一覧の検索用に複合インデックスを作成（ユーザー単位で開始時間の範囲指定・並び替えを行う）
+    let user_id_start_time_index = mongodb::IndexModel::builder()
+        .keys(doc! { "user_id": 1, "start_time": -1 })
+        .options(
+            IndexOptions::builder()
+                .name("idx_user_id_start_time".to_string())
+                .build(),
+        )
+        .build();
+
+    // プロジェクト・企業で絞り込んだ勤怠一覧の検索用に複合インデックスを作成
+    let user_id_project_id_start_time_index = mongodb::IndexModel::builder()
+        .keys(doc! { "user_id": 1, "project_id": 1, "start_time": -1 })
+        .options(
+            IndexOptions::builder()
+                .name("idx_user_id_project_id_start_time".to_string())
+                .build(),
+        )
+        .build();
+
+    // 作業中（終了時間が未設定）の勤怠はユーザーごとに1件までとする（同時に開始された場合の重複を防ぐ）
+    let active_user_id_index = mongodb::IndexModel::builder()
+        .keys(doc! { "user_id": 1 })
+        .options(
+            IndexOptions::builder()
+                .unique(true)
+                .partial_filter_expression(doc! { "end_time": null, "deleted_at": null })
+                .name("idx_user_id_active_unique".to_string())
+                .build(),
+        )
+        .build();
+
+    collection
+        .create_indexes(
+            vec![
+                user_id_index,
+                project_id_index,
+                user_id_start_time_index,
+                user_id_project_id_start_time_index,
+                active_user_id_index,
+            ],
+            None,
+        )
+        .await?;
+    Ok(())
+}
+
+/// invoicesコレクションのインデックス作成
+async fn create_invoices_indexes(db: &Database) -> Result<()> {
+    let collection = db.collection::<InvoiceInDB>("invoices");
+
+    // 請求書番号はユーザーごとに一意
+    let invoice_number_index = mongodb::IndexModel::builder()
+        .keys(doc! { "user_id": 1, "invoice_number": 1 })
+        .options(
+            IndexOptions::builder()
+                .unique(true)
+                .name("idx_user_id_invoice_number_unique".to_string())
+                .build(),
+        )
+        .build();
+
+    // 企業で絞り込んだ請求書一覧の検索用に複合インデックスを作成
+    let user_id_company_id_index = mongodb::IndexModel::builder()
+        .keys(doc! { "user_id": 1, "company_id": 1 })
+        .options(
+            IndexOptions::builder()
+                .name("idx_user_id_company_id".to_string())
+                .build(),
+        )
+        .build();
+
+    collection
+        .create_indexes(vec![invoice_number_index, user_id_company_id_index], None)
+        .await?;
     Ok(())
 }
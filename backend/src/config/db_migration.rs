@@ -0,0 +1,108 @@
+use dotenvy::dotenv;
+use mongodb::{
+    bson::{doc, Document},
+    error::Result,
+    Database,
+};
+
+/// 所有者を持つコレクション
+const OWNED_COLLECTIONS: [&str; 3] = ["companies", "projects", "work_logs"];
+
+/// 所有者(user_id)が未設定の既存ドキュメントを、指定されたユーザーに割り当てる
+///
+/// ユーザー単位でのデータ分離を導入する前に作成されたドキュメントは`user_id`を持たず、
+/// どのユーザーからも参照できなくなるため、起動時に一度だけ移行する想定。
+/// 割り当て先は環境変数`LEGACY_DATA_OWNER_EMAIL`のメールアドレスで指定する（未設定時は何もしない）。
+pub async fn assign_owner_to_legacy_documents(db: &Database) -> Result<()> {
+    dotenv().ok();
+    let owner_email = match dotenvy::var("LEGACY_DATA_OWNER_EMAIL") {
+        Ok(email) if !email.is_empty() => email,
+        _ => {
+            log::info!("LEGACY_DATA_OWNER_EMAILが未設定のため、所有者の移行をスキップします");
+            return Ok(());
+        }
+    };
+
+    let owner = db
+        .collection::<Document>("users")
+        .find_one(doc! { "email": &owner_email }, None)
+        .await?;
+    let owner_id = match owner.and_then(|user| user.get_object_id("_id").ok()) {
+        Some(id) => id,
+        None => {
+            log::error!(
+                "移行先のユーザーが見つかりません。所有者の移行をスキップします: {}",
+                owner_email
+            );
+            return Ok(());
+        }
+    };
+
+    for collection_name in OWNED_COLLECTIONS {
+        let result = db
+            .collection::<Document>(collection_name)
+            .update_many(
+                doc! { "user_id": { "$exists": false } },
+                doc! { "$set": { "user_id": owner_id } },
+                None,
+            )
+            .await?;
+
+        if result.modified_count > 0 {
+            log::info!(
+                "{}: {}件のドキュメントの所有者を{}に割り当てました",
+                collection_name,
+                result.modified_count,
+                owner_email
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// ファミリーID(family_id)が未設定の既存の認証トークンに、自身のIDをファミリーIDとして設定する
+///
+/// リフレッシュトークンのローテーションを導入する前に発行されたトークンは`family_id`を持たないため、
+/// 起動時に移行して引き続きリフレッシュできるようにする。
+pub async fn assign_family_to_legacy_auth_tokens(db: &Database) -> Result<()> {
+    let result = db
+        .collection::<Document>("auth_tokens")
+        .update_many(
+            doc! { "family_id": { "$exists": false } },
+            vec![doc! { "$set": { "family_id": "$_id" } }],
+            None,
+        )
+        .await?;
+
+    if result.modified_count > 0 {
+        log::info!(
+            "auth_tokens: {}件の認証トークンにファミリーIDを設定しました",
+            result.modified_count
+        );
+    }
+    Ok(())
+}
+
+/// メールアドレスの確認を導入する前に登録したユーザーを確認済みとして扱う
+///
+/// 導入後に登録したユーザーは`email_verified_at`をnullとして保存するため、フィールドが存在しないユーザーのみを対象とする。
+/// 確認日時は登録日時とする
+pub async fn mark_legacy_users_email_verified(db: &Database) -> Result<()> {
+    let result = db
+        .collection::<Document>("users")
+        .update_many(
+            doc! { "email_verified_at": { "$exists": false } },
+            vec![doc! { "$set": { "email_verified_at": "$created_at" } }],
+            None,
+        )
+        .await?;
+
+    if result.modified_count > 0 {
+        log::info!(
+            "users: {}件の既存ユーザーのメールアドレスを確認済みにしました",
+            result.modified_count
+        );
+    }
+    Ok(())
+}
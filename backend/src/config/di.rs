@@ -1,11 +1,26 @@
 use crate::clients::aws_s3::S3Client;
+use crate::clients::mailer::Mailer;
+use crate::clients::redis::RedisClient;
+use crate::config::email_verification::EmailVerificationConfig;
+use crate::config::login_attempts::LoginAttemptConfig;
+use crate::config::password_reset::PasswordResetConfig;
+use crate::config::work_logs::WorkLogConfig;
 use crate::repositories::auth::MongoAuthRepository;
 use crate::repositories::companies::MongoCompanyRepository;
+use crate::repositories::invoices::MongoInvoiceRepository;
 use crate::repositories::projects::MongoProjectRepository;
+use crate::repositories::reports::MongoReportRepository;
 use crate::repositories::work_logs::MongoWorkLogRepository;
 use crate::usecases::auth::AuthUseCase;
+use crate::usecases::calendar::CalendarUseCase;
 use crate::usecases::companies::CompanyUseCase;
+use crate::usecases::email_verification::EmailVerifier;
+use crate::usecases::exports::ExportUseCase;
+use crate::usecases::invoices::InvoiceUseCase;
+use crate::usecases::login_attempts::LoginAttemptLimiter;
+use crate::usecases::password_reset::PasswordResetUseCase;
 use crate::usecases::projects::ProjectUseCase;
+use crate::usecases::reports::ReportUseCase;
 use crate::usecases::work_logs::WorkLogUseCase;
 use mongodb::Database;
 use std::env;
@@ -15,9 +30,74 @@ use std::sync::Arc;
 pub fn init_work_logs_usecase(
     db: &Database,
     project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    config: WorkLogConfig,
 ) -> Arc<WorkLogUseCase<MongoWorkLogRepository>> {
     let work_logs_repository = Arc::new(MongoWorkLogRepository::new(db));
-    Arc::new(WorkLogUseCase::new(work_logs_repository, project_usecase))
+    Arc::new(WorkLogUseCase::new(
+        work_logs_repository,
+        project_usecase,
+        config,
+    ))
+}
+
+// report
+pub fn init_report_usecase(
+    db: &Database,
+    company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+    project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+) -> Arc<ReportUseCase<MongoReportRepository>> {
+    let report_repository = Arc::new(MongoReportRepository::new(db));
+    Arc::new(ReportUseCase::new(
+        report_repository,
+        company_usecase,
+        project_usecase,
+        work_logs_usecase,
+    ))
+}
+
+// export
+pub fn init_export_usecase(
+    company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+    project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+) -> Arc<ExportUseCase> {
+    Arc::new(ExportUseCase::new(
+        company_usecase,
+        project_usecase,
+        work_logs_usecase,
+    ))
+}
+
+// invoice
+pub fn init_invoice_usecase(
+    db: &Database,
+    company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+    project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+) -> Arc<InvoiceUseCase<MongoInvoiceRepository>> {
+    let invoice_repository = Arc::new(MongoInvoiceRepository::new(db));
+    Arc::new(InvoiceUseCase::new(
+        invoice_repository,
+        company_usecase,
+        project_usecase,
+        work_logs_usecase,
+    ))
+}
+
+// calendar
+pub fn init_calendar_usecase(
+    auth_usecase: Arc<AuthUseCase<MongoAuthRepository>>,
+    company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+    project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+) -> Arc<CalendarUseCase> {
+    Arc::new(CalendarUseCase::new(
+        auth_usecase,
+        company_usecase,
+        project_usecase,
+        work_logs_usecase,
+    ))
 }
 
 // project
@@ -39,11 +119,33 @@ pub fn init_company_usecase(db: &Database) -> Arc<CompanyUseCase<MongoCompanyRep
 pub fn init_auth_usecase(
     db: &Database,
     s3_client: Arc<S3Client>,
+    redis_client: Arc<RedisClient>,
+    login_attempt_config: LoginAttemptConfig,
+    mailer: Arc<dyn Mailer>,
+    email_verification_config: EmailVerificationConfig,
 ) -> Arc<AuthUseCase<MongoAuthRepository>> {
     let auth_repository = Arc::new(MongoAuthRepository::new(db));
+    let login_attempts = LoginAttemptLimiter::new(redis_client, login_attempt_config);
 
     let jwt_secret = env::var("JWT_SECRET")
         .expect("JWT_SECRETが設定されていません")
         .into_bytes();
-    Arc::new(AuthUseCase::new(auth_repository, &jwt_secret, s3_client))
+    let email_verifier = EmailVerifier::new(mailer, &jwt_secret, email_verification_config);
+    Arc::new(AuthUseCase::new(
+        auth_repository,
+        &jwt_secret,
+        s3_client,
+        login_attempts,
+        email_verifier,
+    ))
+}
+
+// password reset
+pub fn init_password_reset_usecase(
+    db: &Database,
+    mailer: Arc<dyn Mailer>,
+    config: PasswordResetConfig,
+) -> Arc<PasswordResetUseCase<MongoAuthRepository>> {
+    let auth_repository = Arc::new(MongoAuthRepository::new(db));
+    Arc::new(PasswordResetUseCase::new(auth_repository, mailer, config))
 }
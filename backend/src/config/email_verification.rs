@@ -0,0 +1,93 @@
+use dotenvy::dotenv;
+use std::env;
+use std::time::Duration;
+
+const DEFAULT_TOKEN_TTL_HOURS: u64 = 24;
+const DEFAULT_VERIFY_URL: &str = "http://localhost:3000/verify-email";
+const DEFAULT_GRACE_PERIOD_HOURS: u64 = 72;
+
+/// メールアドレスを確認していないアカウントに対する制限
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnverifiedAccountRestriction {
+    /// 制限しない
+    #[default]
+    None,
+    /// 参照（GETなど安全なメソッド）のみ許可する
+    ReadOnly,
+    /// メールアドレスの確認に必要な操作のみ許可する
+    Blocked,
+}
+
+impl UnverifiedAccountRestriction {
+    fn from_env_value(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "read_only" => Some(Self::ReadOnly),
+            "blocked" => Some(Self::Blocked),
+            _ => None,
+        }
+    }
+}
+
+/// メールアドレスの確認の設定
+#[derive(Clone)]
+pub struct EmailVerificationConfig {
+    pub token_ttl: Duration,                       // 確認用リンクの有効期間
+    pub verify_url: String, // メールに記載する確認画面のURL（クエリパラメーター`token`を付与する）
+    pub restriction: UnverifiedAccountRestriction, // 未確認のアカウントに対する制限
+    pub grace_period: Duration, // 登録から制限を始めるまでの猶予期間
+}
+
+impl Default for EmailVerificationConfig {
+    fn default() -> Self {
+        Self {
+            token_ttl: Duration::from_secs(DEFAULT_TOKEN_TTL_HOURS * 60 * 60),
+            verify_url: DEFAULT_VERIFY_URL.to_string(),
+            restriction: UnverifiedAccountRestriction::default(),
+            grace_period: Duration::from_secs(DEFAULT_GRACE_PERIOD_HOURS * 60 * 60),
+        }
+    }
+}
+
+impl EmailVerificationConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        let hours = |name: &str, default: u64| {
+            env::var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+
+        let restriction = match env::var("UNVERIFIED_ACCOUNT_RESTRICTION") {
+            Ok(value) => {
+                UnverifiedAccountRestriction::from_env_value(&value).unwrap_or_else(|| {
+                    log::warn!(
+                        "UNVERIFIED_ACCOUNT_RESTRICTIONの値が不正なため、制限しません: {}",
+                        value
+                    );
+                    UnverifiedAccountRestriction::None
+                })
+            }
+            Err(_) => UnverifiedAccountRestriction::default(),
+        };
+
+        Self {
+            token_ttl: Duration::from_secs(
+                hours(
+                    "EMAIL_VERIFICATION_TOKEN_TTL_HOURS",
+                    DEFAULT_TOKEN_TTL_HOURS,
+                )
+                .max(1)
+                    * 60
+                    * 60,
+            ),
+            verify_url: env::var("EMAIL_VERIFICATION_URL")
+                .unwrap_or_else(|_| DEFAULT_VERIFY_URL.to_string()),
+            restriction,
+            grace_period: Duration::from_secs(
+                hours("UNVERIFIED_ACCOUNT_GRACE_HOURS", DEFAULT_GRACE_PERIOD_HOURS) * 60 * 60,
+            ),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use dotenvy::dotenv;
+use std::env;
+use std::time::Duration;
+
+const DEFAULT_MAX_ACCOUNT_FAILURES: u64 = 5;
+const DEFAULT_MAX_IP_FAILURES: u64 = 20;
+const DEFAULT_FAILURE_WINDOW_SECS: u64 = 15 * 60;
+const DEFAULT_LOCKOUT_SECS: u64 = 15 * 60;
+const DEFAULT_DELAY_AFTER_FAILURES: u64 = 3;
+const DEFAULT_BASE_DELAY_MILLIS: u64 = 500;
+const DEFAULT_MAX_DELAY_MILLIS: u64 = 8000;
+
+/// ログイン失敗の制限（ブルートフォース対策）の設定
+#[derive(Clone)]
+pub struct LoginAttemptConfig {
+    pub max_account_failures: u64,  // アカウントをロックするまでの失敗回数
+    pub max_ip_failures: u64,       // IPアドレスをロックするまでの失敗回数
+    pub failure_window: Duration,   // 失敗回数を数える期間（最後の失敗からの時間）
+    pub lockout_duration: Duration, // ロックする時間
+    pub delay_after_failures: u64,  // 応答を遅延させ始める失敗回数
+    pub base_delay: Duration,       // 最初の遅延時間（以降、失敗するごとに倍にする）
+    pub max_delay: Duration,        // 遅延時間の上限
+}
+
+impl Default for LoginAttemptConfig {
+    fn default() -> Self {
+        Self {
+            max_account_failures: DEFAULT_MAX_ACCOUNT_FAILURES,
+            max_ip_failures: DEFAULT_MAX_IP_FAILURES,
+            failure_window: Duration::from_secs(DEFAULT_FAILURE_WINDOW_SECS),
+            lockout_duration: Duration::from_secs(DEFAULT_LOCKOUT_SECS),
+            delay_after_failures: DEFAULT_DELAY_AFTER_FAILURES,
+            base_delay: Duration::from_millis(DEFAULT_BASE_DELAY_MILLIS),
+            max_delay: Duration::from_millis(DEFAULT_MAX_DELAY_MILLIS),
+        }
+    }
+}
+
+impl LoginAttemptConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        let positive = |name: &str, default: u64| {
+            env::var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .filter(|value| *value > 0)
+                .unwrap_or(default)
+        };
+
+        Self {
+            max_account_failures: positive(
+                "LOGIN_MAX_ACCOUNT_FAILURES",
+                DEFAULT_MAX_ACCOUNT_FAILURES,
+            ),
+            max_ip_failures: positive("LOGIN_MAX_IP_FAILURES", DEFAULT_MAX_IP_FAILURES),
+            failure_window: Duration::from_secs(positive(
+                "LOGIN_FAILURE_WINDOW_SECS",
+                DEFAULT_FAILURE_WINDOW_SECS,
+            )),
+            lockout_duration: Duration::from_secs(positive(
+                "LOGIN_LOCKOUT_SECS",
+                DEFAULT_LOCKOUT_SECS,
+            )),
+            delay_after_failures: positive(
+                "LOGIN_DELAY_AFTER_FAILURES",
+                DEFAULT_DELAY_AFTER_FAILURES,
+            ),
+            base_delay: Duration::from_millis(positive(
+                "LOGIN_BASE_DELAY_MILLIS",
+                DEFAULT_BASE_DELAY_MILLIS,
+            )),
+            max_delay: Duration::from_millis(positive(
+                "LOGIN_MAX_DELAY_MILLIS",
+                DEFAULT_MAX_DELAY_MILLIS,
+            )),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::clients::mailer::{Mailer, OutboxMailer, SmtpMailer};
+use crate::errors::app_error::AppError;
+use dotenvy::dotenv;
+use std::env;
+use std::sync::Arc;
+
+const DEFAULT_OUTBOX_DIR: &str = "./mail_outbox";
+const DEFAULT_SMTP_PORT: u16 = 587;
+
+/// 環境変数の設定からメールの送信手段を生成する
+///
+/// - `MAIL_TRANSPORT=smtp`の場合は、`SMTP_*`の設定でSMTPサーバー経由で送信する
+/// - `MAIL_TRANSPORT=outbox`の場合は、`MAIL_OUTBOX_DIR`のディレクトリに書き出す（開発環境用）
+///
+/// 書き出したメールにはパスワードリセット用のトークンなどが平文で含まれるため、
+/// 未設定・不明な値の場合はアウトボックスにはせず、設定エラーとする
+pub fn create_mailer() -> Result<Arc<dyn Mailer>, AppError> {
+    dotenv().ok();
+    let transport = env::var("MAIL_TRANSPORT").unwrap_or_default();
+    match transport.to_ascii_lowercase().as_str() {
+        "smtp" => create_smtp_mailer(),
+        "outbox" => {
+            let dir =
+                env::var("MAIL_OUTBOX_DIR").unwrap_or_else(|_| DEFAULT_OUTBOX_DIR.to_string());
+            log::warn!(
+                "メールは送信せず、アウトボックスに書き出します（開発環境用）: {}",
+                dir
+            );
+            Ok(Arc::new(OutboxMailer::new(dir)))
+        }
+        _ => Err(AppError::InternalServerError(format!(
+            "MAIL_TRANSPORTにはsmtpまたはoutboxを指定してください: {:?}",
+            transport
+        ))),
+    }
+}
+
+/// SMTPサーバー経由で送信する送信手段を生成する
+fn create_smtp_mailer() -> Result<Arc<dyn Mailer>, AppError> {
+    let host = required_env("SMTP_HOST")?;
+    let port = env::var("SMTP_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(DEFAULT_SMTP_PORT);
+    let credentials = env::var("SMTP_USERNAME")
+        .ok()
+        .filter(|username| !username.is_empty())
+        .map(|username| (username, env::var("SMTP_PASSWORD").unwrap_or_default()));
+    // ローカルのメールサーバーなど、TLSに対応していない場合のみ無効にする
+    let starttls = env::var("SMTP_STARTTLS").map_or(true, |value| value != "false");
+    let from = required_env("MAIL_FROM")?;
+
+    log::info!("メールはSMTPサーバー経由で送信します: {}:{}", host, port);
+    Ok(Arc::new(SmtpMailer::new(
+        &host,
+        port,
+        credentials,
+        starttls,
+        &from,
+    )?))
+}
+
+/// 必須の環境変数を取得する（未設定・空の場合は設定エラー）
+fn required_env(key: &str) -> Result<String, AppError> {
+    env::var(key)
+        .ok()
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| AppError::InternalServerError(format!("{}が設定されていません", key)))
+}
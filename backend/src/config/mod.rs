@@ -1,6 +1,14 @@
+pub mod admin;
 pub mod api_doc;
 pub mod db_index;
+pub mod db_migration;
 pub mod di;
+pub mod email_verification;
+pub mod login_attempts;
+pub mod mailer;
+pub mod password_reset;
 pub mod rate_limit;
 pub mod redis;
 pub mod s3;
+pub mod trash;
+pub mod work_logs;
@@ -0,0 +1,39 @@
+use dotenvy::dotenv;
+use std::env;
+use std::time::Duration;
+
+const DEFAULT_TOKEN_TTL_MINUTES: u64 = 30;
+const DEFAULT_RESET_URL: &str = "http://localhost:3000/password-reset";
+
+/// パスワードリセットの設定
+#[derive(Clone)]
+pub struct PasswordResetConfig {
+    pub token_ttl: Duration, // リセットトークンの有効期間
+    pub reset_url: String, // メールに記載するパスワード再設定画面のURL（クエリパラメーター`token`を付与する）
+}
+
+impl Default for PasswordResetConfig {
+    fn default() -> Self {
+        Self {
+            token_ttl: Duration::from_secs(DEFAULT_TOKEN_TTL_MINUTES * 60),
+            reset_url: DEFAULT_RESET_URL.to_string(),
+        }
+    }
+}
+
+impl PasswordResetConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        let token_ttl_minutes = env::var("PASSWORD_RESET_TOKEN_TTL_MINUTES")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_TOKEN_TTL_MINUTES);
+
+        Self {
+            token_ttl: Duration::from_secs(token_ttl_minutes * 60),
+            reset_url: env::var("PASSWORD_RESET_URL")
+                .unwrap_or_else(|_| DEFAULT_RESET_URL.to_string()),
+        }
+    }
+}
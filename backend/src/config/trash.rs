@@ -0,0 +1,28 @@
+use dotenvy::dotenv;
+use std::env;
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct TrashConfig {
+    pub retention: Duration,      // ゴミ箱の保持期間
+    pub purge_interval: Duration, // 物理削除ジョブの実行間隔
+}
+
+impl TrashConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        let retention_days: u64 = env::var("TRASH_RETENTION_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30);
+        let purge_interval: u64 = env::var("TRASH_PURGE_INTERVAL")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse()
+            .unwrap_or(3600);
+
+        Self {
+            retention: Duration::from_secs(retention_days * 24 * 60 * 60),
+            purge_interval: Duration::from_secs(purge_interval),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use dotenvy::dotenv;
+use std::env;
+
+const DEFAULT_MAX_SESSION_HOURS: i64 = 24;
+
+#[derive(Clone)]
+pub struct WorkLogConfig {
+    pub max_session_hours: i64, // 1件の勤怠の開始から終了までの上限（時間）
+}
+
+impl Default for WorkLogConfig {
+    fn default() -> Self {
+        Self {
+            max_session_hours: DEFAULT_MAX_SESSION_HOURS,
+        }
+    }
+}
+
+impl WorkLogConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        let max_session_hours = env::var("WORK_LOG_MAX_SESSION_HOURS")
+            .ok()
+            .and_then(|hours| hours.parse().ok())
+            .filter(|hours| *hours > 0)
+            .unwrap_or(DEFAULT_MAX_SESSION_HOURS);
+
+        Self { max_session_hours }
+    }
+}
@@ -1 +1,2 @@
 pub mod mongo_error_codes;
+pub mod time_zone;
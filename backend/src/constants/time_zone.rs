@@ -0,0 +1,4 @@
+use chrono_tz::Tz;
+
+/// 日付の区切りや集計の基準とするタイムゾーン（JST）
+pub const DEFAULT_TIME_ZONE: Tz = chrono_tz::Asia::Tokyo;
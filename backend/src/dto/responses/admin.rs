@@ -0,0 +1,8 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct RecalculateTotalsResponse {
+    #[schema(example = 3)]
+    pub corrected_count: u64, // 総稼働時間を補正したプロジェクト数
+}
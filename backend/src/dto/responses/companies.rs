@@ -37,6 +37,12 @@ pub struct CompanyResponse {
     #[serde(serialize_with = "serialize_option_bson_datetime")]
     #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
     pub updated_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub deleted_at: Option<BsonDateTime>,
 }
 
 impl TryFrom<CompanyInDB> for CompanyResponse {
@@ -64,6 +70,7 @@ impl TryFrom<CompanyInDB> for CompanyResponse {
             status: db_company.common.status,
             created_at: db_company.created_at,
             updated_at: db_company.updated_at,
+            deleted_at: db_company.deleted_at,
         })
     }
 }
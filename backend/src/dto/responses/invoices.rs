@@ -0,0 +1,142 @@
+use crate::models::invoices::{InvoiceInDB, InvoiceLineItem, InvoiceStatus};
+use crate::utils::serializer::{
+    serialize_bson_datetime, serialize_object_id, serialize_option_bson_datetime,
+};
+use bson::{oid::ObjectId, DateTime as BsonDateTime};
+use chrono::NaiveDate;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct InvoiceLineItemResponse {
+    #[serde(serialize_with = "serialize_object_id")]
+    #[schema(value_type = String, example = "60a7e3e0f1c1b2a3b4c5d6e7")]
+    pub project_id: ObjectId,
+
+    #[schema(example = "DevTrackr開発")]
+    pub description: String,
+
+    #[schema(example = 9000)]
+    pub work_minutes: i64,
+
+    #[schema(example = 5000)]
+    pub hourly_rate: i32,
+
+    #[schema(example = 750000)]
+    pub amount: i64,
+}
+
+impl From<InvoiceLineItem> for InvoiceLineItemResponse {
+    fn from(item: InvoiceLineItem) -> Self {
+        Self {
+            project_id: item.project_id,
+            description: item.description,
+            work_minutes: item.work_minutes,
+            hourly_rate: item.hourly_rate,
+            amount: item.amount,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct InvoiceResponse {
+    #[serde(serialize_with = "serialize_object_id")]
+    #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
+    pub id: ObjectId,
+
+    #[schema(example = "INV-000001")]
+    pub invoice_number: String,
+
+    #[serde(serialize_with = "serialize_object_id")]
+    #[schema(value_type = String, example = "80a6c1e9f0f7b9001234abcd")]
+    pub company_id: ObjectId,
+
+    #[schema(example = "株式会社テスト")]
+    pub company_name: String,
+
+    #[schema(value_type = String, example = "2024-04-01")]
+    pub period_start: NaiveDate,
+
+    #[schema(value_type = String, example = "2024-04-30")]
+    pub period_end: NaiveDate,
+
+    #[schema(value_type = String, example = "2024-05-01")]
+    pub issue_date: NaiveDate,
+
+    #[schema(value_type = Option<String>, example = "2024-05-31")]
+    pub due_date: Option<NaiveDate>,
+
+    pub line_items: Vec<InvoiceLineItemResponse>,
+
+    #[schema(example = 750000)]
+    pub subtotal: i64,
+
+    #[schema(example = 10)]
+    pub tax_rate: i32,
+
+    #[schema(example = 75000)]
+    pub tax_amount: i64,
+
+    #[schema(example = 825000)]
+    pub total: i64,
+
+    pub status: InvoiceStatus,
+
+    #[schema(example = 12)]
+    pub work_log_count: usize, // 請求対象の勤怠の件数
+
+    #[schema(example = "お振込手数料はご負担ください。")]
+    pub notes: Option<String>,
+
+    #[serde(serialize_with = "serialize_bson_datetime")]
+    #[schema(value_type = String, example = "2023-04-13T12:34:56Z")]
+    pub created_at: BsonDateTime,
+
+    #[serde(serialize_with = "serialize_option_bson_datetime")]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub updated_at: Option<BsonDateTime>,
+}
+
+impl TryFrom<InvoiceInDB> for InvoiceResponse {
+    type Error = &'static str;
+
+    fn try_from(db_invoice: InvoiceInDB) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: db_invoice.id.ok_or("IDが存在しません")?,
+            invoice_number: db_invoice.invoice_number,
+            company_id: db_invoice.company_id,
+            company_name: db_invoice.company_name,
+            period_start: db_invoice.period_start,
+            period_end: db_invoice.period_end,
+            issue_date: db_invoice.issue_date,
+            due_date: db_invoice.due_date,
+            line_items: db_invoice
+                .line_items
+                .into_iter()
+                .map(InvoiceLineItemResponse::from)
+                .collect(),
+            subtotal: db_invoice.subtotal,
+            tax_rate: db_invoice.tax_rate,
+            tax_amount: db_invoice.tax_amount,
+            total: db_invoice.total,
+            status: db_invoice.status,
+            work_log_count: db_invoice.work_log_ids.len(),
+            notes: db_invoice.notes,
+            created_at: db_invoice.created_at,
+            updated_at: db_invoice.updated_at,
+        })
+    }
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct InvoiceCreatedResponse {
+    #[serde(serialize_with = "serialize_object_id")]
+    #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
+    pub id: ObjectId,
+}
+
+impl From<ObjectId> for InvoiceCreatedResponse {
+    fn from(id: ObjectId) -> Self {
+        Self { id }
+    }
+}
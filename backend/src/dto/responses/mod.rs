@@ -1,5 +1,10 @@
+pub mod admin;
 pub mod auth;
 pub mod companies;
+pub mod invoices;
+pub mod pagination;
 pub mod projects;
+pub mod reports;
+pub mod trash;
 pub mod users;
 pub mod work_logs;
@@ -0,0 +1,31 @@
+use crate::dto::responses::companies::CompanyResponse;
+use crate::dto::responses::invoices::InvoiceResponse;
+use crate::dto::responses::projects::ProjectResponse;
+use crate::dto::responses::work_logs::WorkLogResponse;
+use crate::utils::pagination::Page;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Debug, ToSchema)]
+#[aliases(
+    PaginatedProjectResponse = PaginatedResponse<ProjectResponse>,
+    PaginatedCompanyResponse = PaginatedResponse<CompanyResponse>,
+    PaginatedWorkLogResponse = PaginatedResponse<WorkLogResponse>,
+    PaginatedInvoiceResponse = PaginatedResponse<InvoiceResponse>
+)]
+pub struct PaginatedResponse<T> {
+    pub items: Vec<T>, // 取得したページの要素
+    pub total: u64,    // 絞り込み条件に一致する総件数
+    #[schema(example = "eyJrIjpbXX0")]
+    pub next_cursor: Option<String>, // 次のページを取得するためのカーソル（最終ページの場合はnull）
+}
+
+impl<T> From<Page<T>> for PaginatedResponse<T> {
+    fn from(page: Page<T>) -> Self {
+        Self {
+            items: page.items,
+            total: page.total,
+            next_cursor: page.next_cursor,
+        }
+    }
+}
@@ -27,6 +27,12 @@ pub struct ProjectResponse {
     #[serde(serialize_with = "serialize_option_bson_datetime")]
     #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
     pub updated_at: Option<BsonDateTime>,
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub deleted_at: Option<BsonDateTime>,
 }
 
 //  パニック防止
@@ -45,6 +51,7 @@ impl TryFrom<ProjectInDB> for ProjectResponse {
             hourly_pay: db_project.hourly_pay,
             created_at: db_project.created_at,
             updated_at: db_project.updated_at,
+            deleted_at: db_project.deleted_at,
         })
     }
 }
@@ -0,0 +1,37 @@
+use crate::models::reports::{
+    EarningsAmount, EarningsGroup, EarningsGroupBy, SkillUsage, TimeReportGroup, TimeReportGroupBy,
+};
+use chrono::NaiveDate;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct EarningsResponse {
+    #[schema(value_type = String, example = "2024-04-01")]
+    pub from: NaiveDate, // 集計期間の開始日
+    #[schema(value_type = String, example = "2024-06-30")]
+    pub to: NaiveDate, // 集計期間の終了日
+    pub group_by: EarningsGroupBy, // 集計単位
+    #[serde(flatten)]
+    pub total: EarningsAmount, // 期間全体の収入
+    pub groups: Vec<EarningsGroup>, // 集計単位ごとの収入
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct TimeReportResponse {
+    #[schema(value_type = String, example = "2024-04-01")]
+    pub from: NaiveDate, // 集計期間の開始日
+    #[schema(value_type = String, example = "2024-04-30")]
+    pub to: NaiveDate, // 集計期間の終了日
+    pub group_by: TimeReportGroupBy, // 集計単位
+    #[schema(example = 9600)]
+    pub work_minutes: i64, // 期間全体の実作業時間（分）
+    #[schema(example = 20)]
+    pub work_log_count: i64, // 期間全体の勤怠の件数
+    pub groups: Vec<TimeReportGroup>, // 集計単位ごとの作業時間
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct SkillAnalyticsResponse {
+    pub skills: Vec<SkillUsage>, // スキルごとの利用状況（作業時間の多い順）
+}
@@ -0,0 +1,12 @@
+use crate::dto::responses::companies::CompanyResponse;
+use crate::dto::responses::projects::ProjectResponse;
+use crate::dto::responses::work_logs::WorkLogResponse;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct TrashResponse {
+    pub companies: Vec<CompanyResponse>, // 削除済みの企業
+    pub projects: Vec<ProjectResponse>,  // 削除済みのプロジェクト
+    pub work_logs: Vec<WorkLogResponse>, // 削除済みの勤怠
+}
@@ -1,4 +1,6 @@
-use crate::models::users::{EngineerRole, UserInDB};
+use crate::models::auth::AuthTokenInDB;
+use crate::models::users::{EngineerRole, Locale, UserInDB};
+use bson::oid::ObjectId;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 use utoipa::ToSchema;
@@ -7,9 +9,16 @@ use utoipa::ToSchema;
 pub struct UserResponse {
     pub id: String,
     pub email: String,
+    pub email_verified: bool,
+    /// 変更を申請中のメールアドレス（確認用リンクで確認されるとemailに反映される）
+    #[schema(example = "user_updated@example.com")]
+    pub pending_email: Option<String>,
     pub username: String,
     pub role: Option<EngineerRole>,
     pub avatar_url: Option<String>,
+    #[schema(example = "Asia/Tokyo")]
+    pub timezone: String, // 未設定の場合はデフォルト（Asia/Tokyo）
+    pub locale: Locale,
     #[schema(value_type = String, example = "2023-04-13T12:34:56Z")]
     pub created_at: DateTime<Utc>,
     #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
@@ -18,14 +27,61 @@ pub struct UserResponse {
 
 impl From<UserInDB> for UserResponse {
     fn from(user: UserInDB) -> Self {
+        let timezone = user.time_zone().name().to_string();
+        let locale = user.locale();
         Self {
             id: user.id.unwrap().to_string(),
             email: user.email,
+            email_verified: user.email_verified_at.is_some(),
+            pending_email: user.pending_email,
             username: user.username,
             role: user.role,
             avatar_url: user.avatar_url,
+            timezone,
+            locale,
             created_at: user.created_at.into(),
             updated_at: user.updated_at.map(|dt| dt.into()),
         }
     }
 }
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct CalendarFeedResponse {
+    /// カレンダーアプリに登録するURL（第三者に知られた場合は再発行する）
+    #[schema(example = "https://example.com/api/calendar/3q2-7wWx9kQ.ics")]
+    pub url: String,
+}
+
+/// ログイン中のセッション（ログインごとの認証トークンのファミリー）
+#[derive(Serialize, Debug, ToSchema)]
+pub struct SessionResponse {
+    /// セッションID（リフレッシュしても変わらない）
+    #[schema(example = "507f1f77bcf86cd799439011")]
+    pub id: String,
+    #[schema(example = "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_0) ...")]
+    pub user_agent: Option<String>,
+    #[schema(example = "203.0.113.1")]
+    pub ip_address: Option<String>,
+    /// ログインした日時
+    #[schema(value_type = String, example = "2023-04-13T12:34:56Z")]
+    pub signed_in_at: DateTime<Utc>,
+    /// 最後に使用された日時（1分程度の誤差がある）
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// リクエストに使用しているセッションかどうか
+    pub current: bool,
+}
+
+impl SessionResponse {
+    pub fn new(auth_token: AuthTokenInDB, current_token_id: &ObjectId) -> Self {
+        Self {
+            id: auth_token.family_id.to_hex(),
+            user_agent: auth_token.user_agent,
+            ip_address: auth_token.ip_address,
+            // ファミリーIDはログイン時に採番するため、その生成日時をログイン日時とする
+            signed_in_at: auth_token.family_id.timestamp().to_chrono(),
+            last_used_at: auth_token.last_used_at.map(|dt| dt.into()),
+            current: auth_token.id.as_ref() == Some(current_token_id),
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use crate::models::work_logs::WorkLogInDB;
+use crate::models::imports::WorkLogImportError;
+use crate::models::users::Locale;
+use crate::models::work_logs::{WorkLogInDB, WorkLogOverlap};
 use crate::utils::serializer::{
     serialize_bson_datetime, serialize_object_id, serialize_option_bson_datetime,
+    serialize_option_object_id, serialize_vec_object_id,
 };
 use bson::{oid::ObjectId, DateTime as BsonDateTime};
 use serde::Serialize;
@@ -40,6 +43,27 @@ pub struct WorkLogResponse {
 
     #[schema(example = "今日はプロジェクトのキックオフミーティングを行いました。")]
     pub memo: Option<String>,
+
+    #[serde(
+        serialize_with = "serialize_option_bson_datetime",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub deleted_at: Option<BsonDateTime>,
+
+    #[serde(
+        serialize_with = "serialize_option_object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>, example = "66f1c2d3e4f5a6b7c8d9e0f1")]
+    pub invoice_id: Option<ObjectId>, // 請求書に含まれている場合の請求書ID（編集不可）
+
+    #[serde(
+        serialize_with = "serialize_option_object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>, example = "66f1c2d3e4f5a6b7c8d9e0f2")]
+    pub session_id: Option<ObjectId>, // 日をまたぐ勤怠を分割した場合の、分割後の勤怠で共通のID
 }
 
 //  パニック防止
@@ -57,6 +81,9 @@ impl TryFrom<WorkLogInDB> for WorkLogResponse {
             break_time: db_work_log.break_time,
             actual_work_minutes: db_work_log.actual_work_minutes,
             memo: db_work_log.memo,
+            deleted_at: db_work_log.deleted_at,
+            invoice_id: db_work_log.invoice_id,
+            session_id: db_work_log.session_id,
         })
     }
 }
@@ -66,10 +93,94 @@ pub struct WorkLogCreatedResponse {
     #[serde(serialize_with = "serialize_object_id")]
     #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
     pub id: ObjectId,
+
+    #[serde(
+        serialize_with = "serialize_vec_object_id",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    #[schema(value_type = Vec<String>, example = json!(["60a7e3e0f1c1b2a3b4c5d6e7"]))]
+    pub overlapping_work_log_ids: Vec<ObjectId>, // 時間が重複している勤怠（allow_overlap指定時の警告）
 }
 
 impl From<ObjectId> for WorkLogCreatedResponse {
     fn from(id: ObjectId) -> Self {
-        Self { id }
+        Self {
+            id,
+            overlapping_work_log_ids: vec![],
+        }
+    }
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct WorkLogOverlapResponse {
+    #[serde(serialize_with = "serialize_vec_object_id")]
+    #[schema(value_type = Vec<String>, example = json!(["60a7e3e0f1c1b2a3b4c5d6e7", "60a7e3e0f1c1b2a3b4c5d6e8"]))]
+    pub work_log_ids: Vec<ObjectId>, // 時間が重複している2件の勤怠（開始時間の古い順）
+
+    #[serde(serialize_with = "serialize_bson_datetime")]
+    #[schema(value_type = String, example = "2023-04-13T10:00:00Z")]
+    pub start_time: BsonDateTime, // 重複している期間の開始
+
+    #[serde(serialize_with = "serialize_bson_datetime")]
+    #[schema(value_type = String, example = "2023-04-13T10:30:00Z")]
+    pub end_time: BsonDateTime, // 重複している期間の終了
+
+    #[schema(example = 30)]
+    pub overlap_minutes: i64,
+}
+
+impl From<WorkLogOverlap> for WorkLogOverlapResponse {
+    fn from(overlap: WorkLogOverlap) -> Self {
+        Self {
+            overlap_minutes: overlap.overlap_minutes(),
+            work_log_ids: overlap.work_log_ids.to_vec(),
+            start_time: overlap.start_time,
+            end_time: overlap.end_time,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct WorkLogImportResponse {
+    #[schema(example = false)]
+    pub dry_run: bool, // 検証のみ行ったかどうか
+
+    #[schema(example = 120)]
+    pub total_rows: usize, // 入力された行数
+
+    #[schema(example = 118)]
+    pub valid_rows: usize, // エラーのなかった行数
+
+    #[schema(example = 118)]
+    pub imported_rows: usize, // 登録した行数（dry_runの場合は0）
+
+    pub errors: Vec<WorkLogImportErrorResponse>, // エラーのあった行
+}
+
+/// インポートできなかった行のエラー
+#[derive(Serialize, Debug, ToSchema)]
+pub struct WorkLogImportErrorResponse {
+    #[schema(example = 3)]
+    pub line: usize, // 入力データ上の行番号（1始まり）
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "start_time")]
+    pub field: Option<String>, // エラーのあったフィールド
+
+    #[schema(example = "IMPORT_INVALID_START_TIME")]
+    pub code: String, // 機械可読なエラーコード
+
+    #[schema(example = "開始時間はRFC 3339形式で指定してください")]
+    pub message: String,
+}
+
+impl WorkLogImportErrorResponse {
+    pub fn new(error: WorkLogImportError, locale: Locale) -> Self {
+        Self {
+            line: error.line,
+            field: error.field,
+            code: error.message.code().to_string(),
+            message: error.message.localize(locale),
+        }
     }
 }
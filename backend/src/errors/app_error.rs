@@ -1,62 +1,169 @@
+use crate::i18n::{messages, Message, MessageCode};
+use crate::models::users::Locale;
 use actix_web::error::JsonPayloadError;
 use actix_web::web::JsonConfig;
-use actix_web::ResponseError;
 use actix_web::{http::StatusCode, HttpResponse};
+use bson::oid::ObjectId;
 use log;
 use serde::de;
 use serde::{Deserialize, Serialize};
 use serde_json::Error as SerdeError;
 use thiserror::Error;
 use utoipa::ToSchema;
-use validator::ValidationErrors;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+/// JSONリクエストボディのサイズの上限（KB）
+const JSON_PAYLOAD_LIMIT_KB: usize = 256;
 
 // 共通のアプリケーションエラー
+//
+// メッセージはコードとパラメーターで保持し、レスポンスを返す時点で表示言語のメッセージに変換する
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("バリデーションエラー: {0}")]
     ValidationError(ValidationErrors),
 
-    #[error("デシリアライズエラー: {0}")]
-    DeserializeError(ErrorResponse),
+    #[error("デシリアライズエラー: {0:?}")]
+    DeserializeError(Vec<InvalidField>),
 
     #[error("不正なリクエストです: {0}")]
-    BadRequest(String),
+    BadRequest(Message),
 
     #[error("認証エラー: {0}")]
-    Unauthorized(String),
+    Unauthorized(Message),
 
     #[error("アクセス権限がありません: {0}")]
-    Forbidden(String),
+    Forbidden(Message),
 
     #[error("リソースが見つかりません: {0}")]
-    NotFound(String),
+    NotFound(Message),
 
     #[error("データベース接続後のエラー: {0}")]
     DatabaseError(#[from] mongodb::error::Error),
 
     #[error("内部サーバーエラー: {0}")]
-    InternalServerError(String),
+    InternalServerError(String), // ログ出力のみでレスポンスには含めない
 
     #[error("ユニーク制約違反: {0}")]
-    DuplicateError(String),
+    DuplicateError(Message),
+
+    #[error("競合エラー: {0}")]
+    Conflict(Message, Vec<ObjectId>), // 競合したリソースのIDをレスポンスに含める
+
+    #[error("リクエスト回数の上限を超えました: {0}")]
+    TooManyRequests(Message),
+}
+
+/// 入力形式が正しくないフィールド
+#[derive(Debug, Clone)]
+pub struct InvalidField {
+    pub field: String,
+    pub pointer: Option<String>, // リクエストボディ上の位置（JSON Pointer形式）
+    pub message: Message,
+}
+
+impl InvalidField {
+    pub fn new(field: impl Into<String>, message: impl Into<Message>) -> Self {
+        Self {
+            field: field.into(),
+            pointer: None,
+            message: message.into(),
+        }
+    }
+
+    /// バリデーションエラーを、ネストした構造体・リストの要素も含めてすべて列挙する
+    ///
+    /// `ValidationErrors`はフィールドの順序を保持しないため、レスポンスが一定になるようパス順に並べる。
+    pub fn from_validation_errors(errors: &ValidationErrors) -> Vec<Self> {
+        let mut fields = vec![];
+        collect_invalid_fields(errors, &mut vec![], &mut fields);
+        fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+        fields
+            .into_iter()
+            .map(|(path, message)| Self {
+                field: path
+                    .iter()
+                    .fold(String::new(), |field, segment| match segment {
+                        PathSegment::Field(name) if field.is_empty() => name.to_string(),
+                        PathSegment::Field(name) => format!("{}.{}", field, name),
+                        PathSegment::Index(index) => format!("{}[{}]", field, index),
+                    }),
+                pointer: Some(
+                    path.iter()
+                        .fold(String::new(), |pointer, segment| match segment {
+                            PathSegment::Field(name) => format!("{}/{}", pointer, name),
+                            PathSegment::Index(index) => format!("{}/{}", pointer, index),
+                        }),
+                ),
+                message,
+            })
+            .collect()
+    }
+}
+
+/// バリデーションエラーの位置を表すパスの要素
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PathSegment {
+    Field(&'static str),
+    Index(usize),
+}
+
+fn collect_invalid_fields(
+    errors: &ValidationErrors,
+    path: &mut Vec<PathSegment>,
+    fields: &mut Vec<(Vec<PathSegment>, Message)>,
+) {
+    for (&field, kind) in errors.errors() {
+        path.push(PathSegment::Field(field));
+        match kind {
+            ValidationErrorsKind::Field(errors) => fields.extend(
+                errors
+                    .iter()
+                    .map(|error| (path.clone(), Message::from_validation_error(error))),
+            ),
+            ValidationErrorsKind::Struct(errors) => collect_invalid_fields(errors, path, fields),
+            ValidationErrorsKind::List(items) => {
+                for (&index, errors) in items {
+                    path.push(PathSegment::Index(index));
+                    collect_invalid_fields(errors, path, fields);
+                    path.pop();
+                }
+            }
+        }
+        path.pop();
+    }
+}
+
+/// エラーレスポンスの形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ErrorFormat {
+    pub locale: Locale,
+    pub first_field_error_only: bool, // フィールドエラーを最初の1件だけ返す（従来の形式）
 }
 
 // エラーレスポンスの構造体
 #[derive(Debug, Serialize, ToSchema, Clone)]
 pub struct ErrorResponse {
-    error: String,
+    #[schema(example = "リソースが見つかりません")]
+    error: String, // エラーの分類（表示言語）
+    #[schema(example = "WORK_LOG_NOT_FOUND")]
+    code: String, // 機械可読なエラーコード（表示言語によらず一定）
+    #[schema(example = "勤怠が見つかりません")]
+    message: String, // エラーメッセージ（表示言語）
     #[serde(skip_serializing_if = "Vec::is_empty")]
     field_errors: Vec<FieldError>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    code: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    conflicting_ids: Vec<String>,
 }
 
 // フィールドに関連するエラー用
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct FieldError {
+    #[schema(example = "bonus.frequency")]
     field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "/bonus/frequency")]
+    pointer: Option<String>, // リクエストボディ上の位置（JSON Pointer形式）
     message: String,
 }
 
@@ -108,73 +215,45 @@ impl AppError {
             AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             AppError::Forbidden(_) => StatusCode::FORBIDDEN,
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_, _) => StatusCode::CONFLICT,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
             AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
     // バリデーションエラーのフォーマット
-    // UX観点で見ると、ユーザーは通常、一度に1つの問題を修正する傾向があるため複数エラーを集計しない(実装も面倒そう)
-    // Googleフォームなども同様
-    fn format_validation_errors(errors: &ValidationErrors) -> Vec<FieldError> {
-        if let Some((field, error_vec)) = errors.field_errors().iter().next() {
-            if let Some(error) = error_vec.first() {
-                let message = error
-                    .message
-                    .as_ref()
-                    .map(|cow| cow.to_string())
-                    .unwrap_or_else(|| "不正な値です".to_string());
-
-                vec![FieldError {
-                    field: field.to_string(),
-                    message,
-                }]
-            } else {
-                vec![FieldError {
-                    field: "unknown".to_string(),
-                    message: "不正な値です".to_string(),
-                }]
-            }
+    // フォームで一度にすべての誤りを表示できるよう、すべてのフィールドエラーを返す
+    fn format_validation_errors(errors: &ValidationErrors) -> Vec<InvalidField> {
+        let fields = InvalidField::from_validation_errors(errors);
+        if fields.is_empty() {
+            vec![InvalidField::new("unknown", messages::INVALID_VALUE)]
         } else {
-            vec![FieldError {
-                field: "unknown".to_string(),
-                message: "不正な値です".to_string(),
-            }]
+            fields
         }
     }
 
     // デシリアライズエラーのフォーマット
-    fn format_deserialize_errors(error_str: &str) -> Vec<FieldError> {
+    fn format_deserialize_errors(error_str: &str) -> Vec<InvalidField> {
         if error_str.contains("missing field") {
             // "missing field `field_name`" からフィールド名を抽出
             let field = error_str
                 .split('`')
                 .nth(1)
                 .and_then(|s| s.split('`').next())
-                .unwrap_or("unknown")
-                .to_string();
+                .unwrap_or("unknown");
 
-            vec![FieldError {
-                field,
-                message: "必須項目です".to_string(),
-            }]
+            vec![InvalidField::new(field, messages::REQUIRED)]
         } else if error_str.contains("invalid type") {
             let field = error_str
                 .split("at.")
                 .nth(1)
                 .and_then(|s| s.split(' ').next())
-                .unwrap_or("unknown")
-                .to_string();
+                .unwrap_or("unknown");
 
-            vec![FieldError {
-                field,
-                message: "入力された値の型が正しくありません".to_string(),
-            }]
+            vec![InvalidField::new(field, messages::INVALID_TYPE)]
         } else {
-            vec![FieldError {
-                field: "unknown".to_string(),
-                message: "入力形式が正しくありません".to_string(),
-            }]
+            vec![InvalidField::new("unknown", messages::INVALID_FORMAT)]
         }
     }
 
@@ -187,102 +266,105 @@ impl AppError {
         }
     }
 
-    fn to_response(&self) -> ErrorResponse {
+    /// エラーの分類（`error`）とメッセージ（`code`・`message`）
+    fn summary(&self) -> (MessageCode, Message) {
         match self {
             // フィールドレベルのエラー
-            AppError::ValidationError(errors) => ErrorResponse {
-                error: "バリデーションエラー".to_string(),
-                field_errors: Self::format_validation_errors(errors),
-                message: None,
-                code: None,
-            },
-            AppError::DeserializeError(error_response) => {
-                // ErrorResponseをそのまま使用
-                error_response.clone()
-            }
-            AppError::BadRequest(msg) => ErrorResponse {
-                error: "不正なリクエスト".to_string(),
-                field_errors: vec![],
-                message: Some(msg.clone()),
-                code: Some("BAD_REQUEST".to_string()),
-            },
-            AppError::DuplicateError(msg) => ErrorResponse {
-                error: "重複エラー".to_string(),
-                field_errors: vec![],
-                message: Some(msg.clone()),
-                code: Some("DUPLICATE_ENTRY".to_string()),
-            },
+            AppError::ValidationError(_) => (
+                messages::ERROR_VALIDATION,
+                messages::VALIDATION_ERROR.into(),
+            ),
+            AppError::DeserializeError(_) => (
+                messages::ERROR_INVALID_INPUT,
+                messages::INVALID_INPUT.into(),
+            ),
+            AppError::BadRequest(message) => (messages::ERROR_BAD_REQUEST, message.clone()),
+            AppError::DuplicateError(message) => (messages::ERROR_DUPLICATE, message.clone()),
 
             // 認証・認可エラー
-            AppError::Unauthorized(msg) => ErrorResponse {
-                error: "認証エラー".to_string(),
-                field_errors: vec![],
-                message: Some(msg.clone()),
-                code: Some("UNAUTHORIZED".to_string()),
-            },
-            AppError::Forbidden(msg) => ErrorResponse {
-                error: "アクセス権限エラー".to_string(),
-                field_errors: vec![],
-                message: Some(msg.clone()),
-                code: Some("FORBIDDEN".to_string()),
-            },
+            AppError::Unauthorized(message) => (messages::ERROR_UNAUTHORIZED, message.clone()),
+            AppError::Forbidden(message) => (messages::ERROR_FORBIDDEN, message.clone()),
 
             // リソースエラー
-            AppError::NotFound(msg) => ErrorResponse {
-                error: "リソースが見つかりません".to_string(),
-                field_errors: vec![],
-                message: Some(msg.clone()),
-                code: Some("NOT_FOUND".to_string()),
-            },
-
-            // システムエラー
-            AppError::DatabaseError(_) => ErrorResponse {
-                error: "データベースエラー".to_string(),
-                field_errors: vec![],
-                message: Some("データベース処理中にエラーが発生しました".to_string()),
-                code: Some("DATABASE_ERROR".to_string()),
-            },
-            AppError::InternalServerError(_) => ErrorResponse {
-                error: "内部サーバーエラー".to_string(),
-                field_errors: vec![],
-                message: Some("予期せぬエラーが発生しました".to_string()),
-                code: Some("INTERNAL_SERVER_ERROR".to_string()),
-            },
+            AppError::NotFound(message) => (messages::ERROR_NOT_FOUND, message.clone()),
+            AppError::Conflict(message, _) => (messages::ERROR_CONFLICT, message.clone()),
+            AppError::TooManyRequests(message) => {
+                (messages::ERROR_TOO_MANY_REQUESTS, message.clone())
+            }
+
+            // システムエラー（詳細はログ出力のみとする）
+            AppError::DatabaseError(_) => {
+                (messages::ERROR_DATABASE, messages::DATABASE_ERROR.into())
+            }
+            AppError::InternalServerError(_) => (
+                messages::ERROR_INTERNAL,
+                messages::INTERNAL_SERVER_ERROR.into(),
+            ),
+        }
+    }
+
+    /// 指定した形式のエラーレスポンスを生成する
+    pub fn to_response(&self, format: ErrorFormat) -> ErrorResponse {
+        let ErrorFormat {
+            locale,
+            first_field_error_only,
+        } = format;
+        let (error, message) = self.summary();
+        let mut field_errors = match self {
+            AppError::ValidationError(errors) => Self::format_validation_errors(errors),
+            AppError::DeserializeError(fields) => fields.clone(),
+            _ => vec![],
+        };
+        if first_field_error_only {
+            field_errors.truncate(1);
         }
+        let conflicting_ids = match self {
+            AppError::Conflict(_, ids) => ids.iter().map(|id| id.to_hex()).collect(),
+            _ => vec![],
+        };
+
+        ErrorResponse {
+            error: Message::from(error).localize(locale),
+            code: message.code().to_string(),
+            message: message.localize(locale),
+            field_errors: field_errors
+                .into_iter()
+                .map(|field_error| FieldError {
+                    field: field_error.field,
+                    pointer: field_error.pointer,
+                    message: field_error.message.localize(locale),
+                })
+                .collect(),
+            conflicting_ids,
+        }
+    }
+
+    /// 指定した形式のエラーレスポンスのHTTPレスポンスを生成する（ログは出力しない）
+    pub fn formatted_response(&self, format: ErrorFormat) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .content_type("application/json")
+            .json(self.to_response(format))
     }
 }
 
 // 一般的なSerdeErrorのハンドリング
 impl From<SerdeError> for AppError {
     fn from(err: SerdeError) -> Self {
-        AppError::DeserializeError(ErrorResponse {
-            error: "入力エラー".to_string(),
-            field_errors: AppError::format_deserialize_errors(&err.to_string()),
-            message: None,
-            code: None,
-        })
+        AppError::DeserializeError(AppError::format_deserialize_errors(&err.to_string()))
     }
 }
 
 // カスタムデシリアライズエラーのハンドリング
 impl From<de::value::Error> for AppError {
     fn from(_: de::value::Error) -> Self {
-        AppError::DeserializeError(ErrorResponse {
-            error: "デシリアライズエラー".to_string(),
-            field_errors: vec![FieldError {
-                field: "unknown".to_string(),
-                message: "入力形式が正しくありません".to_string(),
-            }],
-            message: None,
-            code: None,
-        })
+        AppError::DeserializeError(vec![InvalidField::new("unknown", messages::INVALID_FORMAT)])
     }
 }
 
 // JsonConfigのエラーハンドラー
 pub fn json_error_handler() -> JsonConfig {
     JsonConfig::default()
-        .limit(262_144) // リクエストボディのサイズ（256 * 1024 = 262,144）. リソース枯渇を防ぐ
+        .limit(JSON_PAYLOAD_LIMIT_KB * 1024) // リクエストボディのサイズ. リソース枯渇を防ぐ
         .error_handler(|err, _| {
             let error: AppError = match &err {
                 JsonPayloadError::Deserialize(json_err) => {
@@ -290,73 +372,48 @@ pub fn json_error_handler() -> JsonConfig {
 
                     if error_msg.contains("missing field") {
                         // 必須フィールドの欠落エラー
-                        let field = error_msg.split('`').nth(1).unwrap_or("unknown").to_string();
-
-                        AppError::DeserializeError(ErrorResponse {
-                            error: "入力エラー".to_string(),
-                            field_errors: vec![FieldError {
-                                field,
-                                message: "必須項目です".to_string(),
-                            }],
-                            message: None,
-                            code: None,
-                        })
+                        let field = error_msg.split('`').nth(1).unwrap_or("unknown");
+
+                        AppError::DeserializeError(vec![InvalidField::new(
+                            field,
+                            messages::REQUIRED,
+                        )])
                     } else {
                         // その他のデシリアライズエラー
-                        AppError::DeserializeError(ErrorResponse {
-                            error: "入力エラー".to_string(),
-                            field_errors: AppError::format_deserialize_errors(&error_msg),
-                            message: None,
-                            code: None,
-                        })
+                        AppError::DeserializeError(AppError::format_deserialize_errors(&error_msg))
                     }
                 }
-                JsonPayloadError::ContentType => AppError::DeserializeError(ErrorResponse {
-                    error: "入力エラー".to_string(),
-                    field_errors: vec![FieldError {
-                        field: "content-type".to_string(),
-                        message: "Content-Typeはapplication/jsonである必要があります".to_string(),
-                    }],
-                    message: None,
-                    code: None,
-                }),
-                JsonPayloadError::Overflow { .. } => AppError::DeserializeError(ErrorResponse {
-                    error: "入力エラー".to_string(),
-                    field_errors: vec![FieldError {
-                        field: "body".to_string(),
-                        message: "リクエストデータが大きすぎます（上限: 256KB）".to_string(),
-                    }],
-                    message: None,
-                    code: None,
-                }),
-                _ => AppError::DeserializeError(ErrorResponse {
-                    error: "入力エラー".to_string(),
-                    field_errors: vec![FieldError {
-                        field: "body".to_string(),
-                        message: "リクエストの形式が正しくありません".to_string(),
-                    }],
-                    message: None,
-                    code: None,
-                }),
+                JsonPayloadError::ContentType => {
+                    AppError::DeserializeError(vec![InvalidField::new(
+                        "content-type",
+                        messages::INVALID_CONTENT_TYPE,
+                    )])
+                }
+                JsonPayloadError::Overflow { .. } => {
+                    AppError::DeserializeError(vec![InvalidField::new(
+                        "body",
+                        messages::PAYLOAD_TOO_LARGE.with("limit", JSON_PAYLOAD_LIMIT_KB),
+                    )])
+                }
+                _ => AppError::DeserializeError(vec![InvalidField::new(
+                    "body",
+                    messages::INVALID_BODY,
+                )]),
             };
 
-            // エラーレスポンスを生成
-            let response = error.error_response();
-            actix_web::error::InternalError::from_response("", response).into()
+            // 表示言語に合わせてレスポンスを生成し直せるよう、AppErrorのまま返す
+            error.into()
         })
 }
 
 impl actix_web::ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        AppError::status_code(self)
+    }
+
+    // 表示言語・フィールドエラーの件数はmiddleware::error_format::FormatErrorsがリクエストに合わせて差し替える
     fn error_response(&self) -> HttpResponse {
         self.log_error();
-
-        let response = match self {
-            AppError::DeserializeError(error_response) => error_response,
-            _ => &self.to_response(),
-        };
-
-        HttpResponse::build(self.status_code())
-            .content_type("application/json")
-            .json(response)
+        self.formatted_response(ErrorFormat::default())
     }
 }
@@ -1,4 +1,5 @@
 use crate::errors::app_error::AppError;
+use crate::i18n::Message;
 use thiserror::Error;
 
 // 低レベルの階層用のエラー
@@ -8,9 +9,7 @@ pub enum RepositoryError {
     #[error("データベースエラー: {0}")]
     DatabaseError(#[from] mongodb::error::Error),
     #[error("ユニーク制約違反: {0}")]
-    DuplicateError(String),
-    #[error("アクセストークンの有効期限が切れています")]
-    ExpiredAccessToken,
+    DuplicateError(Message),
 }
 
 impl From<RepositoryError> for AppError {
@@ -18,9 +17,6 @@ impl From<RepositoryError> for AppError {
         match err {
             RepositoryError::DatabaseError(e) => AppError::DatabaseError(e),
             RepositoryError::DuplicateError(e) => AppError::DuplicateError(e),
-            RepositoryError::ExpiredAccessToken => {
-                AppError::Forbidden("アクセストークンの有効期限が切れています".to_string())
-            }
         }
     }
 }
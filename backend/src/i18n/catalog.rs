@@ -0,0 +1,450 @@
+//! メッセージカタログ（コードごとの日本語・英語のメッセージ）
+//!
+//! `messages`はコードから生成するメッセージで、`messages`モジュールに同名の定数を定義する。
+//! アプリケーションエラーのコードはレスポンスの`code`としてそのまま返す。
+//! `validations`はモデルのバリデーションエラーのメッセージで、
+//! コードは`validator`の属性（`code = "..."`）や`ValidationError::new`に指定する。
+
+use super::MessageCode;
+use crate::models::users::Locale;
+
+macro_rules! catalog {
+    (
+        messages { $($code:ident => ($ja:literal, $en:literal),)* }
+        validations { $($validation_code:ident => ($validation_ja:literal, $validation_en:literal),)* }
+    ) => {
+        /// メッセージのコード
+        pub mod messages {
+            use super::MessageCode;
+
+            $(pub const $code: MessageCode = MessageCode(stringify!($code));)*
+        }
+
+        /// コードに対応する表示言語のメッセージ（未登録のコードの場合は`None`）
+        pub(super) fn template(code: &str, locale: Locale) -> Option<&'static str> {
+            let (ja, en) = match code {
+                $(stringify!($code) => ($ja, $en),)*
+                $(stringify!($validation_code) => ($validation_ja, $validation_en),)*
+                _ => return None,
+            };
+            Some(match locale {
+                Locale::Ja => ja,
+                Locale::En => en,
+            })
+        }
+    };
+}
+
+catalog! {
+    messages {
+        // エラーの分類（レスポンスの`error`）
+        ERROR_VALIDATION => ("バリデーションエラー", "Validation error"),
+        ERROR_INVALID_INPUT => ("入力エラー", "Invalid input"),
+        ERROR_BAD_REQUEST => ("不正なリクエスト", "Bad request"),
+        ERROR_DUPLICATE => ("重複エラー", "Duplicate entry"),
+        ERROR_UNAUTHORIZED => ("認証エラー", "Authentication error"),
+        ERROR_FORBIDDEN => ("アクセス権限エラー", "Access denied"),
+        ERROR_NOT_FOUND => ("リソースが見つかりません", "Resource not found"),
+        ERROR_CONFLICT => ("競合エラー", "Conflict"),
+        ERROR_TOO_MANY_REQUESTS => ("リクエスト回数エラー", "Too many requests"),
+        ERROR_DATABASE => ("データベースエラー", "Database error"),
+        ERROR_INTERNAL => ("内部サーバーエラー", "Internal server error"),
+
+        // 共通
+        VALIDATION_ERROR => ("入力内容に誤りがあります", "Some fields are invalid"),
+        INVALID_INPUT => ("入力形式が正しくありません", "The request could not be parsed"),
+        DATABASE_ERROR => (
+            "データベース処理中にエラーが発生しました",
+            "An error occurred while accessing the database"
+        ),
+        INTERNAL_SERVER_ERROR => ("予期せぬエラーが発生しました", "An unexpected error occurred"),
+        INVALID_ID => ("無効なIDです", "Invalid ID"),
+        INVALID_REQUEST => ("無効なリクエストです", "Invalid request"),
+        INVALID_CURSOR => ("カーソルの形式が不正です", "The cursor is malformed"),
+        CURSOR_SORT_MISMATCH => (
+            "カーソルとソート条件が一致しません",
+            "The cursor does not match the sort order"
+        ),
+        INVALID_CURSOR_VALUE => (
+            "カーソルにソートキーとして扱えない値が含まれています",
+            "The cursor contains a value that cannot be used as a sort key"
+        ),
+
+        // 入力形式（フィールド単位）
+        INVALID_VALUE => ("不正な値です", "Invalid value"),
+        REQUIRED => ("必須項目です", "This field is required"),
+        INVALID_TYPE => ("入力された値の型が正しくありません", "The value has an invalid type"),
+        INVALID_FORMAT => ("入力形式が正しくありません", "The value is malformed"),
+        INVALID_CONTENT_TYPE => (
+            "Content-Typeはapplication/jsonである必要があります",
+            "Content-Type must be application/json"
+        ),
+        PAYLOAD_TOO_LARGE => (
+            "リクエストデータが大きすぎます（上限: {limit}KB）",
+            "The request body is too large (limit: {limit}KB)"
+        ),
+        INVALID_BODY => ("リクエストの形式が正しくありません", "The request body is malformed"),
+
+        // 認証・ユーザー
+        UNAUTHENTICATED => ("認証されていません", "Not authenticated"),
+        INVALID_ACCESS_TOKEN => ("無効なアクセストークンです", "Invalid access token"),
+        INVALID_OR_EXPIRED_TOKEN => ("無効または期限切れのトークンです", "Invalid or expired token"),
+        ACCESS_TOKEN_NOT_FOUND => ("アクセストークンが見つかりません", "Access token not found"),
+        ACCESS_TOKEN_EXPIRED => (
+            "アクセストークンの有効期限が切れています",
+            "The access token has expired"
+        ),
+        TOKEN_NOT_FOUND => ("トークンが見つかりません", "Token not found"),
+        SESSION_NOT_FOUND => ("セッションが見つかりません", "Session not found"),
+        ADMIN_REQUIRED => ("管理者権限が必要です", "Administrator privileges are required"),
+        INVALID_CREDENTIALS => ("無効な認証情報です", "Invalid credentials"),
+        LOGIN_TEMPORARILY_LOCKED => (
+            "ログインの失敗が続いたため一時的にロックしています。{minutes}分後に再度お試しください",
+            "Login is temporarily locked after too many failed attempts. Try again in {minutes} minutes"
+        ),
+        REGISTRATION_FAILED => (
+            "バリデーションに失敗したか、処理中にエラーが発生しました",
+            "Validation failed or an error occurred while processing the request"
+        ),
+        EMAIL_ALREADY_USED => (
+            "メールアドレスが既に使用されています",
+            "The email address is already in use"
+        ),
+        USER_NOT_FOUND => ("ユーザーが見つかりません", "User not found"),
+        INVALID_BASE64_FORMAT => (
+            "base64データの形式が正しくありません",
+            "Invalid base64 data format"
+        ),
+        INVALID_BASE64 => ("無効なbase64データ: {detail}", "Invalid base64 data: {detail}"),
+        UNKNOWN_IMAGE_FORMAT => (
+            "画像フォーマットの判別に失敗しました: {detail}",
+            "Failed to detect the image format: {detail}"
+        ),
+        INVALID_IMAGE => ("無効な画像データ: {detail}", "Invalid image data: {detail}"),
+        CALENDAR_FEED_NOT_FOUND => ("カレンダーフィードが見つかりません", "Calendar feed not found"),
+        EMAIL_NOT_VERIFIED => (
+            "メールアドレスの確認が完了していないため、この操作は行えません。確認用のメールのリンクからメールアドレスを確認してください",
+            "This action is not available until you verify your email address. Use the link in the verification email"
+        ),
+        INVALID_OR_EXPIRED_VERIFICATION_LINK => (
+            "確認用のリンクが無効か、有効期限が切れています。確認用のメールを再送信してください",
+            "The verification link is invalid or has expired. Request a new verification email"
+        ),
+        EMAIL_ALREADY_VERIFIED => ("メールアドレスは確認済みです", "The email address is already verified"),
+        EMAIL_VERIFICATION_MAIL_SUBJECT => (
+            "【DevTrackr】メールアドレスの確認",
+            "[DevTrackr] Verify your email address"
+        ),
+        EMAIL_VERIFICATION_MAIL_BODY => (
+            "以下のURLから{hours}時間以内にメールアドレスを確認してください。\n\n{url}\n\nお心当たりがない場合は、このメールを破棄してください。",
+            "Verify your email address within {hours} hours using the link below.\n\n{url}\n\nIf you did not request this, you can ignore this email."
+        ),
+        INVALID_OR_EXPIRED_RESET_TOKEN => (
+            "リセットトークンが無効か、有効期限が切れています。再度パスワードのリセットを申請してください",
+            "The reset token is invalid or has expired. Request a password reset again"
+        ),
+        PASSWORD_RESET_MAIL_SUBJECT => ("【DevTrackr】パスワードの再設定", "[DevTrackr] Reset your password"),
+        PASSWORD_RESET_MAIL_BODY => (
+            "パスワードの再設定が申請されました。\n以下のURLから{minutes}分以内に新しいパスワードを設定してください。\n\n{url}\n\nお心当たりがない場合は、このメールを破棄してください。パスワードは変更されません。",
+            "We received a request to reset your password.\nSet a new password within {minutes} minutes using the link below.\n\n{url}\n\nIf you did not request this, you can ignore this email. Your password will not be changed."
+        ),
+
+        // 企業
+        COMPANY_NOT_FOUND => ("企業が見つかりません", "Company not found"),
+        COMPANY_TO_UPDATE_NOT_FOUND => (
+            "更新対象の企業が見つかりません",
+            "The company to update was not found"
+        ),
+        COMPANY_TO_DELETE_NOT_FOUND => (
+            "削除対象の企業が見つかりません",
+            "The company to delete was not found"
+        ),
+        COMPANY_TO_RESTORE_NOT_FOUND => (
+            "復元対象の企業が見つかりません",
+            "The company to restore was not found"
+        ),
+
+        // プロジェクト
+        PROJECT_NOT_FOUND => ("プロジェクトが見つかりません", "Project not found"),
+        PROJECT_TO_UPDATE_NOT_FOUND => (
+            "更新対象のプロジェクトが見つかりません",
+            "The project to update was not found"
+        ),
+        PROJECT_TO_DELETE_NOT_FOUND => (
+            "削除対象のプロジェクトが見つかりません",
+            "The project to delete was not found"
+        ),
+        PROJECT_TO_RESTORE_NOT_FOUND => (
+            "復元対象のプロジェクトが見つかりません",
+            "The project to restore was not found"
+        ),
+        PROJECT_COMPANY_NOT_FOUND => (
+            "プロジェクトに関連する企業が見つかりません",
+            "The company of the project was not found"
+        ),
+        PROJECT_COMPANY_DELETED => (
+            "関連する企業が削除されているため復元できません",
+            "The project cannot be restored because its company has been deleted"
+        ),
+
+        // 勤怠
+        WORK_LOG_NOT_FOUND => ("勤怠が見つかりません", "Work log not found"),
+        WORK_LOG_TO_UPDATE_NOT_FOUND => (
+            "更新対象の勤怠が見つかりません",
+            "The work log to update was not found"
+        ),
+        WORK_LOG_TO_DELETE_NOT_FOUND => (
+            "削除対象の勤怠が見つかりません",
+            "The work log to delete was not found"
+        ),
+        WORK_LOG_TO_RESTORE_NOT_FOUND => (
+            "復元対象の勤怠が見つかりません",
+            "The work log to restore was not found"
+        ),
+        WORK_LOG_PROJECT_NOT_FOUND => (
+            "勤怠に関連するプロジェクトが見つかりません",
+            "The project of the work log was not found"
+        ),
+        WORK_LOG_PROJECT_DELETED => (
+            "関連するプロジェクトが削除されているため復元できません",
+            "The work log cannot be restored because its project has been deleted"
+        ),
+        ACTIVE_WORK_LOG_NOT_FOUND => ("作業中の勤怠はありません", "There is no work log in progress"),
+        ACTIVE_WORK_LOG_EXISTS => ("既に作業中の勤怠があります", "A work log is already in progress"),
+        WORK_LOG_ALREADY_STOPPED => ("この勤怠は既に終了しています", "The work log has already been stopped"),
+        BREAK_TIME_EXCEEDS_WORK_TIME => (
+            "休憩時間が作業時間を超えています",
+            "The break time exceeds the working time"
+        ),
+        SESSION_TOO_LONG => (
+            "開始から終了までの時間は{max_hours}時間以内である必要があります",
+            "The time from start to end must be within {max_hours} hours"
+        ),
+        TIMER_SESSION_TOO_LONG => (
+            "開始から終了までの時間は{max_hours}時間以内である必要があります。終了時間を指定して勤怠を更新してください",
+            "The time from start to end must be within {max_hours} hours. Update the work log with an end time instead"
+        ),
+        WORK_LOG_OVERLAP => (
+            "既存の勤怠と時間が重複しています",
+            "The time overlaps with existing work logs"
+        ),
+        WORK_LOG_INVOICED => (
+            "請求書に含まれる勤怠は編集できません",
+            "Work logs included in an invoice cannot be edited"
+        ),
+        INVOICED_WORK_LOGS_EXIST => (
+            "請求書に含まれる勤怠があるため削除できません。先に請求書を取り消してください",
+            "This cannot be deleted because some of its work logs are included in an invoice. Cancel the invoice first"
+        ),
+        WORK_LOGS_CHANGED_DURING_INVOICING => (
+            "請求対象の勤怠が他の請求書の作成中に変更されました。再度お試しください",
+            "The work logs were changed while another invoice was being created. Please try again"
+        ),
+
+        // インポート
+        IMPORT_EMPTY => ("インポートする勤怠がありません", "There are no work logs to import"),
+        IMPORT_TOO_MANY_ROWS => (
+            "一度にインポートできる勤怠は{max}件までです",
+            "Up to {max} work logs can be imported at once"
+        ),
+        IMPORT_INVALID_CSV_HEADER => (
+            "CSVの列見出しを読み込めません: {detail}",
+            "Failed to read the CSV header: {detail}"
+        ),
+        IMPORT_INVALID_UTF8 => ("UTF-8の文字列を指定してください", "The data must be UTF-8 encoded"),
+
+        // インポートの行（フィールド単位）
+        IMPORT_INVALID_ROW => ("行を解釈できません: {detail}", "Failed to parse the row: {detail}"),
+        IMPORT_PROJECT_NOT_FOUND => (
+            "プロジェクト「{project}」が見つかりません",
+            "Project \"{project}\" was not found"
+        ),
+        IMPORT_PROJECT_AMBIGUOUS => (
+            "プロジェクト名「{project}」が複数存在するため、プロジェクトIDで指定してください",
+            "Several projects are named \"{project}\". Specify the project ID instead"
+        ),
+        IMPORT_INVALID_START_TIME => (
+            "開始時間はRFC 3339形式で指定してください",
+            "The start time must be in RFC 3339 format"
+        ),
+        IMPORT_INVALID_END_TIME => (
+            "終了時間はRFC 3339形式で指定してください",
+            "The end time must be in RFC 3339 format"
+        ),
+        IMPORT_END_TIME_REQUIRED => ("終了時間を指定してください", "The end time is required"),
+        IMPORT_OVERLAPS_EXISTING => (
+            "既存の勤怠（{ids}）と時間が重複しています",
+            "The time overlaps with existing work logs ({ids})"
+        ),
+        IMPORT_OVERLAPS_ROW => (
+            "{line}行目の勤怠と時間が重複しています",
+            "The time overlaps with the work log on line {line}"
+        ),
+
+        // 請求書
+        INVOICE_NOT_FOUND => ("請求書が見つかりません", "Invoice not found"),
+        INVOICE_TO_DELETE_NOT_FOUND => (
+            "削除対象の請求書が見つかりません",
+            "The invoice to delete was not found"
+        ),
+        INVOICE_COMPANY_NOT_FOUND => ("請求先の企業が見つかりません", "The billed company was not found"),
+        INVOICE_CONTRACT_TYPE_NOT_SUPPORTED => (
+            "請求書は契約タイプがフリーランス・副業の企業に対してのみ作成できます",
+            "Invoices can only be created for freelance or side job companies"
+        ),
+        INVOICE_NO_WORK_LOGS => (
+            "請求期間に請求対象の勤怠がありません",
+            "There are no billable work logs in the billing period"
+        ),
+        INVOICE_STATUS_TRANSITION_NOT_ALLOWED => (
+            "請求書のステータスを「{from}」から「{to}」に変更することはできません",
+            "The invoice status cannot be changed from {from_status} to {to_status}"
+        ),
+        INVOICE_STATUS_CHANGED => (
+            "請求書のステータスが他の操作により変更されました",
+            "The invoice status was changed by another operation"
+        ),
+        INVOICE_NOT_DRAFT => ("下書き以外の請求書は削除できません", "Only draft invoices can be deleted"),
+        HOURLY_RATE_NOT_SET => (
+            "プロジェクト「{project}」と企業のいずれにも時給が設定されていません",
+            "No hourly rate is set for the project \"{project}\" or its company"
+        ),
+    }
+    validations {
+        // ユーザー
+        email_invalid => ("有効なメールアドレスを入力してください", "Enter a valid email address"),
+        password_too_short => (
+            "パスワードは8文字以上である必要があります",
+            "The password must be at least 8 characters"
+        ),
+        verification_token_required => ("確認用のトークンを指定してください", "The verification token is required"),
+        reset_token_required => ("リセットトークンを指定してください", "The reset token is required"),
+        username_required => ("名前は1文字以上である必要があります", "The name must not be empty"),
+        timezone_invalid => (
+            "タイムゾーンはIANAタイムゾーン名（例: Asia/Tokyo）で指定してください: {value}",
+            "Specify the time zone as an IANA time zone name (e.g. Asia/Tokyo): {value}"
+        ),
+
+        // 企業
+        company_name_length => (
+            "企業名は2〜100文字である必要があります",
+            "The company name must be 2 to 100 characters"
+        ),
+        establishment_year_range => (
+            "設立年は1800年から現在までの間である必要があります",
+            "The establishment year must be between 1800 and the current year"
+        ),
+        location_length => ("所在地は200文字以内である必要があります", "The location must be at most 200 characters"),
+        website_url_invalid => ("有効なURLを入力してください", "Enter a valid URL"),
+        employee_count_range => ("従業員数は1以上である必要があります", "The employee count must be at least 1"),
+        major_clients_too_many => (
+            "主要顧客は最大10件まで登録できます",
+            "Up to 10 major clients can be registered"
+        ),
+        major_services_too_many => (
+            "主要サービスは最大10件まで登録できます",
+            "Up to 10 major services can be registered"
+        ),
+        average_hourly_rate_range => (
+            "平均時給は500円から100,000円の間である必要があります",
+            "The average hourly rate must be between 500 and 100,000 yen"
+        ),
+        annual_sales_range => ("年間売上は0以上である必要があります", "The annual sales must be 0 or more"),
+        fiscal_year_range => ("会計年度は2100年以前である必要があります", "The fiscal year must be 2100 or earlier"),
+        bonus_amount_range => ("ボーナス金額は0以上である必要があります", "The bonus amount must be 0 or more"),
+        bonus_frequency_range => (
+            "ボーナス頻度は1から12の間である必要があります",
+            "The bonus frequency must be between 1 and 12"
+        ),
+        affiliation_start_date_future => (
+            "契約開始日は現在日付より前である必要があります",
+            "The contract start date must not be in the future"
+        ),
+        affiliation_end_date_before_start => (
+            "契約終了日は契約開始日より後である必要があります",
+            "The contract end date must be after the contract start date"
+        ),
+
+        // プロジェクト
+        project_title_length => ("タイトルは1〜100文字である必要があります", "The title must be 1 to 100 characters"),
+        project_description_length => (
+            "説明は1000文字以内である必要があります",
+            "The description must be at most 1000 characters"
+        ),
+        skill_labels_too_many => (
+            "スキルラベルは最大10個まで登録できます",
+            "Up to 10 skill labels can be registered"
+        ),
+        skill_labels_filter_too_many => (
+            "スキルラベルは最大10個まで指定できます",
+            "Up to 10 skill labels can be specified"
+        ),
+        hourly_pay_range => ("時給は0以上である必要があります", "The hourly pay must be 0 or more"),
+        total_working_time_range => (
+            "総作業時間は0以上である必要があります",
+            "The total working time must be 0 or more"
+        ),
+
+        // 勤怠
+        start_time_future => (
+            "開始時間は現在時刻より前である必要があります",
+            "The start time must not be in the future"
+        ),
+        end_time_before_start => (
+            "終了時間は開始時間より後である必要があります",
+            "The end time must be after the start time"
+        ),
+        end_time_future => (
+            "終了時間は現在時刻より前である必要があります",
+            "The end time must not be in the future"
+        ),
+        actual_work_minutes_without_end_time => (
+            "終了時間のない勤怠には実労働時間を指定できません",
+            "Actual work minutes cannot be specified for a work log without an end time"
+        ),
+        break_time_exceeds_session => (
+            "休憩時間は開始から終了までの時間以内である必要があります",
+            "The break time must be within the time from start to end"
+        ),
+        actual_work_minutes_exceeds_session => (
+            "実労働時間は開始から終了までの時間から休憩時間を引いた時間以内である必要があります",
+            "The actual work minutes must be within the time from start to end minus the break time"
+        ),
+        break_time_range => ("休憩時間は0以上である必要があります", "The break time must be 0 or more"),
+        actual_work_minutes_range => (
+            "実労働時間は0以上である必要があります",
+            "The actual work minutes must be 0 or more"
+        ),
+        memo_length => ("メモは0〜1000文字である必要があります", "The memo must be at most 1000 characters"),
+        memo_query_length => (
+            "メモの検索文字列は1〜100文字で指定してください",
+            "The memo search text must be 1 to 100 characters"
+        ),
+
+        // 請求書
+        tax_rate_range => ("税率は0から100の間で指定してください", "The tax rate must be between 0 and 100"),
+        invoice_notes_length => ("備考は1000文字以内である必要があります", "The notes must be at most 1000 characters"),
+        due_date_before_period_end => (
+            "支払期限は請求期間の終了日以降を指定してください",
+            "The due date must be on or after the end of the billing period"
+        ),
+
+        // 検索条件
+        limit_range => ("limitは1から100の間で指定してください", "limit must be between 1 and 100"),
+        period_range => ("fromはto以前の日付を指定してください", "from must be on or before to"),
+        time_range => ("fromはtoより前の日時を指定してください", "from must be before to"),
+        sort_format => (
+            "ソート条件は'field:order'の形式で指定してください",
+            "Invalid sort format. Expected 'field:order'"
+        ),
+        sort_field => (
+            "ソートできないフィールドです: {field}（指定できるフィールド: {allowed}）",
+            "Invalid sort field: {field}. Allowed fields are: {allowed}"
+        ),
+        sort_order => (
+            "ソート順はascまたはdescで指定してください",
+            "Sort order must be either 'asc' or 'desc'"
+        ),
+
+    }
+}
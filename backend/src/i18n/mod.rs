@@ -0,0 +1,133 @@
+//! エラーメッセージ・バリデーションメッセージの多言語対応
+//!
+//! メッセージは安定したコード（`catalog`のキー）と埋め込むパラメーターで表し、
+//! レスポンスを返す時点で表示言語のメッセージに変換する。
+//! メッセージ中の`{name}`はパラメーター`name`の値に置き換えられる。
+
+mod catalog;
+
+pub use catalog::messages;
+
+use crate::models::users::Locale;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::fmt;
+use validator::ValidationError;
+
+/// メッセージカタログに登録されていないコードの場合に使うメッセージ
+const FALLBACK_CODE: MessageCode = messages::INVALID_VALUE;
+
+/// メッセージカタログのキー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageCode(&'static str);
+
+impl MessageCode {
+    pub fn as_str(self) -> &'static str {
+        self.0
+    }
+
+    /// パラメーターを埋め込んだメッセージを生成する
+    pub fn with(self, name: &'static str, value: impl ToString) -> Message {
+        Message::from(self).with(name, value)
+    }
+}
+
+/// 表示言語に依存しないメッセージ（コードとパラメーター）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    code: Cow<'static, str>,
+    params: Vec<(Cow<'static, str>, String)>,
+}
+
+impl Message {
+    /// メッセージのコード（レスポンスの`code`として返す機械可読な値）
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.params.push((Cow::Borrowed(name), value.to_string()));
+        self
+    }
+
+    /// 表示言語のメッセージに変換する
+    pub fn localize(&self, locale: Locale) -> String {
+        let template = catalog::template(&self.code, locale)
+            .or_else(|| catalog::template(FALLBACK_CODE.as_str(), locale))
+            .unwrap_or_default();
+        self.params
+            .iter()
+            .fold(template.to_string(), |message, (name, value)| {
+                message.replace(&format!("{{{}}}", name), value)
+            })
+    }
+
+    /// バリデーションエラーのコードとパラメーターからメッセージを生成する
+    pub fn from_validation_error(error: &ValidationError) -> Self {
+        Self {
+            code: error.code.clone(),
+            params: error
+                .params
+                .iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        Value::String(value) => value.clone(),
+                        value => value.to_string(),
+                    };
+                    (name.clone(), value)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<MessageCode> for Message {
+    fn from(code: MessageCode) -> Self {
+        Self {
+            code: Cow::Borrowed(code.0),
+            params: vec![],
+        }
+    }
+}
+
+/// ログ出力用にデフォルトの表示言語で出力する
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.localize(Locale::default()))
+    }
+}
+
+impl Locale {
+    /// 言語タグ（`ja-JP`、`en`など）に対応する表示言語
+    pub fn from_language_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next().unwrap_or_default();
+        if primary.eq_ignore_ascii_case("ja") {
+            Some(Locale::Ja)
+        } else if primary.eq_ignore_ascii_case("en") {
+            Some(Locale::En)
+        } else {
+            None
+        }
+    }
+}
+
+/// Accept-Languageヘッダーの値から、対応している表示言語のうち最も優先度の高いものを選ぶ
+///
+/// 優先度（q値）が同じ場合は先に記載された言語を優先する。q=0の言語は選ばない。
+pub fn negotiate_locale(accept_language: &str) -> Option<Locale> {
+    let mut selected: Option<(Locale, f32)> = None;
+    for entry in accept_language.split(',') {
+        let mut parts = entry.split(';').map(str::trim);
+        let Some(locale) = parts.next().and_then(Locale::from_language_tag) else {
+            continue;
+        };
+        let quality = parts
+            .find_map(|part| part.strip_prefix("q="))
+            .map_or(Some(1.0), |q| q.parse::<f32>().ok())
+            .unwrap_or(0.0);
+        if quality > 0.0 && selected.is_none_or(|(_, best)| quality > best) {
+            selected = Some((locale, quality));
+        }
+    }
+    selected.map(|(locale, _)| locale)
+}
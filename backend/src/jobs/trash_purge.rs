@@ -0,0 +1,64 @@
+use crate::config::trash::TrashConfig;
+use crate::errors::app_error::AppError;
+use crate::repositories::companies::MongoCompanyRepository;
+use crate::repositories::projects::MongoProjectRepository;
+use crate::repositories::work_logs::MongoWorkLogRepository;
+use crate::usecases::companies::CompanyUseCase;
+use crate::usecases::projects::ProjectUseCase;
+use crate::usecases::work_logs::WorkLogUseCase;
+use bson::DateTime as BsonDateTime;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// 保持期間を過ぎたゴミ箱のデータを定期的に物理削除するジョブを起動する
+pub fn spawn(
+    config: TrashConfig,
+    company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+    project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(config.purge_interval);
+        loop {
+            interval.tick().await;
+            let deleted_before =
+                BsonDateTime::from_system_time(SystemTime::now() - config.retention);
+
+            match purge(
+                deleted_before,
+                &company_usecase,
+                &project_usecase,
+                &work_logs_usecase,
+            )
+            .await
+            {
+                Ok(purged_count) if purged_count > 0 => {
+                    log::info!("ゴミ箱から{}件のデータを物理削除しました", purged_count)
+                }
+                Ok(_) => {}
+                Err(e) => log::error!("ゴミ箱の物理削除に失敗しました: {}", e),
+            }
+        }
+    });
+}
+
+/// 削除日時が指定日時以前のデータを物理削除し、削除件数を返す
+async fn purge(
+    deleted_before: BsonDateTime,
+    company_usecase: &CompanyUseCase<MongoCompanyRepository>,
+    project_usecase: &ProjectUseCase<MongoProjectRepository>,
+    work_logs_usecase: &WorkLogUseCase<MongoWorkLogRepository>,
+) -> Result<u64, AppError> {
+    // 親が残ったまま子が消えることのないよう、子から順に削除する
+    let work_logs = work_logs_usecase
+        .purge_deleted_work_logs(deleted_before)
+        .await?;
+    let projects = project_usecase
+        .purge_deleted_projects(deleted_before)
+        .await?;
+    let companies = company_usecase
+        .purge_deleted_companies(deleted_before)
+        .await?;
+
+    Ok(work_logs + projects + companies)
+}
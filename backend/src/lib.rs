@@ -4,6 +4,8 @@ pub mod config;
 pub mod constants;
 pub mod dto;
 pub mod errors;
+pub mod i18n;
+pub mod jobs;
 pub mod middleware;
 pub mod models;
 pub mod repositories;
@@ -23,6 +23,8 @@ mod config;
 mod constants;
 mod dto;
 mod errors;
+mod i18n;
+mod jobs;
 mod middleware;
 mod models;
 mod repositories;
@@ -123,6 +125,15 @@ async fn main() -> Result<()> {
     // S3Clientの初期化
     let s3_client = Arc::new(clients::aws_s3::S3Client::new(s3_config.clone()));
 
+    // メール送信手段の初期化
+    let mailer = match config::mailer::create_mailer() {
+        Ok(mailer) => mailer,
+        Err(e) => {
+            log::error!("メール送信手段の初期化に失敗しました: {}", e);
+            panic!("メール送信手段の初期化に失敗しました");
+        }
+    };
+
     // データベースの初期化
     let db = db_index::init_db()
         .await
@@ -133,16 +144,87 @@ async fn main() -> Result<()> {
         log::error!("インデックスの作成に失敗しました: {}", e);
     }
 
+    // 所有者未設定の既存データの移行
+    if let Err(e) = config::db_migration::assign_owner_to_legacy_documents(&db).await {
+        log::error!("既存データの所有者の移行に失敗しました: {}", e);
+    }
+
+    // ファミリーID未設定の既存の認証トークンの移行
+    if let Err(e) = config::db_migration::assign_family_to_legacy_auth_tokens(&db).await {
+        log::error!(
+            "既存の認証トークンのファミリーIDの移行に失敗しました: {}",
+            e
+        );
+    }
+
+    // メールアドレスの確認を導入する前に登録したユーザーの移行
+    if let Err(e) = config::db_migration::mark_legacy_users_email_verified(&db).await {
+        log::error!(
+            "既存ユーザーのメールアドレスの確認状態の移行に失敗しました: {}",
+            e
+        );
+    }
+
     // 各ユースケースの初期化
     let company_usecase = di::init_company_usecase(&db);
     let company_usecase_clone = company_usecase.clone();
     let project_usecase = di::init_project_usecase(&db, company_usecase_clone);
 
     let project_usecase_clone = project_usecase.clone();
-    let work_logs_usecase = di::init_work_logs_usecase(&db, project_usecase_clone);
-    let auth_usecase = di::init_auth_usecase(&db, s3_client.clone());
+    let work_logs_usecase = di::init_work_logs_usecase(
+        &db,
+        project_usecase_clone,
+        config::work_logs::WorkLogConfig::from_env(),
+    );
+    let auth_usecase = di::init_auth_usecase(
+        &db,
+        s3_client.clone(),
+        redis_client.clone(),
+        config::login_attempts::LoginAttemptConfig::from_env(),
+        mailer.clone(),
+        config::email_verification::EmailVerificationConfig::from_env(),
+    );
+    let password_reset_usecase = di::init_password_reset_usecase(
+        &db,
+        mailer,
+        config::password_reset::PasswordResetConfig::from_env(),
+    );
+    let report_usecase = di::init_report_usecase(
+        &db,
+        company_usecase.clone(),
+        project_usecase.clone(),
+        work_logs_usecase.clone(),
+    );
+    let export_usecase = di::init_export_usecase(
+        company_usecase.clone(),
+        project_usecase.clone(),
+        work_logs_usecase.clone(),
+    );
+    let invoice_usecase = di::init_invoice_usecase(
+        &db,
+        company_usecase.clone(),
+        project_usecase.clone(),
+        work_logs_usecase.clone(),
+    );
+    let calendar_usecase = di::init_calendar_usecase(
+        auth_usecase.clone(),
+        company_usecase.clone(),
+        project_usecase.clone(),
+        work_logs_usecase.clone(),
+    );
+
+    // 保持期間を過ぎたゴミ箱のデータを定期的に物理削除
+    jobs::trash_purge::spawn(
+        config::trash::TrashConfig::from_env(),
+        company_usecase.clone(),
+        project_usecase.clone(),
+        work_logs_usecase.clone(),
+    );
     let auth_usecase_clone = auth_usecase.clone();
 
+    // 管理者ユーザーの設定
+    let admin_config = config::admin::AdminConfig::from_env();
+
     // JWT認証のミドルウェアを設定
     let jwt_auth_check = HttpAuthentication::bearer(move |req, credentials| {
         let auth_usecase_clone = auth_usecase.clone();
@@ -153,6 +235,7 @@ async fn main() -> Result<()> {
 
     HttpServer::new(move || {
         App::new()
+            .wrap(middleware::error_format::FormatErrors)
             .wrap(middleware::csrf::csrf_middleware())
             .wrap(Logger::default())
             .wrap(middleware::security_headers::SecurityHeaders)
@@ -170,6 +253,7 @@ async fn main() -> Result<()> {
                     .build(),
             )
             .app_data(web::Data::new(s3_config.clone()))
+            .app_data(web::Data::new(admin_config.clone()))
             .service(SwaggerUi::new("/docs/{_:.*}").url("/docs/openapi.json", ApiDoc::openapi()))
             .service(
                 web::scope("/api")
@@ -178,6 +262,10 @@ async fn main() -> Result<()> {
                             .service(api::endpoints::auth::login)
                             .service(api::endpoints::auth::register)
                             .service(api::endpoints::auth::refresh) // アクセストークン無効時にリクエストするAPIなので、認証ミドルウェアは適用しない
+                            // パスワードを忘れた場合に使用するAPIなので、認証ミドルウェアは適用しない
+                            .service(api::endpoints::auth::request_password_reset)
+                            .service(api::endpoints::auth::confirm_password_reset)
+                            .service(api::endpoints::auth::confirm_email_verification)
                             .service(
                                 // logoutのみ認証ミドルウェアを適用
                                 web::scope("")
@@ -185,6 +273,7 @@ async fn main() -> Result<()> {
                                     .service(api::endpoints::auth::logout),
                             ),
                     )
+                    .service(api::routes::calendar_scope())
                     .service(
                         // 認証ミドルウェアを適用
                         web::scope("")
@@ -192,7 +281,13 @@ async fn main() -> Result<()> {
                             .service(api::routes::users_scope())
                             .service(api::routes::projects_scope())
                             .service(api::routes::work_logs_scope())
-                            .service(api::routes::companies_scope()),
+                            .service(api::routes::companies_scope())
+                            .service(api::routes::trash_scope())
+                            .service(api::routes::reports_scope())
+                            .service(api::routes::analytics_scope())
+                            .service(api::routes::exports_scope())
+                            .service(api::routes::invoices_scope())
+                            .service(api::routes::admin_scope()),
                     ),
             )
             .service(web::scope("/").service(web::resource("").to(index)))
@@ -201,7 +296,12 @@ async fn main() -> Result<()> {
             .app_data(web::Data::new(work_logs_usecase.clone()))
             .app_data(web::Data::new(project_usecase.clone()))
             .app_data(web::Data::new(company_usecase.clone()))
+            .app_data(web::Data::new(report_usecase.clone()))
+            .app_data(web::Data::new(export_usecase.clone()))
+            .app_data(web::Data::new(invoice_usecase.clone()))
+            .app_data(web::Data::new(calendar_usecase.clone()))
             .app_data(web::Data::new(auth_usecase_clone.clone()))
+            .app_data(web::Data::new(password_reset_usecase.clone()))
             .app_data(json_error_handler())
     })
     .bind(format!(
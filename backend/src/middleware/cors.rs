@@ -1,5 +1,9 @@
+use crate::middleware::error_format::VALIDATION_ERRORS_HEADER;
 use actix_cors::Cors;
-use actix_web::http::{header, Method};
+use actix_web::http::{
+    header::{self, HeaderName},
+    Method,
+};
 use log;
 use std::env;
 
@@ -29,7 +33,15 @@ pub fn cors_middleware() -> Cors {
     cors = cors.allowed_methods(parsed_methods);
 
     // 許可ヘッダーの設定
-    cors = cors.allowed_headers(&[header::AUTHORIZATION, header::ACCEPT, header::CONTENT_TYPE]);
+    cors = cors.allowed_headers(&[
+        header::AUTHORIZATION,
+        header::ACCEPT,
+        header::CONTENT_TYPE,
+        HeaderName::from_static(VALIDATION_ERRORS_HEADER),
+    ]);
+
+    // ページング用の総件数ヘッダーをフロントエンドから参照できるようにする
+    cors = cors.expose_headers([HeaderName::from_static("x-total-count")]);
 
     // クレデンシャルのサポート
     cors = cors.supports_credentials();
@@ -0,0 +1,111 @@
+use crate::api::extractors::RequestLocale;
+use crate::errors::app_error::{AppError, ErrorFormat};
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::InternalError;
+use actix_web::http::header::HeaderMap;
+use actix_web::Error;
+use futures::future::{ok, Ready};
+use std::future::Future;
+use std::pin::Pin;
+
+/// フィールドエラーを最初の1件だけ返すよう指定するヘッダー（値は`first`）
+pub const VALIDATION_ERRORS_HEADER: &str = "x-validation-errors";
+
+/// エラーレスポンスをリクエストに合わせた形式で生成し直すミドルウェア
+///
+/// `AppError`は`ResponseError`の実装でデフォルトの形式（日本語・すべてのフィールドエラー）の
+/// レスポンスを生成するため、ユーザーの設定・`Accept-Language`ヘッダーの表示言語や
+/// `X-Validation-Errors: first`ヘッダーの指定がある場合は差し替える。
+pub struct FormatErrors;
+
+impl FormatErrors {
+    /// フィールドエラーを最初の1件だけ返すよう指定されているか
+    fn first_field_error_only(headers: &HeaderMap) -> bool {
+        headers
+            .get(VALIDATION_ERRORS_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("first"))
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for FormatErrors
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = FormatErrorsMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(FormatErrorsMiddleware { service })
+    }
+}
+
+pub struct FormatErrorsMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for FormatErrorsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(
+        &self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        // ミドルウェアのエラー（認証エラーなど）はリクエストを参照できないため、先にヘッダーから判定しておく
+        // ※ルーティングで`HttpRequest`を書き換えるため、ここで`HttpRequest`を複製してはならない
+        let header_locale = RequestLocale::accept_language(req.headers()).unwrap_or_default();
+        let first_field_error_only = FormatErrors::first_field_error_only(req.headers());
+        let header_format = ErrorFormat {
+            locale: header_locale,
+            first_field_error_only,
+        };
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(res) => {
+                    // 認証ミドルウェアがユーザーの表示言語を設定した後に判定する
+                    let RequestLocale(locale) = RequestLocale::of(res.request());
+                    let format = ErrorFormat {
+                        locale,
+                        first_field_error_only,
+                    };
+                    let formatted = res
+                        .response()
+                        .error()
+                        .and_then(|error| error.as_error::<AppError>())
+                        .filter(|_| format != ErrorFormat::default())
+                        .map(|error| error.formatted_response(format));
+                    Ok(match formatted {
+                        Some(response) => res.into_response(response).map_into_boxed_body(),
+                        None => res.map_into_boxed_body(),
+                    })
+                }
+                Err(error) => match error.as_error::<AppError>() {
+                    Some(app_error) if header_format != ErrorFormat::default() => {
+                        let response = app_error.formatted_response(header_format);
+                        Err(InternalError::from_response(app_error.to_string(), response).into())
+                    }
+                    _ => Err(error),
+                },
+            }
+        })
+    }
+}
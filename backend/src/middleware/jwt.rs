@@ -1,12 +1,37 @@
+use crate::api::extractors::CurrentUser;
+use crate::config::email_verification::UnverifiedAccountRestriction;
 use crate::errors::app_error::AppError;
+use crate::i18n::messages;
 use crate::repositories::auth::MongoAuthRepository;
 use crate::usecases::auth::AuthUseCase;
 use actix_web::http::Method;
-use actix_web::{dev::ServiceRequest, web, Error as ActixError};
+use actix_web::{dev::ServiceRequest, web, Error as ActixError, HttpMessage};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
 use log::debug;
 use std::sync::Arc;
 
+/// メールアドレスが未確認の場合も制限しないパス（確認用リンクの再送信やメールアドレスの修正に必要な操作）
+const UNVERIFIED_ALLOWED_PATHS: [&str; 3] = [
+    "/api/users/me/",
+    "/api/users/me/email-verification/",
+    "/api/auth/logout/",
+];
+
+/// メールアドレスが未確認のユーザーに対する制限で、リクエストが許可されるか
+fn is_allowed_for_unverified(
+    req: &ServiceRequest,
+    restriction: UnverifiedAccountRestriction,
+) -> bool {
+    if UNVERIFIED_ALLOWED_PATHS.contains(&req.path()) {
+        return true;
+    }
+    match restriction {
+        UnverifiedAccountRestriction::None => true,
+        UnverifiedAccountRestriction::ReadOnly => req.method().is_safe(),
+        UnverifiedAccountRestriction::Blocked => false,
+    }
+}
+
 pub async fn validator(
     req: ServiceRequest,
     credentials: BearerAuth,
@@ -19,13 +44,37 @@ pub async fn validator(
 
     let token = credentials.token();
 
-    match auth_usecase.verify_access_token(token).await {
-        Ok(_) => {
-            debug!("Token validation succeeded");
+    let authenticated = async {
+        // タイムゾーンなどの設定は更新がすぐに反映されるよう、JWTのクレームではなく
+        // トークンの検証時に同時に取得したユーザー情報から設定する
+        let (claims, user) = auth_usecase.verify_access_token(token).await?;
+        let current_user = CurrentUser::try_from(claims)?.with_preferences(&user);
+        let restriction = auth_usecase.unverified_restriction(&user);
+        Ok::<_, AppError>((current_user, restriction))
+    };
+
+    match authenticated.await {
+        Ok((current_user, restriction)) => {
+            if !is_allowed_for_unverified(&req, restriction) {
+                debug!(
+                    "Request rejected for unverified email: user_id={}, restriction={:?}",
+                    current_user.user_id, restriction
+                );
+                return Err((
+                    AppError::Forbidden(messages::EMAIL_NOT_VERIFIED.into()).into(),
+                    req,
+                ));
+            }
+            debug!(
+                "Token validation succeeded: user_id={}, role={:?}, time_zone={}",
+                current_user.user_id, current_user.role, current_user.time_zone
+            );
+            // ハンドラーがCurrentUserとして受け取れるよう、検証済みの識別情報をリクエストに格納する
+            req.extensions_mut().insert(current_user);
             Ok(req)
         }
         Err(_) => Err((
-            AppError::Unauthorized("無効または期限切れのトークンです".to_string()).into(),
+            AppError::Unauthorized(messages::INVALID_OR_EXPIRED_TOKEN.into()).into(),
             req,
         )),
     }
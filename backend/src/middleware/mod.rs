@@ -1,5 +1,6 @@
 pub mod cors;
 pub mod csrf;
+pub mod error_format;
 pub mod jwt;
 pub mod rate_limit;
 pub mod security_headers;
@@ -5,11 +5,11 @@ use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct AuthTokenLogin {
-    #[validate(email(message = "有効なメールアドレスを入力してください"))]
+    #[validate(email(code = "email_invalid"))]
     #[schema(example = "user@example.com")]
     pub email: String,
 
-    #[validate(length(min = 8, message = "パスワードは8文字以上である必要があります"))]
+    #[validate(length(min = 8, code = "password_too_short"))]
     #[schema(example = "password123")]
     pub password: String,
 }
@@ -23,6 +23,10 @@ pub struct AuthTokenInDB {
     #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
     pub user_id: ObjectId,
 
+    // リフレッシュトークンのファミリーID（ログイン時に採番し、ローテーション後のトークンに引き継ぐ）
+    #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
+    pub family_id: ObjectId,
+
     #[schema(value_type = String, example = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9...")]
     pub access_token: String,
 
@@ -40,4 +44,98 @@ pub struct AuthTokenInDB {
 
     #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
     pub updated_at: Option<BsonDateTime>,
+
+    // ローテーションした日時（ローテーション済みのトークンは使用できず、再利用の検知のためだけに残す）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub rotated_at: Option<BsonDateTime>,
+
+    // ログイン・リフレッシュしたクライアントのUser-Agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_0) ...")]
+    pub user_agent: Option<String>,
+
+    // ログイン・リフレッシュしたクライアントのIPアドレス
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "203.0.113.1")]
+    pub ip_address: Option<String>,
+
+    // 最後に使用された日時（アクセストークンによる認証のたびに一定間隔で更新する）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub last_used_at: Option<BsonDateTime>,
+}
+
+/// 認証トークンを発行したクライアントの情報（セッション一覧での識別用）
+#[derive(Debug, Clone, Default)]
+pub struct SessionClient {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// 削除する認証トークンの範囲
+///
+/// セッションはログインごとに発行したトークンのファミリー（`family_id`）を単位とする
+#[derive(Debug, Clone)]
+pub enum AuthTokenScope {
+    /// 指定した認証トークンのみ
+    Token(ObjectId),
+    /// ユーザーのすべてのセッション
+    User(ObjectId),
+    /// ユーザーの指定したセッション
+    Session {
+        user_id: ObjectId,
+        family_id: ObjectId,
+    },
+    /// ユーザーの指定したセッション以外のすべてのセッション
+    OtherSessions {
+        user_id: ObjectId,
+        family_id: ObjectId,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct PasswordResetRequest {
+    #[validate(email(code = "email_invalid"))]
+    #[schema(example = "user@example.com")]
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct PasswordResetConfirm {
+    /// メールに記載したリセットトークン
+    #[validate(length(min = 1, code = "reset_token_required"))]
+    #[schema(example = "3q2-7wWx9kQ...")]
+    pub token: String,
+
+    #[validate(length(min = 8, code = "password_too_short"))]
+    #[schema(example = "newpassword123")]
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct EmailVerificationConfirm {
+    /// メールに記載した確認用リンクのトークン
+    #[validate(length(min = 1, code = "verification_token_required"))]
+    #[schema(example = "eyJ0eXAiOiJKV1QiLCJhbGciOiJIUzI1NiJ9...")]
+    pub token: String,
+}
+
+/// パスワードリセットトークン（トークン自体は保存せず、ハッシュ値のみを保存する）
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PasswordResetTokenInDB {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub user_id: ObjectId,
+
+    pub token_hash: String,
+
+    pub expires_at: BsonDateTime,
+
+    // 使用した日時（使用済みのトークンは再利用できない）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used_at: Option<BsonDateTime>,
+
+    pub created_at: BsonDateTime,
 }
@@ -1,7 +1,7 @@
 use crate::models::projects::ProjectInDB;
 use bson::{oid::ObjectId, DateTime as BsonDateTime};
 use chrono::{NaiveDate, TimeZone, Utc};
-use chrono_tz::Asia::Tokyo;
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use utoipa::ToSchema;
@@ -9,7 +9,7 @@ use validator::{Validate, ValidationError, ValidationErrors};
 
 // カスタムバリデーション用のトレイト
 trait DateValidator {
-    fn validate_dates(&self) -> Result<(), ValidationError>;
+    fn validate_dates(&self, time_zone: Tz) -> Result<(), ValidationError>;
 }
 
 // バリデーションロジックを共通化するマクロ
@@ -17,27 +17,18 @@ macro_rules! impl_company_validation {
     ($type:ty) => {
         impl DateValidator for $type {
             // カスタムバリデーション: 契約開始日と契約終了日のバリデーションを行う
-            fn validate_dates(&self) -> Result<(), ValidationError> {
-                let today = Tokyo
+            // （今日の日付はユーザーのタイムゾーンで判定する）
+            fn validate_dates(&self, time_zone: Tz) -> Result<(), ValidationError> {
+                let today = time_zone
                     .from_utc_datetime(&Utc::now().naive_utc())
                     .date_naive();
 
                 if self.affiliation_start_date > today {
-                    return Err(ValidationError {
-                        code: "date_validation".into(),
-                        message: Some("契約開始日は現在日付より前である必要があります".into()),
-                        params: Default::default(),
-                    });
+                    return Err(ValidationError::new("affiliation_start_date_future"));
                 }
                 if let Some(end_date) = self.affiliation_end_date {
                     if end_date <= self.affiliation_start_date {
-                        return Err(ValidationError {
-                            code: "date_validation".into(),
-                            message: Some(
-                                "契約終了日は契約開始日より後である必要があります".into(),
-                            ),
-                            params: Default::default(),
-                        });
+                        return Err(ValidationError::new("affiliation_end_date_before_start"));
                     }
                 }
                 Ok(())
@@ -45,20 +36,12 @@ macro_rules! impl_company_validation {
         }
 
         impl $type {
-            pub fn validate_all(&self) -> Result<(), ValidationErrors> {
-                let mut errors = ValidationErrors::new();
-
-                // 既存のバリデーションを実行
-                if let Err(e) = self.common.validate() {
-                    for (field, field_errors) in e.field_errors() {
-                        for error in field_errors {
-                            errors.add(field, error.clone());
-                        }
-                    }
-                }
+            pub fn validate_all(&self, time_zone: Tz) -> Result<(), ValidationErrors> {
+                // 既存のバリデーションを実行（ネストした構造体のエラーもそのまま保持する）
+                let mut errors = self.common.validate().err().unwrap_or_default();
 
                 // カスタムバリデーションを実行
-                if let Err(e) = self.validate_dates() {
+                if let Err(e) = self.validate_dates(time_zone) {
                     errors.add("dates", e);
                 }
 
@@ -96,26 +79,22 @@ pub enum ContractType {
 
 #[derive(Serialize, Deserialize, Debug, Validate, ToSchema)]
 pub struct AnnualSales {
-    #[validate(range(min = 0, message = "年間売上は0以上である必要があります"))]
+    #[validate(range(min = 0, code = "annual_sales_range"))]
     #[schema(example = 100000000)]
     pub amount: i64, // 年間売上
 
-    #[validate(range(max = 2100, message = "会計年度は2100年以前である必要があります"))]
+    #[validate(range(max = 2100, code = "fiscal_year_range"))]
     #[schema(example = 2024)]
     pub fiscal_year: i32, // 会計年度
 }
 
 #[derive(Serialize, Deserialize, Debug, Validate, ToSchema)]
 pub struct Bonus {
-    #[validate(range(min = 0, message = "ボーナス金額は0以上である必要があります"))]
+    #[validate(range(min = 0, code = "bonus_amount_range"))]
     #[schema(example = 1000000)]
     pub amount: i64, // ボーナス金額
 
-    #[validate(range(
-        min = 1,
-        max = 12,
-        message = "ボーナス頻度は1から12の間である必要があります"
-    ))]
+    #[validate(range(min = 1, max = 12, code = "bonus_frequency_range"))]
     #[schema(example = 2)]
     pub frequency: i32, // ボーナス頻度
 }
@@ -123,52 +102,46 @@ pub struct Bonus {
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, Validate, ToSchema)]
 pub struct CompanyCommon {
-    #[validate(length(min = 2, max = 100, message = "企業名は2〜100文字である必要があります"))]
+    #[validate(length(min = 2, max = 100, code = "company_name_length"))]
     #[schema(example = "株式会社テスト")]
     pub company_name: String, // 企業名
-    #[validate(range(
-        min = 1800,
-        max = 2100,
-        message = "設立年は1800年から現在までの間である必要があります"
-    ))]
+    #[validate(range(min = 1800, max = 2100, code = "establishment_year_range"))]
     #[schema(example = 2024)]
     pub establishment_year: i32, // 設立年
 
-    #[validate(length(max = 200, message = "所在地は200文字以内である必要があります"))]
+    #[validate(length(max = 200, code = "location_length"))]
     #[schema(example = "東京都千代田区")]
     pub location: String, // 所在地
 
-    #[validate(url(message = "有効なURLを入力してください"))]
+    #[validate(url(code = "website_url_invalid"))]
     #[schema(example = "https://www.example.com")]
     pub website_url: String, // 企業サイトURL
 
-    #[validate(range(min = 1, message = "従業員数は1以上である必要があります"))]
+    #[validate(range(min = 1, code = "employee_count_range"))]
     #[schema(example = 100)]
     pub employee_count: i32, // 従業員数
 
+    #[validate(nested)]
     pub annual_sales: Option<AnnualSales>, // 年間売上
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contract_type: Option<ContractType>, // 契約タイプ
 
-    #[validate(length(max = 10, message = "主要顧客は最大10件まで登録できます"))]
+    #[validate(length(max = 10, code = "major_clients_too_many"))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub major_clients: Option<Vec<String>>, // 主要顧客
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[validate(length(max = 10, message = "主要サービスは最大10件まで登録できます"))]
+    #[validate(length(max = 10, code = "major_services_too_many"))]
     pub major_services: Option<Vec<String>>, // 主要サービス
 
-    #[validate(range(
-        min = 500,
-        max = 100000,
-        message = "平均時給は500円から100,000円の間である必要があります"
-    ))]
+    #[validate(range(min = 500, max = 100000, code = "average_hourly_rate_range"))]
     #[schema(example = 4000)]
     pub average_hourly_rate: Option<i32>, // 平均時給
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(example = json!({"amount": 100000, "frequency": 1}))]
+    #[validate(nested)]
     pub bonus: Option<Bonus>, // ボーナス
 
     #[schema(example = "PendingContract")]
@@ -181,6 +154,9 @@ pub struct CompanyInDB {
     #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
     pub id: Option<ObjectId>,
 
+    #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
+    pub user_id: ObjectId, // 所有ユーザーID
+
     #[serde(flatten)]
     pub common: CompanyCommon,
 
@@ -197,6 +173,10 @@ pub struct CompanyInDB {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
     pub updated_at: Option<BsonDateTime>, // 更新日時
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub deleted_at: Option<BsonDateTime>, // 削除日時（論理削除）
 }
 
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
@@ -212,11 +192,11 @@ pub struct CompanyCreate {
     #[serde(flatten)]
     pub common: CompanyCommon,
 
-    /// 契約開始日（JST, YYYY-MM-DD形式で受け取ること）
+    /// 契約開始日（ユーザーのタイムゾーン, YYYY-MM-DD形式で受け取ること）
     #[schema(value_type = String, example = "2023-12-01")]
     pub affiliation_start_date: NaiveDate, // 契約開始日
 
-    /// 契約終了日（JST, YYYY-MM-DD形式で受け取ること）
+    /// 契約終了日（ユーザーのタイムゾーン, YYYY-MM-DD形式で受け取ること）
     #[schema(value_type = Option<String>, example = "2024-09-30")]
     pub affiliation_end_date: Option<NaiveDate>, // 契約終了日
 }
@@ -226,11 +206,11 @@ pub struct CompanyUpdate {
     #[serde(flatten)]
     pub common: CompanyCommon,
 
-    /// 契約開始日（JST, YYYY-MM-DD形式で受け取ること）
+    /// 契約開始日（ユーザーのタイムゾーン, YYYY-MM-DD形式で受け取ること）
     #[schema(value_type = String, example = "2023-12-01")]
     pub affiliation_start_date: NaiveDate, // 契約開始日
 
-    /// 契約終了日（JST, YYYY-MM-DD形式で受け取ること）
+    /// 契約終了日（ユーザーのタイムゾーン, YYYY-MM-DD形式で受け取ること）
     #[schema(value_type = Option<String>, example = "2024-09-30")]
     pub affiliation_end_date: Option<NaiveDate>, // 契約終了日
 }
@@ -244,3 +224,15 @@ impl From<CompanyInDB> for CompanyUpdate {
         }
     }
 }
+
+#[derive(Deserialize, Debug, ToSchema, Validate)]
+pub struct CompanyQuery {
+    /// 取得するドキュメント数の制限（省略時は20件）
+    #[schema(example = 10)]
+    #[validate(range(min = 1, max = 100, code = "limit_range"))]
+    pub limit: Option<i64>,
+
+    /// 次のページを取得するためのカーソル（前回のレスポンスの`next_cursor`）
+    #[schema(example = "eyJrIjpbXX0")]
+    pub cursor: Option<String>,
+}
@@ -0,0 +1,116 @@
+use crate::models::reports::validate_period;
+use bson::oid::ObjectId;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// エクスポートの形式
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv, // CSV（UTF-8, BOM付き）
+    Xlsx, // Excel
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Validate, ToSchema)]
+#[validate(schema(function = "validate_work_log_export_period"))]
+pub struct WorkLogExportQuery {
+    /// 出力期間の開始日（YYYY-MM-DD形式, この日を含む）
+    #[schema(value_type = String, example = "2024-04-01")]
+    pub from: NaiveDate,
+
+    /// 出力期間の終了日（YYYY-MM-DD形式, この日を含む）
+    #[schema(value_type = String, example = "2024-04-30")]
+    pub to: NaiveDate,
+
+    /// プロジェクトID
+    #[schema(value_type = Option<String>, example = "60a7e3e0f1c1b2a3b4c5d6e7")]
+    pub project_id: Option<ObjectId>,
+
+    /// 企業ID（企業に紐づく全プロジェクトの勤怠を対象とする）
+    #[schema(value_type = Option<String>, example = "80a6c1e9f0f7b9001234abcd")]
+    pub company_id: Option<ObjectId>,
+
+    /// 出力形式（csv, xlsx）
+    #[serde(default)]
+    #[schema(example = "csv")]
+    pub format: ExportFormat,
+}
+
+fn validate_work_log_export_period(query: &WorkLogExportQuery) -> Result<(), ValidationError> {
+    validate_period(query.from, query.to)
+}
+
+/// エクスポートの列見出し
+pub const WORK_LOG_EXPORT_HEADERS: [&str; 9] = [
+    "プロジェクト",
+    "企業",
+    "開始日時",
+    "終了日時",
+    "休憩時間（分）",
+    "実作業時間（分）",
+    "時給",
+    "金額",
+    "メモ",
+];
+
+/// エクスポートするセルの値
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportCell {
+    Text(String),
+    Number(i64),
+    Empty,
+}
+
+/// エクスポートする勤怠の1行
+#[derive(Debug)]
+pub struct WorkLogExportRow {
+    pub project_title: String,    // プロジェクト名（削除済みの場合は空文字）
+    pub company_name: String,     // 企業名（削除済みの場合は空文字）
+    pub start_time: String,       // 開始日時（ユーザーのタイムゾーン）
+    pub end_time: Option<String>, // 終了日時（ユーザーのタイムゾーン, 作業中の場合は空）
+    pub break_time: i32,          // 休憩時間（分）
+    pub actual_work_minutes: Option<i32>, // 実作業時間（分, 作業中の場合は空）
+    pub hourly_rate: Option<i32>, // 適用した時給
+    pub amount: Option<i64>,      // 金額（実作業時間 × 時給）
+    pub memo: String,             // メモ
+}
+
+impl WorkLogExportRow {
+    /// 列見出しと同じ順序のセルに変換する
+    pub fn cells(self) -> Vec<ExportCell> {
+        let text = ExportCell::Text;
+        let number = |value: Option<i64>| value.map_or(ExportCell::Empty, ExportCell::Number);
+        vec![
+            text(self.project_title),
+            text(self.company_name),
+            text(self.start_time),
+            self.end_time.map_or(ExportCell::Empty, text),
+            ExportCell::Number(self.break_time as i64),
+            number(self.actual_work_minutes.map(i64::from)),
+            number(self.hourly_rate.map(i64::from)),
+            number(self.amount),
+            text(self.memo),
+        ]
+    }
+}
@@ -0,0 +1,83 @@
+use crate::i18n::Message;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+/// 一度にインポートできる最大行数
+pub const IMPORT_MAX_ROWS: usize = 5000;
+
+/// インポートするデータの最大サイズ（バイト）
+pub const IMPORT_MAX_BYTES: usize = 5 * 1024 * 1024;
+
+/// インポートの形式
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    #[default]
+    Csv, // CSV（1行目は列見出し）
+    Jsonl, // JSON Lines（1行に1件のJSONオブジェクト）
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct WorkLogImportQuery {
+    /// 入力形式（csv, jsonl）
+    #[serde(default)]
+    #[schema(example = "csv")]
+    pub format: ImportFormat,
+
+    /// trueの場合は検証のみ行い、勤怠を登録しない
+    #[serde(default)]
+    #[schema(example = false)]
+    pub dry_run: bool,
+
+    /// trueの場合は既存の勤怠や他の行と時間が重複していても登録する
+    #[serde(default)]
+    #[schema(example = false)]
+    pub allow_overlap: bool,
+}
+
+/// インポートする勤怠の1行
+///
+/// 日時は行ごとにエラーを報告するため、文字列のまま受け取って後で解釈する
+#[derive(Deserialize, Debug)]
+pub struct WorkLogImportRecord {
+    pub project: String, // プロジェクトIDまたはプロジェクト名
+
+    pub start_time: String, // 開始時間（RFC 3339形式）
+
+    #[serde(default)]
+    pub end_time: Option<String>, // 終了時間（RFC 3339形式）
+
+    #[serde(default)]
+    pub break_time: Option<i32>, // 休憩時間（分）
+
+    #[serde(default)]
+    pub actual_work_minutes: Option<i32>, // 実作業時間（分）
+
+    #[serde(default)]
+    pub memo: Option<String>, // メモ
+}
+
+/// 解釈した入力の1行
+#[derive(Debug)]
+pub struct ParsedImportRow {
+    pub line: usize, // 入力データ上の行番号（1始まり）
+    pub record: Result<WorkLogImportRecord, Message>,
+}
+
+/// インポートできなかった行のエラー（メッセージはレスポンスの生成時に表示言語に変換する）
+#[derive(Debug, Clone)]
+pub struct WorkLogImportError {
+    pub line: usize,           // 入力データ上の行番号（1始まり）
+    pub field: Option<String>, // エラーのあったフィールド
+    pub message: Message,
+}
+
+impl WorkLogImportError {
+    pub fn new(line: usize, field: Option<&str>, message: impl Into<Message>) -> Self {
+        Self {
+            line,
+            field: field.map(str::to_string),
+            message: message.into(),
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use crate::models::reports::validate_period;
+use bson::{oid::ObjectId, DateTime as BsonDateTime};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// 消費税率のデフォルト値（%）
+pub const DEFAULT_TAX_RATE: i32 = 10;
+
+/// 請求書のステータス
+///
+/// 下書き → 送付済み → 入金済みの順にのみ進められる
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum InvoiceStatus {
+    #[default]
+    Draft, // 下書き
+    Sent, // 送付済み
+    Paid, // 入金済み
+}
+
+impl InvoiceStatus {
+    /// 表示名
+    pub fn label(&self) -> &'static str {
+        match self {
+            InvoiceStatus::Draft => "下書き",
+            InvoiceStatus::Sent => "送付済み",
+            InvoiceStatus::Paid => "入金済み",
+        }
+    }
+
+    /// 指定したステータスに変更できるかどうか
+    pub fn can_transition_to(&self, next: InvoiceStatus) -> bool {
+        matches!(
+            (self, next),
+            (InvoiceStatus::Draft, InvoiceStatus::Sent)
+                | (InvoiceStatus::Sent, InvoiceStatus::Paid)
+        )
+    }
+}
+
+/// 請求書の明細（プロジェクトごと）
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct InvoiceLineItem {
+    #[schema(value_type = String, example = "60a7e3e0f1c1b2a3b4c5d6e7")]
+    pub project_id: ObjectId,
+
+    #[schema(example = "DevTrackr開発")]
+    pub description: String, // 品目（作成時点のプロジェクト名）
+
+    #[schema(example = 9000)]
+    pub work_minutes: i64, // 実作業時間（分）
+
+    #[schema(example = 5000)]
+    pub hourly_rate: i32, // 適用した時給
+
+    #[schema(example = 750000)]
+    pub amount: i64, // 金額（税抜）
+}
+
+fn default_tax_rate() -> i32 {
+    DEFAULT_TAX_RATE
+}
+
+#[derive(Deserialize, Debug, Validate, ToSchema)]
+#[validate(schema(function = "validate_invoice_create"))]
+pub struct InvoiceCreate {
+    /// 請求先の企業ID（契約タイプがFreelanceまたはSideJobの企業のみ）
+    #[schema(value_type = String, example = "80a6c1e9f0f7b9001234abcd")]
+    pub company_id: ObjectId,
+
+    /// 請求期間の開始日（ユーザーのタイムゾーン, YYYY-MM-DD形式, この日を含む）
+    #[schema(value_type = String, example = "2024-04-01")]
+    pub period_start: NaiveDate,
+
+    /// 請求期間の終了日（ユーザーのタイムゾーン, YYYY-MM-DD形式, この日を含む）
+    #[schema(value_type = String, example = "2024-04-30")]
+    pub period_end: NaiveDate,
+
+    /// 消費税率（%）
+    #[serde(default = "default_tax_rate")]
+    #[validate(range(min = 0, max = 100, code = "tax_rate_range"))]
+    #[schema(example = 10)]
+    pub tax_rate: i32,
+
+    /// 支払期限（YYYY-MM-DD形式）
+    #[schema(value_type = Option<String>, example = "2024-05-31")]
+    pub due_date: Option<NaiveDate>,
+
+    /// 備考
+    #[validate(length(max = 1000, code = "invoice_notes_length"))]
+    #[schema(example = "お振込手数料はご負担ください。")]
+    pub notes: Option<String>,
+}
+
+fn validate_invoice_create(invoice: &InvoiceCreate) -> Result<(), ValidationError> {
+    validate_period(invoice.period_start, invoice.period_end)?;
+    if let Some(due_date) = invoice.due_date {
+        if due_date < invoice.period_end {
+            return Err(ValidationError::new("due_date_before_period_end"));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct InvoiceStatusUpdate {
+    #[schema(example = "Sent")]
+    pub status: InvoiceStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct InvoiceInDB {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "507f1f77bcf86cd799439011")]
+    pub id: Option<ObjectId>,
+
+    #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
+    pub user_id: ObjectId, // 所有ユーザーID
+
+    #[schema(example = "INV-000001")]
+    pub invoice_number: String, // 請求書番号（ユーザーごとの連番）
+
+    #[schema(value_type = String, example = "80a6c1e9f0f7b9001234abcd")]
+    pub company_id: ObjectId,
+
+    #[schema(example = "株式会社テスト")]
+    pub company_name: String, // 作成時点の企業名
+
+    #[schema(value_type = String, example = "2024-04-01")]
+    pub period_start: NaiveDate, // 請求期間の開始日
+
+    #[schema(value_type = String, example = "2024-04-30")]
+    pub period_end: NaiveDate, // 請求期間の終了日
+
+    #[schema(value_type = String, example = "2024-05-01")]
+    pub issue_date: NaiveDate, // 発行日（ユーザーのタイムゾーン）
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "2024-05-31")]
+    pub due_date: Option<NaiveDate>, // 支払期限
+
+    pub line_items: Vec<InvoiceLineItem>, // 明細
+
+    #[schema(example = 750000)]
+    pub subtotal: i64, // 小計（税抜）
+
+    #[schema(example = 10)]
+    pub tax_rate: i32, // 消費税率（%）
+
+    #[schema(example = 75000)]
+    pub tax_amount: i64, // 消費税額（1円未満切り捨て）
+
+    #[schema(example = 825000)]
+    pub total: i64, // 請求金額（税込）
+
+    pub status: InvoiceStatus,
+
+    #[schema(value_type = Vec<String>)]
+    pub work_log_ids: Vec<ObjectId>, // 請求対象の勤怠（編集不可としてロックする）
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "お振込手数料はご負担ください。")]
+    pub notes: Option<String>, // 備考
+
+    #[schema(value_type = String, example = "2023-04-13T12:34:56Z")]
+    pub created_at: BsonDateTime,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub updated_at: Option<BsonDateTime>,
+}
+
+/// 請求書番号の採番結果から請求書番号の文字列を生成する
+pub fn format_invoice_number(sequence: i64) -> String {
+    format!("INV-{:06}", sequence)
+}
+
+/// 税抜金額と税率から消費税額を計算する（1円未満は切り捨て）
+pub fn calculate_tax_amount(subtotal: i64, tax_rate: i32) -> i64 {
+    subtotal * tax_rate as i64 / 100
+}
+
+#[derive(Debug, Default)]
+pub struct InvoiceFilter {
+    pub company_id: Option<ObjectId>,
+
+    pub status: Option<InvoiceStatus>,
+}
+
+#[derive(Deserialize, Debug, Validate, ToSchema)]
+pub struct InvoiceQuery {
+    /// 企業ID
+    #[schema(value_type = Option<String>, example = "80a6c1e9f0f7b9001234abcd")]
+    pub company_id: Option<ObjectId>,
+
+    /// ステータス（Draft, Sent, Paid）
+    #[schema(example = "Draft")]
+    pub status: Option<InvoiceStatus>,
+
+    /// 取得するドキュメント数の制限（省略時は20件）
+    #[schema(example = 10)]
+    #[validate(range(min = 1, max = 100, code = "limit_range"))]
+    pub limit: Option<i64>,
+
+    /// 次のページを取得するためのカーソル（前回のレスポンスの`next_cursor`）
+    #[schema(example = "eyJrIjpbXX0")]
+    pub cursor: Option<String>,
+}
+
+impl InvoiceQuery {
+    pub fn to_filter(&self) -> InvoiceFilter {
+        InvoiceFilter {
+            company_id: self.company_id,
+            status: self.status,
+        }
+    }
+}
+
+/// 請求書の出力形式
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceDocumentFormat {
+    #[default]
+    Pdf, // PDF
+    Html, // HTML
+}
+
+impl InvoiceDocumentFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            InvoiceDocumentFormat::Pdf => "application/pdf",
+            InvoiceDocumentFormat::Html => "text/html; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            InvoiceDocumentFormat::Pdf => "pdf",
+            InvoiceDocumentFormat::Html => "html",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct InvoiceDocumentQuery {
+    /// 出力形式（pdf, html）
+    #[serde(default)]
+    #[schema(example = "pdf")]
+    pub format: InvoiceDocumentFormat,
+}
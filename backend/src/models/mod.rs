@@ -1,5 +1,9 @@
 pub mod auth;
 pub mod companies;
+pub mod exports;
+pub mod imports;
+pub mod invoices;
 pub mod projects;
+pub mod reports;
 pub mod users;
 pub mod work_logs;
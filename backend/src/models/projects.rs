@@ -30,23 +30,19 @@ impl fmt::Display for ProjectStatus {
 
 #[derive(Deserialize, Debug, Validate, ToSchema)]
 pub struct ProjectCreate {
-    #[validate(length(
-        min = 1,
-        max = 100,
-        message = "タイトルは1〜100文字である必要があります"
-    ))]
+    #[validate(length(min = 1, max = 100, code = "project_title_length"))]
     pub title: String,
 
-    #[validate(length(max = 1000, message = "説明は1000文字以内である必要があります"))]
+    #[validate(length(max = 1000, code = "project_description_length"))]
     pub description: Option<String>,
 
-    #[validate(length(max = 10, message = "スキルラベルは最大10個まで登録できます"))]
+    #[validate(length(max = 10, code = "skill_labels_too_many"))]
     pub skill_labels: Option<Vec<String>>,
 
     #[schema(value_type = String, example = "70a6c1e9f0f7b9001234abcd")]
     pub company_id: ObjectId, // プロジェクトの企業ID
 
-    #[validate(range(min = 0, message = "時給は0以上である必要があります"))]
+    #[validate(range(min = 0, code = "hourly_pay_range"))]
     pub hourly_pay: Option<i32>,
 
     pub status: ProjectStatus,
@@ -54,28 +50,24 @@ pub struct ProjectCreate {
 
 #[derive(Serialize, Deserialize, Debug, Validate, ToSchema)]
 pub struct ProjectUpdate {
-    #[validate(length(
-        min = 1,
-        max = 100,
-        message = "タイトルは1〜100文字である必要があります"
-    ))]
+    #[validate(length(min = 1, max = 100, code = "project_title_length"))]
     pub title: String,
 
-    #[validate(length(max = 1000, message = "説明は1000文字以内である必要があります"))]
+    #[validate(length(max = 1000, code = "project_description_length"))]
     pub description: Option<String>,
 
-    #[validate(length(max = 10, message = "スキルラベルは最大10個まで登録できます"))]
+    #[validate(length(max = 10, code = "skill_labels_too_many"))]
     pub skill_labels: Option<Vec<String>>,
 
     #[schema(value_type = String, example = "70a6c1e9f0f7b9001234abcd")]
     pub company_id: ObjectId, // プロジェクトの企業ID
 
-    #[validate(range(min = 0, message = "時給は0以上である必要があります"))]
+    #[validate(range(min = 0, code = "hourly_pay_range"))]
     pub hourly_pay: Option<i32>,
 
     pub status: ProjectStatus,
 
-    #[validate(range(min = 0, message = "総作業時間は0以上である必要があります"))]
+    #[validate(range(min = 0, code = "total_working_time_range"))]
     pub total_working_time: i64,
 }
 
@@ -86,6 +78,9 @@ pub struct ProjectInDB {
     #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
     pub id: Option<ObjectId>,
 
+    #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
+    pub user_id: ObjectId, // 所有ユーザーID
+
     pub title: String, // プロジェクトのタイトル
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -111,6 +106,10 @@ pub struct ProjectInDB {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
     pub updated_at: Option<BsonDateTime>, // 更新日時
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub deleted_at: Option<BsonDateTime>, // 削除日時（論理削除）
 }
 
 impl From<ProjectInDB> for ProjectUpdate {
@@ -148,13 +147,6 @@ impl ProjectFilter {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "lowercase")]
-pub enum SortOrder {
-    Asc,
-    Desc,
-}
-
 #[derive(Deserialize, ToSchema, Validate, Clone)]
 pub struct ProjectQuery {
     /// プロジェクトのタイトル（部分一致）
@@ -168,22 +160,21 @@ pub struct ProjectQuery {
     /// スキルラベルの一覧
     #[serde(default, deserialize_with = "deserialize_skill_labels")]
     #[schema(example = json!(["C++", "Rust"]), value_type = Vec<String>)]
-    #[validate(length(max = 10, message = "スキルラベルは最大10個まで指定できます"))]
+    #[validate(length(max = 10, code = "skill_labels_filter_too_many"))]
     pub skill_labels: Option<Vec<String>>,
 
     /// 企業ID
     #[schema(value_type = String, example = "80a6c1e9f0f7b9001234abcd")]
     pub company_id: Option<ObjectId>,
 
-    /// 取得するドキュメント数の制限
+    /// 取得するドキュメント数の制限（省略時は20件）
     #[schema(example = 10)]
-    #[validate(range(min = 1, max = 100, message = "limitは1から100の間で指定してください"))]
+    #[validate(range(min = 1, max = 100, code = "limit_range"))]
     pub limit: Option<i64>,
 
-    /// 取得を開始する位置
-    #[schema(example = 0)]
-    #[validate(range(min = 0, message = "offsetは0以上で指定してください"))]
-    pub offset: Option<u64>,
+    /// 次のページを取得するためのカーソル（前回のレスポンスの`next_cursor`）
+    #[schema(example = "eyJrIjpbXX0")]
+    pub cursor: Option<String>,
 
     /// ソート条件（例: "name:asc", "created_at:desc"）
     #[serde(default, deserialize_with = "deserialize_sort_params")]
@@ -210,27 +201,30 @@ impl ProjectQuery {
     }
 
     /// ソートパラメータを MongoDB 用の形式に変換する
-    pub fn parse_sort_params(&self) -> Option<Vec<(String, i8)>> {
-        self.sort.as_ref().map(|sort_params| {
-            sort_params
-                .iter()
-                .filter_map(|param| {
-                    let parts: Vec<&str> = param.split(':').collect();
-                    if parts.len() == 2 {
-                        Some((
-                            parts[0].to_string(),
-                            if parts[1].to_lowercase() == "asc" {
-                                1
-                            } else {
-                                -1
-                            },
-                        ))
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        })
+    pub fn parse_sort_params(&self) -> Vec<(String, i8)> {
+        self.sort
+            .as_ref()
+            .map(|sort_params| {
+                sort_params
+                    .iter()
+                    .filter_map(|param| {
+                        let parts: Vec<&str> = param.split(':').collect();
+                        if parts.len() == 2 {
+                            Some((
+                                parts[0].to_string(),
+                                if parts[1].to_lowercase() == "asc" {
+                                    1
+                                } else {
+                                    -1
+                                },
+                            ))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 }
 
@@ -250,9 +244,7 @@ fn validate_sort_params(sort: &[String]) -> Result<(), ValidationError> {
     for param in sort {
         let parts: Vec<&str> = param.split(':').collect();
         if parts.len() != 2 {
-            let mut err = ValidationError::new("sort_format");
-            err.message = Some("Invalid sort format. Expected 'field:order'".into());
-            return Err(err);
+            return Err(ValidationError::new("sort_format"));
         }
 
         let field = parts[0];
@@ -261,21 +253,14 @@ fn validate_sort_params(sort: &[String]) -> Result<(), ValidationError> {
         // フィールドの検証
         if !ALLOWED_FIELDS.contains(&field) {
             let mut err = ValidationError::new("sort_field");
-            err.message = Some(
-                format!(
-                    "Invalid sort field: {}. Allowed fields are: {:?}",
-                    field, ALLOWED_FIELDS
-                )
-                .into(),
-            );
+            err.add_param("field".into(), &field);
+            err.add_param("allowed".into(), &ALLOWED_FIELDS.join(", "));
             return Err(err);
         }
 
         // ソート順の検証
         if order != "asc" && order != "desc" {
-            let mut err = ValidationError::new("sort_order");
-            err.message = Some("Sort order must be either 'asc' or 'desc'".into());
-            return Err(err);
+            return Err(ValidationError::new("sort_order"));
         }
     }
     Ok(())
@@ -0,0 +1,238 @@
+use crate::utils::serializer::{serialize_bson_datetime, serialize_object_id};
+use bson::{oid::ObjectId, DateTime as BsonDateTime};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// 収入の集計単位
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EarningsGroupBy {
+    WorkLog, // 勤怠ごと
+    Project, // プロジェクトごと
+    Company, // 企業ごと
+    #[default]
+    Month, // 月ごと（ユーザーのタイムゾーン）
+}
+
+#[derive(Deserialize, Debug, Validate, ToSchema)]
+#[validate(schema(function = "validate_earnings_period"))]
+pub struct EarningsQuery {
+    /// 集計期間の開始日（ユーザーのタイムゾーン, YYYY-MM-DD形式, この日を含む）
+    #[schema(value_type = String, example = "2024-04-01")]
+    pub from: NaiveDate,
+
+    /// 集計期間の終了日（ユーザーのタイムゾーン, YYYY-MM-DD形式, この日を含む）
+    #[schema(value_type = String, example = "2024-06-30")]
+    pub to: NaiveDate,
+
+    /// 集計単位（work_log, project, company, month）
+    #[serde(default)]
+    #[schema(example = "month")]
+    pub group_by: EarningsGroupBy,
+}
+
+/// 集計期間のバリデーション
+pub fn validate_period(from: NaiveDate, to: NaiveDate) -> Result<(), ValidationError> {
+    if from > to {
+        return Err(ValidationError::new("period_range"));
+    }
+    Ok(())
+}
+
+fn validate_earnings_period(query: &EarningsQuery) -> Result<(), ValidationError> {
+    validate_period(query.from, query.to)
+}
+
+/// 収入の内訳
+#[derive(Serialize, Debug, Default, Clone, PartialEq, ToSchema)]
+pub struct EarningsAmount {
+    #[schema(example = 6000)]
+    pub work_minutes: i64, // 実作業時間（分）
+
+    #[schema(example = 300000)]
+    pub work_earnings: i64, // 作業による収入（実作業時間 × 時給）
+
+    #[schema(example = 100000)]
+    pub bonus: i64, // ボーナス（年間ボーナスを月割りした額）
+
+    #[schema(example = 400000)]
+    pub total: i64, // 合計収入
+
+    #[schema(example = 0)]
+    pub unpriced_work_minutes: i64, // 時給が設定されておらず収入に含まれない作業時間（分）
+}
+
+impl EarningsAmount {
+    /// 作業時間と収入を加算する（時給が`None`の場合は時給未設定の作業時間として扱う）
+    pub fn add_work(&mut self, minutes: i64, hourly_rate: Option<i32>) {
+        self.work_minutes += minutes;
+        match hourly_rate {
+            Some(rate) => {
+                let earnings = calculate_work_earnings(minutes, rate);
+                self.work_earnings += earnings;
+                self.total += earnings;
+            }
+            None => self.unpriced_work_minutes += minutes,
+        }
+    }
+
+    pub fn add_bonus(&mut self, bonus: i64) {
+        self.bonus += bonus;
+        self.total += bonus;
+    }
+}
+
+/// 作業時間（分）と時給から作業による収入を計算する（1円未満は四捨五入）
+pub fn calculate_work_earnings(minutes: i64, hourly_rate: i32) -> i64 {
+    (minutes * hourly_rate as i64 + 30) / 60
+}
+
+/// 集計単位ごとの収入
+#[derive(Serialize, Debug, ToSchema)]
+pub struct EarningsGroup {
+    /// グループのキー（勤怠ID・プロジェクトID・企業ID・年月(YYYY-MM)のいずれか）
+    #[schema(example = "2024-04")]
+    pub key: String,
+
+    /// 表示名（勤怠・プロジェクトはプロジェクト名、企業は企業名、月は年月）
+    #[schema(example = "2024-04")]
+    pub label: String,
+
+    /// 適用した時給（勤怠・プロジェクト単位の集計のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 3000)]
+    pub hourly_rate: Option<i32>,
+
+    #[serde(flatten)]
+    pub amount: EarningsAmount,
+}
+
+/// 作業時間の集計単位
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeReportGroupBy {
+    #[default]
+    Day, // 日ごと（YYYY-MM-DD）
+    Week,       // 週ごと（ISO週, YYYY-Www）
+    Month,      // 月ごと（YYYY-MM）
+    Project,    // プロジェクトごと
+    Company,    // 企業ごと
+    SkillLabel, // スキルラベルごと
+}
+
+#[derive(Deserialize, Debug, Validate, ToSchema)]
+#[validate(schema(function = "validate_time_report_period"))]
+pub struct TimeReportQuery {
+    /// 集計期間の開始日（YYYY-MM-DD形式, この日を含む）
+    #[schema(value_type = String, example = "2024-04-01")]
+    pub from: NaiveDate,
+
+    /// 集計期間の終了日（YYYY-MM-DD形式, この日を含む）
+    #[schema(value_type = String, example = "2024-04-30")]
+    pub to: NaiveDate,
+
+    /// 集計単位（day, week, month, project, company, skill_label）
+    #[serde(default)]
+    #[schema(example = "day")]
+    pub group_by: TimeReportGroupBy,
+}
+
+fn validate_time_report_period(query: &TimeReportQuery) -> Result<(), ValidationError> {
+    validate_period(query.from, query.to)
+}
+
+/// 集計単位ごとの作業時間
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct TimeReportGroup {
+    /// グループのキー（日付・週・年月・プロジェクトID・企業ID・スキルラベルのいずれか）
+    #[schema(example = "2024-04-01")]
+    pub key: String,
+
+    /// 表示名（プロジェクト名・企業名。それ以外はキーと同じ）
+    #[schema(example = "2024-04-01")]
+    pub label: String,
+
+    #[schema(example = 480)]
+    pub work_minutes: i64, // 実作業時間（分）
+
+    #[schema(example = 2)]
+    pub work_log_count: i64, // 勤怠の件数
+}
+
+#[derive(Deserialize, Debug, Validate, ToSchema)]
+#[validate(schema(function = "validate_skill_analytics_period"))]
+pub struct SkillAnalyticsQuery {
+    /// 集計期間の開始日（YYYY-MM-DD形式, この日を含む）。未指定の場合は全期間
+    #[schema(value_type = Option<String>, example = "2024-01-01")]
+    pub from: Option<NaiveDate>,
+
+    /// 集計期間の終了日（YYYY-MM-DD形式, この日を含む）。未指定の場合は全期間
+    #[schema(value_type = Option<String>, example = "2024-12-31")]
+    pub to: Option<NaiveDate>,
+
+    /// 取得するスキル数の制限（作業時間の多い順）
+    #[schema(example = 10)]
+    #[validate(range(min = 1, max = 100, code = "limit_range"))]
+    pub limit: Option<i64>,
+}
+
+fn validate_skill_analytics_period(query: &SkillAnalyticsQuery) -> Result<(), ValidationError> {
+    match (query.from, query.to) {
+        (Some(from), Some(to)) => validate_period(from, to),
+        _ => Ok(()),
+    }
+}
+
+/// スキルの利用状況
+#[derive(Serialize, Debug, ToSchema)]
+pub struct SkillUsage {
+    #[schema(example = "Rust")]
+    pub skill_label: String, // スキルラベル
+
+    #[schema(example = 9600)]
+    pub work_minutes: i64, // スキルを使用したプロジェクトでの実作業時間（分）
+
+    #[schema(example = 20)]
+    pub work_log_count: i64, // 勤怠の件数
+
+    #[schema(example = 2)]
+    pub project_count: i64, // スキルを使用したプロジェクト数
+
+    #[serde(serialize_with = "serialize_bson_datetime")]
+    #[schema(value_type = String, example = "2023-04-13T12:34:56Z")]
+    pub first_used_at: BsonDateTime, // 最初に使用した日時（勤怠の開始時間）
+
+    #[serde(serialize_with = "serialize_bson_datetime")]
+    #[schema(value_type = String, example = "2024-03-31T18:00:00Z")]
+    pub last_used_at: BsonDateTime, // 最後に使用した日時（勤怠の開始時間）
+
+    pub trend: Vec<SkillTrend>, // 月ごとの作業時間の推移（古い順）
+
+    pub companies: Vec<SkillCompany>, // スキルを使用した企業（作業時間の多い順）
+}
+
+/// 月ごとのスキルの作業時間
+#[derive(Serialize, Debug, ToSchema)]
+pub struct SkillTrend {
+    #[schema(example = "2024-04")]
+    pub month: String, // 年月（YYYY-MM）
+
+    #[schema(example = 480)]
+    pub work_minutes: i64, // 実作業時間（分）
+}
+
+/// スキルを使用した企業
+#[derive(Serialize, Debug, ToSchema)]
+pub struct SkillCompany {
+    #[serde(serialize_with = "serialize_object_id")]
+    #[schema(value_type = String, example = "70a6c1e9f0f7b9001234abcd")]
+    pub company_id: ObjectId, // 企業ID
+
+    #[schema(example = "株式会社テスト")]
+    pub company_name: String, // 企業名（削除済みの企業は空文字）
+
+    #[schema(example = 480)]
+    pub work_minutes: i64, // 実作業時間（分）
+}
@@ -1,7 +1,9 @@
+use crate::constants::time_zone::DEFAULT_TIME_ZONE;
 use bson::{oid::ObjectId, DateTime as BsonDateTime};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 #[derive(Serialize, Deserialize, Debug, Default, ToSchema, Clone)]
 pub enum EngineerRole {
@@ -16,17 +18,26 @@ pub enum EngineerRole {
     ProjectManager,
 }
 
+/// ユーザーの表示言語
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    Ja,
+    En,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate, Clone)]
 pub struct UserCreate {
-    #[validate(email(message = "有効なメールアドレスを入力してください"))]
+    #[validate(email(code = "email_invalid"))]
     #[schema(example = "user@example.com")]
     pub email: String,
 
-    #[validate(length(min = 8, message = "パスワードは8文字以上である必要があります"))]
+    #[validate(length(min = 8, code = "password_too_short"))]
     #[schema(example = "password123")]
     pub password: String,
 
-    #[validate(length(min = 1, message = "名前は1文字以上である必要があります"))]
+    #[validate(length(min = 1, code = "username_required"))]
     #[schema(example = "John Doe")]
     pub username: String,
 }
@@ -34,15 +45,15 @@ pub struct UserCreate {
 // 画像など部分更新をサポートするためにOptionを使用する
 #[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct UserUpdate {
-    #[validate(email(message = "有効なメールアドレスを入力してください"))]
+    #[validate(email(code = "email_invalid"))]
     #[schema(example = "user_updated@example.com")]
     pub email: String,
 
-    #[validate(length(min = 8, message = "パスワードは8文字以上である必要があります"))]
+    #[validate(length(min = 8, code = "password_too_short"))]
     #[schema(example = "newpassword123")]
     pub password: Option<String>,
 
-    #[validate(length(min = 1, message = "名前は1文字以上である必要があります"))]
+    #[validate(length(min = 1, code = "username_required"))]
     #[schema(example = "John Doe Updated")]
     pub username: String,
 
@@ -53,6 +64,14 @@ pub struct UserUpdate {
         example = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAACklEQVR4nGMAAQAABQABDQottAAAAABJRU5ErkJggg=="
     )]
     pub avatar: Option<String>,
+
+    // 日付の区切りや集計に使うタイムゾーン（IANAタイムゾーン名）
+    #[validate(custom(function = "validate_time_zone"))]
+    #[schema(example = "Asia/Tokyo")]
+    pub timezone: Option<String>,
+
+    #[schema(example = "ja")]
+    pub locale: Option<Locale>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -67,6 +86,13 @@ pub struct UserUpdateInternal {
 
     // avatar_path指定時に生成されるURL
     pub avatar_url: Option<String>,
+
+    // 指定されなかった場合は現在の設定のままとする
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<Locale>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -87,6 +113,30 @@ pub struct UserInDB {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar_url: Option<String>,
 
+    // カレンダーフィードのURLに含める秘密のトークン（未発行・無効化した場合はNone）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub calendar_feed_token: Option<String>,
+
+    // 未設定の場合はDEFAULT_TIME_ZONE（JST）として扱う
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Asia/Tokyo")]
+    pub timezone: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<Locale>,
+
+    // メールアドレスの確認日時（未確認の場合はNone）
+    // 既存ユーザーの移行でフィールドの有無を判定するため、Noneの場合もnullとして保存する
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub email_verified_at: Option<BsonDateTime>,
+
+    // 変更を申請中のメールアドレス（確認されるまではemailを使用する）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "user_updated@example.com")]
+    pub pending_email: Option<String>,
+
     #[schema(value_type = String, example = "2023-04-13T12:34:56Z")]
     pub created_at: BsonDateTime,
 
@@ -94,3 +144,56 @@ pub struct UserInDB {
     #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
     pub updated_at: Option<BsonDateTime>,
 }
+
+impl UserInDB {
+    /// ユーザーのタイムゾーン（未設定・不正な値の場合はDEFAULT_TIME_ZONE）
+    pub fn time_zone(&self) -> Tz {
+        parse_time_zone(self.timezone.as_deref())
+    }
+
+    /// ユーザーの表示言語（未設定の場合は日本語）
+    pub fn locale(&self) -> Locale {
+        self.locale.unwrap_or_default()
+    }
+}
+
+/// 認証ミドルウェアがリクエストごとに参照するユーザーの設定・状態
+///
+/// 認証トークンの取得時に、ユーザー情報から必要なフィールドのみをまとめて取得する
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserAuthState {
+    #[serde(default)]
+    pub timezone: Option<String>,
+
+    #[serde(default)]
+    pub locale: Option<Locale>,
+
+    #[serde(default)]
+    pub email_verified_at: Option<BsonDateTime>,
+
+    pub created_at: BsonDateTime,
+}
+
+impl UserAuthState {
+    /// ユーザーのタイムゾーン（未設定・不正な値の場合はDEFAULT_TIME_ZONE）
+    pub fn time_zone(&self) -> Tz {
+        parse_time_zone(self.timezone.as_deref())
+    }
+}
+
+/// 保存されているタイムゾーン名を変換する（未設定・不正な値の場合はDEFAULT_TIME_ZONE）
+fn parse_time_zone(timezone: Option<&str>) -> Tz {
+    timezone
+        .and_then(|timezone| timezone.parse().ok())
+        .unwrap_or(DEFAULT_TIME_ZONE)
+}
+
+/// タイムゾーンがIANAタイムゾーンデータベースに存在する名前か検証する
+fn validate_time_zone(timezone: &str) -> Result<(), ValidationError> {
+    if timezone.parse::<Tz>().is_err() {
+        let mut err = ValidationError::new("timezone_invalid");
+        err.add_param("value".into(), &timezone);
+        return Err(err);
+    }
+    Ok(())
+}
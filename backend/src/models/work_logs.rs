@@ -1,5 +1,9 @@
-use crate::utils::deserializer::{deserialize_bson_date_time, deserialize_option_bson_date_time};
+use crate::utils::deserializer::{
+    deserialize_bson_date_time, deserialize_option_bson_date_time, deserialize_sort_params,
+};
 use bson::{oid::ObjectId, DateTime as BsonDateTime};
+use chrono::{Duration, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::{Validate, ValidationError, ValidationErrors};
@@ -7,6 +11,9 @@ use validator::{Validate, ValidationError, ValidationErrors};
 // カスタムバリデーション用のトレイト
 trait TimeValidator {
     fn validate_times(&self) -> Result<(), ValidationError>;
+
+    /// 休憩時間・実作業時間が開始〜終了時間に収まっているか検証する（エラーはフィールド名と組で返す）
+    fn validate_work_minutes(&self) -> Result<(), (&'static str, ValidationError)>;
 }
 
 // バリデーションロジックを共通化するマクロ
@@ -18,27 +25,51 @@ macro_rules! impl_work_logs_validation {
                 let now = BsonDateTime::now();
 
                 if self.start_time > now {
-                    return Err(ValidationError {
-                        code: "time_validation".into(),
-                        message: Some("開始時間は現在時刻より前である必要があります".into()),
-                        params: Default::default(),
-                    });
+                    return Err(ValidationError::new("start_time_future"));
                 }
 
                 if let Some(end_time) = self.end_time {
                     if end_time <= self.start_time {
-                        return Err(ValidationError {
-                            code: "time_validation".into(),
-                            message: Some("終了時間は開始時間より後である必要があります".into()),
-                            params: Default::default(),
-                        });
+                        return Err(ValidationError::new("end_time_before_start"));
                     }
                     if end_time > now {
-                        return Err(ValidationError {
-                            code: "time_validation".into(),
-                            message: Some("終了時間は現在時刻より前である必要があります".into()),
-                            params: Default::default(),
-                        });
+                        return Err(ValidationError::new("end_time_future"));
+                    }
+                }
+
+                Ok(())
+            }
+
+            fn validate_work_minutes(&self) -> Result<(), (&'static str, ValidationError)> {
+                let Some(end_time) = self.end_time else {
+                    // 作業中の勤怠の実作業時間は終了時に算出する
+                    if self.actual_work_minutes.is_some() {
+                        return Err((
+                            "actual_work_minutes",
+                            ValidationError::new("actual_work_minutes_without_end_time"),
+                        ));
+                    }
+                    return Ok(());
+                };
+                // 開始・終了時間の前後関係はvalidate_timesで検証する
+                if end_time <= self.start_time {
+                    return Ok(());
+                }
+
+                let break_time = self.break_time.unwrap_or(0) as i64;
+                let session_minutes = calculate_actual_work_minutes(self.start_time, end_time, 0);
+                if break_time > session_minutes {
+                    return Err((
+                        "break_time",
+                        ValidationError::new("break_time_exceeds_session"),
+                    ));
+                }
+                if let Some(actual_work_minutes) = self.actual_work_minutes {
+                    if actual_work_minutes as i64 > session_minutes - break_time {
+                        return Err((
+                            "actual_work_minutes",
+                            ValidationError::new("actual_work_minutes_exceeds_session"),
+                        ));
                     }
                 }
 
@@ -47,22 +78,71 @@ macro_rules! impl_work_logs_validation {
         }
 
         impl $type {
-            pub fn validate_all(&self) -> Result<(), ValidationErrors> {
-                let mut errors = ValidationErrors::new();
+            /// 指定したタイムゾーンの0時をまたぐ勤怠を日ごとに分割する（またがない場合はそのまま返す）
+            ///
+            /// 分割後の勤怠には共通の`session_id`を設定し、休憩時間と実作業時間は各日の時間の比率で按分する
+            pub fn split_by_local_day(self, time_zone: Tz) -> Vec<Self> {
+                let Some(end_time) = self.end_time else {
+                    return vec![self];
+                };
+                let midnights = local_midnights(self.start_time, end_time, time_zone);
+                if midnights.is_empty() {
+                    return vec![self];
+                }
+
+                let mut boundaries = vec![self.start_time];
+                boundaries.extend(midnights);
+                boundaries.push(end_time);
+                let lengths: Vec<i64> = boundaries
+                    .windows(2)
+                    .map(|pair| pair[1].timestamp_millis() - pair[0].timestamp_millis())
+                    .collect();
+                let break_times = self
+                    .break_time
+                    .map(|break_time| allocate_minutes(break_time, &lengths));
+                let actual_work_minutes =
+                    allocate_minutes(self.resolved_actual_work_minutes().unwrap_or(0), &lengths);
+                let session_id = Some(ObjectId::new());
 
+                boundaries
+                    .windows(2)
+                    .enumerate()
+                    .map(|(i, pair)| Self {
+                        project_id: self.project_id,
+                        start_time: pair[0],
+                        end_time: Some(pair[1]),
+                        break_time: break_times.as_ref().map(|break_times| break_times[i]),
+                        actual_work_minutes: Some(actual_work_minutes[i]),
+                        memo: self.memo.clone(),
+                        session_id,
+                    })
+                    .collect()
+            }
+
+            /// 実作業時間（省略された場合は開始・終了時間と休憩時間から算出する）
+            pub fn resolved_actual_work_minutes(&self) -> Option<i32> {
+                self.actual_work_minutes.or_else(|| {
+                    self.end_time.map(|end_time| {
+                        calculate_actual_work_minutes(
+                            self.start_time,
+                            end_time,
+                            self.break_time.unwrap_or(0),
+                        ) as i32
+                    })
+                })
+            }
+
+            pub fn validate_all(&self) -> Result<(), ValidationErrors> {
                 // 既存のバリデーションを実行
-                if let Err(e) = <Self as Validate>::validate(self) {
-                    for (field, field_errors) in e.field_errors() {
-                        for error in field_errors {
-                            errors.add(field, error.clone());
-                        }
-                    }
-                }
+                let mut errors = <Self as Validate>::validate(self).err().unwrap_or_default();
 
                 // カスタムバリデーションを実行
                 if let Err(e) = self.validate_times() {
                     errors.add("time", e);
                 }
+                if let Err((field, e)) = self.validate_work_minutes() {
+                    errors.add(field, e);
+                }
 
                 if errors.is_empty() {
                     Ok(())
@@ -78,7 +158,7 @@ macro_rules! impl_work_logs_validation {
 impl_work_logs_validation!(WorkLogCreate);
 impl_work_logs_validation!(WorkLogUpdate);
 
-#[derive(Serialize, Deserialize, Debug, ToSchema, Validate)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema, Validate)]
 pub struct WorkLogCreate {
     #[schema(value_type = String, example = "60a7e3e0f1c1b2a3b4c5d6e7")]
     pub project_id: ObjectId,
@@ -93,21 +173,25 @@ pub struct WorkLogCreate {
 
     #[serde(default)]
     #[schema(value_type = Option<i32>, example = 30)]
-    #[validate(range(min = 0, message = "休憩時間は0以上である必要があります"))]
+    #[validate(range(min = 0, code = "break_time_range"))]
     pub break_time: Option<i32>,
 
     #[serde(default)]
     #[schema(value_type = Option<i32>, example = 120)]
-    #[validate(range(min = 0, message = "実労働時間は0以上である必要があります"))]
+    #[validate(range(min = 0, code = "actual_work_minutes_range"))]
     pub actual_work_minutes: Option<i32>,
 
     #[serde(default)]
-    #[validate(length(min = 0, max = 1000, message = "メモは0〜1000文字である必要があります"))]
+    #[validate(length(min = 0, max = 1000, code = "memo_length"))]
     #[schema(example = "今日はプロジェクトのキックオフミーティングを行いました。")]
     pub memo: Option<String>,
+
+    // 日をまたぐ勤怠を分割した場合に設定する（リクエストでは指定できない）
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<ObjectId>,
 }
 
-#[derive(Serialize, Deserialize, Debug, ToSchema, Validate)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema, Validate)]
 pub struct WorkLogUpdate {
     #[schema(value_type = String, example = "60a7e3e0f1c1b2a3b4c5d6e7")]
     pub project_id: ObjectId,
@@ -122,18 +206,162 @@ pub struct WorkLogUpdate {
 
     #[serde(default)]
     #[schema(value_type = Option<i32>, example = 30)]
-    #[validate(range(min = 0, message = "休憩時間は0以上である必要があります"))]
+    #[validate(range(min = 0, code = "break_time_range"))]
     pub break_time: Option<i32>,
 
     #[serde(default)]
     #[schema(value_type = Option<i32>, example = 120)]
-    #[validate(range(min = 0, message = "実労働時間は0以上である必要があります"))]
+    #[validate(range(min = 0, code = "actual_work_minutes_range"))]
     pub actual_work_minutes: Option<i32>,
 
     #[serde(default)]
-    #[validate(length(min = 0, max = 1000, message = "メモは0〜1000文字である必要があります"))]
+    #[validate(length(min = 0, max = 1000, code = "memo_length"))]
     #[schema(example = "今日はプロジェクトのキックオフミーティングを行いました。")]
     pub memo: Option<String>,
+
+    // 日をまたぐ勤怠を分割した場合に設定する（リクエストでは指定できない）
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<ObjectId>,
+}
+
+/// タイマーによる作業開始リクエスト
+#[derive(Deserialize, Debug, ToSchema, Validate)]
+pub struct WorkLogStart {
+    #[schema(value_type = String, example = "60a7e3e0f1c1b2a3b4c5d6e7")]
+    pub project_id: ObjectId,
+
+    #[serde(default)]
+    #[validate(length(min = 0, max = 1000, code = "memo_length"))]
+    #[schema(example = "今日はプロジェクトのキックオフミーティングを行いました。")]
+    pub memo: Option<String>,
+}
+
+/// タイマーによる作業終了リクエスト
+#[derive(Deserialize, Debug, Default, ToSchema, Validate)]
+pub struct WorkLogStop {
+    #[serde(default)]
+    #[schema(value_type = Option<i32>, example = 30)]
+    #[validate(range(min = 0, code = "break_time_range"))]
+    pub break_time: Option<i32>,
+
+    #[serde(default)]
+    #[validate(length(min = 0, max = 1000, code = "memo_length"))]
+    #[schema(example = "今日はプロジェクトのキックオフミーティングを行いました。")]
+    pub memo: Option<String>,
+}
+
+/// 勤怠の作成・更新時の時間の重複の扱い
+#[derive(Deserialize, Debug, Default, ToSchema)]
+pub struct WorkLogOverlapQuery {
+    /// trueの場合は既存の勤怠と時間が重複していても登録し、重複した勤怠のIDを警告として返す
+    #[serde(default)]
+    #[schema(example = false)]
+    pub allow_overlap: bool,
+}
+
+/// 時間が重複している2件の勤怠と、重複している期間
+#[derive(Debug, PartialEq)]
+pub struct WorkLogOverlap {
+    pub work_log_ids: [ObjectId; 2], // 開始時間の古い順
+    pub start_time: BsonDateTime,
+    pub end_time: BsonDateTime,
+}
+
+impl WorkLogOverlap {
+    pub fn overlap_minutes(&self) -> i64 {
+        (self.end_time.timestamp_millis() - self.start_time.timestamp_millis()) / 60_000
+    }
+}
+
+/// 開始時間の古い順に並んだ勤怠から、時間が重複している組み合わせを抽出する
+///
+/// 期間は`[start_time, end_time)`として扱い、作業中の勤怠は`now`まで作業しているものとする
+pub fn find_work_log_overlaps(work_logs: &[WorkLogInDB], now: BsonDateTime) -> Vec<WorkLogOverlap> {
+    let mut overlaps = Vec::new();
+    // 後続の勤怠と重なる可能性のある勤怠（ID, 終了時刻）
+    let mut open: Vec<(ObjectId, BsonDateTime)> = Vec::new();
+
+    for work_log in work_logs {
+        let Some(id) = work_log.id else {
+            continue;
+        };
+        let start_time = work_log.start_time;
+        let end_time = work_log.end_time.unwrap_or(now);
+
+        open.retain(|(_, open_end)| *open_end > start_time);
+        for (open_id, open_end) in &open {
+            let overlap_end = (*open_end).min(end_time);
+            if overlap_end > start_time {
+                overlaps.push(WorkLogOverlap {
+                    work_log_ids: [*open_id, id],
+                    start_time,
+                    end_time: overlap_end,
+                });
+            }
+        }
+        if end_time > start_time {
+            open.push((id, end_time));
+        }
+    }
+
+    overlaps
+}
+
+/// 開始〜終了時間の間にある、指定したタイムゾーンでの日付の区切り（0時）
+fn local_midnights(
+    start_time: BsonDateTime,
+    end_time: BsonDateTime,
+    time_zone: Tz,
+) -> Vec<BsonDateTime> {
+    let mut midnights = Vec::new();
+    let mut date = start_time
+        .to_chrono()
+        .with_timezone(&time_zone)
+        .date_naive();
+    loop {
+        date += Duration::days(1);
+        // 夏時間の切り替えで0時が存在しない日は区切りとしない
+        let Some(midnight) = time_zone
+            .from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .earliest()
+        else {
+            continue;
+        };
+        let midnight = BsonDateTime::from_chrono(midnight.with_timezone(&Utc));
+        if midnight >= end_time {
+            return midnights;
+        }
+        midnights.push(midnight);
+    }
+}
+
+/// 分を各区間の長さの比率で按分する（端数は最後の区間に寄せ、合計は元の値と一致させる）
+fn allocate_minutes(minutes: i32, lengths: &[i64]) -> Vec<i32> {
+    let total: i64 = lengths.iter().sum();
+    let mut allocated: Vec<i32> = lengths
+        .iter()
+        .map(|length| {
+            if total == 0 {
+                0
+            } else {
+                (minutes as i64 * length / total) as i32
+            }
+        })
+        .collect();
+    if let Some(last) = allocated.len().checked_sub(1) {
+        allocated[last] = minutes - allocated[..last].iter().sum::<i32>();
+    }
+    allocated
+}
+
+/// 開始・終了時刻と休憩時間（分）から実作業時間（分）を算出する
+pub fn calculate_actual_work_minutes(
+    start_time: BsonDateTime,
+    end_time: BsonDateTime,
+    break_time: i32,
+) -> i64 {
+    let elapsed_minutes = (end_time.timestamp_millis() - start_time.timestamp_millis()) / 60_000;
+    elapsed_minutes - break_time as i64
 }
 
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
@@ -142,6 +370,10 @@ pub struct WorkLogInDB {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
     #[schema(value_type = Option<String>, example = "507f1f77bcf86cd799439011")]
     pub id: Option<ObjectId>, // DB側にID生成させるので任意
+
+    #[schema(value_type = String, example = "507f1f77bcf86cd799439011")]
+    pub user_id: ObjectId, // 所有ユーザーID
+
     #[schema(value_type = String, example = "60a7e3e0f1c1b2a3b4c5d6e7")]
     pub project_id: ObjectId,
 
@@ -170,4 +402,146 @@ pub struct WorkLogInDB {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(value_type = Option<i32>, example = 120)]
     pub actual_work_minutes: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "2023-04-13T12:34:56Z")]
+    pub deleted_at: Option<BsonDateTime>, // 削除日時（論理削除）
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "66f1c2d3e4f5a6b7c8d9e0f1")]
+    pub invoice_id: Option<ObjectId>, // 請求書に含まれている場合の請求書ID（編集不可）
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "66f1c2d3e4f5a6b7c8d9e0f2")]
+    pub session_id: Option<ObjectId>, // 日をまたぐ勤怠を分割した場合の、分割後の勤怠で共通のID
+}
+
+#[derive(Debug, Default)]
+pub struct WorkLogFilter {
+    pub project_id: Option<ObjectId>,
+
+    pub company_id: Option<ObjectId>,
+
+    pub from: Option<BsonDateTime>,
+
+    pub to: Option<BsonDateTime>,
+
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_work_log_query_range"))]
+pub struct WorkLogQuery {
+    /// プロジェクトID
+    #[schema(value_type = Option<String>, example = "60a7e3e0f1c1b2a3b4c5d6e7")]
+    pub project_id: Option<ObjectId>,
+
+    /// 企業ID（企業に紐づく全プロジェクトの勤怠を対象とする）
+    #[schema(value_type = Option<String>, example = "80a6c1e9f0f7b9001234abcd")]
+    pub company_id: Option<ObjectId>,
+
+    /// 開始時間の範囲（この日時以降）
+    #[serde(default, deserialize_with = "deserialize_option_bson_date_time")]
+    #[schema(value_type = Option<String>, example = "2023-04-01T00:00:00Z")]
+    pub from: Option<BsonDateTime>,
+
+    /// 開始時間の範囲（この日時より前）
+    #[serde(default, deserialize_with = "deserialize_option_bson_date_time")]
+    #[schema(value_type = Option<String>, example = "2023-05-01T00:00:00Z")]
+    pub to: Option<BsonDateTime>,
+
+    /// メモ（部分一致）
+    #[schema(example = "キックオフ")]
+    #[validate(length(min = 1, max = 100, code = "memo_query_length"))]
+    pub memo: Option<String>,
+
+    /// 取得するドキュメント数の制限（省略時は20件）
+    #[schema(example = 10)]
+    #[validate(range(min = 1, max = 100, code = "limit_range"))]
+    pub limit: Option<i64>,
+
+    /// 次のページを取得するためのカーソル（前回のレスポンスの`next_cursor`）
+    #[schema(example = "eyJrIjpbXX0")]
+    pub cursor: Option<String>,
+
+    /// ソート条件（例: "start_time:desc"）
+    #[serde(default, deserialize_with = "deserialize_sort_params")]
+    #[schema(example = json!(["start_time:desc"]), value_type = Vec<String>)]
+    #[validate(custom(function = "validate_work_log_sort_params"))]
+    pub sort: Option<Vec<String>>,
+}
+
+impl WorkLogQuery {
+    /// QueryパラメータからWorkLogFilterへの変換を行う
+    pub fn to_filter(&self) -> WorkLogFilter {
+        WorkLogFilter {
+            project_id: self.project_id,
+            company_id: self.company_id,
+            from: self.from,
+            to: self.to,
+            memo: self.memo.clone(),
+        }
+    }
+
+    /// ソートパラメータを MongoDB 用の形式に変換する
+    ///
+    /// ソート指定がない場合は開始時間の新しい順とする
+    pub fn parse_sort_params(&self) -> Vec<(String, i8)> {
+        match &self.sort {
+            Some(sort_params) if !sort_params.is_empty() => sort_params
+                .iter()
+                .filter_map(|param| {
+                    let (field, order) = param.split_once(':')?;
+                    Some((
+                        field.to_string(),
+                        if order.to_lowercase() == "asc" { 1 } else { -1 },
+                    ))
+                })
+                .collect(),
+            _ => vec![("start_time".to_string(), -1)],
+        }
+    }
+}
+
+/// 開始時間の範囲のバリデーション
+fn validate_work_log_query_range(query: &WorkLogQuery) -> Result<(), ValidationError> {
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from >= to {
+            return Err(ValidationError::new("time_range"));
+        }
+    }
+    Ok(())
+}
+
+/// ソートパラメータのバリデーション
+///
+/// 無効な値はクライアントサイドのバグとして400エラーを返す（`ProjectQuery`と同じ方針）
+fn validate_work_log_sort_params(sort: &[String]) -> Result<(), ValidationError> {
+    // 許可されたソートフィールド
+    const ALLOWED_FIELDS: [&str; 5] = [
+        "start_time",
+        "end_time",
+        "actual_work_minutes",
+        "created_at",
+        "updated_at",
+    ];
+
+    for param in sort {
+        let Some((field, order)) = param.split_once(':') else {
+            return Err(ValidationError::new("sort_format"));
+        };
+
+        if !ALLOWED_FIELDS.contains(&field) {
+            let mut err = ValidationError::new("sort_field");
+            err.add_param("field".into(), &field);
+            err.add_param("allowed".into(), &ALLOWED_FIELDS.join(", "));
+            return Err(err);
+        }
+
+        let order = order.to_lowercase();
+        if order != "asc" && order != "desc" {
+            return Err(ValidationError::new("sort_order"));
+        }
+    }
+    Ok(())
 }
@@ -1,9 +1,12 @@
 use crate::constants::mongo_error_codes::mongodb_error_codes;
 use crate::errors::repositories_error::RepositoryError;
-use crate::models::auth::AuthTokenInDB;
-use crate::models::users::{UserInDB, UserUpdateInternal};
+use crate::i18n::messages;
+use crate::models::auth::{AuthTokenInDB, AuthTokenScope, PasswordResetTokenInDB};
+use crate::models::users::{UserAuthState, UserInDB, UserUpdateInternal};
 use async_trait::async_trait;
 use bson::{doc, oid::ObjectId, DateTime as BsonDateTime};
+use futures::TryStreamExt;
+use mongodb::options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument};
 use mongodb::{error::Error as MongoError, Collection, Database};
 
 #[async_trait]
@@ -16,27 +19,98 @@ pub trait AuthRepository {
         username: &str,
     ) -> Result<ObjectId, RepositoryError>;
     async fn save_auth_token(&self, auth_token: &AuthTokenInDB) -> Result<(), RepositoryError>;
-    async fn delete_auth_tokens(&self, access_token: &str) -> Result<bool, RepositoryError>;
-    async fn find_auth_token(&self, token: &str) -> Result<Option<AuthTokenInDB>, RepositoryError>;
+    /// 範囲内の認証トークンを削除し、削除した件数を返す
+    async fn delete_auth_tokens(&self, scope: &AuthTokenScope) -> Result<u64, RepositoryError>;
+    /// アクセストークンの認証トークンと、認証に必要なユーザーの設定・状態を1回の問い合わせで取得する
+    async fn find_auth_token_with_user(
+        &self,
+        token: &str,
+    ) -> Result<Option<(AuthTokenInDB, UserAuthState)>, RepositoryError>;
+    async fn find_auth_token_by_id(
+        &self,
+        token_id: &ObjectId,
+    ) -> Result<Option<AuthTokenInDB>, RepositoryError>;
+    /// ユーザーの有効なセッションの認証トークン（ローテーション前かつリフレッシュトークンが有効期限内）を
+    /// 最後に使用された順に取得する
+    async fn find_active_auth_tokens(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<Vec<AuthTokenInDB>, RepositoryError>;
+    async fn update_auth_token_last_used(
+        &self,
+        token_id: &ObjectId,
+        last_used_at: BsonDateTime,
+    ) -> Result<(), RepositoryError>;
     async fn find_by_refresh_token(
         &self,
         refresh_token: &str,
     ) -> Result<Option<AuthTokenInDB>, RepositoryError>;
-    async fn update_auth_token(&self, auth_token: &AuthTokenInDB) -> Result<(), RepositoryError>;
-    async fn find_user_by_access_token(
+    /// 認証トークンをローテーション済みにする（既にローテーション済みの場合はfalse）
+    async fn mark_auth_token_rotated(&self, token_id: &ObjectId) -> Result<bool, RepositoryError>;
+    /// 同じファミリーの認証トークンをすべて削除し、削除した件数を返す
+    async fn delete_auth_token_family(&self, family_id: &ObjectId) -> Result<u64, RepositoryError>;
+    async fn find_user_by_id(
         &self,
-        access_token: &str,
+        user_id: &ObjectId,
     ) -> Result<Option<UserInDB>, RepositoryError>;
-    async fn update_user_by_access_token(
+    async fn update_user_by_id(
         &self,
-        access_token: &str,
+        user_id: &ObjectId,
         user: &UserUpdateInternal,
     ) -> Result<bool, RepositoryError>;
+    /// カレンダーフィードのトークンを設定する（Noneの場合は削除してフィードを無効化する）
+    async fn update_calendar_feed_token(
+        &self,
+        user_id: &ObjectId,
+        token: Option<&str>,
+    ) -> Result<bool, RepositoryError>;
+    async fn find_user_by_calendar_feed_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<UserInDB>, RepositoryError>;
+    async fn update_password_hash(
+        &self,
+        user_id: &ObjectId,
+        password_hash: &str,
+    ) -> Result<bool, RepositoryError>;
+    /// 変更を申請中のメールアドレスを設定する（Noneの場合は申請を取り消す）
+    async fn update_pending_email(
+        &self,
+        user_id: &ObjectId,
+        pending_email: Option<&str>,
+    ) -> Result<bool, RepositoryError>;
+    /// 現在のメールアドレスを確認済みにする（確認済み・メールアドレスが変更されている場合はfalse）
+    async fn mark_email_verified(
+        &self,
+        user_id: &ObjectId,
+        email: &str,
+    ) -> Result<bool, RepositoryError>;
+    /// 変更を申請中のメールアドレスを確認済みのメールアドレスとして反映する（申請が取り消されている場合はfalse）
+    async fn confirm_pending_email(
+        &self,
+        user_id: &ObjectId,
+        pending_email: &str,
+    ) -> Result<bool, RepositoryError>;
+    async fn save_password_reset_token(
+        &self,
+        reset_token: &PasswordResetTokenInDB,
+    ) -> Result<(), RepositoryError>;
+    /// 未使用かつ有効期限内のパスワードリセットトークンを使用済みにして返す（該当しない場合はNone）
+    async fn consume_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetTokenInDB>, RepositoryError>;
+    /// ユーザーのパスワードリセットトークンをすべて削除し、削除した件数を返す
+    async fn delete_password_reset_tokens(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<u64, RepositoryError>;
 }
 
 pub struct MongoAuthRepository {
     users_collection: Collection<UserInDB>,
     tokens_collection: Collection<AuthTokenInDB>,
+    reset_tokens_collection: Collection<PasswordResetTokenInDB>,
 }
 
 impl MongoAuthRepository {
@@ -44,6 +118,7 @@ impl MongoAuthRepository {
         Self {
             users_collection: db.collection("users"),
             tokens_collection: db.collection("auth_tokens"),
+            reset_tokens_collection: db.collection("password_reset_tokens"),
         }
     }
 }
@@ -70,6 +145,11 @@ impl AuthRepository for MongoAuthRepository {
             username: username.to_string(),
             role: None,
             avatar_url: None,
+            calendar_feed_token: None,
+            timezone: None,
+            locale: None,
+            email_verified_at: None,
+            pending_email: None,
             created_at: BsonDateTime::now(),
             updated_at: None,
         };
@@ -88,7 +168,7 @@ impl AuthRepository for MongoAuthRepository {
                 {
                     if write_error.code == mongodb_error_codes::DUPLICATE_KEY {
                         return Err(RepositoryError::DuplicateError(
-                            "メールアドレスが既に使用されています".to_string(),
+                            messages::EMAIL_ALREADY_USED.into(),
                         ));
                     }
                 }
@@ -106,23 +186,127 @@ impl AuthRepository for MongoAuthRepository {
         Ok(())
     }
 
-    async fn delete_auth_tokens(&self, access_token: &str) -> Result<bool, RepositoryError> {
+    async fn delete_auth_tokens(&self, scope: &AuthTokenScope) -> Result<u64, RepositoryError> {
+        // セッション単位の削除では、ローテーション済みのトークンも含めてファミリーごと削除する
+        let filter = match scope {
+            AuthTokenScope::Token(token_id) => doc! { "_id": token_id },
+            AuthTokenScope::User(user_id) => doc! { "user_id": user_id },
+            AuthTokenScope::Session { user_id, family_id } => {
+                doc! { "user_id": user_id, "family_id": family_id }
+            }
+            AuthTokenScope::OtherSessions { user_id, family_id } => {
+                doc! { "user_id": user_id, "family_id": { "$ne": family_id } }
+            }
+        };
+
         let result = self
             .tokens_collection
-            .delete_one(doc! { "access_token": access_token }, None)
+            .delete_many(filter, None)
             .await
             .map_err(RepositoryError::DatabaseError)?;
 
-        Ok(result.deleted_count > 0)
+        Ok(result.deleted_count)
     }
 
-    async fn find_auth_token(&self, token: &str) -> Result<Option<AuthTokenInDB>, RepositoryError> {
+    async fn find_auth_token_with_user(
+        &self,
+        token: &str,
+    ) -> Result<Option<(AuthTokenInDB, UserAuthState)>, RepositoryError> {
+        let pipeline = vec![
+            // ローテーション済みのトークンのアクセストークンは使用できない
+            doc! {
+                "$match": { "access_token": token, "rotated_at": { "$exists": false } }
+            },
+            doc! { "$limit": 1 },
+            doc! {
+                "$lookup": {
+                    "from": "users",
+                    "localField": "user_id",
+                    "foreignField": "_id",
+                    "pipeline": [
+                        { "$project": { "timezone": 1, "locale": 1, "email_verified_at": 1, "created_at": 1 } }
+                    ],
+                    "as": "user"
+                }
+            },
+            // ユーザーが存在しない場合はトークンも取得しない
+            doc! { "$unwind": "$user" },
+        ];
+
+        let mut cursor = self
+            .tokens_collection
+            .aggregate(pipeline, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        let Some(result) = cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+        else {
+            return Ok(None);
+        };
+
+        let user = result
+            .get_document("user")
+            .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e.to_string())))?;
+        let user: UserAuthState = bson::from_document(user.clone())
+            .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e.to_string())))?;
+        let auth_token: AuthTokenInDB = bson::from_document(result)
+            .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e.to_string())))?;
+
+        Ok(Some((auth_token, user)))
+    }
+
+    async fn find_auth_token_by_id(
+        &self,
+        token_id: &ObjectId,
+    ) -> Result<Option<AuthTokenInDB>, RepositoryError> {
         self.tokens_collection
-            .find_one(doc! { "access_token": token }, None)
+            .find_one(doc! { "_id": token_id }, None)
             .await
             .map_err(RepositoryError::DatabaseError)
     }
 
+    async fn find_active_auth_tokens(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<Vec<AuthTokenInDB>, RepositoryError> {
+        // TTLインデックスによる削除は即時ではないため、有効期限も条件に含める
+        let filter = doc! {
+            "user_id": user_id,
+            "rotated_at": { "$exists": false },
+            "refresh_expires_at": { "$gt": BsonDateTime::now() },
+        };
+        let options = FindOptions::builder()
+            .sort(doc! { "last_used_at": -1, "created_at": -1 })
+            .build();
+
+        self.tokens_collection
+            .find(filter, options)
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+            .try_collect()
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
+    async fn update_auth_token_last_used(
+        &self,
+        token_id: &ObjectId,
+        last_used_at: BsonDateTime,
+    ) -> Result<(), RepositoryError> {
+        self.tokens_collection
+            .update_one(
+                doc! { "_id": token_id },
+                doc! { "$set": { "last_used_at": last_used_at } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(())
+    }
+
     async fn find_by_refresh_token(
         &self,
         refresh_token: &str,
@@ -133,61 +317,47 @@ impl AuthRepository for MongoAuthRepository {
             .map_err(RepositoryError::DatabaseError)
     }
 
-    async fn update_auth_token(&self, auth_token: &AuthTokenInDB) -> Result<(), RepositoryError> {
-        let filter = doc! { "refresh_token": &auth_token.refresh_token };
-        let update = doc! {
-            "$set": {
-                "access_token": &auth_token.access_token,
-                "expires_at": &auth_token.expires_at,
-                "updated_at": &auth_token.updated_at,
-            }
-        };
+    async fn mark_auth_token_rotated(&self, token_id: &ObjectId) -> Result<bool, RepositoryError> {
+        // 同じリフレッシュトークンで同時にリクエストされた場合も、ローテーションできるのは1回だけにする
+        let now = BsonDateTime::now();
+        let result = self
+            .tokens_collection
+            .update_one(
+                doc! { "_id": token_id, "rotated_at": { "$exists": false } },
+                doc! { "$set": { "rotated_at": now, "updated_at": now } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
 
-        self.tokens_collection
-            .update_one(filter, update, None)
+        Ok(result.modified_count > 0)
+    }
+
+    async fn delete_auth_token_family(&self, family_id: &ObjectId) -> Result<u64, RepositoryError> {
+        let result = self
+            .tokens_collection
+            .delete_many(doc! { "family_id": family_id }, None)
             .await
             .map_err(RepositoryError::DatabaseError)?;
 
-        Ok(())
+        Ok(result.deleted_count)
     }
-    async fn find_user_by_access_token(
+
+    async fn find_user_by_id(
         &self,
-        access_token: &str,
+        user_id: &ObjectId,
     ) -> Result<Option<UserInDB>, RepositoryError> {
-        let auth_token = self.find_auth_token(access_token).await?;
-
-        if let Some(token) = auth_token {
-            if token.expires_at > BsonDateTime::now() {
-                return self
-                    .users_collection
-                    .find_one(doc! { "_id": token.user_id }, None)
-                    .await
-                    .map_err(RepositoryError::DatabaseError);
-            } else {
-                return Err(RepositoryError::ExpiredAccessToken);
-            }
-        }
-
-        Ok(None)
+        self.users_collection
+            .find_one(doc! { "_id": user_id }, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)
     }
 
-    async fn update_user_by_access_token(
+    async fn update_user_by_id(
         &self,
-        access_token: &str,
+        user_id: &ObjectId,
         user: &UserUpdateInternal,
     ) -> Result<bool, RepositoryError> {
-        // アクセストークンからAuthTokenを取得
-        let auth_token = self.find_auth_token(access_token).await?.ok_or_else(|| {
-            RepositoryError::DatabaseError(MongoError::custom(
-                "アクセストークンが見つかりません".to_string(),
-            ))
-        })?;
-
-        // トークンの有効期限をチェック
-        if auth_token.expires_at <= BsonDateTime::now() {
-            return Err(RepositoryError::ExpiredAccessToken);
-        }
-
         let mut update_doc = bson::to_document(user)
             .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e)))?;
         update_doc.insert("updated_at", BsonDateTime::now());
@@ -197,7 +367,7 @@ impl AuthRepository for MongoAuthRepository {
 
         match self
             .users_collection
-            .update_one(doc! { "_id": auth_token.user_id }, update, None)
+            .update_one(doc! { "_id": user_id }, update, None)
             .await
         {
             Ok(result) => Ok(result.modified_count > 0),
@@ -208,7 +378,140 @@ impl AuthRepository for MongoAuthRepository {
                 {
                     if write_error.code == mongodb_error_codes::DUPLICATE_KEY {
                         return Err(RepositoryError::DuplicateError(
-                            "メールアドレスが既に使用されています".to_string(),
+                            messages::EMAIL_ALREADY_USED.into(),
+                        ));
+                    }
+                }
+                Err(RepositoryError::DatabaseError(e))
+            }
+        }
+    }
+
+    async fn update_calendar_feed_token(
+        &self,
+        user_id: &ObjectId,
+        token: Option<&str>,
+    ) -> Result<bool, RepositoryError> {
+        let update = match token {
+            Some(token) => doc! {
+                "$set": { "calendar_feed_token": token, "updated_at": BsonDateTime::now() }
+            },
+            None => doc! {
+                "$unset": { "calendar_feed_token": "" },
+                "$set": { "updated_at": BsonDateTime::now() }
+            },
+        };
+
+        let result = self
+            .users_collection
+            .update_one(doc! { "_id": user_id }, update, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        Ok(result.matched_count > 0)
+    }
+
+    async fn find_user_by_calendar_feed_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<UserInDB>, RepositoryError> {
+        self.users_collection
+            .find_one(doc! { "calendar_feed_token": token }, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
+    async fn update_password_hash(
+        &self,
+        user_id: &ObjectId,
+        password_hash: &str,
+    ) -> Result<bool, RepositoryError> {
+        let result = self
+            .users_collection
+            .update_one(
+                doc! { "_id": user_id },
+                doc! {
+                    "$set": { "password_hash": password_hash, "updated_at": BsonDateTime::now() }
+                },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(result.matched_count > 0)
+    }
+
+    async fn update_pending_email(
+        &self,
+        user_id: &ObjectId,
+        pending_email: Option<&str>,
+    ) -> Result<bool, RepositoryError> {
+        let update = match pending_email {
+            Some(pending_email) => doc! {
+                "$set": { "pending_email": pending_email, "updated_at": BsonDateTime::now() }
+            },
+            None => doc! {
+                "$unset": { "pending_email": "" },
+                "$set": { "updated_at": BsonDateTime::now() }
+            },
+        };
+
+        let result = self
+            .users_collection
+            .update_one(doc! { "_id": user_id }, update, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        Ok(result.matched_count > 0)
+    }
+
+    async fn mark_email_verified(
+        &self,
+        user_id: &ObjectId,
+        email: &str,
+    ) -> Result<bool, RepositoryError> {
+        let now = BsonDateTime::now();
+        let result = self
+            .users_collection
+            .update_one(
+                doc! { "_id": user_id, "email": email, "email_verified_at": null },
+                doc! { "$set": { "email_verified_at": now, "updated_at": now } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(result.matched_count > 0)
+    }
+
+    async fn confirm_pending_email(
+        &self,
+        user_id: &ObjectId,
+        pending_email: &str,
+    ) -> Result<bool, RepositoryError> {
+        let now = BsonDateTime::now();
+        let update = doc! {
+            "$set": { "email": pending_email, "email_verified_at": now, "updated_at": now },
+            "$unset": { "pending_email": "" }
+        };
+
+        match self
+            .users_collection
+            .update_one(
+                doc! { "_id": user_id, "pending_email": pending_email },
+                update,
+                None,
+            )
+            .await
+        {
+            Ok(result) => Ok(result.matched_count > 0),
+            Err(e) => {
+                // 申請後に他のユーザーが同じメールアドレスで登録した場合
+                if let mongodb::error::ErrorKind::Write(mongodb::error::WriteFailure::WriteError(
+                    write_error,
+                )) = e.kind.as_ref()
+                {
+                    if write_error.code == mongodb_error_codes::DUPLICATE_KEY {
+                        return Err(RepositoryError::DuplicateError(
+                            messages::EMAIL_ALREADY_USED.into(),
                         ));
                     }
                 }
@@ -216,4 +519,53 @@ impl AuthRepository for MongoAuthRepository {
             }
         }
     }
+
+    async fn save_password_reset_token(
+        &self,
+        reset_token: &PasswordResetTokenInDB,
+    ) -> Result<(), RepositoryError> {
+        self.reset_tokens_collection
+            .insert_one(reset_token, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(())
+    }
+
+    async fn consume_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetTokenInDB>, RepositoryError> {
+        // 同じトークンで同時にリクエストされた場合も、使用できるのは1回だけにする
+        let now = BsonDateTime::now();
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.reset_tokens_collection
+            .find_one_and_update(
+                doc! {
+                    "token_hash": token_hash,
+                    "used_at": { "$exists": false },
+                    "expires_at": { "$gt": now },
+                },
+                doc! { "$set": { "used_at": now } },
+                options,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
+    async fn delete_password_reset_tokens(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<u64, RepositoryError> {
+        let result = self
+            .reset_tokens_collection
+            .delete_many(doc! { "user_id": user_id }, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(result.deleted_count)
+    }
 }
@@ -3,48 +3,131 @@ use crate::models::companies::{
     CompanyCreate, CompanyInDB, CompanyUpdate, CompanyWithProjectsInDB,
 };
 use crate::models::projects::ProjectInDB;
+use crate::models::work_logs::WorkLogInDB;
+use crate::utils::pagination::{keyset_sort, PageCursor};
 use async_trait::async_trait;
 use bson::{doc, oid::ObjectId, DateTime as BsonDateTime};
 use futures::TryStreamExt;
-use mongodb::{error::Error as MongoError, results::InsertOneResult, Collection, Database};
+use mongodb::{
+    error::Error as MongoError, options::FindOptions, results::InsertOneResult, Collection,
+    Database,
+};
 
 #[async_trait]
 pub trait CompanyRepository {
-    async fn find_all(&self) -> Result<Vec<CompanyInDB>, RepositoryError>;
+    /// 企業を作成順に取得する（カーソル指定時はカーソル以降のみ）
+    async fn find_many(
+        &self,
+        user_id: &ObjectId,
+        limit: Option<i64>,
+        cursor: Option<&PageCursor>,
+    ) -> Result<Vec<CompanyInDB>, RepositoryError>;
+
+    async fn count(&self, user_id: &ObjectId) -> Result<u64, RepositoryError>;
 
-    async fn find_all_with_projects(&self)
-        -> Result<Vec<CompanyWithProjectsInDB>, RepositoryError>;
+    async fn find_all_with_projects(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<Vec<CompanyWithProjectsInDB>, RepositoryError>;
 
-    async fn find_by_id(&self, id: &ObjectId) -> Result<Option<CompanyInDB>, RepositoryError>;
+    async fn find_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<CompanyInDB>, RepositoryError>;
 
-    async fn insert_one(&self, company: CompanyCreate) -> Result<ObjectId, RepositoryError>;
+    async fn insert_one(
+        &self,
+        user_id: &ObjectId,
+        company: CompanyCreate,
+    ) -> Result<ObjectId, RepositoryError>;
 
     async fn update_one(
         &self,
+        user_id: &ObjectId,
         id: ObjectId,
         company: &CompanyUpdate,
     ) -> Result<bool, RepositoryError>;
+
+    async fn find_deleted(&self, user_id: &ObjectId) -> Result<Vec<CompanyInDB>, RepositoryError>;
+
+    /// 企業に紐づく勤怠のうち、請求書に含まれるもののIDを取得する
+    async fn find_invoiced_work_log_ids(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Vec<ObjectId>, RepositoryError>;
+
+    /// 企業を論理削除する（紐づくプロジェクトと勤怠も同じ削除日時で論理削除する）
+    async fn soft_delete(&self, user_id: &ObjectId, id: &ObjectId)
+        -> Result<bool, RepositoryError>;
+
+    /// 企業を復元する（企業と同時に削除されたプロジェクトと勤怠も復元する）
+    async fn restore(&self, user_id: &ObjectId, id: &ObjectId) -> Result<bool, RepositoryError>;
+
+    async fn purge_deleted(&self, deleted_before: BsonDateTime) -> Result<u64, RepositoryError>;
 }
 
 pub struct MongoCompanyRepository {
     collection: Collection<CompanyInDB>,
+    projects_collection: Collection<ProjectInDB>,
+    work_logs_collection: Collection<WorkLogInDB>,
 }
 
 impl MongoCompanyRepository {
     pub fn new(db: &Database) -> Self {
         Self {
             collection: db.collection("companies"),
+            projects_collection: db.collection("projects"),
+            work_logs_collection: db.collection("work_logs"),
         }
     }
+
+    /// 企業に紐づくプロジェクトのIDを取得する
+    async fn find_project_ids(
+        &self,
+        user_id: &ObjectId,
+        company_id: &ObjectId,
+        deleted_at: Option<BsonDateTime>,
+    ) -> Result<Vec<ObjectId>, RepositoryError> {
+        let project_ids = self
+            .projects_collection
+            .distinct(
+                "_id",
+                doc! { "company_id": company_id, "user_id": user_id, "deleted_at": deleted_at },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(project_ids
+            .into_iter()
+            .filter_map(|id| id.as_object_id())
+            .collect())
+    }
 }
 
 #[async_trait]
 impl CompanyRepository for MongoCompanyRepository {
-    async fn find_all(&self) -> Result<Vec<CompanyInDB>, RepositoryError> {
+    async fn find_many(
+        &self,
+        user_id: &ObjectId,
+        limit: Option<i64>,
+        page_cursor: Option<&PageCursor>,
+    ) -> Result<Vec<CompanyInDB>, RepositoryError> {
+        let mut query = doc! { "user_id": user_id, "deleted_at": null };
+        if let Some(page_cursor) = page_cursor {
+            query = doc! { "$and": [query, page_cursor.to_filter(&[])] };
+        }
+        let find_options = FindOptions::builder()
+            .limit(limit)
+            .sort(keyset_sort(&[]))
+            .build();
+
         let mut companies = Vec::new();
         let mut cursor = self
             .collection
-            .find(doc! {}, None)
+            .find(query, find_options)
             .await
             .map_err(RepositoryError::DatabaseError)?;
 
@@ -59,17 +142,30 @@ impl CompanyRepository for MongoCompanyRepository {
         Ok(companies)
     }
 
+    async fn count(&self, user_id: &ObjectId) -> Result<u64, RepositoryError> {
+        self.collection
+            .count_documents(doc! { "user_id": user_id, "deleted_at": null }, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
     async fn find_all_with_projects(
         &self,
+        user_id: &ObjectId,
     ) -> Result<Vec<CompanyWithProjectsInDB>, RepositoryError> {
-        let pipeline = vec![doc! {
-            "$lookup": {
-                "from": "projects",
-                "localField": "_id",
-                "foreignField": "company_id",
-                "as": "projects"
-            }
-        }];
+        let pipeline = vec![
+            doc! {
+                "$match": { "user_id": user_id, "deleted_at": null }
+            },
+            doc! {
+                "$lookup": {
+                    "from": "projects",
+                    "localField": "_id",
+                    "foreignField": "company_id",
+                    "as": "projects"
+                }
+            },
+        ];
 
         let mut cursor = self
             .collection
@@ -89,7 +185,8 @@ impl CompanyRepository for MongoCompanyRepository {
                 .get_array("projects")
                 .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e.to_string())))?
                 .iter()
-                .filter_map(|p| bson::from_bson(p.clone()).ok())
+                .filter_map(|p| bson::from_bson::<ProjectInDB>(p.clone()).ok())
+                .filter(|p| p.deleted_at.is_none())
                 .collect();
 
             companies_with_projects.push(CompanyWithProjectsInDB { company, projects });
@@ -98,21 +195,34 @@ impl CompanyRepository for MongoCompanyRepository {
         Ok(companies_with_projects)
     }
 
-    async fn find_by_id(&self, id: &ObjectId) -> Result<Option<CompanyInDB>, RepositoryError> {
+    async fn find_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<CompanyInDB>, RepositoryError> {
         self.collection
-            .find_one(doc! { "_id": id }, None)
+            .find_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": null },
+                None,
+            )
             .await
             .map_err(RepositoryError::DatabaseError)
     }
 
-    async fn insert_one(&self, company: CompanyCreate) -> Result<ObjectId, RepositoryError> {
+    async fn insert_one(
+        &self,
+        user_id: &ObjectId,
+        company: CompanyCreate,
+    ) -> Result<ObjectId, RepositoryError> {
         let company_in_db = CompanyInDB {
             id: None, // MongoDBにID生成を任せる
+            user_id: *user_id,
             common: company.common,
             affiliation_start_date: company.affiliation_start_date,
             affiliation_end_date: company.affiliation_end_date,
             created_at: BsonDateTime::now(),
             updated_at: None,
+            deleted_at: None,
         };
 
         let result: InsertOneResult = self
@@ -130,6 +240,7 @@ impl CompanyRepository for MongoCompanyRepository {
 
     async fn update_one(
         &self,
+        user_id: &ObjectId,
         id: ObjectId,
         company: &CompanyUpdate,
     ) -> Result<bool, RepositoryError> {
@@ -141,9 +252,178 @@ impl CompanyRepository for MongoCompanyRepository {
         };
         let result = self
             .collection
-            .update_one(doc! { "_id": id }, update, None)
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": null },
+                update,
+                None,
+            )
             .await
             .map_err(RepositoryError::DatabaseError)?;
         Ok(result.modified_count > 0)
     }
+
+    async fn find_deleted(&self, user_id: &ObjectId) -> Result<Vec<CompanyInDB>, RepositoryError> {
+        let mut companies = Vec::new();
+        let find_options = FindOptions::builder()
+            .sort(doc! { "deleted_at": -1 })
+            .build();
+        let mut cursor = self
+            .collection
+            .find(
+                doc! { "user_id": user_id, "deleted_at": { "$ne": null } },
+                find_options,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        while let Some(result) = cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+        {
+            companies.push(result);
+        }
+
+        Ok(companies)
+    }
+
+    async fn find_invoiced_work_log_ids(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Vec<ObjectId>, RepositoryError> {
+        let project_ids = self.find_project_ids(user_id, id, None).await?;
+        let work_log_ids = self
+            .work_logs_collection
+            .distinct(
+                "_id",
+                doc! {
+                    "project_id": { "$in": &project_ids },
+                    "user_id": user_id,
+                    "deleted_at": null,
+                    "invoice_id": { "$ne": null }
+                },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(work_log_ids
+            .into_iter()
+            .filter_map(|id| id.as_object_id())
+            .collect())
+    }
+
+    async fn soft_delete(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<bool, RepositoryError> {
+        let deleted_at = BsonDateTime::now();
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": null },
+                doc! { "$set": { "deleted_at": deleted_at } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        if result.modified_count == 0 {
+            return Ok(false);
+        }
+
+        // 復元時に同時に削除したものだけを戻せるよう、同じ削除日時を設定する
+        // 請求書に含まれる勤怠は、確認後に請求書に含まれた場合も削除しない（ゴミ箱から物理削除されないようにする）
+        let project_ids = self.find_project_ids(user_id, id, None).await?;
+        let cascade = doc! { "$set": { "deleted_at": deleted_at } };
+        self.projects_collection
+            .update_many(
+                doc! { "_id": { "$in": &project_ids }, "deleted_at": null },
+                cascade.clone(),
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        self.work_logs_collection
+            .update_many(
+                doc! {
+                    "project_id": { "$in": &project_ids },
+                    "user_id": user_id,
+                    "deleted_at": null,
+                    "invoice_id": null
+                },
+                cascade,
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(true)
+    }
+
+    async fn restore(&self, user_id: &ObjectId, id: &ObjectId) -> Result<bool, RepositoryError> {
+        let Some(deleted_at) = self
+            .collection
+            .find_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": { "$ne": null } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+            .and_then(|company| company.deleted_at)
+        else {
+            return Ok(false);
+        };
+
+        let restore = doc! {
+            "$unset": { "deleted_at": "" },
+            "$set": { "updated_at": BsonDateTime::now() }
+        };
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": deleted_at },
+                restore.clone(),
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        if result.modified_count == 0 {
+            return Ok(false);
+        }
+
+        let project_ids = self.find_project_ids(user_id, id, Some(deleted_at)).await?;
+        self.projects_collection
+            .update_many(
+                doc! { "_id": { "$in": &project_ids }, "deleted_at": deleted_at },
+                restore.clone(),
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        self.work_logs_collection
+            .update_many(
+                doc! {
+                    "project_id": { "$in": &project_ids },
+                    "user_id": user_id,
+                    "deleted_at": deleted_at
+                },
+                restore,
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(true)
+    }
+
+    async fn purge_deleted(&self, deleted_before: BsonDateTime) -> Result<u64, RepositoryError> {
+        let result = self
+            .collection
+            .delete_many(doc! { "deleted_at": { "$lte": deleted_before } }, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        Ok(result.deleted_count)
+    }
 }
@@ -0,0 +1,224 @@
+use crate::errors::repositories_error::RepositoryError;
+use crate::models::invoices::{InvoiceFilter, InvoiceInDB, InvoiceStatus};
+use crate::utils::pagination::{keyset_sort, PageCursor};
+use async_trait::async_trait;
+use bson::{doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document};
+use futures::TryStreamExt;
+use mongodb::{
+    error::Error as MongoError,
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    results::InsertOneResult,
+    Collection, Database,
+};
+
+#[async_trait]
+pub trait InvoiceRepository {
+    /// 請求書を作成順に取得する（カーソル指定時はカーソル以降のみ）
+    async fn find_many(
+        &self,
+        user_id: &ObjectId,
+        filter: &InvoiceFilter,
+        limit: Option<i64>,
+        cursor: Option<&PageCursor>,
+    ) -> Result<Vec<InvoiceInDB>, RepositoryError>;
+
+    async fn count_many(
+        &self,
+        user_id: &ObjectId,
+        filter: &InvoiceFilter,
+    ) -> Result<u64, RepositoryError>;
+
+    async fn find_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<InvoiceInDB>, RepositoryError>;
+
+    /// ユーザーごとの請求書の連番を採番する（1から始まる）
+    async fn next_sequence(&self, user_id: &ObjectId) -> Result<i64, RepositoryError>;
+
+    async fn insert_one(&self, invoice: &InvoiceInDB) -> Result<ObjectId, RepositoryError>;
+
+    /// ステータスを変更する（同時に変更された場合に備え、現在のステータスが`current`の場合のみ更新する）
+    async fn update_status(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+        current: InvoiceStatus,
+        status: InvoiceStatus,
+    ) -> Result<bool, RepositoryError>;
+
+    /// 下書きの請求書を削除する
+    async fn delete_draft(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<bool, RepositoryError>;
+}
+
+pub struct MongoInvoiceRepository {
+    collection: Collection<InvoiceInDB>,
+    counters_collection: Collection<Document>,
+}
+
+impl MongoInvoiceRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection("invoices"),
+            counters_collection: db.collection("counters"),
+        }
+    }
+
+    fn build_query(user_id: &ObjectId, filter: &InvoiceFilter) -> Document {
+        let mut query = doc! { "user_id": user_id };
+        if let Some(company_id) = filter.company_id {
+            query.insert("company_id", company_id);
+        }
+        if let Some(status) = filter.status {
+            query.insert("status", status_to_bson(status));
+        }
+        query
+    }
+}
+
+fn status_to_bson(status: InvoiceStatus) -> Bson {
+    // 列挙型のシリアライズは失敗しない
+    bson::to_bson(&status).unwrap_or(Bson::Null)
+}
+
+#[async_trait]
+impl InvoiceRepository for MongoInvoiceRepository {
+    async fn find_many(
+        &self,
+        user_id: &ObjectId,
+        filter: &InvoiceFilter,
+        limit: Option<i64>,
+        page_cursor: Option<&PageCursor>,
+    ) -> Result<Vec<InvoiceInDB>, RepositoryError> {
+        let mut query = Self::build_query(user_id, filter);
+        if let Some(page_cursor) = page_cursor {
+            query = doc! { "$and": [query, page_cursor.to_filter(&[])] };
+        }
+        let find_options = FindOptions::builder()
+            .limit(limit)
+            .sort(keyset_sort(&[]))
+            .build();
+
+        let mut invoices = Vec::new();
+        let mut cursor = self
+            .collection
+            .find(query, find_options)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        while let Some(result) = cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+        {
+            invoices.push(result);
+        }
+
+        Ok(invoices)
+    }
+
+    async fn count_many(
+        &self,
+        user_id: &ObjectId,
+        filter: &InvoiceFilter,
+    ) -> Result<u64, RepositoryError> {
+        self.collection
+            .count_documents(Self::build_query(user_id, filter), None)
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
+    async fn find_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<InvoiceInDB>, RepositoryError> {
+        self.collection
+            .find_one(doc! { "_id": id, "user_id": user_id }, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
+    async fn next_sequence(&self, user_id: &ObjectId) -> Result<i64, RepositoryError> {
+        // $incはドキュメント単位でアトミックに行われるため、同時に作成されても番号は重複しない
+        let options = FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .build();
+        let counter = self
+            .counters_collection
+            .find_one_and_update(
+                doc! { "_id": format!("invoices:{}", user_id.to_hex()) },
+                doc! { "$inc": { "seq": 1_i64 } },
+                options,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+            .ok_or(RepositoryError::DatabaseError(MongoError::custom(
+                "請求書番号の採番に失敗しました",
+            )))?;
+        counter
+            .get_i64("seq")
+            .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e)))
+    }
+
+    async fn insert_one(&self, invoice: &InvoiceInDB) -> Result<ObjectId, RepositoryError> {
+        let result: InsertOneResult = self
+            .collection
+            .insert_one(invoice, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        result
+            .inserted_id
+            .as_object_id()
+            .ok_or(RepositoryError::DatabaseError(MongoError::custom(
+                "挿入されたドキュメントのIDが無効です",
+            )))
+    }
+
+    async fn update_status(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+        current: InvoiceStatus,
+        status: InvoiceStatus,
+    ) -> Result<bool, RepositoryError> {
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "status": status_to_bson(current) },
+                doc! {
+                    "$set": { "status": status_to_bson(status), "updated_at": BsonDateTime::now() }
+                },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        Ok(result.modified_count > 0)
+    }
+
+    async fn delete_draft(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<bool, RepositoryError> {
+        let result = self
+            .collection
+            .delete_one(
+                doc! {
+                    "_id": id,
+                    "user_id": user_id,
+                    "status": status_to_bson(InvoiceStatus::Draft)
+                },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        Ok(result.deleted_count > 0)
+    }
+}
@@ -1,4 +1,6 @@
 pub mod auth;
 pub mod companies;
+pub mod invoices;
 pub mod projects;
+pub mod reports;
 pub mod work_logs;
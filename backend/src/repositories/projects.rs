@@ -1,5 +1,7 @@
 use crate::errors::repositories_error::RepositoryError;
 use crate::models::projects::{ProjectCreate, ProjectFilter, ProjectInDB, ProjectUpdate};
+use crate::models::work_logs::WorkLogInDB;
+use crate::utils::pagination::{keyset_sort, PageCursor};
 use async_trait::async_trait;
 use bson::{doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document};
 use futures::TryStreamExt;
@@ -7,81 +9,144 @@ use mongodb::{
     error::Error as MongoError, options::FindOptions, results::InsertOneResult, Collection,
     Database,
 };
+use std::collections::HashMap;
 
 #[async_trait]
 pub trait ProjectRepository {
+    /// 条件に一致するプロジェクトを取得する（カーソル指定時はカーソル以降のみ）
     async fn find_many(
         &self,
-        filter: Option<ProjectFilter>,
+        user_id: &ObjectId,
+        filter: Option<&ProjectFilter>,
         limit: Option<i64>,
-        offset: Option<u64>,
-        sort: Option<Vec<(String, i8)>>,
+        cursor: Option<&PageCursor>,
+        sort: &[(String, i8)],
     ) -> Result<Vec<ProjectInDB>, RepositoryError>;
 
-    async fn find_by_id(&self, id: &ObjectId) -> Result<Option<ProjectInDB>, RepositoryError>;
+    async fn count_many(
+        &self,
+        user_id: &ObjectId,
+        filter: Option<&ProjectFilter>,
+    ) -> Result<u64, RepositoryError>;
 
-    async fn insert_one(&self, project: ProjectCreate) -> Result<ObjectId, RepositoryError>;
+    async fn find_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<ProjectInDB>, RepositoryError>;
+
+    async fn insert_one(
+        &self,
+        user_id: &ObjectId,
+        project: ProjectCreate,
+    ) -> Result<ObjectId, RepositoryError>;
 
     async fn update_one(
         &self,
+        user_id: &ObjectId,
         id: ObjectId,
         project: &ProjectUpdate,
     ) -> Result<bool, RepositoryError>;
+
+    async fn find_deleted(&self, user_id: &ObjectId) -> Result<Vec<ProjectInDB>, RepositoryError>;
+
+    async fn find_deleted_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<ProjectInDB>, RepositoryError>;
+
+    /// プロジェクトに紐づく勤怠のうち、請求書に含まれるもののIDを取得する
+    async fn find_invoiced_work_log_ids(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Vec<ObjectId>, RepositoryError>;
+
+    /// プロジェクトを論理削除する（紐づく勤怠も同じ削除日時で論理削除する）
+    async fn soft_delete(&self, user_id: &ObjectId, id: &ObjectId)
+        -> Result<bool, RepositoryError>;
+
+    /// プロジェクトを復元する（プロジェクトと同時に削除された勤怠も復元する）
+    async fn restore(&self, user_id: &ObjectId, id: &ObjectId) -> Result<bool, RepositoryError>;
+
+    async fn purge_deleted(&self, deleted_before: BsonDateTime) -> Result<u64, RepositoryError>;
+
+    /// 勤怠の実作業時間を集計し、プロジェクトの総稼働時間（秒）を再計算する
+    async fn recalculate_total_working_time(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<i64, RepositoryError>;
+
+    /// 全プロジェクトの総稼働時間を再計算し、値が補正されたプロジェクト数を返す
+    async fn recalculate_all_total_working_time(&self) -> Result<u64, RepositoryError>;
 }
 
 pub struct MongoProjectRepository {
     collection: Collection<ProjectInDB>,
+    work_logs_collection: Collection<WorkLogInDB>,
 }
 
 impl MongoProjectRepository {
     pub fn new(db: &Database) -> Self {
         Self {
             collection: db.collection("projects"),
+            work_logs_collection: db.collection("work_logs"),
         }
     }
 }
 
+/// 検索条件からクエリを構築する（ログインユーザーの所有するプロジェクトに限定）
+fn build_query(user_id: &ObjectId, filter: Option<&ProjectFilter>) -> Document {
+    let mut query = doc! { "user_id": user_id, "deleted_at": null };
+
+    if let Some(filter) = filter {
+        if let Some(title) = &filter.title {
+            // タイトルに部分一致するプロジェクトを検索（大文字小文字を無視）
+            query.insert("title", doc! { "$regex": title, "$options": "i" });
+        }
+        if let Some(status) = &filter.status {
+            // ステータスに一致するプロジェクトを検索
+            query.insert("status", status.to_string());
+        }
+        if let Some(labels) = &filter.skill_labels {
+            // 指定されたスキルラベルのいずれかを持つプロジェクトを検索
+            query.insert("skill_labels", doc! { "$in": labels });
+        }
+        if let Some(company_id) = filter.company_id {
+            // 企業IDに一致するプロジェクトを検索
+            query.insert("company_id", company_id);
+        }
+    }
+
+    query
+}
+
 #[async_trait]
 impl ProjectRepository for MongoProjectRepository {
     async fn find_many(
         &self,
-        filter: Option<ProjectFilter>,
+        user_id: &ObjectId,
+        filter: Option<&ProjectFilter>,
         limit: Option<i64>,
-        offset: Option<u64>,
-        sort: Option<Vec<(String, i8)>>,
+        page_cursor: Option<&PageCursor>,
+        sort: &[(String, i8)],
     ) -> Result<Vec<ProjectInDB>, RepositoryError> {
-        // クエリの構築
-        let mut query = Document::new();
-
-        if let Some(filter) = filter {
-            if let Some(title) = filter.title {
-                // タイトルに部分一致するプロジェクトを検索（大文字小文字を無視）
-                query.insert("title", doc! { "$regex": title, "$options": "i" });
-            }
-            if let Some(status) = filter.status {
-                // ステータスに一致するプロジェクトを検索
-                query.insert("status", status.to_string());
-            }
-            if let Some(labels) = filter.skill_labels {
-                // 指定されたスキルラベルのいずれかを持つプロジェクトを検索
-                query.insert("skill_labels", doc! { "$in": labels });
-            }
-            if let Some(company_id) = filter.company_id {
-                // 企業IDに一致するプロジェクトを検索
-                query.insert("company_id", company_id);
-            }
+        let mut query = build_query(user_id, filter);
+        if let Some(page_cursor) = page_cursor {
+            // カーソルより後ろに並ぶプロジェクトに限定
+            query = doc! { "$and": [query, page_cursor.to_filter(sort)] };
         }
 
         // FindOptionsの構築
-        let mut find_options = FindOptions::default();
-        if let Some(limit) = limit {
-            find_options.limit = Some(limit);
-        }
-        if let Some(offset) = offset {
-            find_options.skip = Some(offset);
-        }
-        if let Some(sort_params) = sort {
+        let mut find_options = FindOptions::builder()
+            .limit(limit)
+            .sort(keyset_sort(sort))
+            .build();
+        if !sort.is_empty() {
             // ASCII(英数字)→ひらがな→カタカナ→漢字の順でソート
+            // カーソルの比較にも同じ照合順序が適用される
             find_options.collation = Some(
                 mongodb::options::Collation::builder()
                     .locale("ja") // 日本語ロケール
@@ -90,15 +155,6 @@ impl ProjectRepository for MongoProjectRepository {
                     .numeric_ordering(true) // 数値の自然な順序付け
                     .build(),
             );
-
-            // ソート条件をDocumentに変換
-            let sort_doc = Document::from_iter(
-                sort_params
-                    .into_iter()
-                    .map(|(field, order)| (field, Bson::Int32(order as i32))),
-            );
-
-            find_options.sort = Some(sort_doc);
         }
 
         // クエリの実行
@@ -120,16 +176,39 @@ impl ProjectRepository for MongoProjectRepository {
         Ok(projects)
     }
 
-    async fn find_by_id(&self, id: &ObjectId) -> Result<Option<ProjectInDB>, RepositoryError> {
+    async fn count_many(
+        &self,
+        user_id: &ObjectId,
+        filter: Option<&ProjectFilter>,
+    ) -> Result<u64, RepositoryError> {
         self.collection
-            .find_one(doc! { "_id": id }, None)
+            .count_documents(build_query(user_id, filter), None)
             .await
             .map_err(RepositoryError::DatabaseError)
     }
 
-    async fn insert_one(&self, project: ProjectCreate) -> Result<ObjectId, RepositoryError> {
+    async fn find_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<ProjectInDB>, RepositoryError> {
+        self.collection
+            .find_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": null },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
+    async fn insert_one(
+        &self,
+        user_id: &ObjectId,
+        project: ProjectCreate,
+    ) -> Result<ObjectId, RepositoryError> {
         let project_in_db = ProjectInDB {
             id: None, // MongoDBにID生成を任せる
+            user_id: *user_id,
             title: project.title,
             description: project.description,
             company_id: project.company_id,
@@ -139,6 +218,7 @@ impl ProjectRepository for MongoProjectRepository {
             skill_labels: project.skill_labels,
             created_at: BsonDateTime::now(),
             updated_at: None,
+            deleted_at: None,
         };
 
         let result: InsertOneResult = self.collection.insert_one(&project_in_db, None).await?;
@@ -152,20 +232,286 @@ impl ProjectRepository for MongoProjectRepository {
 
     async fn update_one(
         &self,
+        user_id: &ObjectId,
         id: ObjectId,
         project: &ProjectUpdate,
     ) -> Result<bool, RepositoryError> {
         let mut update_doc = bson::to_document(&project)
             .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e)))?;
+        // 総稼働時間は勤怠から集計する値のため、プロジェクトの更新では変更しない
+        update_doc.remove("total_working_time");
         update_doc.insert("updated_at", BsonDateTime::now());
         let update = doc! {
             "$set": update_doc
         };
         let result = self
             .collection
-            .update_one(doc! { "_id": id }, update, None)
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": null },
+                update,
+                None,
+            )
             .await
             .map_err(RepositoryError::DatabaseError)?;
         Ok(result.modified_count > 0)
     }
+
+    async fn find_deleted(&self, user_id: &ObjectId) -> Result<Vec<ProjectInDB>, RepositoryError> {
+        let find_options = FindOptions::builder()
+            .sort(doc! { "deleted_at": -1 })
+            .build();
+        let mut cursor = self
+            .collection
+            .find(
+                doc! { "user_id": user_id, "deleted_at": { "$ne": null } },
+                find_options,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        let mut projects = Vec::new();
+        while let Some(result) = cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+        {
+            projects.push(result);
+        }
+
+        Ok(projects)
+    }
+
+    async fn find_deleted_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<ProjectInDB>, RepositoryError> {
+        self.collection
+            .find_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": { "$ne": null } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
+    async fn find_invoiced_work_log_ids(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Vec<ObjectId>, RepositoryError> {
+        let work_log_ids = self
+            .work_logs_collection
+            .distinct(
+                "_id",
+                doc! {
+                    "project_id": id,
+                    "user_id": user_id,
+                    "deleted_at": null,
+                    "invoice_id": { "$ne": null }
+                },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(work_log_ids
+            .into_iter()
+            .filter_map(|id| id.as_object_id())
+            .collect())
+    }
+
+    async fn soft_delete(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<bool, RepositoryError> {
+        let deleted_at = BsonDateTime::now();
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": null },
+                doc! { "$set": { "deleted_at": deleted_at } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        if result.modified_count == 0 {
+            return Ok(false);
+        }
+
+        // 復元時に同時に削除したものだけを戻せるよう、同じ削除日時を設定する
+        // 請求書に含まれる勤怠は、確認後に請求書に含まれた場合も削除しない（ゴミ箱から物理削除されないようにする）
+        self.work_logs_collection
+            .update_many(
+                doc! {
+                    "project_id": id,
+                    "user_id": user_id,
+                    "deleted_at": null,
+                    "invoice_id": null
+                },
+                doc! { "$set": { "deleted_at": deleted_at } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(true)
+    }
+
+    async fn restore(&self, user_id: &ObjectId, id: &ObjectId) -> Result<bool, RepositoryError> {
+        let Some(deleted_at) = self
+            .find_deleted_by_id(user_id, id)
+            .await?
+            .and_then(|project| project.deleted_at)
+        else {
+            return Ok(false);
+        };
+
+        let restore = doc! {
+            "$unset": { "deleted_at": "" },
+            "$set": { "updated_at": BsonDateTime::now() }
+        };
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": deleted_at },
+                restore.clone(),
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        if result.modified_count == 0 {
+            return Ok(false);
+        }
+
+        self.work_logs_collection
+            .update_many(
+                doc! { "project_id": id, "user_id": user_id, "deleted_at": deleted_at },
+                restore,
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(true)
+    }
+
+    async fn purge_deleted(&self, deleted_before: BsonDateTime) -> Result<u64, RepositoryError> {
+        let result = self
+            .collection
+            .delete_many(doc! { "deleted_at": { "$lte": deleted_before } }, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        Ok(result.deleted_count)
+    }
+
+    async fn recalculate_total_working_time(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<i64, RepositoryError> {
+        let totals = self
+            .aggregate_total_working_time(doc! {
+                "project_id": id,
+                "user_id": user_id,
+                "deleted_at": null
+            })
+            .await?;
+        let total_working_time = totals.get(id).copied().unwrap_or(0);
+
+        self.collection
+            .update_one(
+                doc! { "_id": id, "user_id": user_id },
+                doc! { "$set": { "total_working_time": total_working_time } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(total_working_time)
+    }
+
+    async fn recalculate_all_total_working_time(&self) -> Result<u64, RepositoryError> {
+        let totals = self
+            .aggregate_total_working_time(doc! { "deleted_at": null })
+            .await?;
+
+        let mut cursor = self
+            .collection
+            .find(doc! {}, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        let mut corrected_count = 0;
+        while let Some(project) = cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+        {
+            let Some(id) = project.id else {
+                continue;
+            };
+            let total_working_time = totals.get(&id).copied().unwrap_or(0);
+            if project.total_working_time == total_working_time {
+                continue;
+            }
+
+            self.collection
+                .update_one(
+                    doc! { "_id": id },
+                    doc! { "$set": { "total_working_time": total_working_time } },
+                    None,
+                )
+                .await
+                .map_err(RepositoryError::DatabaseError)?;
+            corrected_count += 1;
+        }
+
+        Ok(corrected_count)
+    }
+}
+
+impl MongoProjectRepository {
+    /// 条件に一致する勤怠の実作業時間をプロジェクトごとに合計し、秒単位で返す
+    async fn aggregate_total_working_time(
+        &self,
+        filter: Document,
+    ) -> Result<HashMap<ObjectId, i64>, RepositoryError> {
+        let pipeline = vec![
+            doc! { "$match": filter },
+            doc! {
+                "$group": {
+                    "_id": "$project_id",
+                    "total_minutes": { "$sum": { "$ifNull": ["$actual_work_minutes", 0] } }
+                }
+            },
+        ];
+
+        let mut cursor = self
+            .work_logs_collection
+            .aggregate(pipeline, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        let mut totals = HashMap::new();
+        while let Some(result) = cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+        {
+            let project_id = result
+                .get_object_id("_id")
+                .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e.to_string())))?;
+            // $sumの結果はInt32/Int64のどちらにもなり得る
+            let total_minutes = match result.get("total_minutes") {
+                Some(Bson::Int32(minutes)) => *minutes as i64,
+                Some(Bson::Int64(minutes)) => *minutes,
+                _ => 0,
+            };
+            totals.insert(project_id, total_minutes * 60); // 分を秒に変換
+        }
+
+        Ok(totals)
+    }
 }
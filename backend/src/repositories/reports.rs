@@ -0,0 +1,419 @@
+use crate::errors::repositories_error::RepositoryError;
+use crate::models::reports::{
+    SkillCompany, SkillTrend, SkillUsage, TimeReportGroup, TimeReportGroupBy,
+};
+use crate::models::work_logs::WorkLogInDB;
+use async_trait::async_trait;
+use bson::{doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document};
+use chrono_tz::Tz;
+use futures::TryStreamExt;
+use mongodb::{error::Error as MongoError, Collection, Database};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+#[async_trait]
+pub trait ReportRepository {
+    /// 期間内の勤怠の実作業時間を集計単位ごとに集計する
+    async fn aggregate_work_minutes(
+        &self,
+        user_id: &ObjectId,
+        from: BsonDateTime,
+        to: BsonDateTime,
+        group_by: TimeReportGroupBy,
+        time_zone: Tz,
+    ) -> Result<Vec<TimeReportGroup>, RepositoryError>;
+
+    /// 期間内の勤怠の実作業時間と件数の合計を取得する
+    async fn sum_work_minutes(
+        &self,
+        user_id: &ObjectId,
+        from: BsonDateTime,
+        to: BsonDateTime,
+    ) -> Result<(i64, i64), RepositoryError>;
+
+    /// スキルラベルごとの利用状況を作業時間の多い順に集計する（期間が`None`の場合は全期間）
+    async fn aggregate_skill_usage(
+        &self,
+        user_id: &ObjectId,
+        from: Option<BsonDateTime>,
+        to: Option<BsonDateTime>,
+        limit: Option<i64>,
+        time_zone: Tz,
+    ) -> Result<Vec<SkillUsage>, RepositoryError>;
+}
+
+pub struct MongoReportRepository {
+    work_logs_collection: Collection<WorkLogInDB>,
+}
+
+impl MongoReportRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            work_logs_collection: db.collection("work_logs"),
+        }
+    }
+}
+
+/// 集計対象の勤怠に絞り込む（作業中で実作業時間が確定していない勤怠は除く）
+fn match_stage(
+    user_id: &ObjectId,
+    from: Option<BsonDateTime>,
+    to: Option<BsonDateTime>,
+) -> Document {
+    let mut query = doc! {
+        "user_id": user_id,
+        "deleted_at": null,
+        "actual_work_minutes": { "$ne": null },
+    };
+    if from.is_some() || to.is_some() {
+        let mut range = Document::new();
+        if let Some(from) = from {
+            range.insert("$gte", from);
+        }
+        if let Some(to) = to {
+            range.insert("$lt", to);
+        }
+        query.insert("start_time", range);
+    }
+    doc! { "$match": query }
+}
+
+/// 勤怠にプロジェクトを結合する（削除済みのプロジェクトの勤怠は集計しない）
+fn lookup_project_stages() -> Vec<Document> {
+    vec![
+        doc! {
+            "$lookup": {
+                "from": "projects",
+                "localField": "project_id",
+                "foreignField": "_id",
+                "as": "project"
+            }
+        },
+        doc! { "$unwind": "$project" },
+        doc! { "$match": { "project.deleted_at": null } },
+    ]
+}
+
+/// 開始時間を指定したタイムゾーンで文字列に変換する式
+fn date_key(format: &str, time_zone: Tz) -> Document {
+    doc! {
+        "$dateToString": {
+            "date": "$start_time",
+            "format": format,
+            "timezone": time_zone.name(),
+        }
+    }
+}
+
+/// 集計単位に応じて、グループのキーと表示名を算出するまでのステージを組み立てる
+fn group_key_stages(group_by: TimeReportGroupBy, time_zone: Tz) -> (Vec<Document>, Bson, Bson) {
+    match group_by {
+        TimeReportGroupBy::Day => {
+            let key = Bson::Document(date_key("%Y-%m-%d", time_zone));
+            (vec![], key.clone(), key)
+        }
+        TimeReportGroupBy::Week => {
+            let key = Bson::Document(date_key("%G-W%V", time_zone));
+            (vec![], key.clone(), key)
+        }
+        TimeReportGroupBy::Month => {
+            let key = Bson::Document(date_key("%Y-%m", time_zone));
+            (vec![], key.clone(), key)
+        }
+        TimeReportGroupBy::Project => (
+            lookup_project_stages(),
+            Bson::Document(doc! { "$toString": "$project_id" }),
+            Bson::String("$project.title".to_string()),
+        ),
+        TimeReportGroupBy::Company => {
+            let mut stages = lookup_project_stages();
+            stages.extend([
+                doc! {
+                    "$lookup": {
+                        "from": "companies",
+                        "localField": "project.company_id",
+                        "foreignField": "_id",
+                        "as": "company"
+                    }
+                },
+                doc! { "$unwind": "$company" },
+                doc! { "$match": { "company.deleted_at": null } },
+            ]);
+            (
+                stages,
+                Bson::Document(doc! { "$toString": "$company._id" }),
+                Bson::String("$company.company_name".to_string()),
+            )
+        }
+        TimeReportGroupBy::SkillLabel => {
+            // 複数のスキルラベルを持つプロジェクトの勤怠は、各スキルラベルに計上する
+            let mut stages = lookup_project_stages();
+            stages.push(doc! { "$unwind": "$project.skill_labels" });
+            let key = Bson::String("$project.skill_labels".to_string());
+            (stages, key.clone(), key)
+        }
+    }
+}
+
+/// $sumの結果はInt32/Int64のどちらにもなり得る
+fn get_sum(document: &Document, key: &str) -> i64 {
+    match document.get(key) {
+        Some(Bson::Int32(value)) => *value as i64,
+        Some(Bson::Int64(value)) => *value,
+        _ => 0,
+    }
+}
+
+/// スキルの集計結果（スキル・月・企業ごとの内訳を含む）
+#[derive(Deserialize)]
+struct SkillUsageRecord {
+    #[serde(rename = "_id")]
+    skill_label: String,
+    work_minutes: i64,
+    work_log_count: i64,
+    project_count: i64,
+    first_used_at: BsonDateTime,
+    last_used_at: BsonDateTime,
+    breakdown: Vec<SkillBreakdownRecord>,
+}
+
+#[derive(Deserialize)]
+struct SkillBreakdownRecord {
+    month: String,
+    company_id: ObjectId,
+    company_name: Option<String>,
+    work_minutes: i64,
+}
+
+impl From<SkillUsageRecord> for SkillUsage {
+    fn from(record: SkillUsageRecord) -> Self {
+        let mut trend = BTreeMap::new();
+        let mut companies: HashMap<ObjectId, SkillCompany> = HashMap::new();
+        for entry in record.breakdown {
+            *trend.entry(entry.month).or_insert(0) += entry.work_minutes;
+            companies
+                .entry(entry.company_id)
+                .or_insert_with(|| SkillCompany {
+                    company_id: entry.company_id,
+                    company_name: entry.company_name.unwrap_or_default(),
+                    work_minutes: 0,
+                })
+                .work_minutes += entry.work_minutes;
+        }
+        let mut companies: Vec<SkillCompany> = companies.into_values().collect();
+        companies.sort_by(|a, b| {
+            b.work_minutes
+                .cmp(&a.work_minutes)
+                .then_with(|| a.company_name.cmp(&b.company_name))
+        });
+
+        Self {
+            skill_label: record.skill_label,
+            work_minutes: record.work_minutes,
+            work_log_count: record.work_log_count,
+            project_count: record.project_count,
+            first_used_at: record.first_used_at,
+            last_used_at: record.last_used_at,
+            trend: trend
+                .into_iter()
+                .map(|(month, work_minutes)| SkillTrend {
+                    month,
+                    work_minutes,
+                })
+                .collect(),
+            companies,
+        }
+    }
+}
+
+#[async_trait]
+impl ReportRepository for MongoReportRepository {
+    async fn aggregate_work_minutes(
+        &self,
+        user_id: &ObjectId,
+        from: BsonDateTime,
+        to: BsonDateTime,
+        group_by: TimeReportGroupBy,
+        time_zone: Tz,
+    ) -> Result<Vec<TimeReportGroup>, RepositoryError> {
+        let (stages, key, label) = group_key_stages(group_by, time_zone);
+        // 期間単位は時系列順、それ以外は作業時間の多い順に並べる
+        let sort = match group_by {
+            TimeReportGroupBy::Day | TimeReportGroupBy::Week | TimeReportGroupBy::Month => {
+                doc! { "_id": 1 }
+            }
+            _ => doc! { "work_minutes": -1, "_id": 1 },
+        };
+
+        let mut pipeline = vec![match_stage(user_id, Some(from), Some(to))];
+        pipeline.extend(stages);
+        pipeline.extend([
+            doc! {
+                "$group": {
+                    "_id": key,
+                    "label": { "$first": label },
+                    "work_minutes": { "$sum": "$actual_work_minutes" },
+                    "work_log_count": { "$sum": 1 },
+                }
+            },
+            doc! { "$sort": sort },
+        ]);
+
+        let mut cursor = self
+            .work_logs_collection
+            .aggregate(pipeline, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        let mut groups = Vec::new();
+        while let Some(result) = cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+        {
+            let key = result
+                .get_str("_id")
+                .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e.to_string())))?
+                .to_string();
+            groups.push(TimeReportGroup {
+                label: result.get_str("label").unwrap_or(&key).to_string(),
+                key,
+                work_minutes: get_sum(&result, "work_minutes"),
+                work_log_count: get_sum(&result, "work_log_count"),
+            });
+        }
+
+        Ok(groups)
+    }
+
+    async fn sum_work_minutes(
+        &self,
+        user_id: &ObjectId,
+        from: BsonDateTime,
+        to: BsonDateTime,
+    ) -> Result<(i64, i64), RepositoryError> {
+        let pipeline = vec![
+            match_stage(user_id, Some(from), Some(to)),
+            doc! {
+                "$group": {
+                    "_id": null,
+                    "work_minutes": { "$sum": "$actual_work_minutes" },
+                    "work_log_count": { "$sum": 1 },
+                }
+            },
+        ];
+
+        let mut cursor = self
+            .work_logs_collection
+            .aggregate(pipeline, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        // 対象の勤怠がない場合は結果が返らない
+        Ok(cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+            .map_or((0, 0), |result| {
+                (
+                    get_sum(&result, "work_minutes"),
+                    get_sum(&result, "work_log_count"),
+                )
+            }))
+    }
+
+    async fn aggregate_skill_usage(
+        &self,
+        user_id: &ObjectId,
+        from: Option<BsonDateTime>,
+        to: Option<BsonDateTime>,
+        limit: Option<i64>,
+        time_zone: Tz,
+    ) -> Result<Vec<SkillUsage>, RepositoryError> {
+        let mut pipeline = vec![match_stage(user_id, from, to)];
+        pipeline.extend(lookup_project_stages());
+        pipeline.extend([
+            // 複数のスキルラベルを持つプロジェクトの勤怠は、各スキルラベルに計上する
+            doc! { "$unwind": "$project.skill_labels" },
+            doc! {
+                "$lookup": {
+                    "from": "companies",
+                    "localField": "project.company_id",
+                    "foreignField": "_id",
+                    "as": "company"
+                }
+            },
+            // スキル・月・企業ごとに集計
+            doc! {
+                "$group": {
+                    "_id": {
+                        "skill_label": "$project.skill_labels",
+                        "month": date_key("%Y-%m", time_zone),
+                        "company_id": "$project.company_id",
+                    },
+                    "company_name": { "$first": { "$arrayElemAt": ["$company.company_name", 0] } },
+                    "work_minutes": { "$sum": "$actual_work_minutes" },
+                    "work_log_count": { "$sum": 1 },
+                    "first_used_at": { "$min": "$start_time" },
+                    "last_used_at": { "$max": "$start_time" },
+                    "project_ids": { "$addToSet": "$project_id" },
+                }
+            },
+            // スキルごとに集計し、月・企業ごとの内訳を保持する
+            doc! {
+                "$group": {
+                    "_id": "$_id.skill_label",
+                    "work_minutes": { "$sum": "$work_minutes" },
+                    "work_log_count": { "$sum": "$work_log_count" },
+                    "first_used_at": { "$min": "$first_used_at" },
+                    "last_used_at": { "$max": "$last_used_at" },
+                    "project_ids": { "$push": "$project_ids" },
+                    "breakdown": {
+                        "$push": {
+                            "month": "$_id.month",
+                            "company_id": "$_id.company_id",
+                            "company_name": "$company_name",
+                            "work_minutes": "$work_minutes",
+                        }
+                    },
+                }
+            },
+            doc! {
+                "$addFields": {
+                    "project_count": {
+                        "$size": {
+                            "$reduce": {
+                                "input": "$project_ids",
+                                "initialValue": [],
+                                "in": { "$setUnion": ["$$value", "$$this"] }
+                            }
+                        }
+                    }
+                }
+            },
+            doc! { "$sort": { "work_minutes": -1, "_id": 1 } },
+        ]);
+        if let Some(limit) = limit {
+            pipeline.push(doc! { "$limit": limit });
+        }
+
+        let mut cursor = self
+            .work_logs_collection
+            .aggregate(pipeline, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        let mut skills = Vec::new();
+        while let Some(result) = cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+        {
+            let record: SkillUsageRecord = bson::from_document(result)
+                .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e.to_string())))?;
+            skills.push(SkillUsage::from(record));
+        }
+
+        Ok(skills)
+    }
+}
@@ -1,44 +1,281 @@
+use crate::constants::mongo_error_codes::mongodb_error_codes;
 use crate::errors::repositories_error::RepositoryError;
-use crate::models::work_logs::{WorkLogCreate, WorkLogInDB, WorkLogUpdate};
+use crate::i18n::messages;
+use crate::models::projects::ProjectInDB;
+use crate::models::work_logs::{WorkLogCreate, WorkLogFilter, WorkLogInDB, WorkLogUpdate};
+use crate::utils::pagination::{keyset_sort, PageCursor};
 use async_trait::async_trait;
-use bson::{doc, oid::ObjectId, DateTime as BsonDateTime};
-use futures::stream::TryStreamExt;
-use mongodb::{error::Error as MongoError, results::InsertOneResult, Collection, Database};
+use bson::{doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document};
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use mongodb::{
+    error::{Error as MongoError, ErrorKind, WriteFailure},
+    options::FindOptions,
+    results::InsertOneResult,
+    Collection, Database,
+};
 
 #[async_trait]
 pub trait WorkLogRepository {
-    async fn find_all(&self) -> Result<Vec<WorkLogInDB>, RepositoryError>;
+    /// 条件に一致する勤怠を取得する（カーソル指定時はカーソル以降のみ）
+    async fn find_many(
+        &self,
+        user_id: &ObjectId,
+        filter: &WorkLogFilter,
+        limit: Option<i64>,
+        cursor: Option<&PageCursor>,
+        sort: &[(String, i8)],
+    ) -> Result<Vec<WorkLogInDB>, RepositoryError>;
+
+    async fn count_many(
+        &self,
+        user_id: &ObjectId,
+        filter: &WorkLogFilter,
+    ) -> Result<u64, RepositoryError>;
 
-    async fn find_by_id(&self, id: &ObjectId) -> Result<Option<WorkLogInDB>, RepositoryError>;
+    /// 条件に一致する勤怠を開始時間の古い順に1件ずつ取得する（全件をメモリに載せないためのストリーム）
+    async fn stream_many(
+        &self,
+        user_id: &ObjectId,
+        filter: &WorkLogFilter,
+    ) -> Result<BoxStream<'static, Result<WorkLogInDB, RepositoryError>>, RepositoryError>;
 
-    async fn insert_one(&self, work_logs: &WorkLogCreate) -> Result<ObjectId, RepositoryError>;
+    async fn find_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<WorkLogInDB>, RepositoryError>;
 
+    async fn insert_one(
+        &self,
+        user_id: &ObjectId,
+        work_logs: &WorkLogCreate,
+    ) -> Result<ObjectId, RepositoryError>;
+
+    /// 複数の勤怠を一括で登録し、登録した件数を返す
+    async fn insert_many(
+        &self,
+        user_id: &ObjectId,
+        work_logs: &[WorkLogCreate],
+    ) -> Result<usize, RepositoryError>;
+
+    /// 請求書に含まれていない勤怠を更新する（該当する勤怠がない場合はfalse）
     async fn update_one(
         &self,
+        user_id: &ObjectId,
         id: ObjectId,
         work_logs: &WorkLogUpdate,
     ) -> Result<bool, RepositoryError>;
+
+    /// 終了時刻が未設定（作業中）の勤怠を取得する
+    async fn find_active(&self, user_id: &ObjectId)
+        -> Result<Option<WorkLogInDB>, RepositoryError>;
+
+    /// 期間`[start_time, end_time)`と重なる勤怠を開始時間の古い順に取得する
+    ///
+    /// 終了時刻が未設定の勤怠・期間は終わりがないものとして扱う
+    /// （`exclude_id`の勤怠と、`exclude_session_id`を持つ分割された勤怠は対象外とする）
+    async fn find_overlapping(
+        &self,
+        user_id: &ObjectId,
+        start_time: BsonDateTime,
+        end_time: Option<BsonDateTime>,
+        exclude_id: Option<&ObjectId>,
+        exclude_session_id: Option<&ObjectId>,
+    ) -> Result<Vec<WorkLogInDB>, RepositoryError>;
+
+    /// 作業中の勤怠に終了時刻を設定する（既に終了している場合は更新しない）
+    async fn stop(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+        end_time: BsonDateTime,
+        break_time: i32,
+        actual_work_minutes: i32,
+        memo: Option<&str>,
+    ) -> Result<bool, RepositoryError>;
+
+    /// 請求書に含まれていない勤怠に請求書IDを設定し、設定した件数を返す
+    async fn lock_for_invoice(
+        &self,
+        user_id: &ObjectId,
+        ids: &[ObjectId],
+        invoice_id: &ObjectId,
+    ) -> Result<u64, RepositoryError>;
+
+    /// 請求書IDの設定を解除する
+    async fn unlock_invoice(
+        &self,
+        user_id: &ObjectId,
+        invoice_id: &ObjectId,
+    ) -> Result<u64, RepositoryError>;
+
+    async fn find_deleted(&self, user_id: &ObjectId) -> Result<Vec<WorkLogInDB>, RepositoryError>;
+
+    async fn find_deleted_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<WorkLogInDB>, RepositoryError>;
+
+    /// 請求書に含まれていない勤怠を論理削除する（該当する勤怠がない場合はfalse）
+    async fn soft_delete(&self, user_id: &ObjectId, id: &ObjectId)
+        -> Result<bool, RepositoryError>;
+
+    async fn restore(&self, user_id: &ObjectId, id: &ObjectId) -> Result<bool, RepositoryError>;
+
+    async fn purge_deleted(&self, deleted_before: BsonDateTime) -> Result<u64, RepositoryError>;
 }
 
 pub struct MongoWorkLogRepository {
     collection: Collection<WorkLogInDB>,
+    projects_collection: Collection<ProjectInDB>,
 }
 
 impl MongoWorkLogRepository {
     pub fn new(db: &Database) -> Self {
         Self {
             collection: db.collection("work_logs"),
+            projects_collection: db.collection("projects"),
+        }
+    }
+
+    /// 検索条件からクエリを構築する（ログインユーザーの削除されていない勤怠に限定）
+    async fn build_query(
+        &self,
+        user_id: &ObjectId,
+        filter: &WorkLogFilter,
+    ) -> Result<Document, RepositoryError> {
+        let mut query = doc! { "user_id": user_id, "deleted_at": null };
+
+        if let Some(company_id) = filter.company_id {
+            // 企業に紐づくプロジェクトの勤怠を検索（プロジェクトIDも指定された場合は両方に一致するもの）
+            let project_ids: Vec<Bson> = self
+                .projects_collection
+                .distinct(
+                    "_id",
+                    doc! { "company_id": company_id, "user_id": user_id, "deleted_at": null },
+                    None,
+                )
+                .await
+                .map_err(RepositoryError::DatabaseError)?
+                .into_iter()
+                .filter(|id| {
+                    filter
+                        .project_id
+                        .is_none_or(|project_id| id.as_object_id() == Some(project_id))
+                })
+                .collect();
+            query.insert("project_id", doc! { "$in": project_ids });
+        } else if let Some(project_id) = filter.project_id {
+            query.insert("project_id", project_id);
+        }
+
+        if filter.from.is_some() || filter.to.is_some() {
+            // 開始時間が指定範囲内の勤怠を検索
+            let mut range = Document::new();
+            if let Some(from) = filter.from {
+                range.insert("$gte", from);
+            }
+            if let Some(to) = filter.to {
+                range.insert("$lt", to);
+            }
+            query.insert("start_time", range);
+        }
+
+        if let Some(memo) = &filter.memo {
+            // メモに部分一致する勤怠を検索（大文字小文字を無視、入力は正規表現として解釈しない）
+            query.insert(
+                "memo",
+                doc! { "$regex": escape_regex(memo), "$options": "i" },
+            );
         }
+
+        Ok(query)
+    }
+}
+
+/// 登録する勤怠のドキュメントを生成する
+fn new_work_log_in_db(
+    user_id: &ObjectId,
+    work_logs: &WorkLogCreate,
+    created_at: BsonDateTime,
+) -> WorkLogInDB {
+    WorkLogInDB {
+        id: None, // MongoDBにID生成を任せる
+        user_id: *user_id,
+        project_id: work_logs.project_id,
+        start_time: work_logs.start_time,
+        end_time: work_logs.end_time,
+        memo: work_logs.memo.clone(),
+        break_time: work_logs.break_time,
+        actual_work_minutes: work_logs.resolved_actual_work_minutes(),
+        created_at,
+        updated_at: None,
+        deleted_at: None,
+        invoice_id: None,
+        session_id: work_logs.session_id,
+    }
+}
+
+/// 正規表現のメタ文字をエスケープする
+fn escape_regex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 書き込み時のエラーを変換する
+///
+/// 勤怠のユニーク制約は作業中の勤怠（ユーザーごとに1件まで）のみのため、重複は作業中の勤怠の重複として扱う
+fn map_write_error(e: MongoError) -> RepositoryError {
+    let duplicated = match e.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(write_error)) => {
+            write_error.code == mongodb_error_codes::DUPLICATE_KEY
+        }
+        ErrorKind::BulkWrite(failure) => failure
+            .write_errors
+            .iter()
+            .flatten()
+            .any(|write_error| write_error.code == mongodb_error_codes::DUPLICATE_KEY),
+        _ => false,
+    };
+    if duplicated {
+        RepositoryError::DuplicateError(messages::ACTIVE_WORK_LOG_EXISTS.into())
+    } else {
+        RepositoryError::DatabaseError(e)
     }
 }
 
 #[async_trait]
 impl WorkLogRepository for MongoWorkLogRepository {
-    async fn find_all(&self) -> Result<Vec<WorkLogInDB>, RepositoryError> {
+    async fn find_many(
+        &self,
+        user_id: &ObjectId,
+        filter: &WorkLogFilter,
+        limit: Option<i64>,
+        page_cursor: Option<&PageCursor>,
+        sort: &[(String, i8)],
+    ) -> Result<Vec<WorkLogInDB>, RepositoryError> {
+        let mut query = self.build_query(user_id, filter).await?;
+        if let Some(page_cursor) = page_cursor {
+            // カーソルより後ろに並ぶ勤怠に限定
+            query = doc! { "$and": [query, page_cursor.to_filter(sort)] };
+        }
+
+        let find_options = FindOptions::builder()
+            .limit(limit)
+            .sort(keyset_sort(sort))
+            .build();
+
         let mut work_logs = Vec::new();
         let mut cursor = self
             .collection
-            .find(doc! {}, None)
+            .find(query, find_options)
             .await
             .map_err(RepositoryError::DatabaseError)?;
 
@@ -53,31 +290,63 @@ impl WorkLogRepository for MongoWorkLogRepository {
         Ok(work_logs)
     }
 
-    async fn find_by_id(&self, id: &ObjectId) -> Result<Option<WorkLogInDB>, RepositoryError> {
+    async fn count_many(
+        &self,
+        user_id: &ObjectId,
+        filter: &WorkLogFilter,
+    ) -> Result<u64, RepositoryError> {
+        let query = self.build_query(user_id, filter).await?;
         self.collection
-            .find_one(doc! { "_id": id }, None)
+            .count_documents(query, None)
             .await
             .map_err(RepositoryError::DatabaseError)
     }
 
-    async fn insert_one(&self, work_logs: &WorkLogCreate) -> Result<ObjectId, RepositoryError> {
-        let work_logs_in_db = WorkLogInDB {
-            id: None, // MongoDBにID生成を任せる
-            project_id: work_logs.project_id,
-            start_time: work_logs.start_time,
-            end_time: work_logs.end_time.map(BsonDateTime::from),
-            memo: work_logs.memo.clone(),
-            break_time: work_logs.break_time,
-            actual_work_minutes: work_logs.actual_work_minutes,
-            created_at: BsonDateTime::now(),
-            updated_at: None,
-        };
+    async fn stream_many(
+        &self,
+        user_id: &ObjectId,
+        filter: &WorkLogFilter,
+    ) -> Result<BoxStream<'static, Result<WorkLogInDB, RepositoryError>>, RepositoryError> {
+        let query = self.build_query(user_id, filter).await?;
+        let find_options = FindOptions::builder()
+            .sort(doc! { "start_time": 1, "_id": 1 })
+            .build();
+
+        let cursor = self
+            .collection
+            .find(query, find_options)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        Ok(cursor.map_err(RepositoryError::DatabaseError).boxed())
+    }
+
+    async fn find_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<WorkLogInDB>, RepositoryError> {
+        self.collection
+            .find_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": null },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
+    async fn insert_one(
+        &self,
+        user_id: &ObjectId,
+        work_logs: &WorkLogCreate,
+    ) -> Result<ObjectId, RepositoryError> {
+        let work_logs_in_db = new_work_log_in_db(user_id, work_logs, BsonDateTime::now());
 
         let result: InsertOneResult = self
             .collection
             .insert_one(&work_logs_in_db, None)
             .await
-            .map_err(RepositoryError::DatabaseError)?;
+            .map_err(map_write_error)?;
         result
             .inserted_id
             .as_object_id()
@@ -86,22 +355,269 @@ impl WorkLogRepository for MongoWorkLogRepository {
             )))
     }
 
+    async fn insert_many(
+        &self,
+        user_id: &ObjectId,
+        work_logs: &[WorkLogCreate],
+    ) -> Result<usize, RepositoryError> {
+        if work_logs.is_empty() {
+            return Ok(0);
+        }
+
+        let now = BsonDateTime::now();
+        let work_logs_in_db: Vec<WorkLogInDB> = work_logs
+            .iter()
+            .map(|work_log| new_work_log_in_db(user_id, work_log, now))
+            .collect();
+
+        let result = self
+            .collection
+            .insert_many(&work_logs_in_db, None)
+            .await
+            .map_err(map_write_error)?;
+        Ok(result.inserted_ids.len())
+    }
+
     async fn update_one(
         &self,
+        user_id: &ObjectId,
         id: ObjectId,
         work_logs: &WorkLogUpdate,
     ) -> Result<bool, RepositoryError> {
         let mut update_doc = bson::to_document(&work_logs)
             .map_err(|e| RepositoryError::DatabaseError(MongoError::custom(e)))?;
+        update_doc.insert(
+            "actual_work_minutes",
+            work_logs
+                .resolved_actual_work_minutes()
+                .map_or(Bson::Null, Bson::Int32),
+        );
         update_doc.insert("updated_at", BsonDateTime::now());
         let update = doc! {
             "$set": update_doc
         };
+        // 請求書への追加と同時に更新された場合も、請求書に含まれた勤怠は更新しない
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": null, "invoice_id": null },
+                update,
+                None,
+            )
+            .await
+            .map_err(map_write_error)?;
+        Ok(result.matched_count > 0)
+    }
+
+    async fn find_active(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<Option<WorkLogInDB>, RepositoryError> {
+        self.collection
+            .find_one(
+                doc! { "user_id": user_id, "end_time": null, "deleted_at": null },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
+    async fn find_overlapping(
+        &self,
+        user_id: &ObjectId,
+        start_time: BsonDateTime,
+        end_time: Option<BsonDateTime>,
+        exclude_id: Option<&ObjectId>,
+        exclude_session_id: Option<&ObjectId>,
+    ) -> Result<Vec<WorkLogInDB>, RepositoryError> {
+        let mut query = doc! {
+            "user_id": user_id,
+            "deleted_at": null,
+            "$or": [
+                { "end_time": null },
+                { "end_time": { "$gt": start_time } },
+            ],
+        };
+        if let Some(end_time) = end_time {
+            query.insert("start_time", doc! { "$lt": end_time });
+        }
+        if let Some(exclude_id) = exclude_id {
+            query.insert("_id", doc! { "$ne": exclude_id });
+        }
+        if let Some(exclude_session_id) = exclude_session_id {
+            query.insert("session_id", doc! { "$ne": exclude_session_id });
+        }
+        let find_options = FindOptions::builder()
+            .sort(doc! { "start_time": 1, "_id": 1 })
+            .build();
+
+        let mut work_logs = Vec::new();
+        let mut cursor = self
+            .collection
+            .find(query, find_options)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        while let Some(result) = cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+        {
+            work_logs.push(result);
+        }
+
+        Ok(work_logs)
+    }
+
+    async fn stop(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+        end_time: BsonDateTime,
+        break_time: i32,
+        actual_work_minutes: i32,
+        memo: Option<&str>,
+    ) -> Result<bool, RepositoryError> {
+        let mut update_doc = doc! {
+            "end_time": end_time,
+            "break_time": break_time,
+            "actual_work_minutes": actual_work_minutes,
+            "updated_at": BsonDateTime::now(),
+        };
+        if let Some(memo) = memo {
+            update_doc.insert("memo", memo);
+        }
+
         let result = self
             .collection
-            .update_one(doc! { "_id": id }, update, None)
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "end_time": null, "deleted_at": null },
+                doc! { "$set": update_doc },
+                None,
+            )
             .await
             .map_err(RepositoryError::DatabaseError)?;
         Ok(result.modified_count > 0)
     }
+
+    async fn lock_for_invoice(
+        &self,
+        user_id: &ObjectId,
+        ids: &[ObjectId],
+        invoice_id: &ObjectId,
+    ) -> Result<u64, RepositoryError> {
+        let result = self
+            .collection
+            .update_many(
+                doc! {
+                    "_id": { "$in": ids },
+                    "user_id": user_id,
+                    "deleted_at": null,
+                    "invoice_id": null
+                },
+                doc! { "$set": { "invoice_id": invoice_id } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        Ok(result.modified_count)
+    }
+
+    async fn unlock_invoice(
+        &self,
+        user_id: &ObjectId,
+        invoice_id: &ObjectId,
+    ) -> Result<u64, RepositoryError> {
+        let result = self
+            .collection
+            .update_many(
+                doc! { "user_id": user_id, "invoice_id": invoice_id },
+                doc! { "$unset": { "invoice_id": "" } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        Ok(result.modified_count)
+    }
+
+    async fn find_deleted(&self, user_id: &ObjectId) -> Result<Vec<WorkLogInDB>, RepositoryError> {
+        let mut work_logs = Vec::new();
+        let find_options = FindOptions::builder()
+            .sort(doc! { "deleted_at": -1 })
+            .build();
+        let mut cursor = self
+            .collection
+            .find(
+                doc! { "user_id": user_id, "deleted_at": { "$ne": null } },
+                find_options,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+
+        while let Some(result) = cursor
+            .try_next()
+            .await
+            .map_err(RepositoryError::DatabaseError)?
+        {
+            work_logs.push(result);
+        }
+
+        Ok(work_logs)
+    }
+
+    async fn find_deleted_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<WorkLogInDB>, RepositoryError> {
+        self.collection
+            .find_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": { "$ne": null } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)
+    }
+
+    async fn soft_delete(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<bool, RepositoryError> {
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": null, "invoice_id": null },
+                doc! { "$set": { "deleted_at": BsonDateTime::now() } },
+                None,
+            )
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        Ok(result.modified_count > 0)
+    }
+
+    async fn restore(&self, user_id: &ObjectId, id: &ObjectId) -> Result<bool, RepositoryError> {
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": id, "user_id": user_id, "deleted_at": { "$ne": null } },
+                doc! {
+                    "$unset": { "deleted_at": "" },
+                    "$set": { "updated_at": BsonDateTime::now() }
+                },
+                None,
+            )
+            .await
+            .map_err(map_write_error)?;
+        Ok(result.modified_count > 0)
+    }
+
+    async fn purge_deleted(&self, deleted_before: BsonDateTime) -> Result<u64, RepositoryError> {
+        let result = self
+            .collection
+            .delete_many(doc! { "deleted_at": { "$lte": deleted_before } }, None)
+            .await
+            .map_err(RepositoryError::DatabaseError)?;
+        Ok(result.deleted_count)
+    }
 }
@@ -1,60 +1,103 @@
 use crate::clients::aws_s3::S3Client;
+use crate::config::email_verification::UnverifiedAccountRestriction;
 use crate::errors::app_error::AppError;
 use crate::errors::repositories_error::RepositoryError;
-use crate::models::auth::AuthTokenInDB;
-use crate::models::users::{UserCreate, UserInDB, UserUpdate, UserUpdateInternal};
+use crate::i18n::messages;
+use crate::models::auth::{AuthTokenInDB, AuthTokenScope, SessionClient};
+use crate::models::users::{
+    EngineerRole, UserAuthState, UserCreate, UserInDB, UserUpdate, UserUpdateInternal,
+};
 use crate::repositories::auth::AuthRepository;
+use crate::usecases::email_verification::EmailVerifier;
+use crate::usecases::login_attempts::LoginAttemptLimiter;
 use crate::utils::jwt;
 use crate::utils::jwt::Claims;
-use crate::utils::password::{hash_password, verify_password};
+use crate::utils::password::{hash_password, verify_dummy_password, verify_password};
+use crate::utils::token::generate_random_token;
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
+use bson::oid::ObjectId;
 use bson::DateTime as BsonDateTime;
 use chrono::Utc;
 use std::sync::Arc;
 
+/// 認証トークンの最終使用日時を更新する間隔（リクエストごとの書き込みを避けるため）
+const LAST_USED_UPDATE_INTERVAL_SECS: i64 = 60;
+
 pub struct AuthUseCase<R: AuthRepository> {
     repository: Arc<R>,
     jwt_secret: Vec<u8>,
     s3_client: Arc<S3Client>,
+    login_attempts: LoginAttemptLimiter,
+    email_verifier: EmailVerifier,
 }
 
 impl<R: AuthRepository> AuthUseCase<R> {
-    pub fn new(repository: Arc<R>, jwt_secret: &[u8], s3_client: Arc<S3Client>) -> Self {
+    pub fn new(
+        repository: Arc<R>,
+        jwt_secret: &[u8],
+        s3_client: Arc<S3Client>,
+        login_attempts: LoginAttemptLimiter,
+        email_verifier: EmailVerifier,
+    ) -> Self {
         Self {
             repository,
             jwt_secret: jwt_secret.to_vec(),
             s3_client,
+            login_attempts,
+            email_verifier,
         }
     }
 
     /// ユーザーログイン処理
     ///
+    /// - ロック中でないか確認（失敗が続いている場合は応答を遅延させる）
     /// - メールアドレスでユーザーを検索
     /// - パスワードを検証
-    /// - 認証トークンを生成して保存
-    pub async fn login(&self, email: &str, password: &str) -> Result<AuthTokenInDB, AppError> {
-        let user = self
-            .repository
-            .find_user_by_email(email)
-            .await?
-            .ok_or_else(|| AppError::NotFound("ユーザーが見つかりません".to_string()))?;
+    /// - 認証トークンを生成して保存（クライアントの情報をセッションとして記録）
+    ///
+    /// ユーザーの存在を推測されないよう、ユーザーが存在しない場合とパスワードが誤っている場合は
+    /// 同じエラーを返し、同じ時間をかけてパスワードを検証する
+    pub async fn login(
+        &self,
+        email: &str,
+        password: &str,
+        client: &SessionClient,
+    ) -> Result<AuthTokenInDB, AppError> {
+        let ip = client.ip_address.as_deref();
+        self.login_attempts.before_attempt(email, ip).await?;
+
+        let user = self.repository.find_user_by_email(email).await?;
+        let verified = match &user {
+            Some(user) => verify_password(password, &user.password_hash),
+            None => {
+                verify_dummy_password(password);
+                false
+            }
+        };
+        let Some(user) = user.filter(|_| verified) else {
+            self.login_attempts.record_failure(email, ip).await?;
+            return Err(AppError::BadRequest(messages::INVALID_CREDENTIALS.into()));
+        };
+        self.login_attempts.record_success(email).await?;
 
-        if verify_password(password, &user.password_hash) {
-            let auth_token = self.create_auth_token(&user.id.unwrap().to_string())?;
-            self.repository.save_auth_token(&auth_token).await?;
-            Ok(auth_token)
-        } else {
-            Err(AppError::Forbidden("無効な認証情報です".to_string()))
-        }
+        let auth_token =
+            self.create_auth_token(&user.id.unwrap(), user.role.clone(), None, client.clone())?;
+        self.repository.save_auth_token(&auth_token).await?;
+        Ok(auth_token)
     }
 
     /// ユーザー登録処理
     ///
     /// - パスワードをハッシュ化
     /// - ユーザーを作成
+    /// - メールアドレスの確認用リンクを送信
     /// - 認証トークンを生成して保存
-    pub async fn register(&self, user_create: &UserCreate) -> Result<AuthTokenInDB, AppError> {
+    pub async fn register(
+        &self,
+        user_create: &UserCreate,
+        client: &SessionClient,
+    ) -> Result<AuthTokenInDB, AppError> {
         let password_hash = hash_password(&user_create.password)
             .map_err(|e| AppError::InternalServerError(e.to_string()))?;
         let user_id = self
@@ -65,32 +108,48 @@ impl<R: AuthRepository> AuthUseCase<R> {
                 if let RepositoryError::DuplicateError(e) = e {
                     // 他者の個人情報を推測できないようにするため、実際のエラー内容はログ出力のみとし返す文言を変更する
                     log::info!("ユーザー登録でユニーク制約違反が発生: {}", e);
-                    AppError::BadRequest(
-                        "バリデーションに失敗したか、処理中にエラーが発生しました".to_string(),
-                    )
+                    AppError::BadRequest(messages::REGISTRATION_FAILED.into())
                 } else {
                     AppError::from(e)
                 }
             })?;
 
-        let auth_token = self.create_auth_token(&user_id.to_string())?;
+        // 登録直後の表示言語は既定値のため、確認用のメールも既定の言語で送信する
+        self.email_verifier
+            .send_verification_mail(&user_id, &user_create.email, Default::default())
+            .await;
+
+        let auth_token = self.create_auth_token(&user_id, None, None, client.clone())?;
         self.repository.save_auth_token(&auth_token).await?;
         Ok(auth_token)
     }
 
     /// ログイン中のユーザー更新処理
+    ///
+    /// メールアドレスを変更する場合は、新しいメールアドレスを変更申請中として保存して確認用リンクを送信し、
+    /// 確認されるまでは現在のメールアドレスを使用する
     pub async fn update_me(
         &self,
-        access_token: &str,
+        user_id: &ObjectId,
         user_update: &UserUpdate,
     ) -> Result<bool, AppError> {
+        let current_user = self
+            .repository
+            .find_user_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::USER_NOT_FOUND.into()))?;
+        self.request_email_change(&current_user, &user_update.email)
+            .await?;
+
         // MongoDBではPUTとPATCHともに部分更新できるので、全フィールド渡さずともNoneで上書きされる事はない
         let mut user_update_internal = UserUpdateInternal {
-            email: user_update.email.clone(),
+            email: current_user.email.clone(),
             password: None,
             username: user_update.username.clone(),
             role: user_update.role.clone(),
             avatar_url: None,
+            timezone: user_update.timezone.clone(),
+            locale: user_update.locale,
         };
 
         if let Some(avatar_data) = &user_update.avatar {
@@ -98,11 +157,11 @@ impl<R: AuthRepository> AuthUseCase<R> {
             let base64_data = avatar_data
                 .split_once(",")
                 .map(|(_, data)| data)
-                .ok_or_else(|| AppError::BadRequest("Invalid base64 data format".to_string()))?;
+                .ok_or_else(|| AppError::BadRequest(messages::INVALID_BASE64_FORMAT.into()))?;
 
             let image_data = STANDARD
                 .decode(base64_data)
-                .map_err(|e| AppError::BadRequest(format!("無効なbase64データ: {}", e)))?;
+                .map_err(|e| AppError::BadRequest(messages::INVALID_BASE64.with("detail", e)))?;
 
             let new_avatar_key = self.s3_client.upload_avatar(&image_data).await?;
             let new_avatar_url = self.s3_client.get_public_url(&new_avatar_key);
@@ -119,18 +178,120 @@ impl<R: AuthRepository> AuthUseCase<R> {
         // ユーザー情報を更新
         Ok(self
             .repository
-            .update_user_by_access_token(access_token, &user_update_internal)
+            .update_user_by_id(user_id, &user_update_internal)
             .await?)
     }
 
+    /// メールアドレスの変更を申請する
+    ///
+    /// - 現在のメールアドレスと同じ場合は、変更の申請を取り消す
+    /// - 他のユーザーが使用しているメールアドレスには変更できない
+    /// - 変更を申請中のメールアドレスと同じ場合は、確認用リンクを再送信しない
+    async fn request_email_change(&self, user: &UserInDB, email: &str) -> Result<(), AppError> {
+        let user_id = user.id.ok_or_else(|| {
+            AppError::InternalServerError("ユーザーIDが設定されていません".to_string())
+        })?;
+
+        if user.email == email {
+            if user.pending_email.is_some() {
+                self.repository.update_pending_email(&user_id, None).await?;
+            }
+            return Ok(());
+        }
+        if user.pending_email.as_deref() == Some(email) {
+            return Ok(());
+        }
+        if self.repository.find_user_by_email(email).await?.is_some() {
+            return Err(AppError::DuplicateError(
+                messages::EMAIL_ALREADY_USED.into(),
+            ));
+        }
+
+        self.repository
+            .update_pending_email(&user_id, Some(email))
+            .await?;
+        self.email_verifier
+            .send_verification_mail(&user_id, email, user.locale())
+            .await;
+        Ok(())
+    }
+
+    /// 確認用リンクのトークンでメールアドレスを確認する
+    ///
+    /// - 現在のメールアドレスのトークンの場合は、確認済みにする（確認済みの場合は使用済みのリンクとする）
+    /// - 変更を申請中のメールアドレスのトークンの場合は、メールアドレスを変更する
+    /// - 確認後にメールアドレスが変更・再申請された場合など、いずれにも一致しない場合は無効なリンクとする
+    pub async fn verify_email(&self, token: &str) -> Result<(), AppError> {
+        let invalid_link =
+            || AppError::BadRequest(messages::INVALID_OR_EXPIRED_VERIFICATION_LINK.into());
+
+        let claims = self.email_verifier.verify_token(token)?;
+        let user_id = ObjectId::parse_str(&claims.sub).map_err(|_| invalid_link())?;
+        let user = self
+            .repository
+            .find_user_by_id(&user_id)
+            .await?
+            .ok_or_else(invalid_link)?;
+
+        let verified = if user.email == claims.email {
+            self.repository
+                .mark_email_verified(&user_id, &claims.email)
+                .await?
+        } else if user.pending_email.as_deref() == Some(claims.email.as_str()) {
+            let confirmed = self
+                .repository
+                .confirm_pending_email(&user_id, &claims.email)
+                .await?;
+            if confirmed {
+                log::info!("メールアドレスの変更を確認しました: user_id={}", user_id);
+            }
+            confirmed
+        } else {
+            false
+        };
+
+        if verified {
+            Ok(())
+        } else {
+            Err(invalid_link())
+        }
+    }
+
+    /// 確認用リンクを再送信する（変更を申請中の場合は新しいメールアドレスに送信する）
+    pub async fn resend_verification_email(&self, user_id: &ObjectId) -> Result<(), AppError> {
+        let user = self
+            .repository
+            .find_user_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::USER_NOT_FOUND.into()))?;
+
+        let email = match (&user.pending_email, user.email_verified_at) {
+            (Some(pending_email), _) => pending_email,
+            (None, None) => &user.email,
+            (None, Some(_)) => {
+                return Err(AppError::BadRequest(
+                    messages::EMAIL_ALREADY_VERIFIED.into(),
+                ))
+            }
+        };
+        self.email_verifier
+            .send_verification_mail(user_id, email, user.locale())
+            .await;
+        Ok(())
+    }
+
+    /// メールアドレスが未確認のユーザーに適用する制限を取得する
+    pub fn unverified_restriction(&self, user: &UserAuthState) -> UnverifiedAccountRestriction {
+        self.email_verifier.restriction_for(user)
+    }
+
     /// ログイン中のユーザー情報を取得
-    pub async fn get_current_user(&self, access_token: &str) -> Result<UserInDB, AppError> {
-        // アクセストークンからユーザー情報を直接取得
+    pub async fn get_current_user(&self, user_id: &ObjectId) -> Result<UserInDB, AppError> {
         let mut user = self
             .repository
-            .find_user_by_access_token(access_token)
+            .find_user_by_id(user_id)
             .await?
-            .ok_or_else(|| AppError::NotFound("ユーザーが見つかりません".to_string()))?;
+            .ok_or_else(|| AppError::NotFound(messages::USER_NOT_FOUND.into()))?;
 
         if let Some(avatar_url) = &user.avatar_url {
             let public_url = self.s3_client.get_public_url(avatar_url);
@@ -140,55 +301,159 @@ impl<R: AuthRepository> AuthUseCase<R> {
         Ok(user)
     }
 
+    /// カレンダーフィードのトークンを発行する（発行済みの場合は再発行し、以前のURLは無効になる）
+    pub async fn rotate_calendar_feed_token(&self, user_id: &ObjectId) -> Result<String, AppError> {
+        let token = generate_random_token();
+        if !self
+            .repository
+            .update_calendar_feed_token(user_id, Some(&token))
+            .await?
+        {
+            return Err(AppError::NotFound(messages::USER_NOT_FOUND.into()));
+        }
+        Ok(token)
+    }
+
+    /// カレンダーフィードを無効化する
+    pub async fn disable_calendar_feed(&self, user_id: &ObjectId) -> Result<(), AppError> {
+        if !self
+            .repository
+            .update_calendar_feed_token(user_id, None)
+            .await?
+        {
+            return Err(AppError::NotFound(messages::USER_NOT_FOUND.into()));
+        }
+        Ok(())
+    }
+
+    /// カレンダーフィードのトークンからユーザーを取得する
+    pub async fn find_user_by_calendar_feed_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<UserInDB>, AppError> {
+        Ok(self
+            .repository
+            .find_user_by_calendar_feed_token(token)
+            .await?)
+    }
+
     /// ユーザーログアウト処理
     ///
     /// - アクセストークンとリフレッシュトークンを削除
-    pub async fn logout(&self, auth_header: &str) -> Result<(), AppError> {
-        let token = jwt::extract_token(auth_header);
-
-        // アクセストークンをキーに削除
-        let result = self.repository.delete_auth_tokens(&token).await?;
+    pub async fn logout(&self, token_id: &ObjectId) -> Result<(), AppError> {
+        // トークンIDをキーに削除
+        let deleted_count = self
+            .repository
+            .delete_auth_tokens(&AuthTokenScope::Token(*token_id))
+            .await?;
 
-        if result {
+        if deleted_count > 0 {
             Ok(())
         } else {
-            Err(AppError::NotFound("トークンが見つかりません".to_string()))
+            Err(AppError::NotFound(messages::TOKEN_NOT_FOUND.into()))
         }
     }
 
     /// アクセストークンの有効期限を検証
-    pub async fn verify_access_token(&self, access_token: &str) -> Result<Claims, AppError> {
+    ///
+    /// 認証ミドルウェアがリクエストごとに使用するため、ユーザーのタイムゾーンなどの設定・状態も
+    /// トークンと同時に取得して返す（ユーザー情報を改めて取得しない）
+    pub async fn verify_access_token(
+        &self,
+        access_token: &str,
+    ) -> Result<(Claims, UserAuthState), AppError> {
         let claims = jwt::verify_token(access_token, &self.jwt_secret)
-            .map_err(|_| AppError::Unauthorized("無効なアクセストークンです".to_string()))?;
+            .map_err(|_| AppError::Unauthorized(messages::INVALID_ACCESS_TOKEN.into()))?;
 
-        // DBからトークンを取得
-        let auth_token = self
+        // DBからトークンとユーザーの設定・状態を取得
+        let (auth_token, user) = self
             .repository
-            .find_auth_token(access_token)
+            .find_auth_token_with_user(access_token)
             .await
             .map_err(|_| {
                 AppError::InternalServerError("アクセストークンの検証に失敗しました".to_string())
             })?
-            .ok_or_else(|| {
-                AppError::Unauthorized("アクセストークンが見つかりません".to_string())
-            })?;
+            .ok_or_else(|| AppError::Unauthorized(messages::ACCESS_TOKEN_NOT_FOUND.into()))?;
 
         // 現在時刻と有効期限を比較
         if Utc::now() > auth_token.expires_at.into() {
             return Err(AppError::Unauthorized(
-                "アクセストークンの有効期限が切れています".to_string(),
+                messages::ACCESS_TOKEN_EXPIRED.into(),
             ));
         }
 
-        Ok(claims)
+        // セッション一覧に表示する最終使用日時を更新
+        let now = Utc::now();
+        let needs_update = auth_token.last_used_at.is_none_or(|last_used_at| {
+            (now - last_used_at.to_chrono()).num_seconds() >= LAST_USED_UPDATE_INTERVAL_SECS
+        });
+        if let (true, Some(token_id)) = (needs_update, auth_token.id) {
+            self.repository
+                .update_auth_token_last_used(&token_id, BsonDateTime::from_chrono(now))
+                .await?;
+        }
+
+        Ok((claims, user))
+    }
+
+    /// ログイン中のユーザーの有効なセッション（ログインごとの認証トークンのファミリー）の一覧を取得
+    pub async fn get_sessions(&self, user_id: &ObjectId) -> Result<Vec<AuthTokenInDB>, AppError> {
+        Ok(self.repository.find_active_auth_tokens(user_id).await?)
+    }
+
+    /// ログイン中のユーザーの指定したセッションを失効させる（他の端末からのログアウト）
+    pub async fn revoke_session(
+        &self,
+        user_id: &ObjectId,
+        session_id: &ObjectId,
+    ) -> Result<(), AppError> {
+        let deleted_count = self
+            .repository
+            .delete_auth_tokens(&AuthTokenScope::Session {
+                user_id: *user_id,
+                family_id: *session_id,
+            })
+            .await?;
+
+        if deleted_count > 0 {
+            Ok(())
+        } else {
+            Err(AppError::NotFound(messages::SESSION_NOT_FOUND.into()))
+        }
     }
 
-    /// リフレッシュトークンの有効期限を検証
-    pub async fn verify_refresh_token(&self, refresh_token: &str) -> Result<Claims, AppError> {
-        let claims = jwt::verify_token(refresh_token, &self.jwt_secret)
-            .map_err(|_| AppError::BadRequest("無効なリクエストです".to_string()))?; // あえて曖昧なエラーメッセージを返す
+    /// 現在のセッション以外のすべてのセッションを失効させ、失効させた認証トークンの件数を返す
+    pub async fn revoke_other_sessions(
+        &self,
+        user_id: &ObjectId,
+        token_id: &ObjectId,
+    ) -> Result<u64, AppError> {
+        let current_token = self
+            .repository
+            .find_auth_token_by_id(token_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::TOKEN_NOT_FOUND.into()))?;
+
+        Ok(self
+            .repository
+            .delete_auth_tokens(&AuthTokenScope::OtherSessions {
+                user_id: *user_id,
+                family_id: current_token.family_id,
+            })
+            .await?)
+    }
+
+    /// リフレッシュトークンを検証し、対応する認証トークンを返す
+    ///
+    /// ローテーション済みのリフレッシュトークンが使われた場合は、トークンが盗まれた可能性があるため
+    /// 同じファミリーの認証トークンをすべて失効させ、再ログインを求める
+    pub async fn verify_refresh_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<AuthTokenInDB, AppError> {
+        jwt::verify_token(refresh_token, &self.jwt_secret)
+            .map_err(|_| AppError::BadRequest(messages::INVALID_REQUEST.into()))?; // あえて曖昧なエラーメッセージを返す
 
-        log::info!("refresh_token: {}", refresh_token);
         // DBからリフレッシュトークンを取得
         let auth_token = self
             .repository
@@ -203,70 +468,124 @@ impl<R: AuthRepository> AuthUseCase<R> {
                 // エラー内容はログ出力のみとし返す文言を変更する
             })?
             .ok_or_else(|| {
-                AppError::BadRequest("無効なリクエストです".to_string()) // あえて曖昧なエラーメッセージを返す
+                AppError::BadRequest(messages::INVALID_REQUEST.into()) // あえて曖昧なエラーメッセージを返す
             })?;
 
+        // ローテーション済みのリフレッシュトークンの再利用を検知
+        if auth_token.rotated_at.is_some() {
+            self.revoke_token_family(&auth_token).await?;
+            return Err(AppError::BadRequest(messages::INVALID_REQUEST.into()));
+            // あえて曖昧なエラーメッセージを返す
+        }
+
         // リフレッシュトークンの有効期限を比較
         if Utc::now() > auth_token.refresh_expires_at.into() {
+            // トークンの値はログに出力しない（ログから再利用されないようにする）
             log::error!(
-                "リフレッシュトークンの有効期限が切れています: {}",
-                refresh_token
+                "リフレッシュトークンの有効期限が切れています: token_id={:?}, family_id={}",
+                auth_token.id,
+                auth_token.family_id
             );
-            return Err(AppError::BadRequest("無効なリクエストです".to_string()));
+            return Err(AppError::BadRequest(messages::INVALID_REQUEST.into()));
             // あえて曖昧なエラーメッセージを返す
         }
 
-        Ok(claims)
+        Ok(auth_token)
     }
 
     /// トークンのリフレッシュ処理
     ///
     /// - リフレッシュトークンを検証
-    /// - 新しい認証トークンを生成して保存
-    pub async fn refresh_token(&self, refresh_token: &str) -> Result<AuthTokenInDB, AppError> {
-        let claims = self.verify_refresh_token(refresh_token).await?;
-
-        // リフレッシュ専用の関数を使用
-        let (new_access_token, new_expires_at) =
-            jwt::create_refreshed_access_token(&claims.sub, &self.jwt_secret)
-                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    /// - 使用したリフレッシュトークンをローテーション済みにする
+    /// - 同じファミリーの新しい認証トークン（アクセストークン・リフレッシュトークン）を生成して保存
+    ///
+    /// クライアントの情報を取得できない場合は、ローテーション前のトークンの値を引き継ぐ
+    pub async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        client: &SessionClient,
+    ) -> Result<AuthTokenInDB, AppError> {
+        let auth_token = self.verify_refresh_token(refresh_token).await?;
+        let token_id = auth_token.id.ok_or_else(|| {
+            AppError::InternalServerError("認証トークンのIDが設定されていません".to_string())
+        })?;
+
+        // 同じリフレッシュトークンで先にローテーションされていた場合も再利用とみなす
+        if !self.repository.mark_auth_token_rotated(&token_id).await? {
+            self.revoke_token_family(&auth_token).await?;
+            return Err(AppError::BadRequest(messages::INVALID_REQUEST.into()));
+            // あえて曖昧なエラーメッセージを返す
+        }
 
-        // 既存のAuthTokenInDBを取得
-        let mut auth_token = self
+        // ロール変更を反映するため、最新のユーザー情報からロールを取得する
+        let role = self
             .repository
-            .find_by_refresh_token(refresh_token)
+            .find_user_by_id(&auth_token.user_id)
             .await?
-            .ok_or_else(|| {
-                log::error!("リフレッシュトークンが見つかりません: {}", refresh_token);
-                AppError::BadRequest("無効なリクエストです".to_string()) // あえて曖昧なエラーメッセージを返す
-            })?;
+            .ok_or_else(|| AppError::BadRequest(messages::INVALID_REQUEST.into()))?
+            .role;
 
-        // 更新内容を設定
-        auth_token.access_token = new_access_token;
-        auth_token.expires_at = new_expires_at;
-        auth_token.updated_at = Some(BsonDateTime::now());
-
-        // DBを更新
-        self.repository.update_auth_token(&auth_token).await?;
+        let client = SessionClient {
+            user_agent: client.user_agent.clone().or(auth_token.user_agent),
+            ip_address: client.ip_address.clone().or(auth_token.ip_address),
+        };
+        let new_auth_token = self.create_auth_token(
+            &auth_token.user_id,
+            role,
+            Some(auth_token.family_id),
+            client,
+        )?;
+        self.repository.save_auth_token(&new_auth_token).await?;
+
+        Ok(new_auth_token)
+    }
 
-        Ok(auth_token)
+    /// 同じファミリーの認証トークンをすべて失効させる（リフレッシュトークンの再利用を検知した場合）
+    async fn revoke_token_family(&self, auth_token: &AuthTokenInDB) -> Result<(), AppError> {
+        let revoked_count = self
+            .repository
+            .delete_auth_token_family(&auth_token.family_id)
+            .await?;
+        log::warn!(
+            "ローテーション済みのリフレッシュトークンが再利用されたため、トークンファミリーを失効させました: user_id={}, family_id={}, 失効数={}",
+            auth_token.user_id,
+            auth_token.family_id,
+            revoked_count
+        );
+        Ok(())
     }
 
     /// 認証トークンを生成
-    fn create_auth_token(&self, user_id: &str) -> Result<AuthTokenInDB, AppError> {
+    ///
+    /// ドキュメントIDを事前に採番し、トークンのjtiとして埋め込む。
+    /// ファミリーIDはリフレッシュ時には引き継ぎ、ログイン時（未指定の場合）はドキュメントIDと同じ値とする
+    fn create_auth_token(
+        &self,
+        user_id: &ObjectId,
+        role: Option<EngineerRole>,
+        family_id: Option<ObjectId>,
+        client: SessionClient,
+    ) -> Result<AuthTokenInDB, AppError> {
+        let id = ObjectId::new();
         let (access_token, refresh_token, expires_at, refresh_expires_at) =
-            jwt::create_token_pair(user_id, &self.jwt_secret)
+            jwt::create_token_pair(&user_id.to_hex(), &id.to_hex(), role, &self.jwt_secret)
                 .map_err(|e| AppError::InternalServerError(e.to_string()))?;
 
+        let now = BsonDateTime::now();
         Ok(AuthTokenInDB {
-            id: None,
-            user_id: user_id.parse().unwrap(),
+            id: Some(id),
+            user_id: *user_id,
+            family_id: family_id.unwrap_or(id),
             access_token,
             refresh_token,
             expires_at,
             refresh_expires_at,
-            created_at: BsonDateTime::now(),
+            created_at: now,
             updated_at: None,
+            rotated_at: None,
+            user_agent: client.user_agent,
+            ip_address: client.ip_address,
+            last_used_at: Some(now),
         })
     }
 }
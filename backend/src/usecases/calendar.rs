@@ -0,0 +1,111 @@
+use crate::errors::app_error::AppError;
+use crate::i18n::messages;
+use crate::models::projects::ProjectInDB;
+use crate::models::work_logs::WorkLogFilter;
+use crate::repositories::auth::MongoAuthRepository;
+use crate::repositories::companies::MongoCompanyRepository;
+use crate::repositories::projects::MongoProjectRepository;
+use crate::repositories::work_logs::MongoWorkLogRepository;
+use crate::usecases::auth::AuthUseCase;
+use crate::usecases::companies::CompanyUseCase;
+use crate::usecases::projects::ProjectUseCase;
+use crate::usecases::work_logs::WorkLogUseCase;
+use crate::utils::ical::ICalendarBuilder;
+use bson::oid::ObjectId;
+use futures::stream::TryStreamExt;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::try_join;
+
+/// 予定のUIDのドメイン部分
+const UID_DOMAIN: &str = "devtrackr";
+
+pub struct CalendarUseCase {
+    auth_usecase: Arc<AuthUseCase<MongoAuthRepository>>,
+    company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+    project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+}
+
+impl CalendarUseCase {
+    pub fn new(
+        auth_usecase: Arc<AuthUseCase<MongoAuthRepository>>,
+        company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+        project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+        work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+    ) -> Self {
+        Self {
+            auth_usecase,
+            company_usecase,
+            project_usecase,
+            work_logs_usecase,
+        }
+    }
+
+    /// カレンダーフィード（iCalendar形式）を生成する
+    ///
+    /// - 勤怠はプロジェクト名を件名、メモを説明とした予定にする（作業中の勤怠は含めない）
+    /// - 企業の契約開始日・契約終了日は終日の予定にする
+    pub async fn render_feed(&self, token: &str) -> Result<String, AppError> {
+        let user = self
+            .auth_usecase
+            .find_user_by_calendar_feed_token(token)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::CALENDAR_FEED_NOT_FOUND.into()))?;
+        let user_id = user
+            .id
+            .ok_or_else(|| AppError::InternalServerError("ユーザーIDが存在しません".to_string()))?;
+
+        let filter = WorkLogFilter::default();
+        let (companies, projects, mut work_logs) = try_join!(
+            self.company_usecase.get_all_companies(&user_id, None, None),
+            self.project_usecase
+                .search_projects(&user_id, None, None, None, vec![]),
+            self.work_logs_usecase.stream_work_logs(&user_id, &filter),
+        )?;
+        let projects: HashMap<ObjectId, ProjectInDB> = projects
+            .items
+            .into_iter()
+            .filter_map(|project| Some((project.id?, project)))
+            .collect();
+
+        let mut calendar = ICalendarBuilder::new("DevTrackr");
+
+        while let Some(work_log) = work_logs.try_next().await? {
+            let (Some(id), Some(end_time)) = (work_log.id, work_log.end_time) else {
+                continue;
+            };
+            let summary = projects
+                .get(&work_log.project_id)
+                .map_or("勤怠", |project| project.title.as_str());
+            calendar.add_event(
+                &format!("work-log-{}@{}", id, UID_DOMAIN),
+                work_log.start_time,
+                end_time,
+                summary,
+                work_log.memo.as_deref(),
+            );
+        }
+
+        for company in &companies.items {
+            let Some(id) = company.id else {
+                continue;
+            };
+            let company_name = &company.common.company_name;
+            calendar.add_all_day_event(
+                &format!("company-{}-start@{}", id, UID_DOMAIN),
+                company.affiliation_start_date,
+                &format!("{} 契約開始", company_name),
+            );
+            if let Some(end_date) = company.affiliation_end_date {
+                calendar.add_all_day_event(
+                    &format!("company-{}-end@{}", id, UID_DOMAIN),
+                    end_date,
+                    &format!("{} 契約終了", company_name),
+                );
+            }
+        }
+
+        Ok(calendar.finish())
+    }
+}
@@ -1,10 +1,13 @@
 use crate::errors::app_error::AppError;
+use crate::i18n::messages;
 use crate::models::companies::{
     CompanyCreate, CompanyInDB, CompanyUpdate, CompanyWithProjectsInDB,
 };
 use crate::repositories::companies::CompanyRepository;
-use bson::oid::ObjectId;
+use crate::utils::pagination::{fetch_limit, Page, PageCursor};
+use bson::{oid::ObjectId, DateTime as BsonDateTime};
 use std::sync::Arc;
+use tokio::try_join;
 
 pub struct CompanyUseCase<R: CompanyRepository> {
     repository: Arc<R>,
@@ -15,36 +18,104 @@ impl<R: CompanyRepository> CompanyUseCase<R> {
         Self { repository }
     }
 
-    pub async fn get_all_companies(&self) -> Result<Vec<CompanyInDB>, AppError> {
-        Ok(self.repository.find_all().await?)
+    /// 企業を作成順に1ページ分取得する
+    pub async fn get_all_companies(
+        &self,
+        user_id: &ObjectId,
+        limit: Option<i64>,
+        cursor: Option<PageCursor>,
+    ) -> Result<Page<CompanyInDB>, AppError> {
+        let (companies, total) = try_join!(
+            self.repository
+                .find_many(user_id, fetch_limit(limit), cursor.as_ref()),
+            self.repository.count(user_id),
+        )?;
+        Page::from_fetched(companies, total, limit, &[])
     }
 
     pub async fn get_all_companies_with_projects(
         &self,
+        user_id: &ObjectId,
     ) -> Result<Vec<CompanyWithProjectsInDB>, AppError> {
-        Ok(self.repository.find_all_with_projects().await?)
+        Ok(self.repository.find_all_with_projects(user_id).await?)
     }
 
-    pub async fn get_company_by_id(&self, id: &ObjectId) -> Result<Option<CompanyInDB>, AppError> {
-        Ok(self.repository.find_by_id(id).await?)
+    pub async fn get_company_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<CompanyInDB>, AppError> {
+        Ok(self.repository.find_by_id(user_id, id).await?)
     }
 
-    pub async fn create_company(&self, company: CompanyCreate) -> Result<ObjectId, AppError> {
-        Ok(self.repository.insert_one(company).await?)
+    pub async fn create_company(
+        &self,
+        user_id: &ObjectId,
+        company: CompanyCreate,
+    ) -> Result<ObjectId, AppError> {
+        Ok(self.repository.insert_one(user_id, company).await?)
     }
 
     pub async fn update_company_by_id(
         &self,
+        user_id: &ObjectId,
         id: &ObjectId,
         company: &CompanyUpdate,
     ) -> Result<bool, AppError> {
-        // 既存のドキュメントが存在するか確認
-        if self.repository.find_by_id(id).await?.is_none() {
+        // 既存のドキュメントが存在するか確認（他ユーザーの企業は存在しないものとして扱う）
+        if self.repository.find_by_id(user_id, id).await?.is_none() {
+            return Err(AppError::NotFound(
+                messages::COMPANY_TO_UPDATE_NOT_FOUND.into(),
+            ));
+        }
+
+        Ok(self.repository.update_one(user_id, *id, company).await?)
+    }
+
+    pub async fn get_deleted_companies(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<Vec<CompanyInDB>, AppError> {
+        Ok(self.repository.find_deleted(user_id).await?)
+    }
+
+    /// 企業を論理削除する（紐づくプロジェクトと勤怠もゴミ箱に移動する）
+    ///
+    /// 請求書に含まれる勤怠がある場合は、請求内容と食い違わないよう削除できない
+    pub async fn delete_company(&self, user_id: &ObjectId, id: &ObjectId) -> Result<(), AppError> {
+        let invoiced_ids = self
+            .repository
+            .find_invoiced_work_log_ids(user_id, id)
+            .await?;
+        if !invoiced_ids.is_empty() {
+            return Err(AppError::Conflict(
+                messages::INVOICED_WORK_LOGS_EXIST.into(),
+                invoiced_ids,
+            ));
+        }
+        if !self.repository.soft_delete(user_id, id).await? {
             return Err(AppError::NotFound(
-                "更新対象の企業が見つかりません".to_string(),
+                messages::COMPANY_TO_DELETE_NOT_FOUND.into(),
             ));
         }
+        Ok(())
+    }
 
-        Ok(self.repository.update_one(*id, company).await?)
+    /// ゴミ箱から企業を復元する（同時に削除されたプロジェクトと勤怠も復元する）
+    pub async fn restore_company(&self, user_id: &ObjectId, id: &ObjectId) -> Result<(), AppError> {
+        if !self.repository.restore(user_id, id).await? {
+            return Err(AppError::NotFound(
+                messages::COMPANY_TO_RESTORE_NOT_FOUND.into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 削除日時が指定日時以前の企業を物理削除する
+    pub async fn purge_deleted_companies(
+        &self,
+        deleted_before: BsonDateTime,
+    ) -> Result<u64, AppError> {
+        Ok(self.repository.purge_deleted(deleted_before).await?)
     }
 }
@@ -0,0 +1,89 @@
+use crate::clients::mailer::{Mail, Mailer};
+use crate::config::email_verification::{EmailVerificationConfig, UnverifiedAccountRestriction};
+use crate::errors::app_error::AppError;
+use crate::i18n::{messages, Message};
+use crate::models::users::{Locale, UserAuthState};
+use crate::utils::jwt::{self, EmailVerificationClaims};
+use bson::oid::ObjectId;
+use chrono::Utc;
+use std::sync::Arc;
+
+/// 署名付きの確認用リンクをメールで送信し、メールアドレスの所有を確認する
+///
+/// 確認用のトークンはDBに保存せず、ユーザーIDと確認するメールアドレスを署名して埋め込む。
+/// 確認後やメールアドレスの再変更後は、埋め込んだメールアドレスがユーザー情報と一致しなくなるため使用できない
+pub struct EmailVerifier {
+    mailer: Arc<dyn Mailer>,
+    jwt_secret: Vec<u8>,
+    config: EmailVerificationConfig,
+}
+
+impl EmailVerifier {
+    pub fn new(
+        mailer: Arc<dyn Mailer>,
+        jwt_secret: &[u8],
+        config: EmailVerificationConfig,
+    ) -> Self {
+        Self {
+            mailer,
+            jwt_secret: jwt_secret.to_vec(),
+            config,
+        }
+    }
+
+    /// 確認用リンクをメールで送信する（送信に失敗した場合は再送信できるため、ログ出力のみとする）
+    pub async fn send_verification_mail(&self, user_id: &ObjectId, email: &str, locale: Locale) {
+        let token = match jwt::create_email_verification_token(
+            &user_id.to_hex(),
+            email,
+            self.config.token_ttl,
+            &self.jwt_secret,
+        ) {
+            Ok(token) => token,
+            Err(e) => {
+                log::error!(
+                    "メールアドレスの確認用トークンの生成に失敗しました: user_id={}, error={}",
+                    user_id,
+                    e
+                );
+                return;
+            }
+        };
+
+        let url = format!("{}?token={}", self.config.verify_url, token);
+        let mail = Mail {
+            to: email.to_string(),
+            subject: Message::from(messages::EMAIL_VERIFICATION_MAIL_SUBJECT).localize(locale),
+            body: messages::EMAIL_VERIFICATION_MAIL_BODY
+                .with("url", url)
+                .with("hours", self.config.token_ttl.as_secs() / 3600)
+                .localize(locale),
+        };
+        if let Err(e) = self.mailer.send(&mail).await {
+            log::error!(
+                "メールアドレスの確認用メールの送信に失敗しました: user_id={}, error={}",
+                user_id,
+                e
+            );
+        }
+    }
+
+    /// 確認用のトークンを検証し、ユーザーIDと確認するメールアドレスを返す
+    pub fn verify_token(&self, token: &str) -> Result<EmailVerificationClaims, AppError> {
+        jwt::verify_email_verification_token(token, &self.jwt_secret).map_err(|_| {
+            AppError::BadRequest(messages::INVALID_OR_EXPIRED_VERIFICATION_LINK.into())
+        })
+    }
+
+    /// ユーザーに適用する制限（確認済み、または登録から猶予期間内の場合は制限しない）
+    pub fn restriction_for(&self, user: &UserAuthState) -> UnverifiedAccountRestriction {
+        if user.email_verified_at.is_some() {
+            return UnverifiedAccountRestriction::None;
+        }
+        let elapsed = Utc::now() - user.created_at.to_chrono();
+        if elapsed.to_std().unwrap_or_default() < self.config.grace_period {
+            return UnverifiedAccountRestriction::None;
+        }
+        self.config.restriction
+    }
+}
@@ -0,0 +1,122 @@
+use crate::errors::app_error::AppError;
+use crate::models::companies::CompanyInDB;
+use crate::models::exports::{WorkLogExportQuery, WorkLogExportRow};
+use crate::models::projects::ProjectInDB;
+use crate::models::reports::calculate_work_earnings;
+use crate::models::work_logs::{WorkLogFilter, WorkLogInDB};
+use crate::repositories::companies::MongoCompanyRepository;
+use crate::repositories::projects::MongoProjectRepository;
+use crate::repositories::work_logs::MongoWorkLogRepository;
+use crate::usecases::companies::CompanyUseCase;
+use crate::usecases::projects::ProjectUseCase;
+use crate::usecases::reports::{resolve_hourly_rate, start_of_day};
+use crate::usecases::work_logs::WorkLogUseCase;
+use bson::{oid::ObjectId, DateTime as BsonDateTime};
+use chrono::Duration;
+use chrono_tz::Tz;
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::try_join;
+
+pub struct ExportUseCase {
+    company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+    project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+}
+
+impl ExportUseCase {
+    pub fn new(
+        company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+        project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+        work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+    ) -> Self {
+        Self {
+            company_usecase,
+            project_usecase,
+            work_logs_usecase,
+        }
+    }
+
+    /// 期間内の勤怠をエクスポート用の行に変換して開始時間の古い順に取得する
+    ///
+    /// プロジェクト・企業は事前に読み込み、勤怠はカーソルから1件ずつ変換する。
+    /// 金額は収入レポートと同じ時給（プロジェクトの時給、未設定の場合は企業の平均時給）で計算する。
+    /// 期間の区切りと日時の表示はユーザーのタイムゾーンで行う。
+    pub async fn export_work_logs(
+        &self,
+        user_id: &ObjectId,
+        query: &WorkLogExportQuery,
+        time_zone: Tz,
+    ) -> Result<BoxStream<'static, Result<WorkLogExportRow, AppError>>, AppError> {
+        let filter = WorkLogFilter {
+            project_id: query.project_id,
+            company_id: query.company_id,
+            from: Some(start_of_day(query.from, time_zone)),
+            to: Some(start_of_day(query.to + Duration::days(1), time_zone)),
+            ..Default::default()
+        };
+        let (companies, projects, work_logs) = try_join!(
+            self.company_usecase.get_all_companies(user_id, None, None),
+            self.project_usecase
+                .search_projects(user_id, None, None, None, vec![]),
+            self.work_logs_usecase.stream_work_logs(user_id, &filter),
+        )?;
+
+        let companies: HashMap<ObjectId, CompanyInDB> = companies
+            .items
+            .into_iter()
+            .filter_map(|company| Some((company.id?, company)))
+            .collect();
+        let projects: HashMap<ObjectId, ProjectInDB> = projects
+            .items
+            .into_iter()
+            .filter_map(|project| Some((project.id?, project)))
+            .collect();
+
+        Ok(work_logs
+            .map_ok(move |work_log| {
+                let project = projects.get(&work_log.project_id);
+                let company = project.and_then(|project| companies.get(&project.company_id));
+                to_export_row(work_log, project, company, time_zone)
+            })
+            .boxed())
+    }
+}
+
+fn to_export_row(
+    work_log: WorkLogInDB,
+    project: Option<&ProjectInDB>,
+    company: Option<&CompanyInDB>,
+    time_zone: Tz,
+) -> WorkLogExportRow {
+    let hourly_rate = resolve_hourly_rate(project, company);
+    let amount = work_log
+        .actual_work_minutes
+        .zip(hourly_rate)
+        .map(|(minutes, rate)| calculate_work_earnings(minutes as i64, rate));
+
+    WorkLogExportRow {
+        project_title: project.map_or_else(String::new, |project| project.title.clone()),
+        company_name: company
+            .map_or_else(String::new, |company| company.common.company_name.clone()),
+        start_time: format_local_date_time(work_log.start_time, time_zone),
+        end_time: work_log
+            .end_time
+            .map(|end_time| format_local_date_time(end_time, time_zone)),
+        break_time: work_log.break_time.unwrap_or(0),
+        actual_work_minutes: work_log.actual_work_minutes,
+        hourly_rate,
+        amount,
+        memo: work_log.memo.unwrap_or_default(),
+    }
+}
+
+/// 日時を指定したタイムゾーンの「YYYY-MM-DD HH:MM」形式に変換する
+fn format_local_date_time(date_time: BsonDateTime, time_zone: Tz) -> String {
+    date_time
+        .to_chrono()
+        .with_timezone(&time_zone)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
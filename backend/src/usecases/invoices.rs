@@ -0,0 +1,294 @@
+use crate::errors::app_error::AppError;
+use crate::i18n::messages;
+use crate::models::companies::{CompanyInDB, ContractType};
+use crate::models::invoices::{
+    calculate_tax_amount, format_invoice_number, InvoiceCreate, InvoiceFilter, InvoiceInDB,
+    InvoiceLineItem, InvoiceStatus,
+};
+use crate::models::projects::{ProjectFilter, ProjectInDB};
+use crate::models::reports::calculate_work_earnings;
+use crate::models::work_logs::{WorkLogFilter, WorkLogInDB};
+use crate::repositories::companies::MongoCompanyRepository;
+use crate::repositories::invoices::InvoiceRepository;
+use crate::repositories::projects::MongoProjectRepository;
+use crate::repositories::work_logs::MongoWorkLogRepository;
+use crate::usecases::companies::CompanyUseCase;
+use crate::usecases::projects::ProjectUseCase;
+use crate::usecases::reports::{resolve_hourly_rate, start_of_day};
+use crate::usecases::work_logs::WorkLogUseCase;
+use crate::utils::pagination::{fetch_limit, Page, PageCursor};
+use bson::{oid::ObjectId, DateTime as BsonDateTime};
+use chrono::{Duration, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::try_join;
+
+pub struct InvoiceUseCase<R: InvoiceRepository> {
+    repository: Arc<R>,
+    company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+    project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+}
+
+impl<R: InvoiceRepository> InvoiceUseCase<R> {
+    pub fn new(
+        repository: Arc<R>,
+        company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+        project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+        work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+    ) -> Self {
+        Self {
+            repository,
+            company_usecase,
+            project_usecase,
+            work_logs_usecase,
+        }
+    }
+
+    /// 請求書を作成順に1ページ分取得する
+    pub async fn search_invoices(
+        &self,
+        user_id: &ObjectId,
+        filter: &InvoiceFilter,
+        limit: Option<i64>,
+        cursor: Option<PageCursor>,
+    ) -> Result<Page<InvoiceInDB>, AppError> {
+        let (invoices, total) = try_join!(
+            self.repository
+                .find_many(user_id, filter, fetch_limit(limit), cursor.as_ref()),
+            self.repository.count_many(user_id, filter),
+        )?;
+        Page::from_fetched(invoices, total, limit, &[])
+    }
+
+    pub async fn get_invoice_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<InvoiceInDB>, AppError> {
+        Ok(self.repository.find_by_id(user_id, id).await?)
+    }
+
+    /// 企業・請求期間の勤怠から請求書を作成する
+    ///
+    /// - 請求対象は期間内（ユーザーのタイムゾーン）に開始した終了済みの勤怠のうち、他の請求書に含まれていないもの
+    /// - 明細はプロジェクトごとにまとめ、金額は `実作業時間の合計 × 時給` で計算する（時給は収入レポートと同じ）
+    /// - 請求対象の勤怠は請求書IDを設定してロックし、請求書の削除まで編集できないようにする
+    pub async fn create_invoice(
+        &self,
+        user_id: &ObjectId,
+        invoice: &InvoiceCreate,
+        time_zone: Tz,
+    ) -> Result<ObjectId, AppError> {
+        let company = self
+            .company_usecase
+            .get_company_by_id(user_id, &invoice.company_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::INVOICE_COMPANY_NOT_FOUND.into()))?;
+        if !matches!(
+            company.common.contract_type,
+            Some(ContractType::Freelance | ContractType::SideJob)
+        ) {
+            return Err(AppError::BadRequest(
+                messages::INVOICE_CONTRACT_TYPE_NOT_SUPPORTED.into(),
+            ));
+        }
+
+        let filter = WorkLogFilter {
+            company_id: Some(invoice.company_id),
+            from: Some(start_of_day(invoice.period_start, time_zone)),
+            to: Some(start_of_day(
+                invoice.period_end + Duration::days(1),
+                time_zone,
+            )),
+            ..Default::default()
+        };
+        let project_filter = ProjectFilter {
+            company_id: Some(invoice.company_id),
+            ..Default::default()
+        };
+        let (projects, work_logs) = try_join!(
+            self.project_usecase
+                .search_projects(user_id, Some(project_filter), None, None, vec![]),
+            self.work_logs_usecase.search_work_logs(
+                user_id,
+                &filter,
+                None,
+                None,
+                vec![("start_time".to_string(), 1)],
+            ),
+        )?;
+        let projects: HashMap<ObjectId, ProjectInDB> = projects
+            .items
+            .into_iter()
+            .filter_map(|project| Some((project.id?, project)))
+            .collect();
+
+        let (line_items, work_log_ids) = build_line_items(&work_logs.items, &projects, &company)?;
+        if line_items.is_empty() {
+            return Err(AppError::BadRequest(messages::INVOICE_NO_WORK_LOGS.into()));
+        }
+        let subtotal: i64 = line_items.iter().map(|item| item.amount).sum();
+        let tax_amount = calculate_tax_amount(subtotal, invoice.tax_rate);
+
+        // 採番後に作成に失敗した場合は欠番となるが、番号の重複を避けることを優先する
+        let sequence = self.repository.next_sequence(user_id).await?;
+
+        // 勤怠のロックに請求書IDが必要なため、IDはアプリ側で生成する
+        let invoice_id = ObjectId::new();
+        self.work_logs_usecase
+            .lock_work_logs_for_invoice(user_id, &work_log_ids, &invoice_id)
+            .await?;
+
+        let invoice_in_db = InvoiceInDB {
+            id: Some(invoice_id),
+            user_id: *user_id,
+            invoice_number: format_invoice_number(sequence),
+            company_id: invoice.company_id,
+            company_name: company.common.company_name.clone(),
+            period_start: invoice.period_start,
+            period_end: invoice.period_end,
+            issue_date: time_zone
+                .from_utc_datetime(&Utc::now().naive_utc())
+                .date_naive(),
+            due_date: invoice.due_date,
+            line_items,
+            subtotal,
+            tax_rate: invoice.tax_rate,
+            tax_amount,
+            total: subtotal + tax_amount,
+            status: InvoiceStatus::Draft,
+            work_log_ids,
+            notes: invoice.notes.clone().filter(|notes| !notes.is_empty()),
+            created_at: BsonDateTime::now(),
+            updated_at: None,
+        };
+        if let Err(e) = self.repository.insert_one(&invoice_in_db).await {
+            self.work_logs_usecase
+                .unlock_work_logs_for_invoice(user_id, &invoice_id)
+                .await?;
+            return Err(e.into());
+        }
+
+        Ok(invoice_id)
+    }
+
+    /// 請求書のステータスを変更する（下書き → 送付済み → 入金済みの順にのみ変更できる）
+    pub async fn update_invoice_status(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+        status: InvoiceStatus,
+    ) -> Result<InvoiceInDB, AppError> {
+        let invoice = self
+            .repository
+            .find_by_id(user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::INVOICE_NOT_FOUND.into()))?;
+
+        if !invoice.status.can_transition_to(status) {
+            return Err(AppError::BadRequest(
+                messages::INVOICE_STATUS_TRANSITION_NOT_ALLOWED
+                    .with("from", invoice.status.label())
+                    .with("to", status.label())
+                    .with("from_status", format!("{:?}", invoice.status))
+                    .with("to_status", format!("{:?}", status)),
+            ));
+        }
+        if !self
+            .repository
+            .update_status(user_id, id, invoice.status, status)
+            .await?
+        {
+            return Err(AppError::BadRequest(
+                messages::INVOICE_STATUS_CHANGED.into(),
+            ));
+        }
+
+        self.repository
+            .find_by_id(user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::INVOICE_NOT_FOUND.into()))
+    }
+
+    /// 下書きの請求書を削除し、含まれていた勤怠のロックを解除する
+    ///
+    /// 送付済みの請求書は取引の記録として残すため削除できない
+    pub async fn delete_invoice(&self, user_id: &ObjectId, id: &ObjectId) -> Result<(), AppError> {
+        let invoice = self
+            .repository
+            .find_by_id(user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::INVOICE_TO_DELETE_NOT_FOUND.into()))?;
+        if invoice.status != InvoiceStatus::Draft {
+            return Err(AppError::BadRequest(messages::INVOICE_NOT_DRAFT.into()));
+        }
+
+        if !self.repository.delete_draft(user_id, id).await? {
+            return Err(AppError::BadRequest(
+                messages::INVOICE_STATUS_CHANGED.into(),
+            ));
+        }
+        self.work_logs_usecase
+            .unlock_work_logs_for_invoice(user_id, id)
+            .await
+    }
+}
+
+/// 請求対象の勤怠をプロジェクトごとの明細にまとめる（明細は最初の勤怠の開始時間順）
+///
+/// 戻り値は（明細, 請求対象の勤怠ID）
+fn build_line_items(
+    work_logs: &[WorkLogInDB],
+    projects: &HashMap<ObjectId, ProjectInDB>,
+    company: &CompanyInDB,
+) -> Result<(Vec<InvoiceLineItem>, Vec<ObjectId>), AppError> {
+    let mut line_items: Vec<InvoiceLineItem> = Vec::new();
+    let mut indexes: HashMap<ObjectId, usize> = HashMap::new();
+    let mut work_log_ids = Vec::new();
+
+    for work_log in work_logs {
+        // 作業中の勤怠と、既に他の請求書に含まれている勤怠は対象外
+        let (Some(id), Some(minutes), None) = (
+            work_log.id,
+            work_log.actual_work_minutes,
+            work_log.invoice_id,
+        ) else {
+            continue;
+        };
+        let Some(project) = projects.get(&work_log.project_id) else {
+            continue;
+        };
+
+        let index = match indexes.get(&work_log.project_id) {
+            Some(index) => *index,
+            None => {
+                let hourly_rate =
+                    resolve_hourly_rate(Some(project), Some(company)).ok_or_else(|| {
+                        AppError::BadRequest(
+                            messages::HOURLY_RATE_NOT_SET.with("project", &project.title),
+                        )
+                    })?;
+                line_items.push(InvoiceLineItem {
+                    project_id: work_log.project_id,
+                    description: project.title.clone(),
+                    work_minutes: 0,
+                    hourly_rate,
+                    amount: 0,
+                });
+                indexes.insert(work_log.project_id, line_items.len() - 1);
+                line_items.len() - 1
+            }
+        };
+        line_items[index].work_minutes += minutes as i64;
+        work_log_ids.push(id);
+    }
+
+    // 勤怠ごとに端数処理すると誤差が積み重なるため、金額はプロジェクトの合計時間から計算する
+    for item in &mut line_items {
+        item.amount = calculate_work_earnings(item.work_minutes, item.hourly_rate);
+    }
+
+    Ok((line_items, work_log_ids))
+}
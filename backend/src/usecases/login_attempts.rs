@@ -0,0 +1,173 @@
+use crate::clients::redis::RedisClient;
+use crate::config::login_attempts::LoginAttemptConfig;
+use crate::errors::app_error::AppError;
+use crate::i18n::messages;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// ログイン失敗を数える単位（アカウント・IPアドレス）
+struct AttemptTarget {
+    scope: &'static str,
+    id: String,
+    max_failures: u64,
+}
+
+impl AttemptTarget {
+    fn failures_key(&self) -> String {
+        format!("login_failures:{}:{}", self.scope, self.id)
+    }
+
+    fn lock_key(&self) -> String {
+        format!("login_lock:{}:{}", self.scope, self.id)
+    }
+}
+
+/// ログインの失敗回数をRedisで数え、段階的な遅延と一時的なロックでブルートフォース攻撃を防ぐ
+///
+/// 失敗回数はアカウント（メールアドレス）とIPアドレスのそれぞれで数える。
+/// 存在しないメールアドレスも同様に数えるため、ロックの有無からユーザーの存在は推測できない。
+pub struct LoginAttemptLimiter {
+    redis_client: Arc<RedisClient>,
+    config: LoginAttemptConfig,
+}
+
+impl LoginAttemptLimiter {
+    pub fn new(redis_client: Arc<RedisClient>, config: LoginAttemptConfig) -> Self {
+        Self {
+            redis_client,
+            config,
+        }
+    }
+
+    fn targets(&self, email: &str, ip: Option<&str>) -> Vec<AttemptTarget> {
+        let mut targets = vec![AttemptTarget {
+            scope: "account",
+            id: email.trim().to_lowercase(),
+            max_failures: self.config.max_account_failures,
+        }];
+        if let Some(ip) = ip {
+            targets.push(AttemptTarget {
+                scope: "ip",
+                id: ip.to_string(),
+                max_failures: self.config.max_ip_failures,
+            });
+        }
+        targets
+    }
+
+    /// ログインを試行できるか確認する
+    ///
+    /// - ロック中の場合はエラーを返す
+    /// - 失敗が続いている場合は、失敗回数に応じて応答を遅延させる
+    pub async fn before_attempt(&self, email: &str, ip: Option<&str>) -> Result<(), AppError> {
+        let mut failures = 0;
+        for target in self.targets(email, ip) {
+            self.check_lock(&target).await?;
+            let target_failures: Option<u64> = self
+                .redis_client
+                .get(&target.failures_key())
+                .await
+                .map_err(redis_error)?;
+            failures = failures.max(target_failures.unwrap_or(0));
+        }
+
+        let delay = self.delay(failures);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        Ok(())
+    }
+
+    /// ログインの失敗を記録し、失敗回数が上限に達した場合はロックする
+    pub async fn record_failure(&self, email: &str, ip: Option<&str>) -> Result<(), AppError> {
+        let window = self.config.failure_window.as_secs();
+        let lockout = self.config.lockout_duration.as_secs();
+
+        for target in self.targets(email, ip) {
+            let failures = self
+                .redis_client
+                .increment(&target.failures_key(), window)
+                .await
+                .map_err(redis_error)?;
+            if failures < target.max_failures {
+                continue;
+            }
+
+            // ロック解除を記録できるよう、ロックのキーはロック期間が過ぎた後もしばらく保持する
+            let locked_until = Utc::now().timestamp() + lockout as i64;
+            self.redis_client
+                .set_with_expiry(&target.lock_key(), locked_until, lockout + window)
+                .await
+                .map_err(redis_error)?;
+            log::warn!(
+                "ログインの失敗が{}回に達したため{}秒間ロックしました: {}={}",
+                failures,
+                lockout,
+                target.scope,
+                target.id
+            );
+        }
+        Ok(())
+    }
+
+    /// ログインの成功時にアカウントの失敗回数をリセットする
+    ///
+    /// 複数のアカウントに対する試行を検知できるよう、IPアドレスの失敗回数はリセットしない
+    pub async fn record_success(&self, email: &str) -> Result<(), AppError> {
+        let keys: Vec<String> = self
+            .targets(email, None)
+            .iter()
+            .map(AttemptTarget::failures_key)
+            .collect();
+        self.redis_client.delete(&keys).await.map_err(redis_error)
+    }
+
+    /// ロック中の場合はエラーを返す（ロック期間が過ぎている場合はロックを解除する）
+    async fn check_lock(&self, target: &AttemptTarget) -> Result<(), AppError> {
+        let locked_until: Option<i64> = self
+            .redis_client
+            .get(&target.lock_key())
+            .await
+            .map_err(redis_error)?;
+        let Some(locked_until) = locked_until else {
+            return Ok(());
+        };
+
+        let remaining_secs = locked_until - Utc::now().timestamp();
+        if remaining_secs > 0 {
+            let remaining_minutes = (remaining_secs + 59) / 60;
+            return Err(AppError::TooManyRequests(
+                messages::LOGIN_TEMPORARILY_LOCKED.with("minutes", remaining_minutes),
+            ));
+        }
+
+        // ロック期間が過ぎた後の最初の試行で、ロックを解除して失敗回数を数え直す
+        self.redis_client
+            .delete(&[target.lock_key(), target.failures_key()])
+            .await
+            .map_err(redis_error)?;
+        log::info!(
+            "ログインのロックを解除しました: {}={}",
+            target.scope,
+            target.id
+        );
+        Ok(())
+    }
+
+    /// 失敗回数に応じた遅延時間（一定回数を超えると、失敗するごとに倍にする）
+    fn delay(&self, failures: u64) -> Duration {
+        if failures < self.config.delay_after_failures {
+            return Duration::ZERO;
+        }
+        let exponent = (failures - self.config.delay_after_failures).min(16) as u32;
+        self.config
+            .base_delay
+            .saturating_mul(2u32.pow(exponent))
+            .min(self.config.max_delay)
+    }
+}
+
+fn redis_error(e: std::io::Error) -> AppError {
+    AppError::InternalServerError(format!("ログイン試行回数の管理に失敗しました: {}", e))
+}
@@ -1,4 +1,11 @@
 pub mod auth;
+pub mod calendar;
 pub mod companies;
+pub mod email_verification;
+pub mod exports;
+pub mod invoices;
+pub mod login_attempts;
+pub mod password_reset;
 pub mod projects;
+pub mod reports;
 pub mod work_logs;
@@ -0,0 +1,146 @@
+use crate::clients::mailer::{Mail, Mailer};
+use crate::config::password_reset::PasswordResetConfig;
+use crate::errors::app_error::AppError;
+use crate::i18n::{messages, Message};
+use crate::models::auth::{AuthTokenScope, PasswordResetTokenInDB};
+use crate::models::users::UserInDB;
+use crate::repositories::auth::AuthRepository;
+use crate::utils::password::hash_password;
+use crate::utils::token::{generate_random_token, hash_token};
+use bson::DateTime as BsonDateTime;
+use chrono::Utc;
+use std::sync::Arc;
+
+pub struct PasswordResetUseCase<R: AuthRepository> {
+    repository: Arc<R>,
+    mailer: Arc<dyn Mailer>,
+    config: PasswordResetConfig,
+}
+
+impl<R: AuthRepository> PasswordResetUseCase<R> {
+    pub fn new(repository: Arc<R>, mailer: Arc<dyn Mailer>, config: PasswordResetConfig) -> Self {
+        Self {
+            repository,
+            mailer,
+            config,
+        }
+    }
+
+    /// パスワードリセットの申請処理
+    ///
+    /// ユーザーの存在を推測されないよう、ユーザーが存在しない場合やメールの送信に失敗した場合も成功として扱う。
+    /// また、存在する場合のみトークンの発行やメールの送信を待つとレスポンス時間の差で推測されるため、
+    /// それらはレスポンスを待たずに別タスクで行う
+    pub async fn request_reset(self: &Arc<Self>, email: &str) -> Result<(), AppError>
+    where
+        R: 'static,
+    {
+        let Some(user) = self.repository.find_user_by_email(email).await? else {
+            log::info!("存在しないメールアドレスでパスワードリセットが申請されました");
+            return Ok(());
+        };
+
+        let usecase = Arc::clone(self);
+        actix_web::rt::spawn(async move {
+            if let Err(e) = usecase.issue_reset_token(&user).await {
+                log::error!(
+                    "パスワードリセットのトークンの発行に失敗しました: user_id={:?}, error={}",
+                    user.id,
+                    e
+                );
+            }
+        });
+        Ok(())
+    }
+
+    /// リセットトークンの発行処理
+    ///
+    /// - 発行済みのリセットトークンを無効化
+    /// - 新しいリセットトークンを発行し、ハッシュ値のみを保存
+    /// - パスワード再設定画面のURLをメールで送信（送信に失敗した場合はログ出力のみとする）
+    async fn issue_reset_token(&self, user: &UserInDB) -> Result<(), AppError> {
+        let user_id = user.id.ok_or_else(|| {
+            AppError::InternalServerError("ユーザーIDが設定されていません".to_string())
+        })?;
+
+        self.repository
+            .delete_password_reset_tokens(&user_id)
+            .await?;
+
+        let token = generate_random_token();
+        let now = Utc::now();
+        let reset_token = PasswordResetTokenInDB {
+            id: None,
+            user_id,
+            token_hash: hash_token(&token),
+            expires_at: BsonDateTime::from_chrono(now + self.config.token_ttl),
+            used_at: None,
+            created_at: BsonDateTime::from_chrono(now),
+        };
+        self.repository
+            .save_password_reset_token(&reset_token)
+            .await?;
+
+        if let Err(e) = self.mailer.send(&self.reset_mail(user, &token)).await {
+            log::error!(
+                "パスワードリセットのメールの送信に失敗しました: user_id={}, error={}",
+                user_id,
+                e
+            );
+        }
+        Ok(())
+    }
+
+    /// パスワードの再設定処理
+    ///
+    /// - リセットトークンを使用済みにする（未使用かつ有効期限内の場合のみ）
+    /// - パスワードを更新
+    /// - 漏洩したパスワードでのログインを継続させないよう、既存の認証トークンをすべて失効させる
+    pub async fn confirm_reset(&self, token: &str, new_password: &str) -> Result<(), AppError> {
+        let reset_token = self
+            .repository
+            .consume_password_reset_token(&hash_token(token))
+            .await?
+            .ok_or_else(|| AppError::BadRequest(messages::INVALID_OR_EXPIRED_RESET_TOKEN.into()))?;
+
+        let password_hash = hash_password(new_password)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        if !self
+            .repository
+            .update_password_hash(&reset_token.user_id, &password_hash)
+            .await?
+        {
+            return Err(AppError::BadRequest(
+                messages::INVALID_OR_EXPIRED_RESET_TOKEN.into(),
+            ));
+        }
+
+        let revoked_count = self
+            .repository
+            .delete_auth_tokens(&AuthTokenScope::User(reset_token.user_id))
+            .await?;
+        self.repository
+            .delete_password_reset_tokens(&reset_token.user_id)
+            .await?;
+        log::info!(
+            "パスワードを再設定し、認証トークンを失効させました: user_id={}, 失効数={}",
+            reset_token.user_id,
+            revoked_count
+        );
+        Ok(())
+    }
+
+    /// ユーザーの表示言語でパスワードリセットのメールを作成
+    fn reset_mail(&self, user: &UserInDB, token: &str) -> Mail {
+        let locale = user.locale();
+        let url = format!("{}?token={}", self.config.reset_url, token);
+        Mail {
+            to: user.email.clone(),
+            subject: Message::from(messages::PASSWORD_RESET_MAIL_SUBJECT).localize(locale),
+            body: messages::PASSWORD_RESET_MAIL_BODY
+                .with("url", url)
+                .with("minutes", self.config.token_ttl.as_secs() / 60)
+                .localize(locale),
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use crate::errors::app_error::AppError;
+use crate::i18n::messages;
 use crate::models::projects::{ProjectCreate, ProjectFilter, ProjectInDB, ProjectUpdate};
 use crate::repositories::companies::MongoCompanyRepository;
 use crate::repositories::projects::ProjectRepository;
 use crate::usecases::companies::CompanyUseCase;
-use bson::oid::ObjectId;
+use crate::utils::pagination::{fetch_limit, Page, PageCursor};
+use bson::{oid::ObjectId, DateTime as BsonDateTime};
 use std::sync::Arc;
+use tokio::try_join;
 
 pub struct ProjectUseCase<R: ProjectRepository> {
     repository: Arc<R>,
@@ -22,53 +25,148 @@ impl<R: ProjectRepository> ProjectUseCase<R> {
         }
     }
 
-    /// プロジェクトを検索し、条件に一致するプロジェクトを取得する。
-    /// パラメータが `None` の場合は全てのプロジェクトを取得する。
+    /// プロジェクトを検索し、条件に一致するプロジェクトを1ページ分取得する。
+    /// パラメータが `None` の場合は全てのプロジェクトを対象とする。
     pub async fn search_projects(
         &self,
+        user_id: &ObjectId,
         filter: Option<ProjectFilter>,
         limit: Option<i64>,
-        offset: Option<u64>,
-        sort: Option<Vec<(String, i8)>>,
-    ) -> Result<Vec<ProjectInDB>, AppError> {
-        Ok(self
-            .repository
-            .find_many(filter, limit, offset, sort)
-            .await?)
+        cursor: Option<PageCursor>,
+        sort: Vec<(String, i8)>,
+    ) -> Result<Page<ProjectInDB>, AppError> {
+        let (projects, total) = try_join!(
+            self.repository.find_many(
+                user_id,
+                filter.as_ref(),
+                fetch_limit(limit),
+                cursor.as_ref(),
+                &sort
+            ),
+            self.repository.count_many(user_id, filter.as_ref()),
+        )?;
+        Page::from_fetched(projects, total, limit, &sort)
     }
 
-    pub async fn get_project_by_id(&self, id: &ObjectId) -> Result<Option<ProjectInDB>, AppError> {
-        Ok(self.repository.find_by_id(id).await?)
+    pub async fn get_project_by_id(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<Option<ProjectInDB>, AppError> {
+        Ok(self.repository.find_by_id(user_id, id).await?)
     }
 
-    pub async fn create_project(&self, project: ProjectCreate) -> Result<ObjectId, AppError> {
+    pub async fn create_project(
+        &self,
+        user_id: &ObjectId,
+        project: ProjectCreate,
+    ) -> Result<ObjectId, AppError> {
         self.company_usecase
-            .get_company_by_id(&project.company_id)
+            .get_company_by_id(user_id, &project.company_id)
             .await?
-            .ok_or_else(|| {
-                AppError::NotFound("プロジェクトに関連する企業が見つかりません".to_string())
-            })?;
-        Ok(self.repository.insert_one(project).await?)
+            .ok_or_else(|| AppError::NotFound(messages::PROJECT_COMPANY_NOT_FOUND.into()))?;
+        Ok(self.repository.insert_one(user_id, project).await?)
     }
 
     pub async fn update_project(
         &self,
+        user_id: &ObjectId,
         id: &ObjectId,
         project: &ProjectUpdate,
     ) -> Result<bool, AppError> {
         // 既存のドキュメントが存在するか
-        if self.repository.find_by_id(id).await?.is_none() {
+        if self.repository.find_by_id(user_id, id).await?.is_none() {
             return Err(AppError::NotFound(
-                "更新対象のプロジェクトが見つかりません".to_string(),
+                messages::PROJECT_TO_UPDATE_NOT_FOUND.into(),
             ));
         }
         self.company_usecase
-            .get_company_by_id(&project.company_id)
+            .get_company_by_id(user_id, &project.company_id)
             .await?
-            .ok_or_else(|| {
-                AppError::NotFound("プロジェクトに関連する企業が見つかりません".to_string())
-            })?;
+            .ok_or_else(|| AppError::NotFound(messages::PROJECT_COMPANY_NOT_FOUND.into()))?;
+
+        Ok(self.repository.update_one(user_id, *id, project).await?)
+    }
+
+    pub async fn get_deleted_projects(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<Vec<ProjectInDB>, AppError> {
+        Ok(self.repository.find_deleted(user_id).await?)
+    }
+
+    /// プロジェクトを論理削除する（紐づく勤怠もゴミ箱に移動する）
+    ///
+    /// 請求書に含まれる勤怠がある場合は、請求内容と食い違わないよう削除できない
+    pub async fn delete_project(&self, user_id: &ObjectId, id: &ObjectId) -> Result<(), AppError> {
+        let invoiced_ids = self
+            .repository
+            .find_invoiced_work_log_ids(user_id, id)
+            .await?;
+        if !invoiced_ids.is_empty() {
+            return Err(AppError::Conflict(
+                messages::INVOICED_WORK_LOGS_EXIST.into(),
+                invoiced_ids,
+            ));
+        }
+        if !self.repository.soft_delete(user_id, id).await? {
+            return Err(AppError::NotFound(
+                messages::PROJECT_TO_DELETE_NOT_FOUND.into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// ゴミ箱からプロジェクトを復元する（同時に削除された勤怠も復元する）
+    pub async fn restore_project(&self, user_id: &ObjectId, id: &ObjectId) -> Result<(), AppError> {
+        let project = self
+            .repository
+            .find_deleted_by_id(user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::PROJECT_TO_RESTORE_NOT_FOUND.into()))?;
+
+        // 関連する企業が削除されたままだと復元後に参照できないため、先に企業の復元を求める
+        if self
+            .company_usecase
+            .get_company_by_id(user_id, &project.company_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::BadRequest(
+                messages::PROJECT_COMPANY_DELETED.into(),
+            ));
+        }
+
+        if !self.repository.restore(user_id, id).await? {
+            return Err(AppError::NotFound(
+                messages::PROJECT_TO_RESTORE_NOT_FOUND.into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 削除日時が指定日時以前のプロジェクトを物理削除する
+    pub async fn purge_deleted_projects(
+        &self,
+        deleted_before: BsonDateTime,
+    ) -> Result<u64, AppError> {
+        Ok(self.repository.purge_deleted(deleted_before).await?)
+    }
+
+    /// 勤怠の実作業時間からプロジェクトの総稼働時間を再計算する
+    pub async fn recalculate_total_working_time(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<i64, AppError> {
+        Ok(self
+            .repository
+            .recalculate_total_working_time(user_id, id)
+            .await?)
+    }
 
-        Ok(self.repository.update_one(*id, project).await?)
+    /// 全プロジェクトの総稼働時間を再計算し、補正したプロジェクト数を返す（管理者向け）
+    pub async fn recalculate_all_total_working_time(&self) -> Result<u64, AppError> {
+        Ok(self.repository.recalculate_all_total_working_time().await?)
     }
 }
@@ -0,0 +1,306 @@
+use crate::errors::app_error::AppError;
+use crate::models::companies::CompanyInDB;
+use crate::models::projects::ProjectInDB;
+use crate::models::reports::{
+    EarningsAmount, EarningsGroup, EarningsGroupBy, EarningsQuery, SkillAnalyticsQuery, SkillUsage,
+    TimeReportGroup, TimeReportQuery,
+};
+use crate::models::work_logs::{WorkLogFilter, WorkLogInDB};
+use crate::repositories::companies::MongoCompanyRepository;
+use crate::repositories::projects::MongoProjectRepository;
+use crate::repositories::reports::ReportRepository;
+use crate::repositories::work_logs::MongoWorkLogRepository;
+use crate::usecases::companies::CompanyUseCase;
+use crate::usecases::projects::ProjectUseCase;
+use crate::usecases::work_logs::WorkLogUseCase;
+use bson::{oid::ObjectId, DateTime as BsonDateTime};
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::try_join;
+
+pub struct ReportUseCase<R: ReportRepository> {
+    repository: Arc<R>,
+    company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+    project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+}
+
+impl<R: ReportRepository> ReportUseCase<R> {
+    pub fn new(
+        repository: Arc<R>,
+        company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
+        project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+        work_logs_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+    ) -> Self {
+        Self {
+            repository,
+            company_usecase,
+            project_usecase,
+            work_logs_usecase,
+        }
+    }
+
+    /// 期間内の収入を集計する
+    ///
+    /// - 作業による収入は `実作業時間 × 時給` で計算する（時給はプロジェクトの時給、未設定の場合は企業の平均時給）
+    /// - ボーナスは `金額 × 頻度` を年額とし、契約期間内の日数で月割りする
+    /// - ボーナスは勤怠・プロジェクトに按分できないため、勤怠・プロジェクト単位の集計では合計にのみ含める
+    /// - 期間・月の区切りはユーザーのタイムゾーンで判定する
+    pub async fn calculate_earnings(
+        &self,
+        user_id: &ObjectId,
+        query: &EarningsQuery,
+        time_zone: Tz,
+    ) -> Result<(EarningsAmount, Vec<EarningsGroup>), AppError> {
+        let filter = WorkLogFilter {
+            from: Some(start_of_day(query.from, time_zone)),
+            to: Some(start_of_day(query.to + Duration::days(1), time_zone)),
+            ..Default::default()
+        };
+        let (companies, projects, work_logs) = try_join!(
+            self.company_usecase.get_all_companies(user_id, None, None),
+            self.project_usecase
+                .search_projects(user_id, None, None, None, vec![]),
+            self.work_logs_usecase.search_work_logs(
+                user_id,
+                &filter,
+                None,
+                None,
+                vec![("start_time".to_string(), 1)],
+            ),
+        )?;
+
+        let companies: HashMap<ObjectId, CompanyInDB> = companies
+            .items
+            .into_iter()
+            .filter_map(|company| Some((company.id?, company)))
+            .collect();
+        let projects: HashMap<ObjectId, ProjectInDB> = projects
+            .items
+            .into_iter()
+            .filter_map(|project| Some((project.id?, project)))
+            .collect();
+
+        let mut earnings = EarningsAggregator::default();
+        for work_log in &work_logs.items {
+            let project = projects.get(&work_log.project_id);
+            let company = project.and_then(|project| companies.get(&project.company_id));
+            earnings.add_work_log(query.group_by, work_log, project, company, time_zone);
+        }
+        for (company_id, company) in &companies {
+            earnings.add_bonus(query, company_id, company);
+        }
+
+        Ok(earnings.finish(query.group_by))
+    }
+
+    /// 期間内の作業時間を集計し、合計（実作業時間, 勤怠の件数）と集計単位ごとの作業時間を取得する
+    ///
+    /// 日・週・月の区切りはユーザーのタイムゾーンで判定する
+    pub async fn aggregate_work_time(
+        &self,
+        user_id: &ObjectId,
+        query: &TimeReportQuery,
+        time_zone: Tz,
+    ) -> Result<((i64, i64), Vec<TimeReportGroup>), AppError> {
+        let from = start_of_day(query.from, time_zone);
+        let to = start_of_day(query.to + Duration::days(1), time_zone);
+        Ok(try_join!(
+            self.repository.sum_work_minutes(user_id, from, to),
+            self.repository
+                .aggregate_work_minutes(user_id, from, to, query.group_by, time_zone),
+        )?)
+    }
+
+    /// スキルラベルごとに、スキルを使用したプロジェクトでの作業時間・使用期間・推移・企業を集計する
+    pub async fn analyze_skills(
+        &self,
+        user_id: &ObjectId,
+        query: &SkillAnalyticsQuery,
+        time_zone: Tz,
+    ) -> Result<Vec<SkillUsage>, AppError> {
+        Ok(self
+            .repository
+            .aggregate_skill_usage(
+                user_id,
+                query.from.map(|from| start_of_day(from, time_zone)),
+                query
+                    .to
+                    .map(|to| start_of_day(to + Duration::days(1), time_zone)),
+                query.limit,
+                time_zone,
+            )
+            .await?)
+    }
+}
+
+/// 集計結果を組み立てる（グループは初めて現れた順に保持する）
+#[derive(Default)]
+struct EarningsAggregator {
+    total: EarningsAmount,
+    groups: Vec<EarningsGroup>,
+    indexes: HashMap<String, usize>,
+}
+
+impl EarningsAggregator {
+    fn group(&mut self, key: String, label: &str, hourly_rate: Option<i32>) -> &mut EarningsGroup {
+        let index = *self.indexes.entry(key.clone()).or_insert_with(|| {
+            self.groups.push(EarningsGroup {
+                key,
+                label: label.to_string(),
+                hourly_rate,
+                amount: EarningsAmount::default(),
+            });
+            self.groups.len() - 1
+        });
+        &mut self.groups[index]
+    }
+
+    fn add_work_log(
+        &mut self,
+        group_by: EarningsGroupBy,
+        work_log: &WorkLogInDB,
+        project: Option<&ProjectInDB>,
+        company: Option<&CompanyInDB>,
+        time_zone: Tz,
+    ) {
+        // 作業中の勤怠は実作業時間が確定していないため集計しない
+        let Some(minutes) = work_log.actual_work_minutes else {
+            return;
+        };
+        let minutes = minutes as i64;
+        let hourly_rate = resolve_hourly_rate(project, company);
+        let project_title = project.map_or("", |project| project.title.as_str());
+
+        self.total.add_work(minutes, hourly_rate);
+        let group = match group_by {
+            EarningsGroupBy::WorkLog => self.group(
+                work_log.id.map(|id| id.to_hex()).unwrap_or_default(),
+                project_title,
+                hourly_rate,
+            ),
+            EarningsGroupBy::Project => {
+                self.group(work_log.project_id.to_hex(), project_title, hourly_rate)
+            }
+            EarningsGroupBy::Company => {
+                let Some(company) = company else {
+                    return;
+                };
+                self.group(
+                    company.id.map(|id| id.to_hex()).unwrap_or_default(),
+                    &company.common.company_name,
+                    None,
+                )
+            }
+            EarningsGroupBy::Month => {
+                let month = month_key(work_log.start_time, time_zone);
+                self.group(month.clone(), &month, None)
+            }
+        };
+        group.amount.add_work(minutes, hourly_rate);
+    }
+
+    fn add_bonus(&mut self, query: &EarningsQuery, company_id: &ObjectId, company: &CompanyInDB) {
+        let Some(bonus) = &company.common.bonus else {
+            return;
+        };
+        let annual_bonus = bonus.amount * bonus.frequency as i64;
+
+        // 集計期間と契約期間が重なる範囲を月ごとに日割りする
+        let from = query.from.max(company.affiliation_start_date);
+        let to = company
+            .affiliation_end_date
+            .map_or(query.to, |end_date| query.to.min(end_date));
+        if from > to {
+            return;
+        }
+        let mut month_start = first_day_of_month(from);
+        while month_start <= to {
+            let next_month_start = first_day_of_next_month(month_start);
+            let days_in_month = (next_month_start - month_start).num_days();
+            let start = from.max(month_start);
+            let end = to.min(next_month_start - Duration::days(1));
+            let days = (end - start).num_days() + 1;
+            // 1円未満は四捨五入
+            let amount = (annual_bonus * days + 6 * days_in_month) / (12 * days_in_month);
+
+            self.total.add_bonus(amount);
+            match query.group_by {
+                EarningsGroupBy::Company => self
+                    .group(company_id.to_hex(), &company.common.company_name, None)
+                    .amount
+                    .add_bonus(amount),
+                EarningsGroupBy::Month => {
+                    let month = month_start.format("%Y-%m").to_string();
+                    self.group(month.clone(), &month, None)
+                        .amount
+                        .add_bonus(amount)
+                }
+                EarningsGroupBy::WorkLog | EarningsGroupBy::Project => {}
+            }
+
+            month_start = next_month_start;
+        }
+    }
+
+    fn finish(mut self, group_by: EarningsGroupBy) -> (EarningsAmount, Vec<EarningsGroup>) {
+        match group_by {
+            // 勤怠は開始時間順のまま返す
+            EarningsGroupBy::WorkLog => {}
+            EarningsGroupBy::Month => self.groups.sort_by(|a, b| a.key.cmp(&b.key)),
+            EarningsGroupBy::Project | EarningsGroupBy::Company => {
+                self.groups.sort_by_key(|group| Reverse(group.amount.total))
+            }
+        }
+        (self.total, self.groups)
+    }
+}
+
+/// 勤怠に適用する時給（プロジェクトの時給、未設定の場合は企業の平均時給）
+pub fn resolve_hourly_rate(
+    project: Option<&ProjectInDB>,
+    company: Option<&CompanyInDB>,
+) -> Option<i32> {
+    project
+        .and_then(|project| project.hourly_pay)
+        .or_else(|| company.and_then(|company| company.common.average_hourly_rate))
+}
+
+/// 指定したタイムゾーンでの日付の始まりをBsonDateTimeに変換する
+///
+/// 夏時間の切り替えで0時が存在しない日は、その日の最初の時刻（切り替え直後）とする
+pub fn start_of_day(date: NaiveDate, time_zone: Tz) -> BsonDateTime {
+    let start = (0..24)
+        .filter_map(|hour| {
+            date.and_time(NaiveTime::MIN)
+                .checked_add_signed(Duration::hours(hour))
+        })
+        .find_map(|local| time_zone.from_local_datetime(&local).earliest())
+        .expect("1日のうちに存在する時刻があること");
+    BsonDateTime::from_chrono(start)
+}
+
+/// 開始時間の指定したタイムゾーンでの年月（YYYY-MM）
+fn month_key(date_time: BsonDateTime, time_zone: Tz) -> String {
+    date_time
+        .to_chrono()
+        .with_timezone(&time_zone)
+        .format("%Y-%m")
+        .to_string()
+}
+
+fn first_day_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn first_day_of_next_month(date: NaiveDate) -> NaiveDate {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(date)
+}
@@ -1,102 +1,836 @@
-use crate::errors::app_error::AppError;
-use crate::models::projects::ProjectUpdate;
-use crate::models::work_logs::{WorkLogCreate, WorkLogInDB, WorkLogUpdate};
+use crate::config::work_logs::WorkLogConfig;
+use crate::errors::app_error::{AppError, InvalidField};
+use crate::errors::repositories_error::RepositoryError;
+use crate::i18n::{messages, Message, MessageCode};
+use crate::models::imports::{ParsedImportRow, WorkLogImportError, WorkLogImportRecord};
+use crate::models::projects::ProjectInDB;
+use crate::models::work_logs::{
+    calculate_actual_work_minutes, find_work_log_overlaps, WorkLogCreate, WorkLogFilter,
+    WorkLogInDB, WorkLogOverlap, WorkLogStart, WorkLogStop, WorkLogUpdate,
+};
 use crate::repositories::projects::MongoProjectRepository;
 use crate::repositories::work_logs::WorkLogRepository;
 use crate::usecases::projects::ProjectUseCase;
-use bson::oid::ObjectId;
+use crate::utils::pagination::{fetch_limit, Page, PageCursor};
+use bson::{oid::ObjectId, DateTime as BsonDateTime};
+use chrono_tz::Tz;
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use tokio::try_join;
 
 pub struct WorkLogUseCase<R: WorkLogRepository> {
     repository: Arc<R>,
     project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+    config: WorkLogConfig,
 }
 
 impl<R: WorkLogRepository> WorkLogUseCase<R> {
     pub fn new(
         repository: Arc<R>,
         project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
+        config: WorkLogConfig,
     ) -> Self {
         Self {
             repository,
             project_usecase,
+            config,
         }
     }
 
-    pub async fn get_all_work_logs(&self) -> Result<Vec<WorkLogInDB>, AppError> {
-        Ok(self.repository.find_all().await?)
+    /// 勤怠を検索し、条件に一致する勤怠とページング前の総件数を取得する
+    pub async fn search_work_logs(
+        &self,
+        user_id: &ObjectId,
+        filter: &WorkLogFilter,
+        limit: Option<i64>,
+        cursor: Option<PageCursor>,
+        sort: Vec<(String, i8)>,
+    ) -> Result<Page<WorkLogInDB>, AppError> {
+        let (work_logs, total) = try_join!(
+            self.repository
+                .find_many(user_id, filter, fetch_limit(limit), cursor.as_ref(), &sort),
+            self.repository.count_many(user_id, filter),
+        )?;
+        Page::from_fetched(work_logs, total, limit, &sort)
+    }
+
+    /// 条件に一致する勤怠を開始時間の古い順にストリームで取得する（エクスポート用）
+    pub async fn stream_work_logs(
+        &self,
+        user_id: &ObjectId,
+        filter: &WorkLogFilter,
+    ) -> Result<BoxStream<'static, Result<WorkLogInDB, AppError>>, AppError> {
+        let work_logs = self.repository.stream_many(user_id, filter).await?;
+        Ok(work_logs.map_err(AppError::from).boxed())
     }
 
     pub async fn get_work_logs_by_id(
         &self,
+        user_id: &ObjectId,
         id: &ObjectId,
     ) -> Result<Option<WorkLogInDB>, AppError> {
-        Ok(self.repository.find_by_id(id).await?)
+        Ok(self.repository.find_by_id(user_id, id).await?)
     }
 
-    pub async fn create_work_logs(&self, work_logs: &WorkLogCreate) -> Result<ObjectId, AppError> {
-        // プロジェクトの取得と勤怠時間の作成を並行して実行
-        let (project, inserted_id) = try_join!(
-            self.project_usecase
-                .get_project_by_id(&work_logs.project_id),
-            async { Ok(self.repository.insert_one(work_logs).await?) }
-        )?;
-
-        let associated_project = project.ok_or_else(|| {
-            AppError::NotFound("勤怠に関連するプロジェクトが見つかりません".to_string())
-        })?;
+    /// 勤怠を作成する
+    ///
+    /// 既存の勤怠と時間が重複する場合はエラーとする（`allow_overlap`の場合は作成し、重複した勤怠のIDを返す）。
+    /// 日付（ユーザーのタイムゾーンの0時）をまたぐ勤怠は日ごとに分割して登録する。
+    /// 戻り値は（作成した勤怠のID（分割した場合は最初の勤怠のID）, 時間が重複している勤怠のID）
+    pub async fn create_work_logs(
+        &self,
+        user_id: &ObjectId,
+        work_logs: &WorkLogCreate,
+        allow_overlap: bool,
+        time_zone: Tz,
+    ) -> Result<(ObjectId, Vec<ObjectId>), AppError> {
+        // 他ユーザーのプロジェクトに勤怠を紐付けられないよう、先にプロジェクトの所有者を確認する
+        self.ensure_associated_project(user_id, &work_logs.project_id)
+            .await?;
+        if work_logs.end_time.is_none() {
+            self.ensure_no_active_work_log(user_id, None).await?;
+        }
+        self.check_session_length(work_logs.start_time, work_logs.end_time)
+            .map_err(AppError::BadRequest)?;
+        let overlapping_ids = self
+            .check_overlaps(
+                user_id,
+                work_logs.start_time,
+                work_logs.end_time,
+                None,
+                allow_overlap,
+            )
+            .await?;
 
-        // 実作業時間を使用して総稼働時間を更新
-        let actual_work_minutes = work_logs.actual_work_minutes.unwrap_or(0);
-        let updated_total_working_time =
-            associated_project.total_working_time + actual_work_minutes as i64 * 60; // 分を秒に変換
+        let mut segments = work_logs.clone().split_by_local_day(time_zone);
+        let rest = segments.split_off(1);
+        let inserted_id = self
+            .repository
+            .insert_one(user_id, &segments[0])
+            .await
+            .map_err(active_work_log_error)?;
+        if !rest.is_empty() {
+            self.repository
+                .insert_many(user_id, &rest)
+                .await
+                .map_err(active_work_log_error)?;
+        }
 
-        let project_update = ProjectUpdate {
-            total_working_time: updated_total_working_time,
-            ..ProjectUpdate::from(associated_project)
-        };
         self.project_usecase
-            .update_project(&work_logs.project_id, &project_update)
+            .recalculate_total_working_time(user_id, &work_logs.project_id)
             .await?;
 
-        Ok(inserted_id)
+        Ok((inserted_id, overlapping_ids))
     }
 
+    /// 勤怠を一括で登録する
+    ///
+    /// 全ての行を検証し、エラーのない行のみを登録する（`dry_run`の場合は登録しない）。
+    /// 日付をまたぐ行は作成時と同じく日ごとに分割して登録する。
+    /// プロジェクトはIDまたはプロジェクト名で指定でき、総稼働時間は登録後にプロジェクトごとに1回だけ再集計する。
+    /// 戻り値は（登録できる行数, エラーのあった行）
+    pub async fn import_work_logs(
+        &self,
+        user_id: &ObjectId,
+        rows: Vec<ParsedImportRow>,
+        dry_run: bool,
+        allow_overlap: bool,
+        time_zone: Tz,
+    ) -> Result<(usize, Vec<WorkLogImportError>), AppError> {
+        let projects = self
+            .project_usecase
+            .search_projects(user_id, None, None, None, vec![])
+            .await?;
+        let resolver = ProjectResolver::new(&projects.items);
+
+        let mut work_logs = Vec::new();
+        let mut errors = Vec::new();
+        for row in rows {
+            let record = match row.record {
+                Ok(record) => record,
+                Err(message) => {
+                    errors.push(WorkLogImportError::new(row.line, None, message));
+                    continue;
+                }
+            };
+            match to_work_log_create(row.line, record, &resolver) {
+                Ok(work_log) => {
+                    match self.check_session_length(work_log.start_time, work_log.end_time) {
+                        Ok(()) => work_logs.push((row.line, work_log)),
+                        Err(message) => {
+                            errors.push(WorkLogImportError::new(row.line, Some("time"), message))
+                        }
+                    }
+                }
+                Err(row_errors) => errors.extend(row_errors),
+            }
+        }
+        if !allow_overlap {
+            work_logs = self
+                .reject_overlapping_imports(user_id, work_logs, &mut errors)
+                .await?;
+            errors.sort_by_key(|error| error.line);
+        }
+
+        let valid_rows = work_logs.len();
+        if dry_run || work_logs.is_empty() {
+            return Ok((valid_rows, errors));
+        }
+
+        let work_logs: Vec<WorkLogCreate> = work_logs
+            .into_iter()
+            .flat_map(|(_, work_log)| work_log.split_by_local_day(time_zone))
+            .collect();
+
+        self.repository
+            .insert_many(user_id, &work_logs)
+            .await
+            .map_err(active_work_log_error)?;
+
+        let project_ids: BTreeSet<ObjectId> = work_logs
+            .iter()
+            .map(|work_log| work_log.project_id)
+            .collect();
+        for project_id in &project_ids {
+            self.project_usecase
+                .recalculate_total_working_time(user_id, project_id)
+                .await?;
+        }
+
+        Ok((valid_rows, errors))
+    }
+
+    /// 勤怠を更新する
+    ///
+    /// 時間の重複・日付をまたぐ場合の扱いは作成時と同じ（分割した場合は最初の勤怠を更新し、残りを新たに登録する）。
+    /// 戻り値は時間が重複している勤怠のID
     pub async fn update_work_logs(
         &self,
+        user_id: &ObjectId,
         id: &ObjectId,
         work_logs: &WorkLogUpdate,
-    ) -> Result<bool, AppError> {
+        allow_overlap: bool,
+        time_zone: Tz,
+    ) -> Result<Vec<ObjectId>, AppError> {
         // 既存の勤怠ドキュメントが存在するか確認
-        if self.repository.find_by_id(id).await?.is_none() {
+        let current = self
+            .repository
+            .find_by_id(user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::WORK_LOG_TO_UPDATE_NOT_FOUND.into()))?;
+        ensure_not_invoiced(&current)?;
+
+        // 付け替え先のプロジェクトも自分のものであることを確認
+        self.ensure_associated_project(user_id, &work_logs.project_id)
+            .await?;
+        if work_logs.end_time.is_none() {
+            self.ensure_no_active_work_log(user_id, Some(id)).await?;
+        }
+        self.check_session_length(work_logs.start_time, work_logs.end_time)
+            .map_err(AppError::BadRequest)?;
+        // 日をまたいで分割された勤怠の一部を編集する場合、同じセッションの他の勤怠とは重複として扱わない
+        let overlapping_ids = self
+            .check_overlaps(
+                user_id,
+                work_logs.start_time,
+                work_logs.end_time,
+                Some(&current),
+                allow_overlap,
+            )
+            .await?;
+
+        let segments = work_logs.clone().split_by_local_day(time_zone);
+        self.update_with_segments(user_id, id, segments).await?;
+
+        // 総稼働時間は勤怠から再集計する（プロジェクトを付け替えた場合は元のプロジェクトも再集計する）
+        self.project_usecase
+            .recalculate_total_working_time(user_id, &work_logs.project_id)
+            .await?;
+        if current.project_id != work_logs.project_id {
+            self.project_usecase
+                .recalculate_total_working_time(user_id, &current.project_id)
+                .await?;
+        }
+
+        Ok(overlapping_ids)
+    }
+
+    /// 時間が重複している勤怠の組み合わせを全て取得する（重複の解消用）
+    pub async fn find_overlapping_work_logs(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<Vec<WorkLogOverlap>, AppError> {
+        let work_logs: Vec<WorkLogInDB> = self
+            .stream_work_logs(user_id, &WorkLogFilter::default())
+            .await?
+            .try_collect()
+            .await?;
+        Ok(find_work_log_overlaps(&work_logs, BsonDateTime::now()))
+    }
+
+    /// 作業中の勤怠を取得する
+    pub async fn get_active_work_log(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<Option<WorkLogInDB>, AppError> {
+        Ok(self.repository.find_active(user_id).await?)
+    }
+
+    /// タイマーを開始し、終了時刻が未設定の勤怠を作成する
+    ///
+    /// 作業中の勤怠はユーザーごとに1件までとする
+    pub async fn start_work_log(
+        &self,
+        user_id: &ObjectId,
+        start: &WorkLogStart,
+    ) -> Result<ObjectId, AppError> {
+        self.ensure_associated_project(user_id, &start.project_id)
+            .await?;
+        self.ensure_no_active_work_log(user_id, None).await?;
+
+        let work_logs = WorkLogCreate {
+            project_id: start.project_id,
+            start_time: BsonDateTime::now(),
+            end_time: None,
+            break_time: None,
+            actual_work_minutes: None,
+            memo: start.memo.clone(),
+            session_id: None,
+        };
+
+        self.repository
+            .insert_one(user_id, &work_logs)
+            .await
+            .map_err(active_work_log_error)
+    }
+
+    /// タイマーを停止し、開始・終了時刻と休憩時間から実作業時間を算出する
+    ///
+    /// 作業時間が上限を超える場合は停止できない（終了時間を指定して勤怠を更新する）。
+    /// 日付をまたいだ場合は作成時と同じく日ごとに分割する
+    pub async fn stop_work_log(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+        stop: &WorkLogStop,
+        time_zone: Tz,
+    ) -> Result<WorkLogInDB, AppError> {
+        let work_log = self
+            .repository
+            .find_by_id(user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::WORK_LOG_NOT_FOUND.into()))?;
+
+        if work_log.end_time.is_some() {
+            return Err(AppError::BadRequest(
+                messages::WORK_LOG_ALREADY_STOPPED.into(),
+            ));
+        }
+
+        let end_time = BsonDateTime::now();
+        self.check_session_length(work_log.start_time, Some(end_time))
+            .map_err(|_| {
+                AppError::BadRequest(
+                    messages::TIMER_SESSION_TOO_LONG
+                        .with("max_hours", self.config.max_session_hours),
+                )
+            })?;
+        let break_time = stop.break_time.or(work_log.break_time).unwrap_or(0);
+        let actual_work_minutes =
+            calculate_actual_work_minutes(work_log.start_time, end_time, break_time);
+        if actual_work_minutes < 0 {
+            return Err(AppError::BadRequest(
+                messages::BREAK_TIME_EXCEEDS_WORK_TIME.into(),
+            ));
+        }
+
+        // 同時に停止された場合に備え、終了時刻が未設定の場合のみ更新する
+        let stopped = self
+            .repository
+            .stop(
+                user_id,
+                id,
+                end_time,
+                break_time,
+                actual_work_minutes as i32,
+                stop.memo.as_deref(),
+            )
+            .await?;
+        if !stopped {
+            return Err(AppError::BadRequest(
+                messages::WORK_LOG_ALREADY_STOPPED.into(),
+            ));
+        }
+
+        let stopped_work_log = WorkLogUpdate {
+            project_id: work_log.project_id,
+            start_time: work_log.start_time,
+            end_time: Some(end_time),
+            break_time: Some(break_time),
+            actual_work_minutes: Some(actual_work_minutes as i32),
+            memo: stop.memo.clone().or(work_log.memo),
+            session_id: None,
+        };
+        let segments = stopped_work_log.split_by_local_day(time_zone);
+        if segments.len() > 1 {
+            self.update_with_segments(user_id, id, segments).await?;
+        }
+
+        self.project_usecase
+            .recalculate_total_working_time(user_id, &work_log.project_id)
+            .await?;
+
+        self.repository
+            .find_by_id(user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::WORK_LOG_NOT_FOUND.into()))
+    }
+
+    /// 請求書に含める勤怠をロックする
+    ///
+    /// 他の請求書に含まれている・削除された勤怠が1件でもあればロックを解除し、エラーとする
+    pub async fn lock_work_logs_for_invoice(
+        &self,
+        user_id: &ObjectId,
+        ids: &[ObjectId],
+        invoice_id: &ObjectId,
+    ) -> Result<(), AppError> {
+        let locked = self
+            .repository
+            .lock_for_invoice(user_id, ids, invoice_id)
+            .await?;
+        if locked != ids.len() as u64 {
+            self.repository.unlock_invoice(user_id, invoice_id).await?;
+            return Err(AppError::BadRequest(
+                messages::WORK_LOGS_CHANGED_DURING_INVOICING.into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 請求書に含まれる勤怠のロックを解除する
+    pub async fn unlock_work_logs_for_invoice(
+        &self,
+        user_id: &ObjectId,
+        invoice_id: &ObjectId,
+    ) -> Result<(), AppError> {
+        self.repository.unlock_invoice(user_id, invoice_id).await?;
+        Ok(())
+    }
+
+    pub async fn get_deleted_work_logs(
+        &self,
+        user_id: &ObjectId,
+    ) -> Result<Vec<WorkLogInDB>, AppError> {
+        Ok(self.repository.find_deleted(user_id).await?)
+    }
+
+    /// 勤怠を論理削除し、プロジェクトの総稼働時間を再計算する
+    pub async fn delete_work_logs(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<(), AppError> {
+        let work_log = self
+            .repository
+            .find_by_id(user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::WORK_LOG_TO_DELETE_NOT_FOUND.into()))?;
+        ensure_not_invoiced(&work_log)?;
+
+        if !self.repository.soft_delete(user_id, id).await? {
+            return Err(self
+                .rejected_write_error(user_id, id, messages::WORK_LOG_TO_DELETE_NOT_FOUND)
+                .await);
+        }
+
+        self.project_usecase
+            .recalculate_total_working_time(user_id, &work_log.project_id)
+            .await?;
+
+        Ok(())
+    }
+
+    /// ゴミ箱から勤怠を復元し、プロジェクトの総稼働時間を再計算する
+    pub async fn restore_work_logs(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+    ) -> Result<(), AppError> {
+        let work_log = self
+            .repository
+            .find_deleted_by_id(user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::WORK_LOG_TO_RESTORE_NOT_FOUND.into()))?;
+
+        // 関連するプロジェクトが削除されたままだと復元後に参照できないため、先にプロジェクトの復元を求める
+        if self
+            .project_usecase
+            .get_project_by_id(user_id, &work_log.project_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::BadRequest(
+                messages::WORK_LOG_PROJECT_DELETED.into(),
+            ));
+        }
+        if work_log.end_time.is_none() {
+            self.ensure_no_active_work_log(user_id, None).await?;
+        }
+
+        if !self
+            .repository
+            .restore(user_id, id)
+            .await
+            .map_err(active_work_log_error)?
+        {
             return Err(AppError::NotFound(
-                "更新対象の勤怠が見つかりません".to_string(),
+                messages::WORK_LOG_TO_RESTORE_NOT_FOUND.into(),
             ));
         }
 
-        // プロジェクトの取得と勤怠時間の更新を並行して実行
-        let (project, _) = try_join!(self.project_usecase.get_project_by_id(id), async {
-            Ok(self.repository.update_one(*id, work_logs).await?)
-        })?;
+        self.project_usecase
+            .recalculate_total_working_time(user_id, &work_log.project_id)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 削除日時が指定日時以前の勤怠を物理削除する
+    pub async fn purge_deleted_work_logs(
+        &self,
+        deleted_before: BsonDateTime,
+    ) -> Result<u64, AppError> {
+        Ok(self.repository.purge_deleted(deleted_before).await?)
+    }
 
-        let associated_project = project.ok_or_else(|| {
-            AppError::NotFound("勤怠に関連するプロジェクトが見つかりません".to_string())
-        })?;
+    /// 日ごとに分割した勤怠のうち、最初の勤怠で既存の勤怠を更新し、残りを新たに登録する
+    async fn update_with_segments(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+        mut segments: Vec<WorkLogUpdate>,
+    ) -> Result<(), AppError> {
+        let rest: Vec<WorkLogCreate> = segments
+            .split_off(1)
+            .into_iter()
+            .map(|segment| WorkLogCreate {
+                project_id: segment.project_id,
+                start_time: segment.start_time,
+                end_time: segment.end_time,
+                break_time: segment.break_time,
+                actual_work_minutes: segment.actual_work_minutes,
+                memo: segment.memo,
+                session_id: segment.session_id,
+            })
+            .collect();
+
+        if !self
+            .repository
+            .update_one(user_id, *id, &segments[0])
+            .await
+            .map_err(active_work_log_error)?
+        {
+            return Err(self
+                .rejected_write_error(user_id, id, messages::WORK_LOG_TO_UPDATE_NOT_FOUND)
+                .await);
+        }
+        if !rest.is_empty() {
+            self.repository
+                .insert_many(user_id, &rest)
+                .await
+                .map_err(active_work_log_error)?;
+        }
+        Ok(())
+    }
+
+    /// 勤怠の更新・削除が対象なしとなった場合のエラー
+    ///
+    /// 確認後に請求書に含まれた場合は編集不可、削除された場合は見つからないエラーとする
+    async fn rejected_write_error(
+        &self,
+        user_id: &ObjectId,
+        id: &ObjectId,
+        not_found: MessageCode,
+    ) -> AppError {
+        match self.repository.find_by_id(user_id, id).await {
+            Ok(Some(work_log)) => ensure_not_invoiced(&work_log)
+                .err()
+                .unwrap_or_else(|| AppError::NotFound(not_found.into())),
+            Ok(None) => AppError::NotFound(not_found.into()),
+            Err(e) => e.into(),
+        }
+    }
+
+    /// 開始から終了までの時間が上限以内か確認する（終了時間が未設定の場合は確認しない）
+    fn check_session_length(
+        &self,
+        start_time: BsonDateTime,
+        end_time: Option<BsonDateTime>,
+    ) -> Result<(), Message> {
+        let Some(end_time) = end_time else {
+            return Ok(());
+        };
+        let max_millis = self.config.max_session_hours * 60 * 60 * 1000;
+        if end_time.timestamp_millis() - start_time.timestamp_millis() > max_millis {
+            return Err(messages::SESSION_TOO_LONG.with("max_hours", self.config.max_session_hours));
+        }
+        Ok(())
+    }
 
-        // 実作業時間を使用して総稼働時間を更新
-        let actual_work_minutes = work_logs.actual_work_minutes.unwrap_or(0);
-        let updated_total_working_time =
-            associated_project.total_working_time + actual_work_minutes as i64 * 60; // 分を秒に変換
+    /// 期間が既存の勤怠と重複していないか確認し、重複している勤怠のIDを返す
+    ///
+    /// `allow_overlap`でない場合、重複があればエラーとする
+    /// （`exclude`の勤怠と、`exclude`と同じセッションから分割された勤怠は対象外とする）
+    async fn check_overlaps(
+        &self,
+        user_id: &ObjectId,
+        start_time: BsonDateTime,
+        end_time: Option<BsonDateTime>,
+        exclude: Option<&WorkLogInDB>,
+        allow_overlap: bool,
+    ) -> Result<Vec<ObjectId>, AppError> {
+        let exclude_id = exclude.and_then(|work_log| work_log.id.as_ref());
+        let exclude_session_id = exclude.and_then(|work_log| work_log.session_id.as_ref());
+        let overlapping_ids: Vec<ObjectId> = self
+            .repository
+            .find_overlapping(
+                user_id,
+                start_time,
+                end_time,
+                exclude_id,
+                exclude_session_id,
+            )
+            .await?
+            .into_iter()
+            .filter_map(|work_log| work_log.id)
+            .collect();
+        if !overlapping_ids.is_empty() && !allow_overlap {
+            return Err(AppError::Conflict(
+                messages::WORK_LOG_OVERLAP.into(),
+                overlapping_ids,
+            ));
+        }
+        Ok(overlapping_ids)
+    }
 
-        let project_update = ProjectUpdate {
-            total_working_time: updated_total_working_time,
-            ..ProjectUpdate::from(associated_project)
+    /// インポートする行のうち、既存の勤怠または先の行と時間が重複する行をエラーとして除外する
+    async fn reject_overlapping_imports(
+        &self,
+        user_id: &ObjectId,
+        work_logs: Vec<(usize, WorkLogCreate)>,
+        errors: &mut Vec<WorkLogImportError>,
+    ) -> Result<Vec<(usize, WorkLogCreate)>, AppError> {
+        // インポートする行は全て終了済みのため、全行の期間をまとめて既存の勤怠を1回で取得する
+        let (Some(from), Some(to)) = (
+            work_logs
+                .iter()
+                .map(|(_, work_log)| work_log.start_time)
+                .min(),
+            work_logs
+                .iter()
+                .filter_map(|(_, work_log)| work_log.end_time)
+                .max(),
+        ) else {
+            return Ok(work_logs);
         };
+        let now = BsonDateTime::now();
+        let existing: Vec<(ObjectId, BsonDateTime, BsonDateTime)> = self
+            .repository
+            .find_overlapping(user_id, from, Some(to), None, None)
+            .await?
+            .into_iter()
+            .filter_map(|work_log| {
+                Some((
+                    work_log.id?,
+                    work_log.start_time,
+                    work_log.end_time.unwrap_or(now),
+                ))
+            })
+            .collect();
+
+        let mut accepted: Vec<(usize, WorkLogCreate)> = Vec::new();
+        for (line, work_log) in work_logs {
+            let start_time = work_log.start_time;
+            let end_time = work_log.end_time.unwrap_or(now);
+            let overlaps = |other_start: BsonDateTime, other_end: BsonDateTime| {
+                other_start < end_time && start_time < other_end
+            };
+
+            let overlapping_ids: Vec<String> = existing
+                .iter()
+                .filter(|(_, other_start, other_end)| overlaps(*other_start, *other_end))
+                .map(|(id, _, _)| id.to_hex())
+                .collect();
+            if !overlapping_ids.is_empty() {
+                errors.push(WorkLogImportError::new(
+                    line,
+                    Some("time"),
+                    messages::IMPORT_OVERLAPS_EXISTING.with("ids", overlapping_ids.join(", ")),
+                ));
+                continue;
+            }
+            if let Some((other_line, _)) = accepted
+                .iter()
+                .find(|(_, other)| overlaps(other.start_time, other.end_time.unwrap_or(now)))
+            {
+                errors.push(WorkLogImportError::new(
+                    line,
+                    Some("time"),
+                    messages::IMPORT_OVERLAPS_ROW.with("line", other_line),
+                ));
+                continue;
+            }
+            accepted.push((line, work_log));
+        }
+
+        Ok(accepted)
+    }
+
+    /// 作業中の勤怠が他に存在しないことを確認する（`exclude_id`の勤怠は対象外とする）
+    async fn ensure_no_active_work_log(
+        &self,
+        user_id: &ObjectId,
+        exclude_id: Option<&ObjectId>,
+    ) -> Result<(), AppError> {
+        let active = self.repository.find_active(user_id).await?;
+        if active.is_some_and(|work_log| work_log.id.as_ref() != exclude_id) {
+            return Err(AppError::BadRequest(
+                messages::ACTIVE_WORK_LOG_EXISTS.into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 勤怠に関連するプロジェクトがログインユーザーのものとして存在するか確認する
+    async fn ensure_associated_project(
+        &self,
+        user_id: &ObjectId,
+        project_id: &ObjectId,
+    ) -> Result<(), AppError> {
         self.project_usecase
-            .update_project(&work_logs.project_id, &project_update)
-            .await?;
+            .get_project_by_id(user_id, project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(messages::WORK_LOG_PROJECT_NOT_FOUND.into()))?;
+        Ok(())
+    }
+}
 
-        Ok(true)
+/// 作業中の勤怠の重複（同時に開始された場合のユニーク制約違反）は、事前の確認と同じエラーとして返す
+fn active_work_log_error(e: RepositoryError) -> AppError {
+    match e {
+        RepositoryError::DuplicateError(message) => AppError::BadRequest(message),
+        e => e.into(),
     }
 }
+
+/// 請求書に含まれる勤怠は請求内容と食い違わないよう編集・削除できない
+fn ensure_not_invoiced(work_log: &WorkLogInDB) -> Result<(), AppError> {
+    if work_log.invoice_id.is_some() {
+        return Err(AppError::BadRequest(messages::WORK_LOG_INVOICED.into()));
+    }
+    Ok(())
+}
+
+/// インポートする行のプロジェクト（IDまたはプロジェクト名）を解決する
+struct ProjectResolver<'a> {
+    ids: HashMap<ObjectId, &'a ProjectInDB>,
+    titles: HashMap<&'a str, Vec<ObjectId>>,
+}
+
+impl<'a> ProjectResolver<'a> {
+    fn new(projects: &'a [ProjectInDB]) -> Self {
+        let mut ids = HashMap::new();
+        let mut titles: HashMap<&str, Vec<ObjectId>> = HashMap::new();
+        for project in projects {
+            let Some(id) = project.id else {
+                continue;
+            };
+            ids.insert(id, project);
+            titles.entry(project.title.as_str()).or_default().push(id);
+        }
+        Self { ids, titles }
+    }
+
+    fn resolve(&self, project: &str) -> Result<ObjectId, Message> {
+        if let Ok(id) = ObjectId::parse_str(project) {
+            if self.ids.contains_key(&id) {
+                return Ok(id);
+            }
+        }
+        match self.titles.get(project).map(Vec::as_slice) {
+            Some([id]) => Ok(*id),
+            Some(_) => Err(messages::IMPORT_PROJECT_AMBIGUOUS.with("project", project)),
+            None => Err(messages::IMPORT_PROJECT_NOT_FOUND.with("project", project)),
+        }
+    }
+}
+
+/// インポートする行を検証し、勤怠の登録データに変換する
+fn to_work_log_create(
+    line: usize,
+    record: WorkLogImportRecord,
+    resolver: &ProjectResolver,
+) -> Result<WorkLogCreate, Vec<WorkLogImportError>> {
+    let mut errors = Vec::new();
+
+    let project_id = resolver
+        .resolve(&record.project)
+        .map_err(|message| errors.push(WorkLogImportError::new(line, Some("project"), message)))
+        .ok();
+    let start_time = BsonDateTime::parse_rfc3339_str(&record.start_time)
+        .map_err(|_| {
+            errors.push(WorkLogImportError::new(
+                line,
+                Some("start_time"),
+                messages::IMPORT_INVALID_START_TIME,
+            ))
+        })
+        .ok();
+    // 過去の勤怠の取り込みが目的のため、作業中の勤怠はインポートできない
+    let end_time = match record.end_time.as_deref() {
+        Some(end_time) if !end_time.is_empty() => BsonDateTime::parse_rfc3339_str(end_time)
+            .map_err(|_| {
+                errors.push(WorkLogImportError::new(
+                    line,
+                    Some("end_time"),
+                    messages::IMPORT_INVALID_END_TIME,
+                ))
+            })
+            .ok(),
+        _ => {
+            errors.push(WorkLogImportError::new(
+                line,
+                Some("end_time"),
+                messages::IMPORT_END_TIME_REQUIRED,
+            ));
+            None
+        }
+    };
+
+    let (Some(project_id), Some(start_time), Some(end_time)) = (project_id, start_time, end_time)
+    else {
+        return Err(errors);
+    };
+
+    let work_log = WorkLogCreate {
+        project_id,
+        start_time,
+        end_time: Some(end_time),
+        break_time: record.break_time,
+        actual_work_minutes: record.actual_work_minutes,
+        memo: record.memo.filter(|memo| !memo.is_empty()),
+        session_id: None,
+    };
+    if let Err(validation_errors) = work_log.validate_all() {
+        errors.extend(
+            InvalidField::from_validation_errors(&validation_errors)
+                .into_iter()
+                .map(|field| WorkLogImportError::new(line, Some(&field.field), field.message)),
+        );
+        return Err(errors);
+    }
+
+    Ok(work_log)
+}
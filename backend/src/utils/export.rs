@@ -0,0 +1,84 @@
+use crate::errors::app_error::AppError;
+use crate::models::exports::ExportCell;
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+
+/// Excelで開いた際に文字化けしないよう、CSVの先頭に付与するBOM
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// セルの並びをCSVの1行（改行を含む）に変換する
+pub fn csv_line<I, T>(record: I) -> Result<Vec<u8>, AppError>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(record)
+        .map_err(|e| AppError::InternalServerError(format!("CSVの生成に失敗しました: {}", e)))?;
+    writer
+        .into_inner()
+        .map_err(|e| AppError::InternalServerError(format!("CSVの生成に失敗しました: {}", e)))
+}
+
+/// セルの並びをCSVの1行に変換する
+pub fn csv_cells(cells: Vec<ExportCell>) -> Result<Vec<u8>, AppError> {
+    csv_line(cells.into_iter().map(|cell| match cell {
+        ExportCell::Text(value) => value,
+        ExportCell::Number(value) => value.to_string(),
+        ExportCell::Empty => String::new(),
+    }))
+}
+
+/// XLSXを1行ずつ組み立てる
+///
+/// XLSXはZIP形式のため途中までの内容を送信できない。ワークブックの生成は全行の書き込み後に行う。
+pub struct XlsxBuilder {
+    worksheet: Worksheet,
+    row: u32,
+}
+
+impl XlsxBuilder {
+    pub fn new(sheet_name: &str, headers: &[&str]) -> Result<Self, AppError> {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_name(sheet_name).map_err(xlsx_error)?;
+        let header_format = Format::new().set_bold();
+        for (col, header) in headers.iter().enumerate() {
+            worksheet
+                .write_string_with_format(0, col as u16, *header, &header_format)
+                .map_err(xlsx_error)?;
+        }
+        Ok(Self { worksheet, row: 1 })
+    }
+
+    pub fn write_row(&mut self, cells: Vec<ExportCell>) -> Result<(), AppError> {
+        for (col, cell) in cells.into_iter().enumerate() {
+            let col = col as u16;
+            match cell {
+                ExportCell::Text(value) => {
+                    self.worksheet
+                        .write_string(self.row, col, value)
+                        .map_err(xlsx_error)?;
+                }
+                ExportCell::Number(value) => {
+                    self.worksheet
+                        .write_number(self.row, col, value as f64)
+                        .map_err(xlsx_error)?;
+                }
+                ExportCell::Empty => {}
+            }
+        }
+        self.row += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<Vec<u8>, AppError> {
+        self.worksheet.autofit();
+        let mut workbook = Workbook::new();
+        workbook.push_worksheet(self.worksheet);
+        workbook.save_to_buffer().map_err(xlsx_error)
+    }
+}
+
+fn xlsx_error(e: XlsxError) -> AppError {
+    AppError::InternalServerError(format!("XLSXの生成に失敗しました: {}", e))
+}
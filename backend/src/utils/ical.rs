@@ -0,0 +1,126 @@
+use bson::DateTime as BsonDateTime;
+use chrono::{Duration, NaiveDate};
+
+/// iCalendar（RFC 5545）形式のカレンダーを組み立てる
+pub struct ICalendarBuilder {
+    lines: Vec<String>,
+    dtstamp: String,
+}
+
+impl ICalendarBuilder {
+    pub fn new(calendar_name: &str) -> Self {
+        let lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//DevTrackr//Work Logs//JA".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+            "METHOD:PUBLISH".to_string(),
+            format!("X-WR-CALNAME:{}", escape_text(calendar_name)),
+        ];
+        Self {
+            lines,
+            dtstamp: format_date_time(BsonDateTime::now()),
+        }
+    }
+
+    /// 日時を指定した予定を追加する
+    pub fn add_event(
+        &mut self,
+        uid: &str,
+        start: BsonDateTime,
+        end: BsonDateTime,
+        summary: &str,
+        description: Option<&str>,
+    ) {
+        self.push_event(
+            uid,
+            format!("DTSTART:{}", format_date_time(start)),
+            format!("DTEND:{}", format_date_time(end)),
+            summary,
+            description,
+        );
+    }
+
+    /// 終日の予定を追加する
+    pub fn add_all_day_event(&mut self, uid: &str, date: NaiveDate, summary: &str) {
+        // DTENDは翌日（その日を含まない）を指定する
+        self.push_event(
+            uid,
+            format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+            format!(
+                "DTEND;VALUE=DATE:{}",
+                (date + Duration::days(1)).format("%Y%m%d")
+            ),
+            summary,
+            None,
+        );
+    }
+
+    fn push_event(
+        &mut self,
+        uid: &str,
+        dtstart: String,
+        dtend: String,
+        summary: &str,
+        description: Option<&str>,
+    ) {
+        self.lines.push("BEGIN:VEVENT".to_string());
+        self.lines.push(format!("UID:{}", uid));
+        self.lines.push(format!("DTSTAMP:{}", self.dtstamp));
+        self.lines.push(dtstart);
+        self.lines.push(dtend);
+        self.lines.push(format!("SUMMARY:{}", escape_text(summary)));
+        if let Some(description) = description.filter(|description| !description.is_empty()) {
+            self.lines
+                .push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        self.lines.push("END:VEVENT".to_string());
+    }
+
+    pub fn finish(mut self) -> String {
+        self.lines.push("END:VCALENDAR".to_string());
+        let mut calendar = String::new();
+        for line in &self.lines {
+            fold_line(line, &mut calendar);
+        }
+        calendar
+    }
+}
+
+/// 日時をUTCの「YYYYMMDDTHHMMSSZ」形式に変換する
+fn format_date_time(date_time: BsonDateTime) -> String {
+    date_time.to_chrono().format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// テキスト値のエスケープ（バックスラッシュ・セミコロン・カンマ・改行）
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 1行を75オクテット以内に折り返してCRLFで出力する（マルチバイト文字の途中では折り返さない）
+fn fold_line(line: &str, output: &mut String) {
+    const MAX_OCTETS: usize = 75;
+
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_OCTETS {
+            // 継続行は先頭の空白1文字を含めて数える
+            output.push_str("\r\n ");
+            octets = 1;
+        }
+        output.push(c);
+        octets += c.len_utf8();
+    }
+    output.push_str("\r\n");
+}
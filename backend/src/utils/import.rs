@@ -0,0 +1,79 @@
+use crate::errors::app_error::AppError;
+use crate::i18n::messages;
+use crate::models::imports::{ImportFormat, ParsedImportRow, IMPORT_MAX_ROWS};
+
+/// 入力データを1行ずつ解釈する（行単位の形式エラーは各行の結果として返す）
+pub fn parse_work_log_import(
+    body: &[u8],
+    format: ImportFormat,
+) -> Result<Vec<ParsedImportRow>, AppError> {
+    // Excelで保存したCSVなどに付与されるBOMを除去
+    let body = body.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(body);
+
+    let rows = match format {
+        ImportFormat::Csv => parse_csv(body)?,
+        ImportFormat::Jsonl => parse_jsonl(body)?,
+    };
+
+    if rows.is_empty() {
+        return Err(AppError::BadRequest(messages::IMPORT_EMPTY.into()));
+    }
+    if rows.len() > IMPORT_MAX_ROWS {
+        return Err(AppError::BadRequest(
+            messages::IMPORT_TOO_MANY_ROWS.with("max", IMPORT_MAX_ROWS),
+        ));
+    }
+
+    Ok(rows)
+}
+
+fn parse_csv(body: &[u8]) -> Result<Vec<ParsedImportRow>, AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(body);
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::BadRequest(messages::IMPORT_INVALID_CSV_HEADER.with("detail", e)))?
+        .clone();
+
+    let mut rows = Vec::new();
+    for (index, result) in reader.records().enumerate() {
+        // 列見出しが1行目のため、データは2行目から始まる
+        let fallback_line = index + 2;
+        let row = match result {
+            Ok(record) => ParsedImportRow {
+                line: record
+                    .position()
+                    .map_or(fallback_line, |position| position.line() as usize),
+                record: record
+                    .deserialize(Some(&headers))
+                    .map_err(|e| messages::IMPORT_INVALID_ROW.with("detail", e)),
+            },
+            Err(e) => ParsedImportRow {
+                line: e
+                    .position()
+                    .map_or(fallback_line, |position| position.line() as usize),
+                record: Err(messages::IMPORT_INVALID_ROW.with("detail", e)),
+            },
+        };
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn parse_jsonl(body: &[u8]) -> Result<Vec<ParsedImportRow>, AppError> {
+    let body = std::str::from_utf8(body)
+        .map_err(|_| AppError::BadRequest(messages::IMPORT_INVALID_UTF8.into()))?;
+
+    Ok(body
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| ParsedImportRow {
+            line: index + 1,
+            record: serde_json::from_str(line)
+                .map_err(|e| messages::IMPORT_INVALID_ROW.with("detail", e)),
+        })
+        .collect())
+}
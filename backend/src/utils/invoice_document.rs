@@ -0,0 +1,237 @@
+use crate::models::invoices::InvoiceInDB;
+use crate::utils::pdf::{PdfBuilder, PAGE_HEIGHT, PAGE_WIDTH};
+use chrono::NaiveDate;
+
+const MARGIN: f32 = 50.0;
+const ROW_HEIGHT: f32 = 20.0;
+
+/// 請求書をHTMLに変換する（ブラウザの印刷機能でそのまま印刷できるよう、スタイルは埋め込む）
+pub fn render_invoice_html(invoice: &InvoiceInDB) -> String {
+    let mut rows = String::new();
+    for item in &invoice.line_items {
+        rows.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            escape_html(&item.description),
+            format_hours(item.work_minutes),
+            format_yen(item.hourly_rate as i64),
+            format_yen(item.amount),
+        ));
+    }
+    let due_date = invoice
+        .due_date
+        .map(|due_date| format!("<p>お支払期限: {}</p>\n", format_date(due_date)))
+        .unwrap_or_default();
+    let notes = invoice
+        .notes
+        .as_deref()
+        .map(|notes| {
+            format!(
+                "<h2>備考</h2>\n<p class=\"notes\">{}</p>\n",
+                escape_html(notes)
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="ja">
+<head>
+<meta charset="utf-8">
+<title>請求書 {number}</title>
+<style>
+body {{ font-family: sans-serif; margin: 40px; color: #222; }}
+h1 {{ text-align: center; letter-spacing: 0.5em; }}
+table {{ width: 100%; border-collapse: collapse; margin-top: 16px; }}
+th, td {{ border: 1px solid #999; padding: 6px 8px; }}
+th {{ background: #eee; }}
+.num {{ text-align: right; }}
+.total {{ font-size: 1.4em; font-weight: bold; }}
+.notes {{ white-space: pre-wrap; }}
+</style>
+</head>
+<body>
+<h1>請求書</h1>
+<p>請求書番号: {number}<br>発行日: {issue_date}</p>
+<h2>{company} 御中</h2>
+<p>請求期間: {period_start} 〜 {period_end}</p>
+{due_date}<p class="total">ご請求金額: {total}（税込）</p>
+<table>
+<thead><tr><th>品目</th><th>作業時間</th><th>単価（時給）</th><th>金額</th></tr></thead>
+<tbody>
+{rows}</tbody>
+<tfoot>
+<tr><td colspan="3" class="num">小計</td><td class="num">{subtotal}</td></tr>
+<tr><td colspan="3" class="num">消費税（{tax_rate}%）</td><td class="num">{tax_amount}</td></tr>
+<tr><td colspan="3" class="num">合計</td><td class="num">{total}</td></tr>
+</tfoot>
+</table>
+{notes}</body>
+</html>
+"#,
+        number = escape_html(&invoice.invoice_number),
+        issue_date = format_date(invoice.issue_date),
+        company = escape_html(&invoice.company_name),
+        period_start = format_date(invoice.period_start),
+        period_end = format_date(invoice.period_end),
+        due_date = due_date,
+        total = format_yen(invoice.total),
+        rows = rows,
+        subtotal = format_yen(invoice.subtotal),
+        tax_rate = invoice.tax_rate,
+        tax_amount = format_yen(invoice.tax_amount),
+        notes = notes,
+    )
+}
+
+/// 請求書をPDFに変換する（明細が1ページに収まらない場合は改ページする）
+pub fn render_invoice_pdf(invoice: &InvoiceInDB) -> Vec<u8> {
+    let right = PAGE_WIDTH - MARGIN;
+    // 列の右端の位置（品目, 作業時間, 単価, 金額）
+    let columns = [MARGIN, 330.0, 430.0, right];
+
+    let mut pdf = PdfBuilder::new();
+    let mut y = PAGE_HEIGHT - MARGIN - 20.0;
+    pdf.text(PAGE_WIDTH / 2.0 - 36.0, y, 24.0, "請求書");
+    y -= 40.0;
+    pdf.text_right(
+        right,
+        y,
+        10.0,
+        &format!("請求書番号: {}", invoice.invoice_number),
+    );
+    pdf.text(MARGIN, y, 16.0, &format!("{} 御中", invoice.company_name));
+    y -= 16.0;
+    pdf.text_right(
+        right,
+        y,
+        10.0,
+        &format!("発行日: {}", format_date(invoice.issue_date)),
+    );
+    y -= 28.0;
+    pdf.text(
+        MARGIN,
+        y,
+        11.0,
+        &format!(
+            "請求期間: {} 〜 {}",
+            format_date(invoice.period_start),
+            format_date(invoice.period_end)
+        ),
+    );
+    if let Some(due_date) = invoice.due_date {
+        y -= 18.0;
+        pdf.text(
+            MARGIN,
+            y,
+            11.0,
+            &format!("お支払期限: {}", format_date(due_date)),
+        );
+    }
+    y -= 30.0;
+    pdf.text(
+        MARGIN,
+        y,
+        16.0,
+        &format!("ご請求金額: {}（税込）", format_yen(invoice.total)),
+    );
+    y -= 30.0;
+
+    let header = |pdf: &mut PdfBuilder, y: f32| {
+        pdf.line(MARGIN, y + ROW_HEIGHT - 5.0, right, y + ROW_HEIGHT - 5.0);
+        pdf.text(columns[0], y, 10.0, "品目");
+        pdf.text_right(columns[1], y, 10.0, "作業時間");
+        pdf.text_right(columns[2], y, 10.0, "単価（時給）");
+        pdf.text_right(columns[3], y, 10.0, "金額");
+        pdf.line(MARGIN, y - 5.0, right, y - 5.0);
+    };
+    header(&mut pdf, y);
+    for item in &invoice.line_items {
+        y -= ROW_HEIGHT;
+        if y < MARGIN + ROW_HEIGHT {
+            pdf.new_page();
+            y = PAGE_HEIGHT - MARGIN - ROW_HEIGHT;
+            header(&mut pdf, y);
+            y -= ROW_HEIGHT;
+        }
+        pdf.text(columns[0], y, 10.0, &item.description);
+        pdf.text_right(columns[1], y, 10.0, &format_hours(item.work_minutes));
+        pdf.text_right(columns[2], y, 10.0, &format_yen(item.hourly_rate as i64));
+        pdf.text_right(columns[3], y, 10.0, &format_yen(item.amount));
+    }
+    pdf.line(MARGIN, y - 5.0, right, y - 5.0);
+
+    // 合計欄と備考は途中で改ページしないよう、収まらない場合は次のページに描画する
+    let notes: Vec<&str> = invoice
+        .notes
+        .as_deref()
+        .map_or(vec![], |notes| notes.lines().collect());
+    let footer_height = ROW_HEIGHT * (4 + notes.len()) as f32 + 20.0;
+    if y - footer_height < MARGIN {
+        pdf.new_page();
+        y = PAGE_HEIGHT - MARGIN;
+    }
+    let totals = [
+        ("小計".to_string(), invoice.subtotal),
+        (
+            format!("消費税（{}%）", invoice.tax_rate),
+            invoice.tax_amount,
+        ),
+        ("合計".to_string(), invoice.total),
+    ];
+    for (label, amount) in totals {
+        y -= ROW_HEIGHT;
+        pdf.text_right(columns[2], y, 10.0, &label);
+        pdf.text_right(columns[3], y, 10.0, &format_yen(amount));
+    }
+    if !notes.is_empty() {
+        y -= ROW_HEIGHT + 20.0;
+        pdf.text(MARGIN, y, 10.0, "備考");
+        for line in notes {
+            y -= ROW_HEIGHT;
+            pdf.text(MARGIN, y, 10.0, line);
+        }
+    }
+
+    pdf.finish()
+}
+
+/// 金額を「¥1,234」形式に変換する
+fn format_yen(amount: i64) -> String {
+    let digits = amount.unsigned_abs().to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3 + 2);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(c);
+    }
+    if amount < 0 {
+        format!("-¥{}", formatted)
+    } else {
+        format!("¥{}", formatted)
+    }
+}
+
+/// 分を「12:34」（時間:分）形式に変換する
+fn format_hours(minutes: i64) -> String {
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y年%m月%d日").to_string()
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,18 +1,24 @@
+use crate::models::users::EngineerRole;
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use mongodb::bson::DateTime as BsonDateTime;
 use serde::{Deserialize, Serialize};
 use std::env;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: String, // ユーザーID(subject)
+    pub jti: String, // トークンID(JWT ID). auth_tokensドキュメントのIDと一致する
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<EngineerRole>, // 発行時点のユーザーのロール
     pub exp: usize,  // 有効期限(expiration time)
     pub iat: usize,  // 発行時刻(issued at)
 }
 
 pub fn create_access_token(
     user_id: &str,
+    token_id: &str,
+    role: Option<EngineerRole>,
     secret: &[u8],
 ) -> Result<String, jsonwebtoken::errors::Error> {
     let access_token_exp = env::var("ACCESS_TOKEN_EXPIRY_HOURS")
@@ -28,6 +34,8 @@ pub fn create_access_token(
 
     let claims = Claims {
         sub: user_id.to_owned(),
+        jti: token_id.to_owned(),
+        role,
         exp: expiration as usize,
         iat: now.timestamp() as usize,
     };
@@ -41,6 +49,8 @@ pub fn create_access_token(
 
 pub fn create_refresh_token(
     user_id: &str,
+    token_id: &str,
+    role: Option<EngineerRole>,
     secret: &[u8],
 ) -> Result<String, jsonwebtoken::errors::Error> {
     let refresh_token_exp = env::var("REFRESH_TOKEN_EXPIRY_DAYS")
@@ -55,6 +65,8 @@ pub fn create_refresh_token(
 
     let claims = Claims {
         sub: user_id.to_owned(),
+        jti: token_id.to_owned(),
+        role,
         exp: expiration as usize,
         iat: Utc::now().timestamp() as usize,
     };
@@ -73,41 +85,15 @@ pub fn verify_token(token: &str, secret: &[u8]) -> Result<Claims, jsonwebtoken::
     Ok(token_data.claims)
 }
 
-/// リフレッシュ時の新しいアクセストークン生成
-pub fn create_refreshed_access_token(
-    user_id: &str,
-    secret: &[u8],
-) -> Result<(String, BsonDateTime), jsonwebtoken::errors::Error> {
-    let access_token_exp = env::var("ACCESS_TOKEN_EXPIRY_HOURS")
-        .expect("ACCESS_TOKEN_EXPIRY_HOURSが設定されていません")
-        .parse::<i64>()
-        .expect("ACCESS_TOKEN_EXPIRY_HOURSは有効な整数である必要があります");
-
-    let now = Utc::now();
-    let exp = now + Duration::hours(access_token_exp);
-
-    let claims = Claims {
-        sub: user_id.to_owned(),
-        exp: exp.timestamp() as usize,
-        iat: now.timestamp() as usize,
-    };
-
-    let token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret),
-    )?;
-
-    Ok((token, BsonDateTime::from_chrono(exp)))
-}
-
 /// 認証トークンのペアを生成する関数
 pub fn create_token_pair(
     user_id: &str,
+    token_id: &str,
+    role: Option<EngineerRole>,
     secret: &[u8],
 ) -> Result<(String, String, BsonDateTime, BsonDateTime), jsonwebtoken::errors::Error> {
-    let access_token = create_access_token(user_id, secret)?;
-    let refresh_token = create_refresh_token(user_id, secret)?;
+    let access_token = create_access_token(user_id, token_id, role.clone(), secret)?;
+    let refresh_token = create_refresh_token(user_id, token_id, role, secret)?;
 
     let access_token_exp = env::var("ACCESS_TOKEN_EXPIRY_HOURS")
         .expect("ACCESS_TOKEN_EXPIRY_HOURSが設定されていません")
@@ -125,7 +111,56 @@ pub fn create_token_pair(
     Ok((access_token, refresh_token, expires_at, refresh_expires_at))
 }
 
-/// 認証ヘッダーからトークンを抽出する関数
-pub fn extract_token(auth_header: &str) -> String {
-    auth_header.trim_start_matches("Bearer ").to_string()
+/// メールアドレスの確認用リンクに含めるトークンの用途
+const EMAIL_VERIFICATION_PURPOSE: &str = "email_verification";
+
+/// メールアドレスの確認用トークンのClaims
+///
+/// 認証トークンとは必須の項目が異なるため、互いに取り違えて使用することはできない
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmailVerificationClaims {
+    pub sub: String,     // ユーザーID(subject)
+    pub email: String,   // 確認するメールアドレス
+    pub purpose: String, // トークンの用途
+    pub exp: usize,      // 有効期限(expiration time)
+    pub iat: usize,      // 発行時刻(issued at)
+}
+
+/// メールアドレスの確認用トークンを生成する関数
+pub fn create_email_verification_token(
+    user_id: &str,
+    email: &str,
+    ttl: std::time::Duration,
+    secret: &[u8],
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let now = Utc::now();
+    let claims = EmailVerificationClaims {
+        sub: user_id.to_owned(),
+        email: email.to_owned(),
+        purpose: EMAIL_VERIFICATION_PURPOSE.to_owned(),
+        exp: (now + ttl).timestamp() as usize,
+        iat: now.timestamp() as usize,
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret),
+    )
+}
+
+/// メールアドレスの確認用トークンを検証し、有効な場合はClaimsを返す関数
+pub fn verify_email_verification_token(
+    token: &str,
+    secret: &[u8],
+) -> Result<EmailVerificationClaims, jsonwebtoken::errors::Error> {
+    let validation = Validation::default();
+    let claims =
+        decode::<EmailVerificationClaims>(token, &DecodingKey::from_secret(secret), &validation)?
+            .claims;
+
+    if claims.purpose != EMAIL_VERIFICATION_PURPOSE {
+        return Err(jsonwebtoken::errors::ErrorKind::InvalidToken.into());
+    }
+    Ok(claims)
 }
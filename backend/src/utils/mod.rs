@@ -1,7 +1,14 @@
 pub mod cookie_util;
 pub mod deserializer;
+pub mod export;
+pub mod ical;
+pub mod import;
 pub mod init_data;
+pub mod invoice_document;
 pub mod jwt;
+pub mod pagination;
 pub mod password;
+pub mod pdf;
 pub mod serializer;
 pub mod test_s3_upload;
+pub mod token;
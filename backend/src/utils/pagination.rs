@@ -0,0 +1,293 @@
+use crate::errors::app_error::AppError;
+use crate::i18n::messages;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use bson::{doc, oid::ObjectId, Bson, Document};
+use hmac::{Hmac, Mac};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use sha2::Sha256;
+use std::env;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// カーソルに付与する署名（HMAC-SHA256）のバイト長
+const SIGNATURE_LEN: usize = 32;
+
+lazy_static! {
+    // カーソルの署名鍵（JWTの秘密鍵をそのまま使い回さず、カーソル用に派生させる）
+    static ref CURSOR_KEY: Option<Vec<u8>> = env::var("JWT_SECRET").ok().and_then(|secret| {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).ok()?;
+        mac.update(b"page-cursor");
+        Some(mac.finalize().into_bytes().to_vec())
+    });
+}
+
+/// `limit`が指定されなかった場合の1ページあたりの件数
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+
+/// キーセットページネーション用のカーソル
+///
+/// 最後に返したドキュメントのソートキーの値と`_id`を保持する。
+/// クライアントには中身を意識させないよう、BSONに署名を付けてbase64(URLセーフ)でエンコードした文字列として渡す。
+/// 署名によりクライアントが任意の値を埋め込んだカーソルを作れないようにする。
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageCursor {
+    keys: Vec<CursorKey>,
+    id: ObjectId,
+}
+
+/// カーソルに保持するソートキー（フィールド名、ソート順、最後のドキュメントの値）
+#[derive(Debug, Clone, PartialEq)]
+struct CursorKey {
+    field: String,
+    order: i8,
+    value: Bson,
+}
+
+impl PageCursor {
+    /// ドキュメントとソート条件からカーソルを生成する
+    fn from_item<T: Serialize>(item: &T, sort: &[(String, i8)]) -> Result<Self, AppError> {
+        let document = bson::to_document(item).map_err(|e| {
+            AppError::InternalServerError(format!("カーソルの生成に失敗しました: {}", e))
+        })?;
+        let id = document.get_object_id("_id").map_err(|e| {
+            AppError::InternalServerError(format!("カーソルの生成に失敗しました: {}", e))
+        })?;
+        let keys = sort
+            .iter()
+            .map(|(field, order)| CursorKey {
+                field: field.clone(),
+                order: *order,
+                value: document.get(field).cloned().unwrap_or(Bson::Null),
+            })
+            .collect();
+
+        Ok(Self { keys, id })
+    }
+
+    pub fn encode(&self) -> Result<String, AppError> {
+        let keys: Vec<Bson> = self
+            .keys
+            .iter()
+            .map(|key| {
+                Bson::Document(doc! {
+                    "f": &key.field,
+                    "o": key.order as i32,
+                    "v": key.value.clone(),
+                })
+            })
+            .collect();
+        let document = doc! { "k": keys, "id": self.id };
+        let mut bytes = bson::to_vec(&document).map_err(|e| {
+            AppError::InternalServerError(format!("カーソルの生成に失敗しました: {}", e))
+        })?;
+        let mut mac = cursor_mac()?;
+        mac.update(&bytes);
+        bytes.extend_from_slice(&mac.finalize().into_bytes());
+
+        Ok(URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    /// 署名を検証してカーソルを復元する（不正な形式や署名の場合は`None`）
+    fn decode(value: &str, mut mac: HmacSha256) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(value).ok()?;
+        let (body, signature) = bytes.split_at(bytes.len().checked_sub(SIGNATURE_LEN)?);
+        mac.update(body);
+        mac.verify_slice(signature).ok()?;
+
+        let document = Document::from_reader(body).ok()?;
+        let id = document.get_object_id("id").ok()?;
+        let keys = document
+            .get_array("k")
+            .ok()?
+            .iter()
+            .map(|key| {
+                let key = key.as_document()?;
+                Some(CursorKey {
+                    field: key.get_str("f").ok()?.to_string(),
+                    order: i8::try_from(key.get_i32("o").ok()?).ok()?,
+                    value: key.get("v").cloned().unwrap_or(Bson::Null),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self { keys, id })
+    }
+
+    /// クエリパラメータのカーソルを解析する
+    ///
+    /// カーソルは発行時と同じソート条件（フィールドと順序）でのみ使用できる。
+    /// 改ざんされたカーソル、ソート条件の異なるカーソル、ソートキーとして扱えない値を含むカーソルは400エラーとする。
+    pub fn parse(value: Option<&str>, sort: &[(String, i8)]) -> Result<Option<Self>, AppError> {
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let cursor = Self::decode(value, cursor_mac()?)
+            .ok_or_else(|| AppError::BadRequest(messages::INVALID_CURSOR.into()))?;
+
+        let matches_sort = cursor.keys.len() == sort.len()
+            && cursor
+                .keys
+                .iter()
+                .zip(sort)
+                .all(|(key, (field, order))| key.field == *field && key.order == *order);
+        if !matches_sort {
+            return Err(AppError::BadRequest(messages::CURSOR_SORT_MISMATCH.into()));
+        }
+        if !cursor.keys.iter().all(|key| is_sort_key_value(&key.value)) {
+            return Err(AppError::BadRequest(messages::INVALID_CURSOR_VALUE.into()));
+        }
+
+        Ok(Some(cursor))
+    }
+
+    /// カーソル以降のドキュメントに絞り込むクエリを生成する
+    ///
+    /// ソート条件 `(k1, k2, ..., _id)` に対して
+    /// `k1 > v1 OR (k1 = v1 AND k2 > v2) OR ... OR (k1 = v1 AND ... AND _id > id)` を組み立てる。
+    /// MongoDBではnullが最小値として並ぶため、null値は比較演算子を使わずに扱う。
+    pub fn to_filter(&self, sort: &[(String, i8)]) -> Document {
+        let id_order = id_sort_order(sort);
+        let mut keys: Vec<(&str, &Bson, i8)> = self
+            .keys
+            .iter()
+            .map(|key| (key.field.as_str(), &key.value, key.order))
+            .collect();
+        let id = Bson::ObjectId(self.id);
+        keys.push(("_id", &id, id_order));
+
+        let mut branches = Vec::new();
+        for (i, (field, value, order)) in keys.iter().enumerate() {
+            let Some(after) = after_condition(field, value, *order) else {
+                continue;
+            };
+            let mut conditions: Vec<Document> = keys[..i]
+                .iter()
+                .map(|(field, value, _)| doc! { *field: (*value).clone() })
+                .collect();
+            conditions.push(after);
+            branches.push(doc! { "$and": conditions });
+        }
+
+        doc! { "$or": branches }
+    }
+}
+
+/// カーソルの署名・検証に使うHMACを生成する
+fn cursor_mac() -> Result<HmacSha256, AppError> {
+    let key = CURSOR_KEY.as_deref().ok_or_else(|| {
+        AppError::InternalServerError("JWT_SECRETが設定されていません".to_string())
+    })?;
+    HmacSha256::new_from_slice(key)
+        .map_err(|e| AppError::InternalServerError(format!("カーソルの署名鍵が不正です: {}", e)))
+}
+
+/// ソートキーの値として扱える型か（ドキュメントや配列は比較条件に埋め込むとクエリの意味が変わるため受け付けない）
+fn is_sort_key_value(value: &Bson) -> bool {
+    matches!(
+        value,
+        Bson::Null
+            | Bson::Boolean(_)
+            | Bson::Int32(_)
+            | Bson::Int64(_)
+            | Bson::Double(_)
+            | Bson::Decimal128(_)
+            | Bson::String(_)
+            | Bson::DateTime(_)
+            | Bson::ObjectId(_)
+    )
+}
+
+/// `_id`のソート順（最後のソートキーに合わせる。指定がない場合は昇順）
+fn id_sort_order(sort: &[(String, i8)]) -> i8 {
+    sort.last().map_or(1, |(_, order)| *order)
+}
+
+/// 指定したフィールドがカーソルの値より後ろに並ぶ条件を生成する
+fn after_condition(field: &str, value: &Bson, order: i8) -> Option<Document> {
+    match (value, order >= 0) {
+        // 昇順ではnullが先頭に並ぶため、null以外の値が全て後ろになる
+        (Bson::Null, true) => Some(doc! { field: { "$ne": null } }),
+        // 降順ではnullが末尾に並ぶため、nullより後ろの値は存在しない
+        (Bson::Null, false) => None,
+        (value, true) => Some(doc! { field: { "$gt": value.clone() } }),
+        (value, false) => Some(doc! {
+            "$or": [
+                { field: { "$lt": value.clone() } },
+                { field: null },
+            ]
+        }),
+    }
+}
+
+/// ソート条件に`_id`を加えたソート用ドキュメントを生成する（同じ値のドキュメントの並び順を固定する）
+pub fn keyset_sort(sort: &[(String, i8)]) -> Document {
+    let mut sort_doc = Document::from_iter(
+        sort.iter()
+            .map(|(field, order)| (field.clone(), Bson::Int32(*order as i32))),
+    );
+    sort_doc.insert("_id", Bson::Int32(id_sort_order(sort) as i32));
+    sort_doc
+}
+
+/// 一覧取得の結果（1ページ分）
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub next_cursor: Option<String>,
+}
+
+impl<T: Serialize> Page<T> {
+    /// `limit + 1`件まで取得した結果からページを組み立てる
+    ///
+    /// `limit`を超えた分が取得できた場合のみ、次のページのカーソルを生成する。
+    pub fn from_fetched(
+        mut items: Vec<T>,
+        total: u64,
+        limit: Option<i64>,
+        sort: &[(String, i8)],
+    ) -> Result<Self, AppError> {
+        let limit = page_size(limit);
+        let next_cursor = if items.len() as i64 > limit {
+            items.truncate(limit as usize);
+            items
+                .last()
+                .map(|last| PageCursor::from_item(last, sort))
+                .transpose()?
+                .map(|cursor| cursor.encode())
+                .transpose()?
+        } else {
+            None
+        };
+
+        Ok(Self {
+            items,
+            total,
+            next_cursor,
+        })
+    }
+}
+
+impl<T> Page<T> {
+    /// 要素を変換する（レスポンス型への変換に使用する）
+    pub fn try_map<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<Page<U>, E> {
+        Ok(Page {
+            items: self.items.into_iter().map(f).collect::<Result<_, _>>()?,
+            total: self.total,
+            next_cursor: self.next_cursor,
+        })
+    }
+}
+
+/// 1ページあたりの件数（省略時は`DEFAULT_PAGE_SIZE`）
+fn page_size(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(DEFAULT_PAGE_SIZE)
+}
+
+/// 次ページ取得用に`limit`より1件多く取得する件数
+///
+/// `limit`を省略しても全件を返さないよう、既定の件数で区切る。
+pub fn fetch_limit(limit: Option<i64>) -> Option<i64> {
+    Some(page_size(limit) + 1)
+}
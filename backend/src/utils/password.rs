@@ -1,4 +1,11 @@
 use bcrypt::{hash, verify, DEFAULT_COST};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    // 存在しないユーザーのログイン時に検証するダミーのハッシュ
+    static ref DUMMY_PASSWORD_HASH: String =
+        hash_password("dummy-password").expect("ダミーのパスワードハッシュの生成に失敗しました");
+}
 
 pub fn hash_password(password: &str) -> Result<String, bcrypt::BcryptError> {
     hash(password, DEFAULT_COST)
@@ -9,3 +16,10 @@ pub fn verify_password(password: &str, hash: &str) -> bool {
     log::info!("パスワード検証結果: {}", result);
     result
 }
+
+/// ユーザーが存在しない場合にも、実在するユーザーと同じ時間をかけてパスワードを検証する
+///
+/// 応答時間の差からメールアドレスが登録済みかどうかを推測されないようにするためのもので、結果は常に無視する
+pub fn verify_dummy_password(password: &str) {
+    let _ = verify(password, &DUMMY_PASSWORD_HASH);
+}
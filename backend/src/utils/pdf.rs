@@ -0,0 +1,139 @@
+use pdf_writer::types::{CidFontType, FontFlags, SystemInfo};
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str};
+
+/// A4（ポイント単位）
+pub const PAGE_WIDTH: f32 = 595.0;
+pub const PAGE_HEIGHT: f32 = 842.0;
+
+const FONT_NAME: Name<'static> = Name(b"F1");
+
+/// 日本語のテキストと罫線のみで構成するPDFを組み立てる
+///
+/// フォントはPDFビューアに標準で用意されている日本語フォント（HeiseiKakuGo-W5）を参照し、埋め込まない。
+/// 文字コードはUTF-16BE（UniJIS-UCS2-HW-H）のため、基本多言語面の文字のみ表示できる。
+pub struct PdfBuilder {
+    pages: Vec<Content>,
+}
+
+impl PdfBuilder {
+    pub fn new() -> Self {
+        Self {
+            pages: vec![Content::new()],
+        }
+    }
+
+    /// 改ページする
+    pub fn new_page(&mut self) {
+        self.pages.push(Content::new());
+    }
+
+    /// 左下を原点とした座標にテキストを描画する
+    pub fn text(&mut self, x: f32, y: f32, size: f32, text: &str) {
+        let encoded = encode_text(text);
+        let content = self.current_page();
+        content.begin_text();
+        content.set_font(FONT_NAME, size);
+        content.next_line(x, y);
+        content.show(Str(&encoded));
+        content.end_text();
+    }
+
+    /// 右端の座標を指定してテキストを描画する
+    pub fn text_right(&mut self, right: f32, y: f32, size: f32, text: &str) {
+        self.text(right - text_width(text, size), y, size, text);
+    }
+
+    /// 罫線を描画する
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        let content = self.current_page();
+        content.set_line_width(0.5);
+        content.move_to(x1, y1);
+        content.line_to(x2, y2);
+        content.stroke();
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        let mut pdf = Pdf::new();
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        let font_id = Ref::new(3);
+        let cid_font_id = Ref::new(4);
+        let descriptor_id = Ref::new(5);
+        let first_page_id = 6;
+
+        let page_ids: Vec<Ref> = (0..self.pages.len())
+            .map(|i| Ref::new(first_page_id + 2 * i as i32))
+            .collect();
+
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .kids(page_ids.iter().copied())
+            .count(page_ids.len() as i32);
+
+        pdf.type0_font(font_id)
+            .base_font(Name(b"HeiseiKakuGo-W5"))
+            .encoding_predefined(Name(b"UniJIS-UCS2-HW-H"))
+            .descendant_font(cid_font_id);
+        // 各ライターはドロップ時に書き込みが完了する
+        let mut cid_font = pdf.cid_font(cid_font_id);
+        cid_font
+            .subtype(CidFontType::Type0)
+            .base_font(Name(b"HeiseiKakuGo-W5"))
+            .system_info(SystemInfo {
+                registry: Str(b"Adobe"),
+                ordering: Str(b"Japan1"),
+                supplement: 2,
+            })
+            .font_descriptor(descriptor_id)
+            .default_width(1000.0);
+        // UniJIS-UCS2-HW-HではASCIIが半角のCIDに割り当てられる
+        cid_font.widths().same(231, 632, 500.0);
+        drop(cid_font);
+        pdf.font_descriptor(descriptor_id)
+            .name(Name(b"HeiseiKakuGo-W5"))
+            .flags(FontFlags::SYMBOLIC)
+            .bbox(Rect::new(-92.0, -250.0, 1010.0, 922.0))
+            .italic_angle(0.0)
+            .ascent(752.0)
+            .descent(-221.0)
+            .cap_height(737.0)
+            .stem_v(116.0);
+
+        for (page_id, content) in page_ids.iter().zip(self.pages) {
+            let content_id = Ref::new(page_id.get() + 1);
+            let mut page = pdf.page(*page_id);
+            page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+                .parent(page_tree_id)
+                .contents(content_id);
+            page.resources().fonts().pair(FONT_NAME, font_id);
+            drop(page);
+            pdf.stream(content_id, &content.finish());
+        }
+
+        pdf.finish()
+    }
+
+    fn current_page(&mut self) -> &mut Content {
+        // 最初のページはnewで作成しているため、常に1ページ以上存在する
+        self.pages.last_mut().expect("PDFのページが存在しません")
+    }
+}
+
+impl Default for PdfBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// テキストの幅（半角文字は全角の半分として概算する）
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars()
+        .map(|c| if c.is_ascii() || c == '¥' { 0.5 } else { 1.0 })
+        .sum::<f32>()
+        * size
+}
+
+/// テキストをUTF-16BEに変換する
+fn encode_text(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_be_bytes).collect()
+}
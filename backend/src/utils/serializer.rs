@@ -35,3 +35,25 @@ where
         None => serializer.serialize_none(),
     }
 }
+
+// Option<ObjectId>をシリアライズするためのヘルパー関数
+pub fn serialize_option_object_id<S>(
+    object_id: &Option<ObjectId>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match object_id {
+        Some(id) => serialize_object_id(id, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+// Vec<ObjectId>を16進数文字列の配列としてシリアライズするためのヘルパー関数
+pub fn serialize_vec_object_id<S>(object_ids: &[ObjectId], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(object_ids.iter().map(|id| id.to_hex()))
+}
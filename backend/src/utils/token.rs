@@ -0,0 +1,17 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+/// URLに含められる推測不可能なランダムトークンを生成する（256bit）
+pub fn generate_random_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// トークンをDBに保存するためのハッシュ値（SHA-256の16進数表記）
+///
+/// ランダムトークンは十分な長さがあるため、パスワードと異なりソルトやストレッチングは不要
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
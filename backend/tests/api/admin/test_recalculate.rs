@@ -0,0 +1,43 @@
+use crate::common::test_app::TestApp;
+use actix_web::{http::StatusCode, test};
+use serde_json::Value;
+
+const RECALCULATE_ENDPOINT: &str = "/api/admin/recalculate-total-working-time/";
+
+#[actix_web::test]
+async fn test_recalculate_total_working_time_success() {
+    /*
+    管理者が総稼働時間の再計算を実行できることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let response = context
+            .authenticated_request(test::TestRequest::post(), RECALCULATE_ENDPOINT)
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        assert!(body["corrected_count"].is_u64());
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_recalculate_total_working_time_forbidden() {
+    /*
+    管理者以外のユーザーは総稼働時間の再計算を実行できず403エラーが返ることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let another_token = context.app.register_another_user().await;
+
+        let response = context
+            .request_with_token(
+                test::TestRequest::post(),
+                RECALCULATE_ENDPOINT,
+                &another_token,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    })
+    .await;
+}
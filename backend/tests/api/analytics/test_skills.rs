@@ -0,0 +1,122 @@
+use crate::api::work_logs::helper::create_test_work_log;
+use crate::common::test_app::TestApp;
+use actix_web::{http::StatusCode, test};
+use chrono::Utc;
+use chrono_tz::Asia::Tokyo;
+use rstest::rstest;
+use serde_json::Value;
+
+const SKILL_ANALYTICS_ENDPOINT: &str = "/api/analytics/skills/";
+
+#[actix_web::test]
+async fn test_get_skill_analytics() {
+    /*
+    プロジェクトのスキルラベルごとに作業時間・推移・企業が集計されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        // スキルラベル「Rust」「MongoDB」を持つプロジェクトの勤怠（実作業時間45分）を作成
+        create_test_work_log(&context).await;
+
+        let response = context
+            .authenticated_request(test::TestRequest::get(), SKILL_ANALYTICS_ENDPOINT)
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        let skills = body["skills"].as_array().unwrap();
+        assert_eq!(skills.len(), 2);
+
+        let this_month = Utc::now().with_timezone(&Tokyo).format("%Y-%m").to_string();
+        let mut labels = Vec::new();
+        for skill in skills {
+            assert_eq!(skill["work_minutes"], 45);
+            assert_eq!(skill["work_log_count"], 1);
+            assert_eq!(skill["project_count"], 1);
+            assert!(skill["first_used_at"].is_string());
+            assert!(skill["last_used_at"].is_string());
+
+            let trend = skill["trend"].as_array().unwrap();
+            assert_eq!(trend.len(), 1);
+            assert_eq!(trend[0]["month"], this_month.as_str());
+            assert_eq!(trend[0]["work_minutes"], 45);
+
+            let companies = skill["companies"].as_array().unwrap();
+            assert_eq!(companies.len(), 1);
+            assert_eq!(companies[0]["company_name"], "テスト企業");
+            assert_eq!(companies[0]["work_minutes"], 45);
+
+            labels.push(skill["skill_label"].as_str().unwrap());
+        }
+        labels.sort();
+        assert_eq!(labels, vec!["MongoDB", "Rust"]);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_skill_analytics_with_limit() {
+    /*
+    limitを指定した場合は指定した件数のスキルのみ返されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        create_test_work_log(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?limit=1", SKILL_ANALYTICS_ENDPOINT),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["skills"].as_array().unwrap().len(), 1);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_skill_analytics_out_of_period() {
+    /*
+    期間外の勤怠は集計されないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        create_test_work_log(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?from=2020-01-01&to=2020-12-31", SKILL_ANALYTICS_ENDPOINT),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        assert!(body["skills"].as_array().unwrap().is_empty());
+    })
+    .await;
+}
+
+#[rstest]
+#[case::reversed_period("from=2024-05-01&to=2024-04-01")]
+#[case::invalid_date("from=2024-13-01")]
+#[case::zero_limit("limit=0")]
+#[case::too_large_limit("limit=101")]
+#[actix_web::test]
+async fn test_get_skill_analytics_invalid_query(#[case] query: &str) {
+    /*
+    不正なクエリパラメータの場合は400エラーとなることを確認するテスト
+     */
+    let query = query.to_string();
+    TestApp::run_authenticated_test(|context| async move {
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?{}", SKILL_ANALYTICS_ENDPOINT, query),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
@@ -1,4 +1,5 @@
 pub mod test_login;
 pub mod test_logout;
+pub mod test_password_reset;
 pub mod test_refresh;
 pub mod test_register;
@@ -2,9 +2,13 @@ use crate::api::helper::validation::{
     assert_validation_error, assert_validation_error_with_custom_error, ValidationTestCase,
 };
 use crate::common::test_app::TestApp;
+use crate::common::test_context::TestContext;
+use actix_web::dev::ServiceResponse;
 use actix_web::{http::StatusCode, test};
 use rstest::rstest;
 use serde_json::json;
+use std::net::SocketAddr;
+use uuid::Uuid;
 
 const LOGIN_ENDPOINT: &str = "/api/auth/login/";
 
@@ -72,34 +76,115 @@ async fn test_login_success() {
     .await;
 }
 
+#[rstest]
+#[case::unknown_email(false)]
+#[case::wrong_password(true)]
 #[actix_web::test]
-async fn test_login_invalid_credentials() {
+async fn test_login_invalid_credentials(#[case] registered_email: bool) {
     /*
-    無効なメールアドレスかパスワードの場合は400エラーが返ることを確認するテスト
+    メールアドレスが未登録の場合とパスワードが誤っている場合で、同じ400エラーが返ることを確認するテスト
+    （ユーザーの存在を推測できないこと）
      */
     TestApp::run_test(|context| async move {
-        let payload = json!({
-            "email": "test@example.com",
-            "password": "wrongpassword"
-        });
-        // 認証不要のAPIなのでtest::call_serviceを直接使用
-        let res = test::call_service(
-            context.service(),
-            test::TestRequest::post()
-                .uri(LOGIN_ENDPOINT)
-                .set_json(&payload)
-                .to_request(),
+        let email = if registered_email {
+            context.app.test_user.email.clone()
+        } else {
+            format!("unknown_{}@example.com", Uuid::now_v7())
+        };
+        let response = call_login(&context, &email, "wrongpassword", None).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(
+            body,
+            json!({
+                "error": "不正なリクエスト",
+                "code": "INVALID_CREDENTIALS",
+                "message": "無効な認証情報です"
+            })
+        );
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_login_locked_after_account_failures() {
+    /*
+    同じアカウントでログインの失敗が上限に達すると、正しいパスワードでも一時的にログインできなくなることを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let email = context.app.test_user.email.clone();
+        for _ in 0..context.app.login_attempt_config.max_account_failures {
+            let response = call_login(&context, &email, "wrongpassword", None).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        let response = call_login(&context, &email, &context.app.test_user.password, None).await;
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "LOGIN_TEMPORARILY_LOCKED");
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_login_locked_after_ip_failures() {
+    /*
+    同じIPアドレスから複数のアカウントへのログインの失敗が上限に達すると、そのIPアドレスからはログインできなくなることを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let ip = random_ip();
+        for _ in 0..context.app.login_attempt_config.max_ip_failures {
+            let email = format!("unknown_{}@example.com", Uuid::now_v7());
+            let response = call_login(&context, &email, "wrongpassword", Some(ip)).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        let locked_response = call_login(
+            &context,
+            &context.app.test_user.email,
+            &context.app.test_user.password,
+            Some(ip),
         )
         .await;
+        assert_eq!(locked_response.status(), StatusCode::TOO_MANY_REQUESTS);
 
-        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
-
-        let body: serde_json::Value = test::read_body_json(res).await;
-        assert_eq!(body, json!({"error": "認証に失敗しました"}));
+        // 別のIPアドレスからはログインできる
+        let response = call_login(
+            &context,
+            &context.app.test_user.email,
+            &context.app.test_user.password,
+            Some(random_ip()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
     })
     .await;
 }
 
+/// ログインAPIを呼び出す（IPアドレスを指定しない場合はIPアドレスごとの失敗回数を数えない）
+async fn call_login(
+    context: &TestContext,
+    email: &str,
+    password: &str,
+    ip: Option<SocketAddr>,
+) -> ServiceResponse {
+    let mut request = test::TestRequest::post()
+        .uri(LOGIN_ENDPOINT)
+        .set_json(json!({ "email": email, "password": password }));
+    if let Some(ip) = ip {
+        request = request.peer_addr(ip);
+    }
+    test::call_service(context.service(), request.to_request()).await
+}
+
+/// テストの並行実行や再実行で失敗回数が混ざらないよう、ランダムなIPアドレスを生成する
+fn random_ip() -> SocketAddr {
+    let octets: [u8; 4] = rand::random();
+    SocketAddr::from(([10, octets[1], octets[2], octets[3]], 8080))
+}
+
 #[rstest]
 // メールアドレスのバリデーション
 #[case::invalid_email(
@@ -0,0 +1,245 @@
+use crate::api::helper::validation::{
+    assert_validation_error_with_custom_error, ValidationTestCase,
+};
+use crate::common::test_app::TestApp;
+use crate::common::test_context::TestContext;
+use actix_web::dev::ServiceResponse;
+use actix_web::{http::StatusCode, test};
+use chrono::{Duration, Utc};
+use devtrackr_api::models::auth::PasswordResetTokenInDB;
+use mongodb::bson::{doc, DateTime as BsonDateTime};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+const REQUEST_ENDPOINT: &str = "/api/auth/password-reset/request/";
+const CONFIRM_ENDPOINT: &str = "/api/auth/password-reset/confirm/";
+const LOGIN_ENDPOINT: &str = "/api/auth/login/";
+const USERS_ME_ENDPOINT: &str = "/api/users/me/";
+const NEW_PASSWORD: &str = "newpassword123";
+
+async fn call_request(context: &TestContext, email: &str) -> ServiceResponse {
+    test::call_service(
+        context.service(),
+        test::TestRequest::post()
+            .uri(REQUEST_ENDPOINT)
+            .set_json(json!({ "email": email }))
+            .to_request(),
+    )
+    .await
+}
+
+async fn call_confirm(context: &TestContext, token: &str, new_password: &str) -> ServiceResponse {
+    test::call_service(
+        context.service(),
+        test::TestRequest::post()
+            .uri(CONFIRM_ENDPOINT)
+            .set_json(json!({ "token": token, "new_password": new_password }))
+            .to_request(),
+    )
+    .await
+}
+
+async fn call_login(context: &TestContext, password: &str) -> ServiceResponse {
+    test::call_service(
+        context.service(),
+        test::TestRequest::post()
+            .uri(LOGIN_ENDPOINT)
+            .set_json(json!({ "email": context.app.test_user.email, "password": password }))
+            .to_request(),
+    )
+    .await
+}
+
+/// テスト用ヘルパー関数. パスワードリセットを申請し、メールに記載されたリセットトークンを返す
+async fn request_reset_token(context: &TestContext) -> String {
+    let email = &context.app.test_user.email;
+    let sent_count = context.app.sent_mails(email).await.len();
+    let response = call_request(context, email).await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    // メールはレスポンスを待たずに送信される
+    let mail = context.app.wait_for_new_mail(email, sent_count).await;
+    mail.body
+        .split_whitespace()
+        .find_map(|word| word.split_once("?token=").map(|(_, token)| token))
+        .expect("メールにリセット用のURLが記載されていません")
+        .to_string()
+}
+
+#[actix_web::test]
+async fn test_password_reset_success() {
+    /*
+    メールのリセットトークンでパスワードを再設定でき、既存のセッションがすべて失効することを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let token = request_reset_token(&context).await;
+
+        let response = call_confirm(&context, &token, NEW_PASSWORD).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // 再設定前に発行したアクセストークンは使用できない
+        let response = context
+            .request_with_token(
+                test::TestRequest::get(),
+                USERS_ME_ENDPOINT,
+                context.app.access_token.as_ref().unwrap(),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // 新しいパスワードでのみログインできる
+        let response = call_login(&context, NEW_PASSWORD).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = call_login(&context, &context.app.test_user.password).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_password_reset_token_is_single_use() {
+    /*
+    使用済みのリセットトークンは再利用できないことを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let token = request_reset_token(&context).await;
+
+        let response = call_confirm(&context, &token, NEW_PASSWORD).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = call_confirm(&context, &token, "anotherpassword123").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "INVALID_OR_EXPIRED_RESET_TOKEN");
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_password_reset_previous_token_invalidated() {
+    /*
+    再度申請した場合は、以前に発行したリセットトークンが使用できなくなることを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let previous_token = request_reset_token(&context).await;
+        let latest_token = request_reset_token(&context).await;
+
+        let response = call_confirm(&context, &previous_token, NEW_PASSWORD).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = call_confirm(&context, &latest_token, NEW_PASSWORD).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_password_reset_expired_token() {
+    /*
+    有効期限が切れたリセットトークンは使用できないことを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let token = request_reset_token(&context).await;
+
+        let expired_at = BsonDateTime::from_chrono(Utc::now() - Duration::minutes(1));
+        context
+            .app
+            .test_db
+            .db
+            .collection::<PasswordResetTokenInDB>("password_reset_tokens")
+            .update_many(doc! {}, doc! { "$set": { "expires_at": expired_at } }, None)
+            .await
+            .expect("リセットトークンの期限切れ設定に失敗しました");
+
+        let response = call_confirm(&context, &token, NEW_PASSWORD).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // パスワードは変更されていない
+        let response = call_login(&context, &context.app.test_user.password).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_password_reset_unknown_email() {
+    /*
+    登録されていないメールアドレスでも同じレスポンスを返し、メールは送信しないことを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let email = "unknown_user@example.com";
+        let response = call_request(&context, email).await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        assert!(context.app.sent_mails(email).await.is_empty());
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_password_reset_invalid_token() {
+    /*
+    発行していないリセットトークンは使用できないことを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let response = call_confirm(&context, "invalid-token", NEW_PASSWORD).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "INVALID_OR_EXPIRED_RESET_TOKEN");
+    })
+    .await;
+}
+
+#[rstest]
+#[case::short_password(
+    ValidationTestCase {
+        name: "短すぎるパスワード",
+        payload: json!({
+            "token": "reset-token",
+            "new_password": "short"
+        }),
+        field: "new_password",
+        expected_message: "パスワードは8文字以上である必要があります"
+    }
+)]
+#[case::empty_token(
+    ValidationTestCase {
+        name: "空のリセットトークン",
+        payload: json!({
+            "token": "",
+            "new_password": NEW_PASSWORD
+        }),
+        field: "token",
+        expected_message: "リセットトークンを指定してください"
+    }
+)]
+#[actix_web::test]
+async fn test_password_reset_confirm_invalid_input(#[case] test_case: ValidationTestCase) {
+    /*
+    パラメータに不備がある場合、バリデーションエラーが発生することを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let response = test::call_service(
+            context.service(),
+            test::TestRequest::post()
+                .uri(CONFIRM_ENDPOINT)
+                .set_json(&test_case.payload)
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(
+            response.status(),
+            StatusCode::BAD_REQUEST,
+            "{}",
+            test_case.name
+        );
+        let body: Value = test::read_body_json(response).await;
+        assert_validation_error_with_custom_error(
+            &body,
+            test_case.field,
+            test_case.expected_message,
+        );
+    })
+    .await;
+}
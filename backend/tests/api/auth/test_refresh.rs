@@ -1,4 +1,6 @@
 use crate::common::test_app::TestApp;
+use crate::common::test_context::TestContext;
+use actix_web::dev::ServiceResponse;
 use actix_web::{http::StatusCode, test};
 use chrono::{Duration, Utc};
 use devtrackr_api::models::auth::AuthTokenInDB;
@@ -7,6 +9,7 @@ use mongodb::bson::{doc, DateTime as BsonDateTime};
 use serde_json::{json, Value};
 
 const REFRESH_ENDPOINT: &str = "/api/auth/refresh/";
+const USERS_ME_ENDPOINT: &str = "/api/users/me/";
 
 /// テスト用ヘルパー関数. リフレッシュトークンを期限切れにする
 pub async fn expire_refresh_token(test_app: &TestApp, refresh_token: &str) {
@@ -109,22 +112,49 @@ async fn test_refresh_success() {
             "アクセストークンが更新されていません"
         );
 
-        // アクセストークンが更新されていることをDBでも確認
+        // リフレッシュトークンがローテーションされていることを確認
+        let new_refresh_token = cookies
+            .iter()
+            .find(|c| c.starts_with("refresh_token="))
+            .expect("リフレッシュトークンのCookieが見つかりません")
+            .split(';')
+            .next()
+            .unwrap()
+            .trim_start_matches("refresh_token=");
+        assert_ne!(
+            refresh_token, new_refresh_token,
+            "リフレッシュトークンがローテーションされていません"
+        );
+
+        // 使用したトークンはローテーション済みになり、同じファミリーの新しいトークンが保存されていることをDBでも確認
         let collection = context
             .app
             .test_db
             .db
             .collection::<AuthTokenInDB>("auth_tokens");
-        let updated_token = collection
-            .find_one(doc! { "refresh_token": refresh_token }, None)
+        let rotated_token = collection
+            .find_one(doc! { "refresh_token": &refresh_token }, None)
             .await
             .expect("DBクエリに失敗")
             .expect("トークンが見つかりません");
+        let new_token = collection
+            .find_one(doc! { "refresh_token": new_refresh_token }, None)
+            .await
+            .expect("DBクエリに失敗")
+            .expect("新しいトークンが見つかりません");
 
+        assert!(
+            rotated_token.rotated_at.is_some(),
+            "使用したトークンがローテーション済みになっていません"
+        );
         assert_eq!(
-            updated_token.access_token, new_access_token,
+            new_token.access_token, new_access_token,
             "DBに保存されているアクセストークンが更新されていません"
         );
+        assert_eq!(
+            new_token.family_id, rotated_token.family_id,
+            "ファミリーIDが引き継がれていません"
+        );
 
         assert!(
             decoded_new.iat > decoded_original.iat,
@@ -154,7 +184,7 @@ async fn test_refresh_without_token() {
             json!({
                 "error": "不正なリクエスト",
                 "message": "無効なリクエストです",
-                "code": "BAD_REQUEST"
+                "code": "INVALID_REQUEST"
             })
         );
     })
@@ -188,7 +218,7 @@ async fn test_refresh_with_invalid_token() {
             json!({
                 "error": "不正なリクエスト",
                 "message": "無効なリクエストです",
-                "code": "BAD_REQUEST"
+                "code": "INVALID_REQUEST"
             })
         );
     })
@@ -229,9 +259,72 @@ async fn test_refresh_with_expired_token() {
             json!({
                 "error": "不正なリクエスト",
                 "message": "無効なリクエストです",
-                "code": "BAD_REQUEST"
+                "code": "INVALID_REQUEST"
             })
         );
     })
     .await;
 }
+
+#[actix_web::test]
+async fn test_refresh_with_rotated_token_revokes_family() {
+    /*
+    ローテーション済みのリフレッシュトークンが再利用された場合は、同じファミリーのトークンがすべて失効し、
+    再ログインが必要になることを確認するテスト
+     */
+    TestApp::run_test(|mut context| async move {
+        context.app.login().await;
+        let old_refresh_token = context.app.refresh_token.clone().unwrap();
+
+        // 1回目のリフレッシュでトークンをローテーションする
+        let response = call_refresh(&context, &old_refresh_token).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let cookies: Vec<_> = response.response().cookies().collect();
+        let new_access_token = cookies
+            .iter()
+            .find(|c| c.name() == "access_token")
+            .map(|c| c.value().to_string())
+            .expect("アクセストークンのCookieが見つかりません");
+        let new_refresh_token = cookies
+            .iter()
+            .find(|c| c.name() == "refresh_token")
+            .map(|c| c.value().to_string())
+            .expect("リフレッシュトークンのCookieが見つかりません");
+
+        // ローテーション済みのリフレッシュトークンを再利用する
+        let response = call_refresh(&context, &old_refresh_token).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // 新しいリフレッシュトークンも失効している
+        let response = call_refresh(&context, &new_refresh_token).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // 新しいアクセストークンも失効している
+        let response = test::call_service(
+            context.service(),
+            test::TestRequest::get()
+                .uri(USERS_ME_ENDPOINT)
+                .insert_header(("Authorization", format!("Bearer {}", new_access_token)))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    })
+    .await;
+}
+
+/// リフレッシュトークンをCookieに設定してリフレッシュAPIを呼び出す
+async fn call_refresh(context: &TestContext, refresh_token: &str) -> ServiceResponse {
+    test::call_service(
+        context.service(),
+        test::TestRequest::post()
+            .uri(REFRESH_ENDPOINT)
+            .cookie(
+                actix_web::cookie::Cookie::build("refresh_token", refresh_token)
+                    .path("/")
+                    .finish(),
+            )
+            .to_request(),
+    )
+    .await
+}
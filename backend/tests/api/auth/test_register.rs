@@ -125,7 +125,7 @@ async fn test_register_duplicate_email() {
             json!({
                 "error": "不正なリクエスト",
                 "message": "バリデーションに失敗したか、処理中にエラーが発生しました",
-                "code": "BAD_REQUEST"
+                "code": "REGISTRATION_FAILED"
             })
         );
     })
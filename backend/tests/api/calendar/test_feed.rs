@@ -0,0 +1,109 @@
+use crate::api::work_logs::helper::create_test_work_log;
+use crate::common::test_app::TestApp;
+use crate::common::test_context::TestContext;
+use actix_web::{dev::ServiceResponse, http::header, http::StatusCode, test};
+use serde_json::Value;
+
+const CALENDAR_FEED_SETTINGS_ENDPOINT: &str = "/api/users/me/calendar-feed/";
+
+/// カレンダーフィードのURLを発行し、パス部分を取得する
+async fn rotate_feed(context: &TestContext) -> String {
+    let response = context
+        .authenticated_request(test::TestRequest::post(), CALENDAR_FEED_SETTINGS_ENDPOINT)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(response).await;
+    let url = url::Url::parse(body["url"].as_str().unwrap()).unwrap();
+    url.path().to_string()
+}
+
+/// 認証ヘッダーなしでカレンダーフィードを取得する（カレンダーアプリからのアクセスを想定）
+async fn get_feed(context: &TestContext, path: &str) -> ServiceResponse {
+    test::call_service(
+        context.service(),
+        test::TestRequest::get().uri(path).to_request(),
+    )
+    .await
+}
+
+#[actix_web::test]
+async fn test_get_calendar_feed() {
+    /*
+    発行したURLから認証なしで勤怠・契約期間の予定を含むiCalendarを取得できることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        // 企業「テスト企業」（契約開始日 2023-04-01）のプロジェクトで勤怠を作成
+        create_test_work_log(&context).await;
+        let path = rotate_feed(&context).await;
+        assert!(path.starts_with("/api/calendar/"));
+        assert!(path.ends_with(".ics"));
+
+        let response = get_feed(&context, &path).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response.headers().get(header::CONTENT_TYPE).unwrap();
+        assert_eq!(content_type, "text/calendar; charset=utf-8");
+
+        let body = test::read_body(response).await;
+        let body = std::str::from_utf8(&body).unwrap();
+        assert!(body.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(body.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(body.matches("BEGIN:VEVENT").count(), 2);
+        assert!(body.contains("SUMMARY:テストプロジェクト\r\n"));
+        assert!(body.contains("DESCRIPTION:テスト用の勤怠データです\r\n"));
+        assert!(body.contains("DTSTART;VALUE=DATE:20230401\r\n"));
+        assert!(body.contains("SUMMARY:テスト企業 契約開始\r\n"));
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_rotate_calendar_feed() {
+    /*
+    URLを再発行した場合は以前のURLが無効になることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let old_path = rotate_feed(&context).await;
+        let new_path = rotate_feed(&context).await;
+        assert_ne!(old_path, new_path);
+
+        let response = get_feed(&context, &old_path).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = get_feed(&context, &new_path).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_delete_calendar_feed() {
+    /*
+    カレンダーフィードを無効化した場合はURLにアクセスできなくなることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let path = rotate_feed(&context).await;
+
+        let response = context
+            .authenticated_request(test::TestRequest::delete(), CALENDAR_FEED_SETTINGS_ENDPOINT)
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = get_feed(&context, &path).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_calendar_feed_with_unknown_token() {
+    /*
+    発行されていないトークンの場合は404エラーとなることを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let response = get_feed(&context, "/api/calendar/unknown-token.ics").await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    })
+    .await;
+}
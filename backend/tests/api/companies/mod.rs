@@ -1,4 +1,5 @@
 pub mod helper;
 pub mod test_create;
+pub mod test_delete;
 pub mod test_get;
 pub mod test_update;
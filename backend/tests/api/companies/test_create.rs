@@ -306,6 +306,8 @@ async fn test_create_company_validation(#[case] test_case: ValidationTestCase) {
                 error_body,
                 json!({
                     "error": "入力エラー",
+                    "code": "INVALID_INPUT",
+                    "message": "入力形式が正しくありません",
                     "field_errors": [{
                         "field": "unknown",
                         "message": "入力形式が正しくありません"
@@ -323,3 +325,80 @@ async fn test_create_company_validation(#[case] test_case: ValidationTestCase) {
     })
     .await;
 }
+
+/// ネストした項目を含む複数の項目が不正なペイロード
+fn invalid_nested_payload() -> serde_json::Value {
+    let mut payload = TEST_PAYLOAD.clone();
+    payload["company_name"] = json!("A");
+    payload["annual_sales"]["amount"] = json!(-1);
+    payload["bonus"]["frequency"] = json!(13);
+    payload
+}
+
+#[actix_web::test]
+async fn test_create_company_validation_returns_all_errors() {
+    /*
+    複数の項目が不正な場合、ネストした項目も含めてすべてのエラーがJSON Pointer形式のパスとともに返ることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(invalid_nested_payload()),
+                COMPANIES_ENDPOINT,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error_body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(
+            error_body["field_errors"],
+            json!([
+                {
+                    "field": "annual_sales.amount",
+                    "pointer": "/annual_sales/amount",
+                    "message": "年間売上は0以上である必要があります"
+                },
+                {
+                    "field": "bonus.frequency",
+                    "pointer": "/bonus/frequency",
+                    "message": "ボーナス頻度は1から12の間である必要があります"
+                },
+                {
+                    "field": "company_name",
+                    "pointer": "/company_name",
+                    "message": "企業名は2〜100文字である必要があります"
+                }
+            ])
+        );
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_company_validation_first_error_only() {
+    /*
+    X-Validation-Errors: firstヘッダーを指定した場合、従来どおり最初の1件のエラーだけが返ることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post()
+                    .insert_header(("X-Validation-Errors", "first"))
+                    .set_json(invalid_nested_payload()),
+                COMPANIES_ENDPOINT,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error_body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(
+            error_body["field_errors"],
+            json!([{
+                "field": "annual_sales.amount",
+                "pointer": "/annual_sales/amount",
+                "message": "年間売上は0以上である必要があります"
+            }])
+        );
+    })
+    .await;
+}
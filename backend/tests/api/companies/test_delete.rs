@@ -0,0 +1,102 @@
+use crate::api::invoices::helper::{
+    create_invoiceable_work_log, invoice_payload, INVOICES_ENDPOINT,
+};
+use crate::api::projects::helper::create_test_project;
+use crate::common::test_app::TestApp;
+use actix_web::{http::StatusCode, test};
+use serde_json::{json, Value};
+
+const COMPANIES_ENDPOINT: &str = "/api/companies/";
+const PROJECTS_ENDPOINT: &str = "/api/projects/";
+
+#[actix_web::test]
+async fn test_delete_company_cascades_to_projects() {
+    /*
+    企業を削除すると紐づくプロジェクトも削除され、企業の復元で一緒に復元されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let company_url = format!("{}{}/", COMPANIES_ENDPOINT, project.company_id);
+        let project_url = format!("{}{}/", PROJECTS_ENDPOINT, project.id);
+
+        let response = context
+            .authenticated_request(test::TestRequest::delete(), &company_url)
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = context
+            .authenticated_request(test::TestRequest::get(), &project_url)
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // 企業が削除されたままではプロジェクト単体で復元できない
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post(),
+                &format!("{}restore/", project_url),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post(),
+                &format!("{}restore/", company_url),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = context
+            .authenticated_request(test::TestRequest::get(), &project_url)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_delete_company_with_invoiced_work_logs() {
+    /*
+    請求書に含まれる勤怠がある企業・プロジェクトは削除できず、勤怠がゴミ箱に移動しないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let (company_id, work_log_id) = create_invoiceable_work_log(&context).await;
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(invoice_payload(&company_id)),
+                INVOICES_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let work_log_url = format!("/api/work-logs/{}/", work_log_id);
+        let response = context
+            .authenticated_request(test::TestRequest::get(), &work_log_url)
+            .await;
+        let work_log: Value = test::read_body_json(response).await;
+        let project_url = format!(
+            "{}{}/",
+            PROJECTS_ENDPOINT,
+            work_log["project_id"].as_str().unwrap()
+        );
+
+        for url in [
+            format!("{}{}/", COMPANIES_ENDPOINT, company_id),
+            project_url,
+        ] {
+            let response = context
+                .authenticated_request(test::TestRequest::delete(), &url)
+                .await;
+            assert_eq!(response.status(), StatusCode::CONFLICT, "{}", url);
+            let body: Value = test::read_body_json(response).await;
+            assert_eq!(body["code"], "INVOICED_WORK_LOGS_EXIST");
+            assert_eq!(body["conflicting_ids"], json!([work_log_id]));
+        }
+
+        let response = context
+            .authenticated_request(test::TestRequest::get(), &work_log_url)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    })
+    .await;
+}
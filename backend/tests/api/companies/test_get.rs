@@ -21,11 +21,13 @@ async fn test_get_all_companies_success() {
 
         assert_eq!(response.status(), StatusCode::OK);
         let body: Value = test::read_body_json(response).await;
-        assert!(body.is_array());
+        assert!(body["items"].is_array());
 
         // 作成した企業数分のデータが取得できていることを確認
-        let companies = body.as_array().unwrap();
+        let companies = body["items"].as_array().unwrap();
         assert!(companies.len() >= company_ids.len());
+        assert_eq!(body["total"], companies.len());
+        assert!(body["next_cursor"].is_null());
 
         // 作成した企業のIDが含まれていることを確認
         let response_ids: Vec<String> = companies
@@ -191,7 +193,7 @@ async fn test_get_company_by_id_not_found() {
             json!({
                 "error": "リソースが見つかりません",
                 "message": "企業が見つかりません",
-                "code": "NOT_FOUND"
+                "code": "COMPANY_NOT_FOUND"
             })
         );
     })
@@ -218,9 +220,41 @@ async fn test_get_company_by_id_invalid_id() {
             json!({
                 "error": "不正なリクエスト",
                 "message": "無効なIDです",
-                "code": "BAD_REQUEST"
+                "code": "INVALID_ID"
             })
         );
     })
     .await;
 }
+
+#[actix_web::test]
+async fn test_get_companies_of_another_user_not_visible() {
+    /*
+    他ユーザーが作成した企業は一覧・ID指定のいずれでも取得できないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let company_id = create_test_company(&context).await;
+        let another_token = context.app.register_another_user().await;
+
+        // 一覧に含まれないこと
+        let response = context
+            .request_with_token(test::TestRequest::get(), COMPANIES_ENDPOINT, &another_token)
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body, json!([]));
+
+        // ID指定でも見つからないこと
+        let response = context
+            .request_with_token(
+                test::TestRequest::get(),
+                &format!("{}{}/", COMPANIES_ENDPOINT, company_id),
+                &another_token,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    })
+    .await;
+}
@@ -112,7 +112,7 @@ async fn test_update_company_not_found() {
             json!({
                 "error": "リソースが見つかりません",
                 "message": "更新対象の企業が見つかりません",
-                "code": "NOT_FOUND"
+                "code": "COMPANY_TO_UPDATE_NOT_FOUND"
             })
         );
     })
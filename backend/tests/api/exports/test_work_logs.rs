@@ -0,0 +1,151 @@
+use crate::api::work_logs::helper::create_test_work_log;
+use crate::common::test_app::TestApp;
+use actix_web::{http::header, http::StatusCode, test};
+use bson::oid::ObjectId;
+use chrono::{Duration, Utc};
+use chrono_tz::Asia::Tokyo;
+use rstest::rstest;
+
+const EXPORT_ENDPOINT: &str = "/api/exports/work-logs/";
+
+/// 前日から翌日までの期間を指定したクエリ（JST）
+fn period_query() -> String {
+    let today = Utc::now().with_timezone(&Tokyo).date_naive();
+    format!(
+        "from={}&to={}",
+        today - Duration::days(1),
+        today + Duration::days(1)
+    )
+}
+
+#[actix_web::test]
+async fn test_export_work_logs_csv() {
+    /*
+    勤怠がBOM付きのCSVとして出力され、金額が実作業時間×時給で計算されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        // 時給3000円のプロジェクトで休憩15分・実作業時間45分の勤怠を作成
+        create_test_work_log(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?{}", EXPORT_ENDPOINT, period_query()),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response.headers().get(header::CONTENT_TYPE).unwrap();
+        assert_eq!(content_type, "text/csv; charset=utf-8");
+        let disposition = response
+            .headers()
+            .get(header::CONTENT_DISPOSITION)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(disposition.starts_with("attachment"));
+        assert!(disposition.contains(".csv"));
+
+        let body = test::read_body(response).await;
+        let body = std::str::from_utf8(&body).unwrap();
+        let body = body.strip_prefix('\u{feff}').expect("BOM not found");
+
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "プロジェクト,企業,開始日時,終了日時,休憩時間（分）,実作業時間（分）,時給,金額,メモ"
+        );
+        let columns: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(columns[0], "テストプロジェクト");
+        assert_eq!(columns[1], "テスト企業");
+        assert_eq!(
+            columns[4..],
+            ["15", "45", "3000", "2250", "テスト用の勤怠データです"]
+        );
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_export_work_logs_xlsx() {
+    /*
+    format=xlsxの場合はExcel形式で出力されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        create_test_work_log(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?{}&format=xlsx", EXPORT_ENDPOINT, period_query()),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response.headers().get(header::CONTENT_TYPE).unwrap();
+        assert_eq!(
+            content_type,
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        );
+
+        // XLSXはZIP形式のため、先頭がZIPのシグネチャであることを確認
+        let body = test::read_body(response).await;
+        assert!(body.starts_with(b"PK\x03\x04"));
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_export_work_logs_with_project_filter() {
+    /*
+    プロジェクトを指定した場合は他のプロジェクトの勤怠が出力されないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        create_test_work_log(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "{}?{}&project_id={}",
+                    EXPORT_ENDPOINT,
+                    period_query(),
+                    ObjectId::new()
+                ),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = test::read_body(response).await;
+        let body = std::str::from_utf8(&body).unwrap();
+        // 見出し行のみ
+        assert_eq!(body.lines().count(), 1);
+    })
+    .await;
+}
+
+#[rstest]
+#[case::reversed_period("from=2024-05-01&to=2024-04-01")]
+#[case::missing_period("format=csv")]
+#[case::invalid_format("from=2024-04-01&to=2024-04-30&format=pdf")]
+#[case::invalid_project_id("from=2024-04-01&to=2024-04-30&project_id=invalid")]
+#[actix_web::test]
+async fn test_export_work_logs_invalid_query(#[case] query: &str) {
+    /*
+    不正なクエリパラメータの場合は400エラーとなることを確認するテスト
+     */
+    let query = query.to_string();
+    TestApp::run_authenticated_test(|context| async move {
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?{}", EXPORT_ENDPOINT, query),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
@@ -13,6 +13,8 @@ pub fn assert_validation_error(body: &Value, field_name: &str, message: &str) {
         body,
         &json!({
             "error": "入力エラー",
+            "code": "INVALID_INPUT",
+            "message": "入力形式が正しくありません",
             "field_errors": [{
                 "field": field_name,
                 "message": message
@@ -26,8 +28,11 @@ pub fn assert_validation_error_with_custom_error(body: &Value, field_name: &str,
         body,
         &json!({
             "error": "バリデーションエラー",
+            "code": "VALIDATION_ERROR",
+            "message": "入力内容に誤りがあります",
             "field_errors": [{
                 "field": field_name,
+                "pointer": format!("/{}", field_name),
                 "message": message
             }]
         })
@@ -0,0 +1,86 @@
+use crate::api::work_logs::helper::create_test_work_log;
+use crate::common::test_context::TestContext;
+use actix_web::{http::StatusCode, test};
+use chrono::{Duration, Utc};
+use chrono_tz::Asia::Tokyo;
+use serde_json::{json, Value};
+
+pub const INVOICES_ENDPOINT: &str = "/api/invoices/";
+
+/// 請求書を作成できる勤怠の作成（勤怠の企業の契約タイプをフリーランスに変更する）
+///
+/// 戻り値は（企業ID, 勤怠ID）
+pub async fn create_invoiceable_work_log(context: &TestContext) -> (String, String) {
+    let work_log_id = create_test_work_log(context).await;
+
+    let response = context
+        .authenticated_request(
+            test::TestRequest::get(),
+            &format!("/api/work-logs/{}/", work_log_id),
+        )
+        .await;
+    let work_log: Value = test::read_body_json(response).await;
+    let response = context
+        .authenticated_request(
+            test::TestRequest::get(),
+            &format!(
+                "/api/projects/{}/",
+                work_log["project_id"].as_str().unwrap()
+            ),
+        )
+        .await;
+    let project: Value = test::read_body_json(response).await;
+    let company_id = project["company_id"].as_str().unwrap().to_string();
+
+    let payload = json!({
+        "company_name": "テスト企業",
+        "establishment_year": 2020,
+        "location": "東京都渋谷区",
+        "website_url": "https://example.com",
+        "employee_count": 100,
+        "contract_type": "Freelance",
+        "average_hourly_rate": 4000,
+        "status": "Contract",
+        "affiliation_start_date": "2023-04-01"
+    });
+    let response = context
+        .authenticated_request(
+            test::TestRequest::put().set_json(&payload),
+            &format!("/api/companies/{}/", company_id),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    (company_id, work_log_id)
+}
+
+/// 前日から翌日までを請求期間とした請求書作成データ（JST）
+pub fn invoice_payload(company_id: &str) -> Value {
+    let today = Utc::now().with_timezone(&Tokyo).date_naive();
+    json!({
+        "company_id": company_id,
+        "period_start": (today - Duration::days(1)).to_string(),
+        "period_end": (today + Duration::days(1)).to_string(),
+        "due_date": (today + Duration::days(30)).to_string(),
+        "notes": "お振込手数料はご負担ください"
+    })
+}
+
+/// テスト用請求書の作成
+pub async fn create_test_invoice(context: &TestContext) -> String {
+    let (company_id, _) = create_invoiceable_work_log(context).await;
+
+    let response = context
+        .authenticated_request(
+            test::TestRequest::post().set_json(invoice_payload(&company_id)),
+            INVOICES_ENDPOINT,
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body: Value = test::read_body_json(response).await;
+    body["id"]
+        .as_str()
+        .expect("Invoice ID not found in response")
+        .to_string()
+}
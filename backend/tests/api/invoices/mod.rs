@@ -0,0 +1,4 @@
+pub mod helper;
+pub mod test_create;
+pub mod test_document;
+pub mod test_status;
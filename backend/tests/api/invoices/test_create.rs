@@ -0,0 +1,238 @@
+use crate::api::invoices::helper::{
+    create_invoiceable_work_log, invoice_payload, INVOICES_ENDPOINT,
+};
+use crate::api::work_logs::helper::{create_test_work_log, generate_work_log_update_payload};
+use crate::common::test_app::TestApp;
+use actix_web::{http::StatusCode, test};
+use serde_json::Value;
+
+#[actix_web::test]
+async fn test_create_invoice_success() {
+    /*
+    請求期間の勤怠から請求書が作成され、金額が実作業時間×時給で計算されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        // 時給3000円のプロジェクトで実作業時間45分の勤怠を作成
+        let (company_id, work_log_id) = create_invoiceable_work_log(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(invoice_payload(&company_id)),
+                INVOICES_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: Value = test::read_body_json(response).await;
+        let invoice_id = body["id"].as_str().unwrap();
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}{}/", INVOICES_ENDPOINT, invoice_id),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let invoice: Value = test::read_body_json(response).await;
+        assert_eq!(invoice["invoice_number"], "INV-000001");
+        assert_eq!(invoice["company_name"], "テスト企業");
+        assert_eq!(invoice["status"], "Draft");
+        assert_eq!(invoice["work_log_count"], 1);
+        assert_eq!(invoice["line_items"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            invoice["line_items"][0]["description"],
+            "テストプロジェクト"
+        );
+        assert_eq!(invoice["line_items"][0]["work_minutes"], 45);
+        assert_eq!(invoice["line_items"][0]["hourly_rate"], 3000);
+        assert_eq!(invoice["line_items"][0]["amount"], 2250);
+        assert_eq!(invoice["subtotal"], 2250);
+        assert_eq!(invoice["tax_rate"], 10);
+        assert_eq!(invoice["tax_amount"], 225);
+        assert_eq!(invoice["total"], 2475);
+
+        // 請求書に含まれた勤怠には請求書IDが設定される
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("/api/work-logs/{}/", work_log_id),
+            )
+            .await;
+        let work_log: Value = test::read_body_json(response).await;
+        assert_eq!(work_log["invoice_id"], invoice_id);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_invoice_numbering() {
+    /*
+    請求書番号が連番で採番され、削除した請求書の番号は再利用されないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let (company_id, _) = create_invoiceable_work_log(&context).await;
+
+        let mut numbers = Vec::new();
+        for _ in 0..2 {
+            let response = context
+                .authenticated_request(
+                    test::TestRequest::post().set_json(invoice_payload(&company_id)),
+                    INVOICES_ENDPOINT,
+                )
+                .await;
+            assert_eq!(response.status(), StatusCode::CREATED);
+            let body: Value = test::read_body_json(response).await;
+            let url = format!("{}{}/", INVOICES_ENDPOINT, body["id"].as_str().unwrap());
+
+            let response = context
+                .authenticated_request(test::TestRequest::get(), &url)
+                .await;
+            let invoice: Value = test::read_body_json(response).await;
+            numbers.push(invoice["invoice_number"].as_str().unwrap().to_string());
+
+            // 下書きを削除して勤怠のロックを解除し、同じ勤怠から再度作成する
+            let response = context
+                .authenticated_request(test::TestRequest::delete(), &url)
+                .await;
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        }
+
+        assert_eq!(numbers, vec!["INV-000001", "INV-000002"]);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_invoice_not_freelance_company() {
+    /*
+    契約タイプがフリーランス・副業以外の企業には請求書を作成できないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        // 契約タイプが「契約」の企業の勤怠を作成
+        let work_log_id = create_test_work_log(&context).await;
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("/api/work-logs/{}/", work_log_id),
+            )
+            .await;
+        let work_log: Value = test::read_body_json(response).await;
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "/api/projects/{}/",
+                    work_log["project_id"].as_str().unwrap()
+                ),
+            )
+            .await;
+        let project: Value = test::read_body_json(response).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post()
+                    .set_json(invoice_payload(project["company_id"].as_str().unwrap())),
+                INVOICES_ENDPOINT,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_invoice_without_billable_work_logs() {
+    /*
+    請求対象の勤怠がない場合と、勤怠が既に他の請求書に含まれている場合に作成できないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let (company_id, _) = create_invoiceable_work_log(&context).await;
+
+        // 勤怠のない期間
+        let mut payload = invoice_payload(&company_id);
+        payload["period_start"] = "2024-01-01".into();
+        payload["period_end"] = "2024-01-31".into();
+        payload["due_date"] = "2024-02-29".into();
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(&payload),
+                INVOICES_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // 同じ期間で2回作成すると、2回目は請求対象の勤怠がない
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(invoice_payload(&company_id)),
+                INVOICES_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(invoice_payload(&company_id)),
+                INVOICES_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_invoice_invalid_period() {
+    /*
+    支払期限が請求期間の終了日より前の場合にバリデーションエラーとなることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let (company_id, _) = create_invoiceable_work_log(&context).await;
+
+        let mut payload = invoice_payload(&company_id);
+        payload["due_date"] = "2024-01-01".into();
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(&payload),
+                INVOICES_ENDPOINT,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_invoiced_work_log_is_locked() {
+    /*
+    請求書に含まれた勤怠は更新・削除できないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let (company_id, work_log_id) = create_invoiceable_work_log(&context).await;
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(invoice_payload(&company_id)),
+                INVOICES_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let update_payload = generate_work_log_update_payload(&context).await;
+        let response = context
+            .authenticated_request(
+                test::TestRequest::put().set_json(&update_payload),
+                &format!("/api/work-logs/{}/", work_log_id),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::delete(),
+                &format!("/api/work-logs/{}/", work_log_id),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
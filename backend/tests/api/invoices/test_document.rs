@@ -0,0 +1,75 @@
+use crate::api::invoices::helper::{create_test_invoice, INVOICES_ENDPOINT};
+use crate::common::test_app::TestApp;
+use actix_web::{http::header, http::StatusCode, test};
+use rstest::rstest;
+
+#[rstest]
+#[case::default("", "application/pdf", "pdf")]
+#[case::pdf("?format=pdf", "application/pdf", "pdf")]
+#[case::html("?format=html", "text/html; charset=utf-8", "html")]
+#[actix_web::test]
+async fn test_get_invoice_document(
+    #[case] query: &'static str,
+    #[case] content_type: &'static str,
+    #[case] extension: &'static str,
+) {
+    /*
+    請求書が指定した形式で、請求書番号のファイル名の添付ファイルとして出力されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let invoice_id = create_test_invoice(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}{}/document/{}", INVOICES_ENDPOINT, invoice_id, query),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            content_type
+        );
+        let disposition = response
+            .headers()
+            .get(header::CONTENT_DISPOSITION)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(disposition.starts_with("attachment"));
+        assert!(disposition.contains(&format!("INV-000001.{}", extension)));
+
+        let body = test::read_body(response).await;
+        match extension {
+            "pdf" => assert!(body.starts_with(b"%PDF")),
+            _ => {
+                let html = std::str::from_utf8(&body).unwrap();
+                assert!(html.contains("テスト企業 御中"));
+                assert!(html.contains("¥2,475"));
+            }
+        }
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_invoice_document_invalid_format() {
+    /*
+    未対応の出力形式を指定した場合に400が返されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let invoice_id = create_test_invoice(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}{}/document/?format=docx", INVOICES_ENDPOINT, invoice_id),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
@@ -0,0 +1,126 @@
+use crate::api::invoices::helper::{
+    create_invoiceable_work_log, create_test_invoice, invoice_payload, INVOICES_ENDPOINT,
+};
+use crate::common::test_app::TestApp;
+use crate::common::test_context::TestContext;
+use actix_web::{dev::ServiceResponse, http::StatusCode, test};
+use bson::oid::ObjectId;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+async fn update_status(context: &TestContext, invoice_id: &str, status: &str) -> ServiceResponse {
+    context
+        .authenticated_request(
+            test::TestRequest::put().set_json(json!({ "status": status })),
+            &format!("{}{}/status/", INVOICES_ENDPOINT, invoice_id),
+        )
+        .await
+}
+
+#[actix_web::test]
+async fn test_update_invoice_status_success() {
+    /*
+    請求書のステータスを下書き → 送付済み → 入金済みの順に変更できることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let invoice_id = create_test_invoice(&context).await;
+
+        for status in ["Sent", "Paid"] {
+            let response = update_status(&context, &invoice_id, status).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let body: Value = test::read_body_json(response).await;
+            assert_eq!(body["status"], status);
+        }
+    })
+    .await;
+}
+
+#[rstest]
+#[case::skip_sent("Paid")]
+#[case::same_status("Draft")]
+#[actix_web::test]
+async fn test_update_invoice_status_invalid_transition(#[case] status: &'static str) {
+    /*
+    下書きから送付済み以外のステータスに変更できないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let invoice_id = create_test_invoice(&context).await;
+
+        let response = update_status(&context, &invoice_id, status).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_update_invoice_status_not_found() {
+    /*
+    存在しない請求書のステータス変更で404が返されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let response = update_status(&context, &ObjectId::new().to_hex(), "Sent").await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_delete_draft_invoice_unlocks_work_logs() {
+    /*
+    下書きの請求書を削除すると、含まれていた勤怠が再び編集できるようになることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let (company_id, work_log_id) = create_invoiceable_work_log(&context).await;
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(invoice_payload(&company_id)),
+                INVOICES_ENDPOINT,
+            )
+            .await;
+        let body: Value = test::read_body_json(response).await;
+        let url = format!("{}{}/", INVOICES_ENDPOINT, body["id"].as_str().unwrap());
+
+        let response = context
+            .authenticated_request(test::TestRequest::delete(), &url)
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = context
+            .authenticated_request(test::TestRequest::get(), &url)
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::delete(),
+                &format!("/api/work-logs/{}/", work_log_id),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_delete_sent_invoice() {
+    /*
+    送付済みの請求書は削除できないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let invoice_id = create_test_invoice(&context).await;
+        let response = update_status(&context, &invoice_id, "Sent").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::delete(),
+                &format!("{}{}/", INVOICES_ENDPOINT, invoice_id),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
@@ -1,6 +1,12 @@
+pub mod admin;
+pub mod analytics;
 pub mod auth;
+pub mod calendar;
 pub mod companies;
+pub mod exports;
 pub mod helper;
+pub mod invoices;
 pub mod projects;
+pub mod reports;
 pub mod users;
 pub mod work_logs;
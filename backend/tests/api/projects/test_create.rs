@@ -227,6 +227,8 @@ async fn test_create_project_validation(#[case] test_case: ValidationTestCase) {
                 error_body,
                 json!({
                     "error": "入力エラー",
+                    "code": "INVALID_INPUT",
+                    "message": "入力形式が正しくありません",
                     "field_errors": [{
                         "field": "unknown",
                         "message": "入力形式が正しくありません"
@@ -274,7 +276,44 @@ async fn test_create_project_with_non_existent_company() {
             json!({
                 "error": "リソースが見つかりません",
                 "message": "プロジェクトに関連する企業が見つかりません",
-                "code": "NOT_FOUND"
+                "code": "PROJECT_COMPANY_NOT_FOUND"
+            })
+        );
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_project_with_another_users_company() {
+    /*
+    他ユーザーの企業IDを指定してプロジェクト作成を試みた場合、404エラーが返されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let company_id = create_test_company(&context).await;
+        let another_token = context.app.register_another_user().await;
+
+        let payload = json!({
+            "title": "テストプロジェクト",
+            "status": "Planning",
+            "company_id": company_id,
+        });
+
+        let response = context
+            .request_with_token(
+                test::TestRequest::post().set_json(&payload),
+                PROJECTS_ENDPOINT,
+                &another_token,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let error_body: Value = test::read_body_json(response).await;
+        assert_eq!(
+            error_body,
+            json!({
+                "error": "リソースが見つかりません",
+                "message": "プロジェクトに関連する企業が見つかりません",
+                "code": "PROJECT_COMPANY_NOT_FOUND"
             })
         );
     })
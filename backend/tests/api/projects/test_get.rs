@@ -66,7 +66,7 @@ async fn test_get_all_projects_with_filter() {
 
         assert_eq!(response.status(), StatusCode::OK);
         let body: Value = test::read_body_json(response).await;
-        let projects = body.as_array().unwrap();
+        let projects = body["items"].as_array().unwrap();
 
         assert_eq!(projects.len(), 2);
 
@@ -86,7 +86,7 @@ async fn test_get_all_projects_with_filter() {
 
         assert_eq!(response.status(), StatusCode::OK);
         let body: Value = test::read_body_json(response).await;
-        let projects = body.as_array().unwrap();
+        let projects = body["items"].as_array().unwrap();
         assert_eq!(projects.len(), 2);
         for project in projects.iter() {
             assert_eq!(project["status"], "Planning");
@@ -100,7 +100,7 @@ async fn test_get_all_projects_with_filter() {
 
         assert_eq!(response.status(), StatusCode::OK);
         let body: Value = test::read_body_json(response).await;
-        let projects = body.as_array().unwrap();
+        let projects = body["items"].as_array().unwrap();
         assert_eq!(projects.len(), 1);
         let skill_labels: HashSet<String> = projects[0]["skill_labels"]
             .as_array()
@@ -137,7 +137,7 @@ async fn test_get_all_projects_with_sort() {
             serde_json::to_string_pretty(&body).unwrap()
         );
 
-        let projects = body.as_array().unwrap();
+        let projects = body["items"].as_array().unwrap();
         assert!(projects.len() >= 3);
 
         // タイトルが正しくソートされているか確認
@@ -164,7 +164,7 @@ async fn test_get_all_projects_with_sort() {
 
         assert_eq!(response.status(), StatusCode::OK);
         let body: Value = test::read_body_json(response).await;
-        let projects = body.as_array().unwrap();
+        let projects = body["items"].as_array().unwrap();
 
         // 日付が正しくソートされているか確認
         let dates: Vec<DateTime<Utc>> = projects
@@ -264,7 +264,7 @@ async fn test_get_project_by_id_not_found() {
             json!({
                 "error": "リソースが見つかりません",
                 "message": "プロジェクトが見つかりません",
-                "code": "NOT_FOUND"
+                "code": "PROJECT_NOT_FOUND"
             })
         );
     })
@@ -291,7 +291,7 @@ async fn test_get_project_by_id_invalid_id() {
             json!({
                 "error": "不正なリクエスト",
                 "message": "無効なIDです",
-                "code": "BAD_REQUEST"
+                "code": "INVALID_ID"
             })
         );
     })
@@ -318,7 +318,7 @@ async fn test_get_all_projects_with_multiple_filters() {
 
         assert_eq!(response.status(), StatusCode::OK);
         let body: Value = test::read_body_json(response).await;
-        let projects = body.as_array().unwrap();
+        let projects = body["items"].as_array().unwrap();
 
         for project in projects.iter() {
             assert_eq!(project["status"], "Planning");
@@ -339,89 +339,68 @@ async fn test_get_all_projects_with_pagination() {
         create_test_projects(&context).await;
         create_test_projects(&context).await;
 
-        // ケース1: 最初の2件を取得
-        let url = format!("{}?limit={}&offset={}", PROJECTS_ENDPOINT, 2, 0);
+        // ケース1: 最初の2件を取得し、総件数と次のページのカーソルが返ることを確認
+        let url = format!("{}?limit={}&sort={}", PROJECTS_ENDPOINT, 2, "title:asc");
         let response = context
             .authenticated_request(test::TestRequest::get(), &url)
             .await;
 
         assert_eq!(response.status(), StatusCode::OK);
         let body: Value = test::read_body_json(response).await;
-        let projects = body.as_array().unwrap();
-        assert_eq!(projects.len(), 2, "First page should contain 2 items");
-
-        // ケース2: 次の2件を取得
-        let url = format!("{}?limit={}&offset={}", PROJECTS_ENDPOINT, 2, 2);
-        let response = context
-            .authenticated_request(test::TestRequest::get(), &url)
-            .await;
-
-        assert_eq!(response.status(), StatusCode::OK);
-        let body: Value = test::read_body_json(response).await;
-        let projects = body.as_array().unwrap();
-        assert_eq!(projects.len(), 2, "Second page should contain 2 items");
-
-        // ケース3: 最後のページ（残り2件）
-        let url = format!("{}?limit={}&offset={}", PROJECTS_ENDPOINT, 2, 4);
-        let response = context
-            .authenticated_request(test::TestRequest::get(), &url)
-            .await;
-
-        assert_eq!(response.status(), StatusCode::OK);
-        let body: Value = test::read_body_json(response).await;
-        let projects = body.as_array().unwrap();
-        assert_eq!(projects.len(), 2, "Last page should contain 2 items");
-
-        // ケース4: 範囲外のオフセット
-        let url = format!("{}?limit={}&offset={}", PROJECTS_ENDPOINT, 2, 10);
-        let response = context
-            .authenticated_request(test::TestRequest::get(), &url)
-            .await;
-
-        assert_eq!(response.status(), StatusCode::OK);
-        let body: Value = test::read_body_json(response).await;
-        let projects = body.as_array().unwrap();
-        assert_eq!(projects.len(), 0, "Out of range offset should return empty array");
-
-        // ケース5: limitとoffsetを組み合わせてソート順の一貫性を確認
-        let url = format!(
-            "{}?limit={}&offset={}&sort={}",
-            PROJECTS_ENDPOINT, 2, 0, "title:asc"
-        );
-        let response = context
-            .authenticated_request(test::TestRequest::get(), &url)
-            .await;
-
-        assert_eq!(response.status(), StatusCode::OK);
-        let body: Value = test::read_body_json(response).await;
-        let first_page = body.as_array().unwrap();
-        let first_page_titles: Vec<&str> = first_page
+        let first_page = body["items"].as_array().unwrap();
+        assert_eq!(first_page.len(), 2, "First page should contain 2 items");
+        assert_eq!(body["total"], 6);
+        let mut titles: Vec<String> = first_page
             .iter()
-            .map(|p| p["title"].as_str().unwrap())
+            .map(|p| p["title"].as_str().unwrap().to_string())
             .collect();
-
-        // 2ページ目を取得
-        let url = format!(
-            "{}?limit={}&offset={}&sort={}",
-            PROJECTS_ENDPOINT, 2, 2, "title:asc"
-        );
-        let response = context
-            .authenticated_request(test::TestRequest::get(), &url)
-            .await;
-
-        let body: Value = test::read_body_json(response).await;
-        let second_page = body.as_array().unwrap();
-        let second_page_titles: Vec<&str> = second_page
+        let mut ids: Vec<String> = first_page
             .iter()
-            .map(|p| p["title"].as_str().unwrap())
+            .map(|p| p["id"].as_str().unwrap().to_string())
             .collect();
+        let mut next_cursor = body["next_cursor"]
+            .as_str()
+            .expect("next_cursor should be returned")
+            .to_string();
+
+        // ケース2: カーソルをたどって最後のページまで取得
+        loop {
+            let url = format!(
+                "{}?limit={}&sort={}&cursor={}",
+                PROJECTS_ENDPOINT, 2, "title:asc", next_cursor
+            );
+            let response = context
+                .authenticated_request(test::TestRequest::get(), &url)
+                .await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+            let body: Value = test::read_body_json(response).await;
+            let page = body["items"].as_array().unwrap();
+            assert_eq!(page.len(), 2, "Each page should contain 2 items");
+            assert_eq!(body["total"], 6, "Total should not depend on the cursor");
+            titles.extend(
+                page.iter()
+                    .map(|p| p["title"].as_str().unwrap().to_string()),
+            );
+            ids.extend(page.iter().map(|p| p["id"].as_str().unwrap().to_string()));
 
-        // ページをまたいでもソート順が維持されていることを確認
+            match body["next_cursor"].as_str() {
+                Some(cursor) => next_cursor = cursor.to_string(),
+                None => break,
+            }
+        }
+
+        // 全件が重複なく取得でき、ページをまたいでもソート順が維持されていることを確認
+        assert_eq!(ids.len(), 6);
+        assert_eq!(
+            ids.iter().collect::<HashSet<_>>().len(),
+            6,
+            "Pages should not overlap"
+        );
         assert!(
-            first_page_titles[first_page_titles.len() - 1] <= second_page_titles[0],
-            "Sort order should be maintained across pages. Last item of first page: {:?}, First item of second page: {:?}",
-            first_page_titles[first_page_titles.len() - 1],
-            second_page_titles[0]
+            titles.windows(2).all(|w| w[0] <= w[1]),
+            "Sort order should be maintained across pages: {:?}",
+            titles
         );
     })
     .await;
@@ -447,7 +426,7 @@ async fn test_get_all_projects_with_multiple_sorts() {
 
         assert_eq!(response.status(), StatusCode::OK);
         let body: Value = test::read_body_json(response).await;
-        let projects = body.as_array().unwrap();
+        let projects = body["items"].as_array().unwrap();
 
         // ステータスと作成日時の順序を確認
         let mut prev_status = "";
@@ -484,7 +463,7 @@ async fn test_get_all_projects_with_multiple_sorts() {
 async fn test_get_all_projects_with_invalid_params() {
     /*
     無効なパラメータの場合は無視され、400エラーが返ることを確認するテスト
-    （検索ワードは無視して良いが、sort, limit, offsetはプログラム上のエラーとなりうる）
+    （検索ワードは無視して良いが、sort, limit, cursorはプログラム上のエラーとなりうる）
      */
     TestApp::run_authenticated_test(|context| async move {
         // 無効なソート順
@@ -501,8 +480,8 @@ async fn test_get_all_projects_with_invalid_params() {
             .await;
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
-        // 無効なoffset値
-        let url = format!("{}?offset={}", PROJECTS_ENDPOINT, "invalid");
+        // 無効なカーソル
+        let url = format!("{}?cursor={}", PROJECTS_ENDPOINT, "invalid");
         let response = context
             .authenticated_request(test::TestRequest::get(), &url)
             .await;
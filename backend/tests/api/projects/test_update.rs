@@ -104,7 +104,7 @@ async fn test_update_project_not_found() {
             json!({
                 "error": "リソースが見つかりません",
                 "message": "更新対象のプロジェクトが見つかりません",
-                "code": "NOT_FOUND"
+                "code": "PROJECT_TO_UPDATE_NOT_FOUND"
             })
         );
     })
@@ -0,0 +1,2 @@
+pub mod test_earnings;
+pub mod test_time;
@@ -0,0 +1,119 @@
+use crate::api::work_logs::helper::create_test_work_log;
+use crate::common::test_app::TestApp;
+use actix_web::{http::StatusCode, test};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use chrono_tz::Asia::Tokyo;
+use rstest::rstest;
+use serde_json::Value;
+
+const EARNINGS_ENDPOINT: &str = "/api/reports/earnings/";
+
+/// 前月の1日から当月の末日までの期間（JST）
+fn two_month_period() -> (NaiveDate, NaiveDate) {
+    let today = Utc::now().with_timezone(&Tokyo).date_naive();
+    let this_month = today.with_day(1).unwrap();
+    let previous_month = (this_month - Duration::days(1)).with_day(1).unwrap();
+    let next_month = (this_month + Duration::days(31)).with_day(1).unwrap();
+    (previous_month, next_month - Duration::days(1))
+}
+
+#[actix_web::test]
+async fn test_get_earnings_by_project() {
+    /*
+    プロジェクトごとの収入が実作業時間×時給で計算され、合計にボーナスが含まれることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        // 時給3000円のプロジェクトで45分の勤怠を作成
+        create_test_work_log(&context).await;
+        let (from, to) = two_month_period();
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "{}?from={}&to={}&group_by=project",
+                    EARNINGS_ENDPOINT, from, to
+                ),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["group_by"], "project");
+
+        let groups = body["groups"].as_array().unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0]["label"], "テストプロジェクト");
+        assert_eq!(groups[0]["hourly_rate"], 3000);
+        assert_eq!(groups[0]["work_minutes"], 45);
+        assert_eq!(groups[0]["work_earnings"], 2250);
+        // ボーナスはプロジェクトに按分しない
+        assert_eq!(groups[0]["bonus"], 0);
+
+        // 年間200万円（100万円×年2回）のボーナスを2ヶ月分計上
+        assert_eq!(body["work_earnings"], 2250);
+        assert_eq!(body["bonus"], 333_334);
+        assert_eq!(body["total"], 2250 + 333_334);
+        assert_eq!(body["unpriced_work_minutes"], 0);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_earnings_by_month() {
+    /*
+    月ごとの集計では各月にボーナスが月割りで計上されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        create_test_work_log(&context).await;
+        let (from, to) = two_month_period();
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?from={}&to={}", EARNINGS_ENDPOINT, from, to),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["group_by"], "month");
+
+        let groups = body["groups"].as_array().unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0]["key"], from.format("%Y-%m").to_string());
+        assert_eq!(groups[1]["key"], to.format("%Y-%m").to_string());
+        for group in groups {
+            assert_eq!(group["bonus"], 166_667);
+        }
+        let work_minutes: i64 = groups
+            .iter()
+            .map(|group| group["work_minutes"].as_i64().unwrap())
+            .sum();
+        assert_eq!(work_minutes, 45);
+    })
+    .await;
+}
+
+#[rstest]
+#[case::reversed_period("from=2024-05-01&to=2024-04-01")]
+#[case::invalid_group_by("from=2024-04-01&to=2024-04-30&group_by=day")]
+#[case::missing_period("group_by=month")]
+#[actix_web::test]
+async fn test_get_earnings_invalid_query(#[case] query: &str) {
+    /*
+    無効なクエリパラメータの場合は400エラーが返ることを確認するテスト
+     */
+    let query = query.to_string();
+    TestApp::run_authenticated_test(|context| async move {
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?{}", EARNINGS_ENDPOINT, query),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
@@ -0,0 +1,129 @@
+use crate::api::work_logs::helper::create_test_work_log;
+use crate::common::test_app::TestApp;
+use actix_web::{http::StatusCode, test};
+use chrono::{Duration, Utc};
+use chrono_tz::Asia::Tokyo;
+use rstest::rstest;
+use serde_json::Value;
+
+const TIME_REPORT_ENDPOINT: &str = "/api/reports/time/";
+
+/// 前日から翌日までの期間を指定したクエリ（JST）
+fn period_query() -> String {
+    let today = Utc::now().with_timezone(&Tokyo).date_naive();
+    format!(
+        "from={}&to={}",
+        today - Duration::days(1),
+        today + Duration::days(1)
+    )
+}
+
+#[rstest]
+#[case::day("day", None)]
+#[case::week("week", None)]
+#[case::month("month", None)]
+#[case::project("project", Some("テストプロジェクト"))]
+#[case::company("company", Some("テスト企業"))]
+#[actix_web::test]
+async fn test_get_time_report(#[case] group_by: &str, #[case] label: Option<&str>) {
+    /*
+    集計単位ごとに勤怠の実作業時間が集計されることを確認するテスト
+     */
+    let group_by = group_by.to_string();
+    let label = label.map(str::to_string);
+    TestApp::run_authenticated_test(|context| async move {
+        // 実作業時間45分の勤怠を作成
+        create_test_work_log(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "{}?{}&group_by={}",
+                    TIME_REPORT_ENDPOINT,
+                    period_query(),
+                    group_by
+                ),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["group_by"], group_by.as_str());
+        assert_eq!(body["work_minutes"], 45);
+        assert_eq!(body["work_log_count"], 1);
+
+        let groups = body["groups"].as_array().unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0]["work_minutes"], 45);
+        assert_eq!(groups[0]["work_log_count"], 1);
+        if let Some(label) = label {
+            assert_eq!(groups[0]["label"], label.as_str());
+        }
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_time_report_by_skill_label() {
+    /*
+    スキルラベルごとの集計では、プロジェクトの全てのスキルラベルに作業時間が計上されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        // スキルラベル「Rust」「MongoDB」を持つプロジェクトの勤怠を作成
+        create_test_work_log(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "{}?{}&group_by=skill_label",
+                    TIME_REPORT_ENDPOINT,
+                    period_query()
+                ),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        // 合計は重複して計上しない
+        assert_eq!(body["work_minutes"], 45);
+
+        let mut labels: Vec<&str> = body["groups"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|group| {
+                assert_eq!(group["work_minutes"], 45);
+                group["key"].as_str().unwrap()
+            })
+            .collect();
+        labels.sort();
+        assert_eq!(labels, vec!["MongoDB", "Rust"]);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_time_report_out_of_period() {
+    /*
+    期間外の勤怠は集計されないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        create_test_work_log(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?from=2020-01-01&to=2020-12-31", TIME_REPORT_ENDPOINT),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["work_minutes"], 0);
+        assert_eq!(body["work_log_count"], 0);
+        assert!(body["groups"].as_array().unwrap().is_empty());
+    })
+    .await;
+}
@@ -1,2 +1,5 @@
+pub mod test_email_verification;
 pub mod test_get;
+pub mod test_locale;
+pub mod test_sessions;
 pub mod test_update;
@@ -0,0 +1,263 @@
+use crate::api::helper::validation::assert_validation_error_with_custom_error;
+use crate::common::test_app::TestApp;
+use crate::common::test_context::TestContext;
+use actix_web::dev::ServiceResponse;
+use actix_web::{http::StatusCode, test};
+use chrono::{Duration, Utc};
+use devtrackr_api::models::{auth::SessionClient, users::UserCreate};
+use mongodb::bson::{doc, DateTime as BsonDateTime, Document};
+use serde_json::{json, Value};
+
+const CONFIRM_ENDPOINT: &str = "/api/auth/email-verification/confirm/";
+const RESEND_ENDPOINT: &str = "/api/users/me/email-verification/";
+const USERS_ME_ENDPOINT: &str = "/api/users/me/";
+const CALENDAR_FEED_ENDPOINT: &str = "/api/users/me/calendar-feed/";
+const LOGIN_ENDPOINT: &str = "/api/auth/login/";
+const NEW_EMAIL: &str = "updated@example.com";
+
+async fn call_confirm(context: &TestContext, token: &str) -> ServiceResponse {
+    test::call_service(
+        context.service(),
+        test::TestRequest::post()
+            .uri(CONFIRM_ENDPOINT)
+            .set_json(json!({ "token": token }))
+            .to_request(),
+    )
+    .await
+}
+
+/// テスト用ヘルパー関数. 指定した宛先に最後に送信されたメールから、確認用リンクのトークンを取得する
+async fn latest_verification_token(context: &TestContext, email: &str) -> String {
+    let mails = context.app.sent_mails(email).await;
+    let mail = mails
+        .last()
+        .expect("メールアドレスの確認用メールが送信されていません");
+    mail.body
+        .split_whitespace()
+        .find_map(|word| word.split_once("?token=").map(|(_, token)| token))
+        .expect("メールに確認用のURLが記載されていません")
+        .to_string()
+}
+
+/// テスト用ヘルパー関数. ログイン中のユーザー情報を取得する
+async fn get_me(context: &TestContext) -> Value {
+    let response = context
+        .authenticated_request(test::TestRequest::get(), USERS_ME_ENDPOINT)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    test::read_body_json(response).await
+}
+
+/// テスト用ヘルパー関数. メールアドレスの変更を申請する
+async fn request_email_change(context: &TestContext, email: &str) -> ServiceResponse {
+    context
+        .authenticated_request(
+            test::TestRequest::put().set_json(json!({
+                "email": email,
+                "username": context.app.test_user.username
+            })),
+            USERS_ME_ENDPOINT,
+        )
+        .await
+}
+
+/// テスト用ヘルパー関数. 登録日時を猶予期間より前に変更し、未確認のアカウントに対する制限を適用させる
+async fn expire_grace_period(context: &TestContext) {
+    let created_at = BsonDateTime::from_chrono(Utc::now() - Duration::days(30));
+    context
+        .app
+        .test_db
+        .db
+        .collection::<Document>("users")
+        .update_many(doc! {}, doc! { "$set": { "created_at": created_at } }, None)
+        .await
+        .expect("登録日時の変更に失敗しました");
+}
+
+#[actix_web::test]
+async fn test_verify_email_after_register() {
+    /*
+    登録時に送信された確認用リンクで、メールアドレスを確認済みにできることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let body = get_me(&context).await;
+        assert_eq!(body["email_verified"], false);
+
+        let token = latest_verification_token(&context, &context.app.test_user.email).await;
+        let response = call_confirm(&context, &token).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let body = get_me(&context).await;
+        assert_eq!(body["email_verified"], true);
+        assert!(body["pending_email"].is_null());
+
+        // 確認済みの場合は再送信できない
+        let response = context
+            .authenticated_request(test::TestRequest::post(), RESEND_ENDPOINT)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "EMAIL_ALREADY_VERIFIED");
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_change_email_after_verification() {
+    /*
+    メールアドレスの変更は確認されるまで申請中となり、確認後に新しいメールアドレスでログインできることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let response = request_email_change(&context, NEW_EMAIL).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // 確認されるまでは現在のメールアドレスを使用する
+        let body = get_me(&context).await;
+        assert_eq!(body["email"], context.app.test_user.email);
+        assert_eq!(body["pending_email"], NEW_EMAIL);
+
+        let token = latest_verification_token(&context, NEW_EMAIL).await;
+        let response = call_confirm(&context, &token).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let body = get_me(&context).await;
+        assert_eq!(body["email"], NEW_EMAIL);
+        assert_eq!(body["email_verified"], true);
+        assert!(body["pending_email"].is_null());
+
+        let response = test::call_service(
+            context.service(),
+            test::TestRequest::post()
+                .uri(LOGIN_ENDPOINT)
+                .set_json(json!({
+                    "email": NEW_EMAIL,
+                    "password": context.app.test_user.password
+                }))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 使用済みの確認用リンクは使用できない
+        let response = call_confirm(&context, &token).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_stale_verification_link() {
+    /*
+    メールアドレスを再度変更した場合・変更を取り消した場合は、以前の確認用リンクが使用できなくなることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let first_email = "first@example.com";
+        let response = request_email_change(&context, first_email).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let stale_token = latest_verification_token(&context, first_email).await;
+
+        let response = request_email_change(&context, NEW_EMAIL).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = call_confirm(&context, &stale_token).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "INVALID_OR_EXPIRED_VERIFICATION_LINK");
+
+        // 現在のメールアドレスを指定すると変更の申請が取り消される
+        let token = latest_verification_token(&context, NEW_EMAIL).await;
+        let response = request_email_change(&context, &context.app.test_user.email).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(get_me(&context).await["pending_email"].is_null());
+
+        let response = call_confirm(&context, &token).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(get_me(&context).await["email"], context.app.test_user.email);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_change_email_to_used_address() {
+    /*
+    他のユーザーが使用しているメールアドレスには変更できないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let another_email = "another_user@example.com";
+        context
+            .app
+            .auth_usecase
+            .register(
+                &UserCreate {
+                    email: another_email.to_string(),
+                    password: String::from("password123"),
+                    username: String::from("another_user"),
+                },
+                &SessionClient::default(),
+            )
+            .await
+            .expect("Failed to register another user");
+
+        let response = request_email_change(&context, another_email).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(get_me(&context).await["pending_email"].is_null());
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_unverified_account_restriction() {
+    /*
+    猶予期間を過ぎた未確認のアカウントは参照のみ許可され、確認後は制限が解除されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        expire_grace_period(&context).await;
+
+        let response = context
+            .authenticated_request(test::TestRequest::post(), CALENDAR_FEED_ENDPOINT)
+            .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "EMAIL_NOT_VERIFIED");
+
+        // 参照と確認用リンクの再送信は許可される
+        get_me(&context).await;
+        let response = context
+            .authenticated_request(test::TestRequest::post(), RESEND_ENDPOINT)
+            .await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let token = latest_verification_token(&context, &context.app.test_user.email).await;
+        let response = call_confirm(&context, &token).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = context
+            .authenticated_request(test::TestRequest::post(), CALENDAR_FEED_ENDPOINT)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_verify_email_invalid_token() {
+    /*
+    無効なトークン・空のトークンを指定した場合のエラーを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let response = call_confirm(&context, "invalid-token").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "INVALID_OR_EXPIRED_VERIFICATION_LINK");
+
+        let response = call_confirm(&context, "").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_validation_error_with_custom_error(
+            &body,
+            "token",
+            "確認用のトークンを指定してください",
+        );
+    })
+    .await;
+}
@@ -22,6 +22,9 @@ async fn test_get_current_user_success() {
         assert!(body.get("id").is_some());
         assert!(body.get("email").is_some());
         assert!(body.get("username").is_some());
+        // タイムゾーン・表示言語が未設定の場合はデフォルト値を返す
+        assert_eq!(body["timezone"], "Asia/Tokyo");
+        assert_eq!(body["locale"], "ja");
     })
     .await;
 }
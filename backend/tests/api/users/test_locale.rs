@@ -0,0 +1,153 @@
+use crate::common::test_app::TestApp;
+use actix_web::{http::StatusCode, test};
+use bson::oid::ObjectId;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+const USERS_ENDPOINT: &str = "/api/users/me/";
+const WORK_LOGS_ENDPOINT: &str = "/api/work-logs/";
+const REGISTER_ENDPOINT: &str = "/api/auth/register/";
+
+#[rstest]
+#[case::english("en-US,en;q=0.9", "Resource not found", "Work log not found")]
+#[case::japanese("ja", "リソースが見つかりません", "勤怠が見つかりません")]
+#[case::highest_quality_supported(
+    "fr, ja;q=0.5, en;q=0.8",
+    "Resource not found",
+    "Work log not found"
+)]
+#[case::unsupported("fr", "リソースが見つかりません", "勤怠が見つかりません")]
+#[actix_web::test]
+async fn test_error_message_by_accept_language(
+    #[case] accept_language: &'static str,
+    #[case] expected_error: &'static str,
+    #[case] expected_message: &'static str,
+) {
+    /*
+    エラーメッセージがAccept-Languageヘッダーの言語で返り、codeは言語によらず一定であることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let url = format!("{}{}/", WORK_LOGS_ENDPOINT, ObjectId::new());
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get().insert_header(("Accept-Language", accept_language)),
+                &url,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(
+            body,
+            json!({
+                "error": expected_error,
+                "message": expected_message,
+                "code": "WORK_LOG_NOT_FOUND"
+            })
+        );
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_validation_message_by_accept_language() {
+    /*
+    未認証のリクエストでも、バリデーションエラーのメッセージがAccept-Languageヘッダーの言語で返ることを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let response = test::call_service(
+            context.service(),
+            test::TestRequest::post()
+                .uri(REGISTER_ENDPOINT)
+                .insert_header(("Accept-Language", "en"))
+                .set_json(json!({
+                    "email": "invalid-email",
+                    "password": "password123",
+                    "username": "testuser"
+                }))
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(
+            body,
+            json!({
+                "error": "Validation error",
+                "code": "VALIDATION_ERROR",
+                "message": "Some fields are invalid",
+                "field_errors": [{
+                    "field": "email",
+                    "pointer": "/email",
+                    "message": "Enter a valid email address"
+                }]
+            })
+        );
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_authentication_error_by_accept_language() {
+    /*
+    認証ミドルウェアのエラーもAccept-Languageヘッダーの言語で返ることを確認するテスト
+     */
+    TestApp::run_test(|context| async move {
+        let response = test::call_service(
+            context.service(),
+            test::TestRequest::get()
+                .uri(USERS_ENDPOINT)
+                .insert_header(("Accept-Language", "en"))
+                .insert_header(("Authorization", "Bearer invalid-token"))
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "INVALID_OR_EXPIRED_TOKEN");
+        assert_eq!(body["message"], "Invalid or expired token");
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_error_message_by_user_locale() {
+    /*
+    ユーザーが表示言語を設定している場合は、Accept-Languageヘッダーより優先されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let response = context
+            .authenticated_request(
+                test::TestRequest::put().set_json(json!({
+                    "email": "locale@example.com",
+                    "username": "Locale User",
+                    "locale": "en"
+                })),
+                USERS_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let url = format!("{}{}/", WORK_LOGS_ENDPOINT, "invalid-id");
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get().insert_header(("Accept-Language", "ja")),
+                &url,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(
+            body,
+            json!({
+                "error": "Bad request",
+                "message": "Invalid ID",
+                "code": "INVALID_ID"
+            })
+        );
+    })
+    .await;
+}
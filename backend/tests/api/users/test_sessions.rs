@@ -0,0 +1,225 @@
+use crate::common::test_app::TestApp;
+use crate::common::test_context::TestContext;
+use actix_web::dev::ServiceResponse;
+use actix_web::{http::StatusCode, test};
+use bson::oid::ObjectId;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+
+const SESSIONS_ENDPOINT: &str = "/api/users/me/sessions/";
+const USERS_ME_ENDPOINT: &str = "/api/users/me/";
+const LOGIN_ENDPOINT: &str = "/api/auth/login/";
+
+/// テスト用ヘルパー関数. 別のクライアントからテストユーザーでログインし、アクセストークンを返す
+async fn login_from(context: &TestContext, user_agent: &str, ip: SocketAddr) -> String {
+    let response = test::call_service(
+        context.service(),
+        test::TestRequest::post()
+            .uri(LOGIN_ENDPOINT)
+            .insert_header(("User-Agent", user_agent))
+            .peer_addr(ip)
+            .set_json(json!({
+                "email": context.app.test_user.email,
+                "password": context.app.test_user.password
+            }))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response
+        .response()
+        .cookies()
+        .find(|c| c.name() == "access_token")
+        .map(|c| c.value().to_string())
+        .expect("アクセストークンの取得に失敗しました")
+}
+
+/// テスト用ヘルパー関数. 指定したアクセストークンでセッション一覧を取得する
+async fn get_sessions(context: &TestContext, access_token: &str) -> Vec<Value> {
+    let response = context
+        .request_with_token(test::TestRequest::get(), SESSIONS_ENDPOINT, access_token)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: Value = test::read_body_json(response).await;
+    body.as_array().expect("配列が返されていません").clone()
+}
+
+/// テスト用ヘルパー関数. 指定したアクセストークンでユーザー情報を取得する（セッションの有効性の確認用）
+async fn get_me(context: &TestContext, access_token: &str) -> ServiceResponse {
+    context
+        .request_with_token(test::TestRequest::get(), USERS_ME_ENDPOINT, access_token)
+        .await
+}
+
+/// テスト用ヘルパー関数. セッション一覧から条件に一致するセッションを取得する
+fn find_session<'a>(sessions: &'a [Value], key: &str, value: Value) -> &'a Value {
+    sessions
+        .iter()
+        .find(|session| session[key] == value)
+        .expect("セッションが見つかりません")
+}
+
+#[actix_web::test]
+async fn test_get_sessions() {
+    /*
+    ログイン中のセッションの一覧が、クライアントの情報と現在のセッションかどうかを含めて返ることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let current_token = context.app.access_token.clone().unwrap();
+        let initial_count = get_sessions(&context, &current_token).await.len();
+
+        let ip: SocketAddr = "203.0.113.10:8080".parse().unwrap();
+        let other_token = login_from(&context, "TestBrowser/1.0", ip).await;
+
+        let sessions = get_sessions(&context, &current_token).await;
+        assert_eq!(sessions.len(), initial_count + 1);
+        assert_eq!(
+            sessions.iter().filter(|s| s["current"] == true).count(),
+            1,
+            "現在のセッションは1件のみであること"
+        );
+
+        let other_session = find_session(&sessions, "user_agent", json!("TestBrowser/1.0"));
+        assert_eq!(other_session["current"], false);
+        assert_eq!(other_session["ip_address"], "203.0.113.10");
+        assert!(other_session["signed_in_at"].is_string());
+        assert!(other_session["last_used_at"].is_string());
+
+        // 別のセッションから取得した場合は、そのセッションが現在のセッションになる
+        let sessions_from_other = get_sessions(&context, &other_token).await;
+        assert_eq!(
+            find_session(&sessions_from_other, "current", json!(true))["id"],
+            other_session["id"]
+        );
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_delete_session() {
+    /*
+    指定したセッションを失効させると、そのセッションのトークンが使用できなくなることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let ip: SocketAddr = "203.0.113.11:8080".parse().unwrap();
+        let other_token = login_from(&context, "TestBrowser/1.0", ip).await;
+
+        let current_token = context.app.access_token.clone().unwrap();
+        let sessions = get_sessions(&context, &current_token).await;
+        let other_session = find_session(&sessions, "user_agent", json!("TestBrowser/1.0"));
+
+        let url = format!(
+            "{}{}/",
+            SESSIONS_ENDPOINT,
+            other_session["id"].as_str().unwrap()
+        );
+        let response = context
+            .authenticated_request(test::TestRequest::delete(), &url)
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // 失効させたセッションのトークンは使用できない
+        let response = get_me(&context, &other_token).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // 現在のセッションはそのまま使用できる
+        let response = get_me(&context, &current_token).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            get_sessions(&context, &current_token).await.len(),
+            sessions.len() - 1
+        );
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_delete_other_sessions() {
+    /*
+    現在のセッション以外のすべてのセッションを失効させられることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let other_tokens = vec![
+            login_from(
+                &context,
+                "TestBrowser/1.0",
+                "203.0.113.12:8080".parse().unwrap(),
+            )
+            .await,
+            login_from(
+                &context,
+                "TestBrowser/2.0",
+                "203.0.113.13:8080".parse().unwrap(),
+            )
+            .await,
+        ];
+
+        let response = context
+            .authenticated_request(test::TestRequest::delete(), SESSIONS_ENDPOINT)
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        for other_token in &other_tokens {
+            let response = get_me(&context, other_token).await;
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        let current_token = context.app.access_token.clone().unwrap();
+        let sessions = get_sessions(&context, &current_token).await;
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0]["current"], true);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_delete_session_of_another_user() {
+    /*
+    他のユーザーのセッションは失効させられないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let another_token = context.app.register_another_user().await;
+        let another_sessions = get_sessions(&context, &another_token).await;
+        let another_session = find_session(&another_sessions, "current", json!(true));
+
+        let url = format!(
+            "{}{}/",
+            SESSIONS_ENDPOINT,
+            another_session["id"].as_str().unwrap()
+        );
+        let response = context
+            .authenticated_request(test::TestRequest::delete(), &url)
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "SESSION_NOT_FOUND");
+
+        // 他のユーザーのセッションは引き続き使用できる
+        let response = get_me(&context, &another_token).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_delete_session_not_found() {
+    /*
+    存在しないセッション・無効なIDを指定した場合のエラーを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let url = format!("{}{}/", SESSIONS_ENDPOINT, ObjectId::new());
+        let response = context
+            .authenticated_request(test::TestRequest::delete(), &url)
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let url = format!("{}{}/", SESSIONS_ENDPOINT, "invalid-id");
+        let response = context
+            .authenticated_request(test::TestRequest::delete(), &url)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
@@ -42,7 +42,9 @@ async fn test_update_user_success() {
         assert_eq!(get_response.status(), StatusCode::OK);
         let body: serde_json::Value = test::read_body_json(get_response).await;
 
-        assert_eq!(body["email"], "updated@example.com");
+        // メールアドレスは確認されるまで変更を申請中となる
+        assert_eq!(body["email"], context.app.test_user.email);
+        assert_eq!(body["pending_email"], "updated@example.com");
         assert_eq!(body["username"], "Updated User");
         assert_eq!(body["role"], "FrontEnd");
     })
@@ -81,7 +83,9 @@ async fn test_update_user_with_optional_fields() {
         assert_eq!(get_response.status(), StatusCode::OK);
         let body: serde_json::Value = test::read_body_json(get_response).await;
 
-        assert_eq!(body["email"], "updated@example.com");
+        // メールアドレスは確認されるまで変更を申請中となる
+        assert_eq!(body["email"], context.app.test_user.email);
+        assert_eq!(body["pending_email"], "updated@example.com");
         assert_eq!(body["username"], "Updated User");
         assert_eq!(body["role"], "BackEnd");
         assert!(body["avatar_url"].is_string()); // アバターURLが生成されていることを確認
@@ -117,13 +121,60 @@ async fn test_update_user_without_optional_fields() {
         assert_eq!(get_response.status(), StatusCode::OK);
         let body: serde_json::Value = test::read_body_json(get_response).await;
 
-        assert_eq!(body["email"], "updated@example.com");
+        // メールアドレスは確認されるまで変更を申請中となる
+        assert_eq!(body["email"], context.app.test_user.email);
+        assert_eq!(body["pending_email"], "updated@example.com");
         assert_eq!(body["username"], "Updated User");
         // オプショナルフィールドは既存の値が維持されているはず
     })
     .await;
 }
 
+#[actix_web::test]
+async fn test_update_user_time_zone_and_locale() {
+    /*
+    タイムゾーン・表示言語を更新でき、省略した場合は既存の設定が維持されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let update_payload = json!({
+            "email": "updated@example.com",
+            "username": "Updated User",
+            "timezone": "America/New_York",
+            "locale": "en"
+        });
+        let update_response = context
+            .authenticated_request(
+                test::TestRequest::put().set_json(&update_payload),
+                USERS_ENDPOINT,
+            )
+            .await;
+        assert_eq!(update_response.status(), StatusCode::NO_CONTENT);
+
+        // タイムゾーン・表示言語を省略して更新する
+        let update_response = context
+            .authenticated_request(
+                test::TestRequest::put().set_json(json!({
+                    "email": "updated@example.com",
+                    "username": "Updated User Again"
+                })),
+                USERS_ENDPOINT,
+            )
+            .await;
+        assert_eq!(update_response.status(), StatusCode::NO_CONTENT);
+
+        let get_response = context
+            .authenticated_request(test::TestRequest::get(), USERS_ENDPOINT)
+            .await;
+        assert_eq!(get_response.status(), StatusCode::OK);
+        let body: serde_json::Value = test::read_body_json(get_response).await;
+
+        assert_eq!(body["username"], "Updated User Again");
+        assert_eq!(body["timezone"], "America/New_York");
+        assert_eq!(body["locale"], "en");
+    })
+    .await;
+}
+
 #[actix_web::test]
 async fn test_update_user_unauthorized() {
     /*
@@ -178,6 +229,16 @@ async fn test_update_user_unauthorized() {
     field: "password",
     expected_message: "パスワードは8文字以上である必要があります"
 })]
+#[case(ValidationTestCase {
+    name: "存在しないタイムゾーン",
+    payload: json!({
+        "email": "test@example.com",
+        "username": "Test User",
+        "timezone": "Asia/Atlantis"
+    }),
+    field: "timezone",
+    expected_message: "タイムゾーンはIANAタイムゾーン名（例: Asia/Tokyo）で指定してください: Asia/Atlantis"
+})]
 #[case(ValidationTestCase {
     name: "無効なロール",
     payload: json!({
@@ -199,7 +260,7 @@ async fn test_update_user_unauthorized() {
         "avatar": "invalid-base64-data"
     }),
     field: "unknown",
-    expected_message: "Invalid base64 data format"  // デシリアライズエラー
+    expected_message: "base64データの形式が正しくありません"
 })]
 #[actix_web::test]
 async fn test_update_user_validation(#[case] test_case: ValidationTestCase) {
@@ -221,7 +282,7 @@ async fn test_update_user_validation(#[case] test_case: ValidationTestCase) {
                 json!({
                     "error": "不正なリクエスト",
                     "message": test_case.expected_message,
-                    "code": "BAD_REQUEST"
+                    "code": "INVALID_BASE64_FORMAT"
                 })
             );
         } else if test_case.name == "無効なロール" {
@@ -230,6 +291,8 @@ async fn test_update_user_validation(#[case] test_case: ValidationTestCase) {
                 error_body,
                 json!({
                     "error": "入力エラー",
+                    "code": "INVALID_INPUT",
+                    "message": "入力形式が正しくありません",
                     "field_errors": [{
                         "field": test_case.field,
                         "message": test_case.expected_message
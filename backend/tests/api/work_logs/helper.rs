@@ -37,30 +37,30 @@ pub async fn create_test_work_log(context: &TestContext) -> String {
         .to_string()
 }
 
-/// テスト用の複数勤怠を作成
+/// テスト用の複数勤怠を作成（`create_test_work_log`の勤怠とは時間が重複しない）
 pub async fn create_test_work_logs(context: &TestContext) -> Vec<String> {
     let project = create_test_project(context).await;
     let now = BsonDateTime::now();
     let test_work_logs = vec![
         json!({
             "project_id": project.id,
-            "start_time": BsonDateTime::from_millis(now.timestamp_millis() - 7200000).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true), // 2時間前
-            "end_time": BsonDateTime::from_millis(now.timestamp_millis() - 3600000).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),   // 1時間前
+            "start_time": BsonDateTime::from_millis(now.timestamp_millis() - 14400000).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true), // 4時間前
+            "end_time": BsonDateTime::from_millis(now.timestamp_millis() - 10800000).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),   // 3時間前
             "break_time": 15,
-            "actual_work_minutes": 105,
+            "actual_work_minutes": 45,
             "memo": "テスト勤怠データ1"
         }),
         json!({
             "project_id": project.id,
-            "start_time": BsonDateTime::from_millis(now.timestamp_millis() - 3600000).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true), // 1時間前
-            "end_time": BsonDateTime::from_millis(now.timestamp_millis() - 1800000).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),   // 30分前
+            "start_time": BsonDateTime::from_millis(now.timestamp_millis() - 10800000).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true), // 3時間前
+            "end_time": BsonDateTime::from_millis(now.timestamp_millis() - 9000000).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),   // 2時間30分前
             "break_time": 10,
-            "actual_work_minutes": 50,
+            "actual_work_minutes": 20,
             "memo": "テスト勤怠データ2"
         }),
         json!({
             "project_id": project.id,
-            "start_time": BsonDateTime::from_millis(now.timestamp_millis() - 1800000).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true), // 30分前
+            "start_time": BsonDateTime::from_millis(now.timestamp_millis() - 1200000).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true), // 20分前
             "end_time": null,  // 終了時間なし（作業中）
             "memo": "テスト勤怠データ3"
         }),
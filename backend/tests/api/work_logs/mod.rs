@@ -1,4 +1,9 @@
 pub mod helper;
 pub mod test_create;
+pub mod test_delete;
 pub mod test_get;
+pub mod test_import;
+pub mod test_overlap;
+pub mod test_split;
+pub mod test_timer;
 pub mod test_update;
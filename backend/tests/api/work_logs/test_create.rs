@@ -67,6 +67,48 @@ async fn test_create_work_logs_success() {
     .await;
 }
 
+#[actix_web::test]
+async fn test_create_work_logs_derives_actual_work_minutes() {
+    /*
+    実労働時間を省略した場合に、開始・終了時間と休憩時間から算出されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let now = BsonDateTime::now();
+        let start_time = BsonDateTime::from_millis(now.timestamp_millis() - 3600000); // 1時間前
+
+        let payload = json!({
+            "project_id": project.id,
+            "start_time": start_time.to_chrono().to_rfc3339_opts(SecondsFormat::Secs, true),
+            "end_time": now.to_chrono().to_rfc3339_opts(SecondsFormat::Secs, true),
+            "break_time": 15
+        });
+
+        let create_response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(&payload),
+                WORK_LOGS_ENDPOINT,
+            )
+            .await;
+        assert_eq!(create_response.status(), StatusCode::CREATED);
+        let create_body: serde_json::Value = test::read_body_json(create_response).await;
+
+        let get_response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "{}{}/",
+                    WORK_LOGS_ENDPOINT,
+                    create_body["id"].as_str().unwrap()
+                ),
+            )
+            .await;
+        let get_body: serde_json::Value = test::read_body_json(get_response).await;
+        assert_eq!(get_body["actual_work_minutes"], 45);
+    })
+    .await;
+}
+
 #[actix_web::test]
 async fn test_create_work_logs_unauthorized() {
     /*
@@ -121,7 +163,7 @@ async fn test_create_work_logs_with_non_existent_project() {
             json!({
                 "error": "リソースが見つかりません",
                 "message": "勤怠に関連するプロジェクトが見つかりません",
-                "code": "NOT_FOUND"
+                "code": "WORK_LOG_PROJECT_NOT_FOUND"
             })
         );
     })
@@ -230,6 +272,75 @@ async fn test_create_work_logs_with_non_existent_project() {
         expected_message: "実労働時間は0以上である必要があります"
     }
 )]
+// 休憩時間・実労働時間と開始〜終了時間の整合性のバリデーション
+#[case::break_time_exceeds_session(
+    ValidationTestCase {
+        name: "休憩時間が開始から終了までの時間を超える",
+        payload: {
+            let now = BsonDateTime::now();
+            let start_time = now.timestamp_millis() - 3600000; // 1時間前
+            json!({
+                "start_time": BsonDateTime::from_millis(start_time).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "end_time": now.to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "break_time": 61,
+                "memo": "テストメモ"
+            })
+        },
+        field: "break_time",
+        expected_message: "休憩時間は開始から終了までの時間以内である必要があります"
+    }
+)]
+#[case::actual_work_minutes_exceeds_session(
+    ValidationTestCase {
+        name: "実労働時間が開始から終了までの時間を超える",
+        payload: {
+            let now = BsonDateTime::now();
+            let start_time = now.timestamp_millis() - 3600000; // 1時間前
+            json!({
+                "start_time": BsonDateTime::from_millis(start_time).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "end_time": now.to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "actual_work_minutes": 600,
+                "memo": "テストメモ"
+            })
+        },
+        field: "actual_work_minutes",
+        expected_message: "実労働時間は開始から終了までの時間から休憩時間を引いた時間以内である必要があります"
+    }
+)]
+#[case::actual_work_minutes_exceeds_session_minus_break(
+    ValidationTestCase {
+        name: "実労働時間と休憩時間の合計が開始から終了までの時間を超える",
+        payload: {
+            let now = BsonDateTime::now();
+            let start_time = now.timestamp_millis() - 3600000; // 1時間前
+            json!({
+                "start_time": BsonDateTime::from_millis(start_time).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "end_time": now.to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "break_time": 30,
+                "actual_work_minutes": 45,
+                "memo": "テストメモ"
+            })
+        },
+        field: "actual_work_minutes",
+        expected_message: "実労働時間は開始から終了までの時間から休憩時間を引いた時間以内である必要があります"
+    }
+)]
+#[case::actual_work_minutes_without_end_time(
+    ValidationTestCase {
+        name: "終了時間のない勤怠に実労働時間を指定",
+        payload: {
+            let now = BsonDateTime::now();
+            let start_time = now.timestamp_millis() - 3600000; // 1時間前
+            json!({
+                "start_time": BsonDateTime::from_millis(start_time).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "actual_work_minutes": 30,
+                "memo": "テストメモ"
+            })
+        },
+        field: "actual_work_minutes",
+        expected_message: "終了時間のない勤怠には実労働時間を指定できません"
+    }
+)]
 // メモのバリデーション
 #[case::memo_too_long(
     ValidationTestCase {
@@ -282,6 +393,8 @@ async fn test_create_work_logs_validation(#[case] test_case: ValidationTestCase)
                 error_body,
                 json!({
                     "error": "入力エラー",
+                    "code": "INVALID_INPUT",
+                    "message": "入力形式が正しくありません",
                     "field_errors": [{
                         "field": "unknown",
                         "message": "入力形式が正しくありません"
@@ -0,0 +1,96 @@
+use crate::api::work_logs::helper::create_test_work_log;
+use crate::common::test_app::TestApp;
+use actix_web::{http::StatusCode, test};
+use bson::oid::ObjectId;
+use serde_json::Value;
+
+const WORK_LOGS_ENDPOINT: &str = "/api/work-logs/";
+const TRASH_ENDPOINT: &str = "/api/trash/";
+
+#[actix_web::test]
+async fn test_delete_and_restore_work_log_success() {
+    /*
+    勤怠を削除するとゴミ箱に移動し、復元すると再び取得できることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let work_log_id = create_test_work_log(&context).await;
+        let url = format!("{}{}/", WORK_LOGS_ENDPOINT, work_log_id);
+
+        let response = context
+            .authenticated_request(test::TestRequest::delete(), &url)
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // 削除済みの勤怠は取得できない
+        let response = context
+            .authenticated_request(test::TestRequest::get(), &url)
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // ゴミ箱には削除日時付きで含まれる
+        let response = context
+            .authenticated_request(test::TestRequest::get(), TRASH_ENDPOINT)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        let trashed = body["work_logs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|work_log| work_log["id"] == work_log_id.as_str())
+            .expect("削除した勤怠がゴミ箱に含まれていません");
+        assert!(trashed["deleted_at"].is_string());
+
+        let response = context
+            .authenticated_request(test::TestRequest::post(), &format!("{}restore/", url))
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = context
+            .authenticated_request(test::TestRequest::get(), &url)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        assert!(body.get("deleted_at").is_none());
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_delete_work_log_not_found() {
+    /*
+    存在しない勤怠IDを削除しようとした場合は404エラーが返ることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let url = format!("{}{}/", WORK_LOGS_ENDPOINT, ObjectId::new());
+
+        let response = context
+            .authenticated_request(test::TestRequest::delete(), &url)
+            .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_delete_work_log_of_another_user_not_found() {
+    /*
+    他ユーザーの勤怠は削除できず404エラーが返ることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let work_log_id = create_test_work_log(&context).await;
+        let another_token = context.app.register_another_user().await;
+
+        let response = context
+            .request_with_token(
+                test::TestRequest::delete(),
+                &format!("{}{}/", WORK_LOGS_ENDPOINT, work_log_id),
+                &another_token,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    })
+    .await;
+}
@@ -1,7 +1,10 @@
+use crate::api::projects::helper::create_test_project;
 use crate::api::work_logs::helper::{create_test_work_log, create_test_work_logs};
 use crate::common::test_app::TestApp;
 use actix_web::{http::StatusCode, test};
 use bson::oid::ObjectId;
+use bson::DateTime as BsonDateTime;
+use rstest::rstest;
 use serde_json::{json, Value};
 
 const WORK_LOGS_ENDPOINT: &str = "/api/work-logs/";
@@ -24,7 +27,7 @@ async fn test_get_all_work_logs_success() {
         assert!(body.is_array());
 
         // 作成した勤怠数分のデータが取得できていることを確認
-        let work_logs = body.as_array().unwrap();
+        let work_logs = body["items"].as_array().unwrap();
         assert!(work_logs.len() >= work_log_ids.len());
 
         // 作成した勤怠のIDが含まれていることを確認
@@ -100,7 +103,7 @@ async fn test_get_work_log_by_id_not_found() {
             json!({
                 "error": "リソースが見つかりません",
                 "message": "勤怠が見つかりません",
-                "code": "NOT_FOUND"
+                "code": "WORK_LOG_NOT_FOUND"
             })
         );
     })
@@ -126,9 +129,234 @@ async fn test_get_work_log_by_id_invalid_id() {
             json!({
                 "error": "不正なリクエスト",
                 "message": "無効なIDです",
-                "code": "BAD_REQUEST"
+                "code": "INVALID_ID"
             })
         );
     })
     .await;
 }
+
+#[actix_web::test]
+async fn test_get_work_log_of_another_user_not_found() {
+    /*
+    他ユーザーの勤怠は取得できず404エラーが返ることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let work_log_id = create_test_work_log(&context).await;
+        let another_token = context.app.register_another_user().await;
+
+        let response = context
+            .request_with_token(
+                test::TestRequest::get(),
+                &format!("{}{}/", WORK_LOGS_ENDPOINT, work_log_id),
+                &another_token,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_work_logs_with_pagination() {
+    /*
+    limitとカーソルで勤怠一覧をページングでき、総件数がレスポンスとX-Total-Countヘッダーで返ることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let work_log_ids = create_test_work_logs(&context).await;
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?limit=2&sort=start_time:asc", WORK_LOGS_ENDPOINT),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let total_count = response
+            .headers()
+            .get("X-Total-Count")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<usize>().ok())
+            .expect("X-Total-Count header not found");
+        assert_eq!(total_count, work_log_ids.len());
+
+        let body: Value = test::read_body_json(response).await;
+        let work_logs = body["items"].as_array().unwrap();
+        assert_eq!(work_logs.len(), 2);
+        assert_eq!(body["total"], work_log_ids.len());
+        // 開始時間の古い順に並んでいることを確認
+        assert_eq!(work_logs[0]["id"], work_log_ids[0].as_str());
+        assert_eq!(work_logs[1]["id"], work_log_ids[1].as_str());
+
+        // 次のページはカーソルの続きから取得される
+        let next_cursor = body["next_cursor"]
+            .as_str()
+            .expect("next_cursor should be returned");
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "{}?limit=2&sort=start_time:asc&cursor={}",
+                    WORK_LOGS_ENDPOINT, next_cursor
+                ),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        let work_logs = body["items"].as_array().unwrap();
+        assert_eq!(work_logs[0]["id"], work_log_ids[2].as_str());
+
+        // ソート条件の異なるカーソルは受け付けない
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "{}?limit=2&sort=end_time:asc&cursor={}",
+                    WORK_LOGS_ENDPOINT, next_cursor
+                ),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "CURSOR_SORT_MISMATCH");
+
+        // 同じフィールドでもソート順が異なるカーソルは受け付けない
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "{}?limit=2&sort=start_time:desc&cursor={}",
+                    WORK_LOGS_ENDPOINT, next_cursor
+                ),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "CURSOR_SORT_MISMATCH");
+
+        // 署名と一致しない（改ざんされた）カーソルは受け付けない
+        let mut tampered_cursor = next_cursor.to_string();
+        let last = tampered_cursor.pop().unwrap();
+        tampered_cursor.push(if last == 'A' { 'B' } else { 'A' });
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "{}?limit=2&sort=start_time:asc&cursor={}",
+                    WORK_LOGS_ENDPOINT, tampered_cursor
+                ),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "INVALID_CURSOR");
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_work_logs_default_page_size() {
+    /*
+    limitを省略した場合は既定の件数（20件）で区切られ、次ページのカーソルが返ることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let now = BsonDateTime::now().timestamp_millis();
+        for i in 0..21 {
+            // 1時間ずつずらして重複しない勤怠を作成する
+            let start_time = BsonDateTime::from_millis(now - (i + 2) * 3600000);
+            let end_time = BsonDateTime::from_millis(now - (i + 1) * 3600000);
+            let payload = json!({
+                "project_id": project.id,
+                "start_time": start_time.to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "end_time": end_time.to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "break_time": 0,
+                "actual_work_minutes": 60,
+            });
+            let response = context
+                .authenticated_request(
+                    test::TestRequest::post().set_json(&payload),
+                    WORK_LOGS_ENDPOINT,
+                )
+                .await;
+            assert_eq!(response.status(), StatusCode::CREATED);
+        }
+
+        let response = context
+            .authenticated_request(test::TestRequest::get(), WORK_LOGS_ENDPOINT)
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["items"].as_array().unwrap().len(), 20);
+        // 日付をまたぐ勤怠は分割されるため、総件数は作成数以上になる
+        assert!(body["total"].as_u64().unwrap() >= 21);
+        assert!(body["next_cursor"].is_string());
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_work_logs_filtered_by_project_and_memo() {
+    /*
+    プロジェクトIDとメモで勤怠一覧を絞り込めることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let work_log_ids = create_test_work_logs(&context).await;
+        let other_work_log_id = create_test_work_log(&context).await;
+
+        // 別プロジェクトの勤怠を除外するため、作成した勤怠のプロジェクトIDを取得
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}{}/", WORK_LOGS_ENDPOINT, work_log_ids[0]),
+            )
+            .await;
+        let body: Value = test::read_body_json(response).await;
+        let project_id = body["project_id"].as_str().unwrap().to_string();
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!(
+                    "{}?project_id={}&memo=%E3%83%87%E3%83%BC%E3%82%BF2",
+                    WORK_LOGS_ENDPOINT, project_id
+                ),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        let work_logs = body["items"].as_array().unwrap();
+        assert_eq!(work_logs.len(), 1);
+        assert_eq!(work_logs[0]["id"], work_log_ids[1].as_str());
+        assert_ne!(work_logs[0]["id"], other_work_log_id.as_str());
+    })
+    .await;
+}
+
+#[rstest]
+#[case::invalid_sort_field("sort=memo:asc")]
+#[case::invalid_limit("limit=0")]
+#[case::invalid_cursor("cursor=invalid")]
+#[case::invalid_range("from=2024-05-01T00:00:00Z&to=2024-04-01T00:00:00Z")]
+#[actix_web::test]
+async fn test_get_work_logs_invalid_query(#[case] query: &str) {
+    /*
+    無効なクエリパラメータの場合は400エラーが返ることを確認するテスト
+     */
+    let query = query.to_string();
+    TestApp::run_authenticated_test(|context| async move {
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}?{}", WORK_LOGS_ENDPOINT, query),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
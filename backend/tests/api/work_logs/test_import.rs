@@ -0,0 +1,197 @@
+use crate::api::projects::helper::create_test_project;
+use crate::common::test_app::TestApp;
+use crate::common::test_context::TestContext;
+use actix_web::{http::StatusCode, test};
+use serde_json::{json, Value};
+
+const IMPORT_ENDPOINT: &str = "/api/work-logs/import/";
+const WORK_LOGS_ENDPOINT: &str = "/api/work-logs/";
+
+const CSV_HEADER: &str = "project,start_time,end_time,break_time,actual_work_minutes,memo";
+
+/// インポートを実行してレスポンスボディを取得する
+async fn import(context: &TestContext, query: &str, body: String) -> Value {
+    let response = context
+        .authenticated_request(
+            test::TestRequest::post().set_payload(body),
+            &format!("{}?{}", IMPORT_ENDPOINT, query),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    test::read_body_json(response).await
+}
+
+/// 登録されている勤怠の件数
+async fn count_work_logs(context: &TestContext) -> u64 {
+    let response = context
+        .authenticated_request(test::TestRequest::get(), WORK_LOGS_ENDPOINT)
+        .await;
+    let body: Value = test::read_body_json(response).await;
+    body["total"].as_u64().unwrap()
+}
+
+#[actix_web::test]
+async fn test_import_work_logs_csv() {
+    /*
+    CSVの勤怠がプロジェクトID・プロジェクト名のどちらでも登録され、総稼働時間が再計算されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let body = format!(
+            "{}\n{},2024-04-01T00:00:00Z,2024-04-01T02:00:00Z,30,90,初日\nテストプロジェクト,2024-04-02T00:00:00Z,2024-04-02T01:00:00Z,,60,\n",
+            CSV_HEADER, project.id
+        );
+
+        let body = import(&context, "format=csv", body).await;
+        assert_eq!(body["dry_run"], false);
+        assert_eq!(body["total_rows"], 2);
+        assert_eq!(body["valid_rows"], 2);
+        assert_eq!(body["imported_rows"], 2);
+        assert!(body["errors"].as_array().unwrap().is_empty());
+
+        assert_eq!(count_work_logs(&context).await, 2);
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("/api/projects/{}/", project.id),
+            )
+            .await;
+        let project: Value = test::read_body_json(response).await;
+        assert_eq!(project["total_working_time"], (90 + 60) * 60);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_import_work_logs_jsonl_with_errors() {
+    /*
+    JSON Linesのエラーのある行は行番号付きで報告され、エラーのない行のみ登録されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        create_test_project(&context).await;
+        let lines = [
+            json!({
+                "project": "テストプロジェクト",
+                "start_time": "2024-04-01T00:00:00Z",
+                "end_time": "2024-04-01T01:00:00Z",
+                "actual_work_minutes": 60
+            })
+            .to_string(),
+            json!({
+                "project": "存在しないプロジェクト",
+                "start_time": "2024-04-02T00:00:00Z",
+                "end_time": "2024-04-02T01:00:00Z"
+            })
+            .to_string(),
+            json!({
+                "project": "テストプロジェクト",
+                "start_time": "2024-04-03T01:00:00Z",
+                "end_time": "2024-04-03T00:00:00Z"
+            })
+            .to_string(),
+            "{invalid json}".to_string(),
+        ];
+
+        let body = import(&context, "format=jsonl", lines.join("\n")).await;
+        assert_eq!(body["total_rows"], 4);
+        assert_eq!(body["valid_rows"], 1);
+        assert_eq!(body["imported_rows"], 1);
+
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0]["line"], 2);
+        assert_eq!(errors[0]["field"], "project");
+        assert_eq!(errors[1]["line"], 3);
+        assert_eq!(errors[1]["field"], "time");
+        assert_eq!(errors[2]["line"], 4);
+        assert!(errors[2].get("field").is_none());
+
+        assert_eq!(count_work_logs(&context).await, 1);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_import_work_logs_dry_run() {
+    /*
+    dry_runの場合は検証結果のみ返し、勤怠が登録されないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        create_test_project(&context).await;
+        let body = format!(
+            "{}\nテストプロジェクト,2024-04-01T00:00:00Z,2024-04-01T01:00:00Z,,60,\nテストプロジェクト,invalid,,,,\n",
+            CSV_HEADER
+        );
+
+        let body = import(&context, "dry_run=true", body).await;
+        assert_eq!(body["dry_run"], true);
+        assert_eq!(body["valid_rows"], 1);
+        assert_eq!(body["imported_rows"], 0);
+
+        // 開始時間・終了時間の両方のエラーを報告する
+        let errors = body["errors"].as_array().unwrap();
+        let fields: Vec<&str> = errors
+            .iter()
+            .map(|error| {
+                assert_eq!(error["line"], 3);
+                error["field"].as_str().unwrap()
+            })
+            .collect();
+        assert_eq!(fields, vec!["start_time", "end_time"]);
+
+        assert_eq!(count_work_logs(&context).await, 0);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_import_work_logs_empty() {
+    /*
+    インポートする行がない場合は400エラーとなることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_payload(format!("{}\n", CSV_HEADER)),
+                IMPORT_ENDPOINT,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_import_work_logs_overlap() {
+    /*
+    既存の勤怠や先の行と時間が重複する行がエラーとなり、allow_overlap指定時は登録されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        create_test_project(&context).await;
+        let existing = format!(
+            "{}\nテストプロジェクト,2024-04-01T00:00:00Z,2024-04-01T02:00:00Z,,120,\n",
+            CSV_HEADER
+        );
+        import(&context, "format=csv", existing).await;
+
+        let body = format!(
+            "{}\nテストプロジェクト,2024-04-01T01:00:00Z,2024-04-01T03:00:00Z,,120,\nテストプロジェクト,2024-04-02T00:00:00Z,2024-04-02T02:00:00Z,,120,\nテストプロジェクト,2024-04-02T01:00:00Z,2024-04-02T03:00:00Z,,120,\n",
+            CSV_HEADER
+        );
+        let response = import(&context, "format=csv", body.clone()).await;
+        assert_eq!(response["valid_rows"], 1);
+        assert_eq!(response["imported_rows"], 1);
+        let errors = response["errors"].as_array().unwrap();
+        let lines: Vec<u64> = errors.iter().map(|e| e["line"].as_u64().unwrap()).collect();
+        assert_eq!(lines, vec![2, 4]);
+        assert_eq!(errors[1]["code"], "IMPORT_OVERLAPS_ROW");
+        assert_eq!(errors[1]["message"], "3行目の勤怠と時間が重複しています");
+        assert_eq!(count_work_logs(&context).await, 2);
+
+        let response = import(&context, "format=csv&allow_overlap=true&dry_run=true", body).await;
+        assert_eq!(response["valid_rows"], 3);
+    })
+    .await;
+}
@@ -0,0 +1,191 @@
+use crate::api::projects::helper::create_test_project;
+use crate::common::test_app::TestApp;
+use crate::common::test_context::TestContext;
+use actix_web::{dev::ServiceResponse, http::StatusCode, test};
+use bson::DateTime as BsonDateTime;
+use serde_json::{json, Value};
+
+const WORK_LOGS_ENDPOINT: &str = "/api/work-logs/";
+
+/// 基準時刻`now`から`start_minutes`分前〜`end_minutes`分前の勤怠データ
+///
+/// 重複時間を正確に比較できるよう、1つのテスト内では同じ基準時刻を使う
+fn work_log_payload(project_id: &str, now: i64, start_minutes: i64, end_minutes: i64) -> Value {
+    let to_rfc3339 = |minutes: i64| {
+        BsonDateTime::from_millis(now - minutes * 60_000)
+            .to_chrono()
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    };
+    json!({
+        "project_id": project_id,
+        "start_time": to_rfc3339(start_minutes),
+        "end_time": to_rfc3339(end_minutes),
+        "break_time": 0,
+        "actual_work_minutes": start_minutes - end_minutes,
+    })
+}
+
+async fn create_work_log(context: &TestContext, payload: &Value, query: &str) -> ServiceResponse {
+    context
+        .authenticated_request(
+            test::TestRequest::post().set_json(payload),
+            &format!("{}{}", WORK_LOGS_ENDPOINT, query),
+        )
+        .await
+}
+
+async fn create_work_log_id(context: &TestContext, payload: &Value) -> String {
+    let response = create_work_log(context, payload, "").await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body: Value = test::read_body_json(response).await;
+    body["id"].as_str().unwrap().to_string()
+}
+
+#[actix_web::test]
+async fn test_create_overlapping_work_log_conflict() {
+    /*
+    既存の勤怠と時間が重複する勤怠の作成が409エラーとなり、重複した勤怠のIDが返されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let now = BsonDateTime::now().timestamp_millis();
+        let existing_id =
+            create_work_log_id(&context, &work_log_payload(&project.id, now, 120, 60)).await;
+
+        let response =
+            create_work_log(&context, &work_log_payload(&project.id, now, 90, 30), "").await;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "WORK_LOG_OVERLAP");
+        assert_eq!(body["conflicting_ids"], json!([existing_id]));
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_adjacent_work_log_success() {
+    /*
+    終了時刻と開始時刻が一致するだけの勤怠は重複とみなされないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let now = BsonDateTime::now().timestamp_millis();
+        let payload = work_log_payload(&project.id, now, 120, 60);
+        create_work_log_id(&context, &payload).await;
+
+        let mut next_payload = work_log_payload(&project.id, now, 60, 30);
+        next_payload["start_time"] = payload["end_time"].clone();
+        let response = create_work_log(&context, &next_payload, "").await;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: Value = test::read_body_json(response).await;
+        assert!(body.get("overlapping_work_log_ids").is_none());
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_overlapping_work_log_with_allow_overlap() {
+    /*
+    allow_overlapを指定した場合は重複していても作成され、重複した勤怠のIDが警告として返されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let now = BsonDateTime::now().timestamp_millis();
+        let existing_id =
+            create_work_log_id(&context, &work_log_payload(&project.id, now, 120, 60)).await;
+
+        let response = create_work_log(
+            &context,
+            &work_log_payload(&project.id, now, 90, 30),
+            "?allow_overlap=true",
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["overlapping_work_log_ids"], json!([existing_id]));
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_update_work_log_to_overlap() {
+    /*
+    既存の勤怠と重複する時間への更新は409エラーとなり、allow_overlap指定時はヘッダーで警告されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let now = BsonDateTime::now().timestamp_millis();
+        let existing_id =
+            create_work_log_id(&context, &work_log_payload(&project.id, now, 120, 60)).await;
+        let work_log_id =
+            create_work_log_id(&context, &work_log_payload(&project.id, now, 50, 30)).await;
+        let url = format!("{}{}/", WORK_LOGS_ENDPOINT, work_log_id);
+        let payload = work_log_payload(&project.id, now, 90, 30);
+
+        let response = context
+            .authenticated_request(test::TestRequest::put().set_json(&payload), &url)
+            .await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["conflicting_ids"], json!([existing_id]));
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::put().set_json(&payload),
+                &format!("{}?allow_overlap=true", url),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let header = response
+            .headers()
+            .get("X-Overlapping-Work-Log-Ids")
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert_eq!(header, existing_id);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_get_overlapping_work_logs() {
+    /*
+    時間が重複している勤怠の組み合わせと重複時間が一覧で取得できることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let now = BsonDateTime::now().timestamp_millis();
+        let first_id =
+            create_work_log_id(&context, &work_log_payload(&project.id, now, 180, 120)).await;
+        let second_id =
+            create_work_log_id(&context, &work_log_payload(&project.id, now, 110, 90)).await;
+        let response = create_work_log(
+            &context,
+            &work_log_payload(&project.id, now, 140, 100),
+            "?allow_overlap=true",
+        )
+        .await;
+        let body: Value = test::read_body_json(response).await;
+        let third_id = body["id"].as_str().unwrap().to_string();
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}overlaps/", WORK_LOGS_ENDPOINT),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(response).await;
+        let overlaps = body.as_array().unwrap();
+        assert_eq!(overlaps.len(), 2);
+        assert_eq!(overlaps[0]["work_log_ids"], json!([first_id, third_id]));
+        assert_eq!(overlaps[0]["overlap_minutes"], 20);
+        assert_eq!(overlaps[1]["work_log_ids"], json!([third_id, second_id]));
+        assert_eq!(overlaps[1]["overlap_minutes"], 10);
+    })
+    .await;
+}
@@ -0,0 +1,276 @@
+use crate::api::projects::helper::create_test_project;
+use crate::common::test_app::TestApp;
+use crate::common::test_context::TestContext;
+use actix_web::{http::StatusCode, test};
+use chrono::{DateTime, Duration, FixedOffset, NaiveTime, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Asia::Tokyo;
+use serde_json::{json, Value};
+
+const WORK_LOGS_ENDPOINT: &str = "/api/work-logs/";
+
+/// 3日前の日本時間`hour`時から`hours`時間後までの（開始時間, 終了時間）
+fn local_session(hour: u32, hours: i64) -> (String, String) {
+    let date = Utc::now().with_timezone(&Tokyo).date_naive() - Duration::days(3);
+    let start = Tokyo
+        .from_local_datetime(&date.and_time(NaiveTime::from_hms_opt(hour, 0, 0).unwrap()))
+        .unwrap();
+    let end = start + Duration::hours(hours);
+    (
+        start.to_rfc3339_opts(SecondsFormat::Secs, true),
+        end.to_rfc3339_opts(SecondsFormat::Secs, true),
+    )
+}
+
+fn parse_time(value: &Value) -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339(value.as_str().unwrap()).unwrap()
+}
+
+/// プロジェクトの勤怠を開始時間の古い順に取得する
+async fn get_project_work_logs(context: &TestContext, project_id: &str) -> Vec<Value> {
+    let response = context
+        .authenticated_request(
+            test::TestRequest::get(),
+            &format!(
+                "{}?project_id={}&sort=start_time:asc",
+                WORK_LOGS_ENDPOINT, project_id
+            ),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(response).await;
+    body["items"].as_array().unwrap().clone()
+}
+
+#[actix_web::test]
+async fn test_create_cross_midnight_work_log_split() {
+    /*
+    日本時間の0時をまたぐ勤怠が日ごとに分割され、共通のsession_idと按分された休憩・実作業時間で登録されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let (start_time, end_time) = local_session(22, 4);
+        let payload = json!({
+            "project_id": project.id,
+            "start_time": start_time,
+            "end_time": end_time,
+            "break_time": 20,
+        });
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(&payload),
+                WORK_LOGS_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: Value = test::read_body_json(response).await;
+
+        let work_logs = get_project_work_logs(&context, &project.id).await;
+        assert_eq!(work_logs.len(), 2);
+        assert_eq!(work_logs[0]["id"], body["id"]);
+        assert_eq!(
+            parse_time(&work_logs[0]["start_time"]),
+            parse_time(&json!(start_time))
+        );
+        assert_eq!(work_logs[0]["end_time"], work_logs[1]["start_time"]);
+        assert_eq!(
+            parse_time(&work_logs[1]["end_time"]),
+            parse_time(&json!(end_time))
+        );
+        assert!(work_logs[0]["session_id"].is_string());
+        assert_eq!(work_logs[0]["session_id"], work_logs[1]["session_id"]);
+        for work_log in &work_logs {
+            assert_eq!(work_log["break_time"], 10);
+            assert_eq!(work_log["actual_work_minutes"], 110);
+        }
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_same_day_work_log_not_split() {
+    /*
+    日付をまたがない勤怠は分割されず、session_idも設定されないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let (start_time, end_time) = local_session(9, 8);
+        let payload = json!({
+            "project_id": project.id,
+            "start_time": start_time,
+            "end_time": end_time,
+            "break_time": 60,
+        });
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(&payload),
+                WORK_LOGS_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let work_logs = get_project_work_logs(&context, &project.id).await;
+        assert_eq!(work_logs.len(), 1);
+        assert!(work_logs[0].get("session_id").is_none());
+        assert_eq!(work_logs[0]["actual_work_minutes"], 420);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_work_log_split_by_user_time_zone() {
+    /*
+    日付の区切りはユーザーのタイムゾーンで判定され、UTCの場合は日本時間の0時をまたいでも分割されないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let response = context
+            .authenticated_request(
+                test::TestRequest::put().set_json(json!({
+                    "email": "timezone@example.com",
+                    "username": "Time Zone User",
+                    "timezone": "UTC"
+                })),
+                "/api/users/me/",
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let project = create_test_project(&context).await;
+        // 日本時間の22時〜翌2時（UTCでは13時〜17時）
+        let (start_time, end_time) = local_session(22, 4);
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(json!({
+                    "project_id": project.id,
+                    "start_time": start_time,
+                    "end_time": end_time,
+                })),
+                WORK_LOGS_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let work_logs = get_project_work_logs(&context, &project.id).await;
+        assert_eq!(work_logs.len(), 1);
+        assert!(work_logs[0].get("session_id").is_none());
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_update_work_log_to_cross_midnight_split() {
+    /*
+    勤怠を日付をまたぐ時間に更新すると、更新した勤怠が最初の日の分となり、残りの日の勤怠が登録されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let (start_time, end_time) = local_session(20, 2);
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(json!({
+                    "project_id": project.id,
+                    "start_time": start_time,
+                    "end_time": end_time,
+                })),
+                WORK_LOGS_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: Value = test::read_body_json(response).await;
+        let id = body["id"].as_str().unwrap().to_string();
+
+        let (_, end_time) = local_session(20, 6);
+        let response = context
+            .authenticated_request(
+                test::TestRequest::put().set_json(json!({
+                    "project_id": project.id,
+                    "start_time": start_time,
+                    "end_time": end_time,
+                    "break_time": 0,
+                })),
+                &format!("{}{}/", WORK_LOGS_ENDPOINT, id),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let work_logs = get_project_work_logs(&context, &project.id).await;
+        assert_eq!(work_logs.len(), 2);
+        assert_eq!(work_logs[0]["id"], id.as_str());
+        assert_eq!(work_logs[0]["actual_work_minutes"], 240);
+        assert_eq!(work_logs[1]["actual_work_minutes"], 120);
+        assert_eq!(work_logs[0]["session_id"], work_logs[1]["session_id"]);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_update_split_work_log_not_conflict_with_same_session() {
+    /*
+    分割された勤怠の一部を更新する場合、同じセッションから分割された他の勤怠とは重複エラーにならないことを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let (start_time, end_time) = local_session(22, 4);
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(json!({
+                    "project_id": project.id,
+                    "start_time": start_time,
+                    "end_time": end_time,
+                })),
+                WORK_LOGS_ENDPOINT,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let work_logs = get_project_work_logs(&context, &project.id).await;
+        assert_eq!(work_logs.len(), 2);
+
+        // 2日目の勤怠の開始時間を、1日目の勤怠と重なる時間に変更する
+        let (start_time, _) = local_session(23, 3);
+        let response = context
+            .authenticated_request(
+                test::TestRequest::put().set_json(json!({
+                    "project_id": project.id,
+                    "start_time": start_time,
+                    "end_time": end_time,
+                })),
+                &format!(
+                    "{}{}/",
+                    WORK_LOGS_ENDPOINT,
+                    work_logs[1]["id"].as_str().unwrap()
+                ),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_create_work_log_exceeding_max_session_length() {
+    /*
+    開始から終了までの時間が上限（24時間）を超える勤怠の作成が400エラーとなることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let (start_time, end_time) = local_session(9, 25);
+        let payload = json!({
+            "project_id": project.id,
+            "start_time": start_time,
+            "end_time": end_time,
+        });
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(&payload),
+                WORK_LOGS_ENDPOINT,
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let work_logs = get_project_work_logs(&context, &project.id).await;
+        assert!(work_logs.is_empty());
+    })
+    .await;
+}
@@ -0,0 +1,133 @@
+use crate::api::projects::helper::create_test_project;
+use crate::common::test_app::TestApp;
+use actix_web::{http::StatusCode, test};
+use serde_json::{json, Value};
+
+const WORK_LOGS_ENDPOINT: &str = "/api/work-logs/";
+
+#[actix_web::test]
+async fn test_start_and_stop_work_log_success() {
+    /*
+    タイマーで作業を開始・終了でき、終了時に実作業時間が算出されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+
+        let start_response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(json!({ "project_id": project.id })),
+                &format!("{}start/", WORK_LOGS_ENDPOINT),
+            )
+            .await;
+        assert_eq!(start_response.status(), StatusCode::CREATED);
+        let body: Value = test::read_body_json(start_response).await;
+        let work_log_id = body["id"].as_str().unwrap().to_string();
+
+        // 作業中の勤怠として取得できる
+        let active_response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}active/", WORK_LOGS_ENDPOINT),
+            )
+            .await;
+        assert_eq!(active_response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(active_response).await;
+        assert_eq!(body["id"], work_log_id);
+        assert!(body["end_time"].is_null());
+
+        let stop_response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(json!({ "memo": "タイマーで記録" })),
+                &format!("{}{}/stop/", WORK_LOGS_ENDPOINT, work_log_id),
+            )
+            .await;
+        assert_eq!(stop_response.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(stop_response).await;
+        assert!(body["end_time"].is_string());
+        assert_eq!(body["break_time"], 0);
+        assert_eq!(body["actual_work_minutes"], 0);
+        assert_eq!(body["memo"], "タイマーで記録");
+
+        // 終了後は作業中の勤怠が存在しない
+        let active_response = context
+            .authenticated_request(
+                test::TestRequest::get(),
+                &format!("{}active/", WORK_LOGS_ENDPOINT),
+            )
+            .await;
+        assert_eq!(active_response.status(), StatusCode::NOT_FOUND);
+
+        // 終了済みの勤怠は再度終了できない
+        let stop_response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(json!({})),
+                &format!("{}{}/stop/", WORK_LOGS_ENDPOINT, work_log_id),
+            )
+            .await;
+        assert_eq!(stop_response.status(), StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_start_work_log_while_active_fails() {
+    /*
+    作業中の勤怠がある状態で作業を開始しようとした場合は400エラーが返ることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let start_url = format!("{}start/", WORK_LOGS_ENDPOINT);
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(json!({ "project_id": project.id })),
+                &start_url,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(json!({ "project_id": project.id })),
+                &start_url,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error_body: Value = test::read_body_json(response).await;
+        assert_eq!(error_body["message"], "既に作業中の勤怠があります");
+    })
+    .await;
+}
+
+#[actix_web::test]
+async fn test_start_work_log_concurrently() {
+    /*
+    同時に作業を開始した場合も、作業中の勤怠は1件のみ作成されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let start_url = format!("{}start/", WORK_LOGS_ENDPOINT);
+        let start = || {
+            context.authenticated_request(
+                test::TestRequest::post().set_json(json!({ "project_id": project.id })),
+                &start_url,
+            )
+        };
+
+        let responses = futures::future::join_all((0..5).map(|_| start())).await;
+        let created = responses
+            .iter()
+            .filter(|response| response.status() == StatusCode::CREATED)
+            .count();
+        assert_eq!(created, 1);
+        for response in responses
+            .into_iter()
+            .filter(|response| response.status() != StatusCode::CREATED)
+        {
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let error_body: Value = test::read_body_json(response).await;
+            assert_eq!(error_body["code"], "ACTIVE_WORK_LOG_EXISTS");
+        }
+    })
+    .await;
+}
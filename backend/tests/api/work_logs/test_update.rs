@@ -261,6 +261,75 @@ async fn test_update_work_logs_invalid_id() {
         expected_message: "実労働時間は0以上である必要があります"
     }
 )]
+// 休憩時間・実労働時間と開始〜終了時間の整合性のバリデーション
+#[case::break_time_exceeds_session(
+    ValidationTestCase {
+        name: "休憩時間が開始から終了までの時間を超える",
+        payload: {
+            let now = BsonDateTime::now();
+            let start_time = now.timestamp_millis() - 3600000; // 1時間前
+            json!({
+                "start_time": BsonDateTime::from_millis(start_time).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "end_time": now.to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "break_time": 61,
+                "memo": "テストメモ"
+            })
+        },
+        field: "break_time",
+        expected_message: "休憩時間は開始から終了までの時間以内である必要があります"
+    }
+)]
+#[case::actual_work_minutes_exceeds_session(
+    ValidationTestCase {
+        name: "実労働時間が開始から終了までの時間を超える",
+        payload: {
+            let now = BsonDateTime::now();
+            let start_time = now.timestamp_millis() - 3600000; // 1時間前
+            json!({
+                "start_time": BsonDateTime::from_millis(start_time).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "end_time": now.to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "actual_work_minutes": 600,
+                "memo": "テストメモ"
+            })
+        },
+        field: "actual_work_minutes",
+        expected_message: "実労働時間は開始から終了までの時間から休憩時間を引いた時間以内である必要があります"
+    }
+)]
+#[case::actual_work_minutes_exceeds_session_minus_break(
+    ValidationTestCase {
+        name: "実労働時間と休憩時間の合計が開始から終了までの時間を超える",
+        payload: {
+            let now = BsonDateTime::now();
+            let start_time = now.timestamp_millis() - 3600000; // 1時間前
+            json!({
+                "start_time": BsonDateTime::from_millis(start_time).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "end_time": now.to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "break_time": 30,
+                "actual_work_minutes": 45,
+                "memo": "テストメモ"
+            })
+        },
+        field: "actual_work_minutes",
+        expected_message: "実労働時間は開始から終了までの時間から休憩時間を引いた時間以内である必要があります"
+    }
+)]
+#[case::actual_work_minutes_without_end_time(
+    ValidationTestCase {
+        name: "終了時間のない勤怠に実労働時間を指定",
+        payload: {
+            let now = BsonDateTime::now();
+            let start_time = now.timestamp_millis() - 3600000; // 1時間前
+            json!({
+                "start_time": BsonDateTime::from_millis(start_time).to_chrono().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "actual_work_minutes": 30,
+                "memo": "テストメモ"
+            })
+        },
+        field: "actual_work_minutes",
+        expected_message: "終了時間のない勤怠には実労働時間を指定できません"
+    }
+)]
 // メモのバリデーション
 #[case::memo_too_long(
     ValidationTestCase {
@@ -315,6 +384,8 @@ async fn test_update_work_logs_validation(#[case] test_case: ValidationTestCase)
                 error_body,
                 json!({
                     "error": "入力エラー",
+                    "code": "INVALID_INPUT",
+                    "message": "入力形式が正しくありません",
                     "field_errors": [{
                         "field": "unknown",
                         "message": "入力形式が正しくありません"
@@ -332,3 +403,81 @@ async fn test_update_work_logs_validation(#[case] test_case: ValidationTestCase)
     })
     .await;
 }
+
+#[actix_web::test]
+async fn test_update_work_logs_recalculates_total_working_time() {
+    /*
+    勤怠の更新・プロジェクトの付け替えでプロジェクトの総稼働時間が正しく再計算されることを確認するテスト
+     */
+    TestApp::run_authenticated_test(|context| async move {
+        let project = create_test_project(&context).await;
+        let now = BsonDateTime::now();
+        let start_time = BsonDateTime::from_millis(now.timestamp_millis() - 5400000)
+            .to_chrono()
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let end_time = BsonDateTime::from_millis(now.timestamp_millis() - 1800000)
+            .to_chrono()
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+        let create_response = context
+            .authenticated_request(
+                test::TestRequest::post().set_json(json!({
+                    "project_id": project.id,
+                    "start_time": start_time,
+                    "end_time": end_time,
+                    "actual_work_minutes": 45
+                })),
+                WORK_LOGS_ENDPOINT,
+            )
+            .await;
+        assert_eq!(create_response.status(), StatusCode::CREATED);
+        let body: serde_json::Value = test::read_body_json(create_response).await;
+        let work_log_url = format!("{}{}/", WORK_LOGS_ENDPOINT, body["id"].as_str().unwrap());
+
+        let total_working_time = |project_id: String| {
+            let context = context.clone();
+            async move {
+                let response = context
+                    .authenticated_request(
+                        test::TestRequest::get(),
+                        &format!("/api/projects/{}/", project_id),
+                    )
+                    .await;
+                let body: serde_json::Value = test::read_body_json(response).await;
+                body["total_working_time"].as_i64().unwrap()
+            }
+        };
+        assert_eq!(total_working_time(project.id.clone()).await, 45 * 60);
+
+        // 実作業時間を減らした場合は差分が反映される（加算されない）
+        let update_response = context
+            .authenticated_request(
+                test::TestRequest::put().set_json(json!({
+                    "project_id": project.id,
+                    "start_time": start_time,
+                    "end_time": end_time,
+                    "actual_work_minutes": 40
+                })),
+                &work_log_url,
+            )
+            .await;
+        assert_eq!(update_response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(total_working_time(project.id.clone()).await, 40 * 60);
+
+        // 別のプロジェクトに付け替えた場合は元のプロジェクトから差し引かれる
+        let update_payload = generate_work_log_update_payload(&context).await;
+        let update_response = context
+            .authenticated_request(
+                test::TestRequest::put().set_json(&update_payload),
+                &work_log_url,
+            )
+            .await;
+        assert_eq!(update_response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(total_working_time(project.id.clone()).await, 0);
+        assert_eq!(
+            total_working_time(update_payload["project_id"].as_str().unwrap().to_string()).await,
+            40 * 60
+        );
+    })
+    .await;
+}
@@ -26,31 +26,56 @@ use actix_web::{
     App,
 };
 use actix_web_httpauth::middleware::HttpAuthentication;
+use bson::oid::ObjectId;
 use devtrackr_api::{
     api::{
         self,
         common::not_found,
-        endpoints::auth::{login, logout, refresh, register},
+        endpoints::auth::{
+            confirm_email_verification, confirm_password_reset, login, logout, refresh, register,
+            request_password_reset,
+        },
+    },
+    clients::{
+        self,
+        aws_s3::S3Client,
+        mailer::{Mail, OutboxMail, OutboxMailer},
+        redis::RedisClient,
+    },
+    config::{
+        self,
+        admin::AdminConfig,
+        di,
+        email_verification::{EmailVerificationConfig, UnverifiedAccountRestriction},
+        login_attempts::LoginAttemptConfig,
+        password_reset::PasswordResetConfig,
+        work_logs::WorkLogConfig,
     },
-    clients::{self, aws_s3::S3Client},
-    config::{self, di},
     errors::app_error::json_error_handler,
-    middleware::{csrf, jwt, security_headers::SecurityHeaders},
-    models::users::UserCreate,
+    middleware::{csrf, error_format::FormatErrors, jwt, security_headers::SecurityHeaders},
+    models::{auth::SessionClient, users::UserCreate},
     repositories::{
         auth::MongoAuthRepository, companies::MongoCompanyRepository,
-        projects::MongoProjectRepository, work_logs::MongoWorkLogRepository,
+        invoices::MongoInvoiceRepository, projects::MongoProjectRepository,
+        reports::MongoReportRepository, work_logs::MongoWorkLogRepository,
     },
     usecases::{
-        auth::AuthUseCase, companies::CompanyUseCase, projects::ProjectUseCase,
-        work_logs::WorkLogUseCase,
+        auth::AuthUseCase, calendar::CalendarUseCase, companies::CompanyUseCase,
+        exports::ExportUseCase, invoices::InvoiceUseCase, password_reset::PasswordResetUseCase,
+        projects::ProjectUseCase, reports::ReportUseCase, work_logs::WorkLogUseCase,
     },
 };
 use serde_json::json;
 use std::future::Future;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
+// 別タスクで送信されるメールを待つ回数と間隔
+const MAIL_WAIT_RETRIES: usize = 50;
+const MAIL_WAIT_INTERVAL: Duration = Duration::from_millis(100);
+
 #[derive(Clone)]
 #[allow(dead_code)]
 pub struct TestApp {
@@ -58,9 +83,17 @@ pub struct TestApp {
     pub company_usecase: Arc<CompanyUseCase<MongoCompanyRepository>>,
     pub project_usecase: Arc<ProjectUseCase<MongoProjectRepository>>,
     pub work_log_usecase: Arc<WorkLogUseCase<MongoWorkLogRepository>>,
+    pub report_usecase: Arc<ReportUseCase<MongoReportRepository>>,
+    pub export_usecase: Arc<ExportUseCase>,
+    pub invoice_usecase: Arc<InvoiceUseCase<MongoInvoiceRepository>>,
+    pub calendar_usecase: Arc<CalendarUseCase>,
+    pub password_reset_usecase: Arc<PasswordResetUseCase<MongoAuthRepository>>,
     pub test_db: TestDb,
     pub s3_client: Arc<S3Client>,
     pub test_user: UserCreate,
+    pub admin_config: AdminConfig,
+    pub login_attempt_config: LoginAttemptConfig,
+    pub outbox_dir: PathBuf,
     pub access_token: Option<String>,
     pub refresh_token: Option<String>,
 }
@@ -99,37 +132,102 @@ impl TestApp {
         };
         let s3_client = Arc::new(clients::aws_s3::S3Client::new(s3_config.clone()));
 
+        // Redisクライアントの初期化（ログイン失敗回数の管理に使用）
+        let redis_url = std::env::var("REDIS_URL")?;
+        let redis_client = Arc::new(RedisClient::new(
+            config::redis::create_redis_client(&redis_url)?,
+            Duration::from_secs(5),
+        ));
+        // テストの実行時間を抑えるため、ログイン失敗時の遅延を短くする
+        let login_attempt_config = LoginAttemptConfig {
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+            ..LoginAttemptConfig::default()
+        };
+
+        // 送信したメールを検証できるよう、テストごとのアウトボックスに書き出す
+        let outbox_dir = std::env::temp_dir().join(format!("devtrackr_outbox_{}", uuid));
+        let mailer = Arc::new(OutboxMailer::new(&outbox_dir));
+        // 未確認のアカウントに対する制限を検証できるよう、参照のみ許可する（登録直後は猶予期間のため制限されない）
+        let email_verification_config = EmailVerificationConfig {
+            restriction: UnverifiedAccountRestriction::ReadOnly,
+            ..EmailVerificationConfig::default()
+        };
+
         // ユースケースの初期化
-        let auth_usecase = di::init_auth_usecase(&db, s3_client.clone());
+        let auth_usecase = di::init_auth_usecase(
+            &db,
+            s3_client.clone(),
+            redis_client,
+            login_attempt_config.clone(),
+            mailer.clone(),
+            email_verification_config,
+        );
+        let password_reset_usecase =
+            di::init_password_reset_usecase(&db, mailer, PasswordResetConfig::default());
         let company_usecase = di::init_company_usecase(&db);
         let company_usecase_clone = company_usecase.clone();
         let project_usecase = di::init_project_usecase(&db, company_usecase_clone);
         let project_usecase_clone = project_usecase.clone();
-        let work_log_usecase = di::init_work_logs_usecase(&db, project_usecase_clone);
-        let instance = Self {
+        let work_log_usecase =
+            di::init_work_logs_usecase(&db, project_usecase_clone, WorkLogConfig::default());
+        let report_usecase = di::init_report_usecase(
+            &db,
+            company_usecase.clone(),
+            project_usecase.clone(),
+            work_log_usecase.clone(),
+        );
+        let export_usecase = di::init_export_usecase(
+            company_usecase.clone(),
+            project_usecase.clone(),
+            work_log_usecase.clone(),
+        );
+        let invoice_usecase = di::init_invoice_usecase(
+            &db,
+            company_usecase.clone(),
+            project_usecase.clone(),
+            work_log_usecase.clone(),
+        );
+        let calendar_usecase = di::init_calendar_usecase(
+            auth_usecase.clone(),
+            company_usecase.clone(),
+            project_usecase.clone(),
+            work_log_usecase.clone(),
+        );
+        let mut instance = Self {
             auth_usecase,
             company_usecase,
             project_usecase,
             work_log_usecase,
+            report_usecase,
+            export_usecase,
+            invoice_usecase,
+            calendar_usecase,
+            password_reset_usecase,
             test_db,
             s3_client,
             test_user,
+            admin_config: AdminConfig::default(),
+            login_attempt_config,
+            outbox_dir,
             access_token: None,
             refresh_token: None,
         };
 
-        // テストユーザーの登録
-        instance.register_test_user().await;
+        // テストユーザーの登録（管理者APIの検証のため、テストユーザーを管理者として扱う）
+        let test_user_id = instance.register_test_user().await;
+        instance.admin_config.user_ids.insert(test_user_id);
 
         Ok(instance)
     }
 
     /// テストユーザーの登録
-    async fn register_test_user(&self) {
+    async fn register_test_user(&self) -> ObjectId {
         self.auth_usecase
-            .register(&web::Json(&self.test_user))
+            .register(&web::Json(&self.test_user), &SessionClient::default())
             .await
-            .expect("Failed to register test user");
+            .expect("Failed to register test user")
+            .user_id
     }
 
     pub async fn build_test_app(
@@ -150,12 +248,19 @@ impl TestApp {
 
         test::init_service(
             App::new()
+                .wrap(FormatErrors)
                 .wrap(csrf::csrf_middleware())
                 .wrap(SecurityHeaders)
                 .app_data(web::Data::new(self.auth_usecase.clone()))
                 .app_data(web::Data::new(self.company_usecase.clone()))
                 .app_data(web::Data::new(self.project_usecase.clone()))
                 .app_data(web::Data::new(self.work_log_usecase.clone()))
+                .app_data(web::Data::new(self.report_usecase.clone()))
+                .app_data(web::Data::new(self.export_usecase.clone()))
+                .app_data(web::Data::new(self.invoice_usecase.clone()))
+                .app_data(web::Data::new(self.calendar_usecase.clone()))
+                .app_data(web::Data::new(self.password_reset_usecase.clone()))
+                .app_data(web::Data::new(self.admin_config.clone()))
                 .app_data(json_error_handler())
                 .service(
                     web::scope("/api")
@@ -164,11 +269,15 @@ impl TestApp {
                                 .service(login)
                                 .service(register)
                                 .service(refresh)
+                                .service(request_password_reset)
+                                .service(confirm_password_reset)
+                                .service(confirm_email_verification)
                                 .service(
                                     // logoutのみ認証ミドルウェアを適用
                                     web::scope("").wrap(jwt_auth.clone()).service(logout),
                                 ),
                         )
+                        .service(api::routes::calendar_scope())
                         // 認証が必要なAPIルート
                         .service(
                             web::scope("")
@@ -176,7 +285,13 @@ impl TestApp {
                                 .service(api::routes::users_scope())
                                 .service(api::routes::projects_scope())
                                 .service(api::routes::work_logs_scope())
-                                .service(api::routes::companies_scope()),
+                                .service(api::routes::companies_scope())
+                                .service(api::routes::trash_scope())
+                                .service(api::routes::reports_scope())
+                                .service(api::routes::analytics_scope())
+                                .service(api::routes::exports_scope())
+                                .service(api::routes::invoices_scope())
+                                .service(api::routes::admin_scope()),
                         )
                         .default_service(web::route().to(not_found)),
                 ),
@@ -184,6 +299,22 @@ impl TestApp {
         .await
     }
 
+    /// 別のユーザーを登録し、そのアクセストークンを返す（ユーザー間のデータ分離の検証用）
+    pub async fn register_another_user(&self) -> String {
+        let uuid = Uuid::now_v7();
+        let another_user = UserCreate {
+            email: format!("another_{}@example.com", uuid),
+            password: String::from("password123"),
+            username: format!("another_{}", uuid),
+        };
+
+        self.auth_usecase
+            .register(&another_user, &SessionClient::default())
+            .await
+            .expect("Failed to register another user")
+            .access_token
+    }
+
     /// テストの実行
     pub async fn run_test<F, Fut>(f: F)
     where
@@ -193,10 +324,8 @@ impl TestApp {
         let context = TestContext::new().await;
         f(context.clone()).await;
 
-        // テスト終了時に明示的にDBを破棄
-        if let Err(e) = context.app.test_db.cleanup().await {
-            log::error!("Failed to cleanup test database: {}", e);
-        }
+        // テスト終了時に明示的にDBとアウトボックスを破棄
+        context.app.cleanup().await;
     }
 
     /// 認証付きテストの実行
@@ -208,10 +337,56 @@ impl TestApp {
         let context = TestContext::with_auth().await;
         f(context.clone()).await;
 
-        // テスト終了時に明示的にDBを破棄
-        if let Err(e) = context.app.test_db.cleanup().await {
+        // テスト終了時に明示的にDBとアウトボックスを破棄
+        context.app.cleanup().await;
+    }
+
+    /// テストで作成したDBとアウトボックスを破棄
+    async fn cleanup(&self) {
+        if let Err(e) = self.test_db.cleanup().await {
             log::error!("Failed to cleanup test database: {}", e);
         }
+        let _ = tokio::fs::remove_dir_all(&self.outbox_dir).await;
+    }
+
+    /// アウトボックスに書き出されたメールのうち、指定した宛先のものを送信順に取得
+    pub async fn sent_mails(&self, to: &str) -> Vec<Mail> {
+        let Ok(mut entries) = tokio::fs::read_dir(&self.outbox_dir).await else {
+            return vec![];
+        };
+        let mut paths = vec![];
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            paths.push(entry.path());
+        }
+        // ファイル名（UUID v7）の順に送信順となる
+        paths.sort();
+
+        let mut mails = vec![];
+        for path in paths {
+            let content = tokio::fs::read(&path)
+                .await
+                .expect("アウトボックスのメールの読み込みに失敗しました");
+            let outbox_mail: OutboxMail = serde_json::from_slice(&content)
+                .expect("アウトボックスのメールの解析に失敗しました");
+            if outbox_mail.mail.to == to {
+                mails.push(outbox_mail.mail);
+            }
+        }
+        mails
+    }
+
+    /// 指定した宛先のメールが`sent_count`件より多く送信されるまで待ち、最後に送信されたメールを取得
+    ///
+    /// レスポンスを待たずに別タスクで送信されるメール（パスワードリセットなど）の確認用
+    pub async fn wait_for_new_mail(&self, to: &str, sent_count: usize) -> Mail {
+        for _ in 0..MAIL_WAIT_RETRIES {
+            let mut mails = self.sent_mails(to).await;
+            if mails.len() > sent_count {
+                return mails.pop().expect("送信されたメールがありません");
+            }
+            actix_web::rt::time::sleep(MAIL_WAIT_INTERVAL).await;
+        }
+        panic!("メールが送信されていません: {}", to);
     }
 
     /// ログインしてトークンを保存
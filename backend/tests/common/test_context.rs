@@ -75,6 +75,21 @@ impl TestContext {
         response
     }
 
+    // 指定したアクセストークンでリクエストするヘルパーメソッド
+    pub async fn request_with_token(
+        &self,
+        req: test::TestRequest,
+        uri: &str,
+        access_token: &str,
+    ) -> ServiceResponse {
+        let request = req
+            .uri(uri)
+            .insert_header(("Authorization", format!("Bearer {}", access_token)))
+            .to_request();
+
+        test::call_service(self.service(), request).await
+    }
+
     // クッキーの検証ヘルパー
     pub fn assert_auth_cookies_cleared(&self, response: &ServiceResponse) {
         let cookies: Vec<_> = response.response().cookies().collect();
@@ -5,7 +5,14 @@ use mongodb::{Client, Collection, Database};
 use std::sync::Arc;
 use uuid::Uuid;
 
-const TEST_COLLECTIONS: &[&str] = &["auth_tokens", "users", "companies", "projects", "work_logs"];
+const TEST_COLLECTIONS: &[&str] = &[
+    "auth_tokens",
+    "password_reset_tokens",
+    "users",
+    "companies",
+    "projects",
+    "work_logs",
+];
 
 #[derive(Clone)]
 pub struct TestDb {
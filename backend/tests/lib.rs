@@ -67,6 +67,7 @@ pub async fn setup() {
         "S3_REGION",
         "MINIO_ENDPOINT",
         "JWT_SECRET",
+        "REDIS_URL",
     ];
 
     for var in required_vars {
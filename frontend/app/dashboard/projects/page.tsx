@@ -6,7 +6,7 @@ import { ProjectTable } from "@/components/features/projects/Table/ProjectTable"
 const bgColor = "bg-main-translucent backdrop-filter backdrop-blur-sm";
 
 export default async function ProjectListPage() {
-    const [{ companies }, projects] = await Promise.all([
+    const [{ companies }, { items: projects }] = await Promise.all([
         getCompaniesWithProjects(),
         getProjects()
     ]);
@@ -96,4 +96,8 @@ export interface CreateCompanyResponse {
 
 export type CompanyResponse = Company;
 
-export type CompaniesResponse = Company[];
+export interface CompaniesResponse {
+  items: Company[];
+  total: number;
+  next_cursor: string | null;
+}
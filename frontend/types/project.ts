@@ -48,7 +48,11 @@ export interface UpdateProjectRequest {
   total_working_time: number;
 }
 
-export type ProjectsResponse = Project[];
+export interface ProjectsResponse {
+  items: Project[];
+  total: number;
+  next_cursor: string | null;
+}
 
 export type ProjectResponse = Project;
 